@@ -31,6 +31,14 @@ export interface VerificationResult {
   epoch: string;
   dataHash: string;
   verifiedAt: number;
+  /** Optional hash of the raw proof, bound into the signed message when present */
+  proofCommitment?: string;
+  /** Recipient's on-chain commitment, bound into the signed message when the program runs in commitment-binding mode */
+  walletCommitment?: string;
+  /** Proof generation time (unix seconds), bound into the signed message; defaults to verifiedAt */
+  proofGeneratedAt?: number;
+  /** Deadline for on-chain submission (unix seconds), bound into the signed message; defaults to verifiedAt + ATTESTATION_TTL_SECONDS */
+  expiresAt?: number;
 }
 
 /**
@@ -36,6 +36,9 @@ interface VerifierAttestation {
     epoch: string;
     dataHash: string;
     verifiedAt: number;
+    proofCommitment?: string;
+    proofGeneratedAt: number;
+    expiresAt: number;
   };
   verifier: string;
   signature: string;
@@ -287,8 +290,27 @@ export async function buildRecordAttestationInstruction(
   const signatureDecoded = attestation.signatureBytes ||
     bs58.decode(attestation.signature);
 
+  // Proof commitment (Borsh Option<[u8; 32]>: 0 = None, 1 + 32 bytes = Some)
+  const proofCommitmentBytes = attestation.result.proofCommitment
+    ? Buffer.concat([Buffer.from([1]), Buffer.from(normalizeHex(attestation.result.proofCommitment), 'hex')])
+    : Buffer.from([0]);
+
+  // Proof generation time (i64, little-endian for Anchor/Borsh)
+  const proofGeneratedAtBigInt = BigInt.asUintN(64, BigInt(attestation.result.proofGeneratedAt));
+  const proofGeneratedAtBytes = new Uint8Array(8);
+  for (let i = 0; i < 8; i++) {
+    proofGeneratedAtBytes[i] = Number((proofGeneratedAtBigInt >> BigInt(i * 8)) & BigInt(0xff));
+  }
+
+  // Attestation expiry (i64, little-endian for Anchor/Borsh)
+  const expiresAtBigInt = BigInt.asUintN(64, BigInt(attestation.result.expiresAt));
+  const expiresAtBytes = new Uint8Array(8);
+  for (let i = 0; i < 8; i++) {
+    expiresAtBytes[i] = Number((expiresAtBigInt >> BigInt(i * 8)) & BigInt(0xff));
+  }
+
   // Instruction data format (matches Anchor program):
-  // discriminator (8) + attestation_hash (32) + proof_type (1) + nullifier (32) + epoch (8) + data_hash (32) + signature (64) = 177 bytes
+  // discriminator (8) + attestation_hash (32) + proof_type (1) + nullifier (32) + epoch (8) + data_hash (32) + signature (64) + proof_commitment (1 or 33) + proof_generated_at (8) + expires_at (8)
   const instructionData = Buffer.concat([
     Buffer.from(discriminator),
     attestationHashBytes,
@@ -297,6 +319,9 @@ export async function buildRecordAttestationInstruction(
     epochBytes,
     dataHashBytes,
     Buffer.from(signatureDecoded),
+    proofCommitmentBytes,
+    proofGeneratedAtBytes,
+    expiresAtBytes,
   ]);
 
   // Account order must match RecordAttestation struct in lib.rs:
@@ -341,7 +366,27 @@ function reconstructMessageBytes(attestation: VerifierAttestation): Uint8Array {
     : attestation.result.dataHash;
   const dataHashBytes = Buffer.from(dataHashHex, 'hex');
 
-  return buildAttestationMessage(proofTypeValue, nullifierBytes, attestationHashBytes, epoch, dataHashBytes);
+  const proofCommitmentBytes = attestation.result.proofCommitment
+    ? Buffer.from(normalizeHex(attestation.result.proofCommitment), 'hex')
+    : undefined;
+
+  return buildAttestationMessage(
+    proofTypeValue,
+    nullifierBytes,
+    attestationHashBytes,
+    epoch,
+    dataHashBytes,
+    BigInt(attestation.result.proofGeneratedAt),
+    BigInt(attestation.result.expiresAt),
+    proofCommitmentBytes
+  );
+}
+
+/**
+ * Strip an optional 0x prefix from a hex string
+ */
+function normalizeHex(hex: string): string {
+  return hex.startsWith('0x') ? hex.slice(2) : hex;
 }
 
 // === High-Level API ===
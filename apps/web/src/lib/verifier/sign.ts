@@ -4,10 +4,10 @@
  * Signs verification results using Ed25519 so they can be verified on-chain.
  * The verifier's public key is registered on the Solana program.
  *
- * IMPORTANT: Message format must match Anchor program's build_attestation_message_v2
+ * IMPORTANT: Message format must match Anchor program's build_attestation_message_v3
  */
 
-import { Keypair } from '@solana/web3.js';
+import { Keypair, PublicKey } from '@solana/web3.js';
 import type { VerificationResult, SignedAttestation } from './types';
 
 // Verifier keypair (initialized once per serverless instance)
@@ -68,8 +68,12 @@ export function getVerifierPublicKey(): string {
 
 // === Constants ===
 
-// Domain separator must match Anchor program (20 bytes)
-const DOMAIN_SEPARATOR = 'vouch_attestation_v2';
+// Domain separators must match Anchor program's attestation_domain_separator
+// (distinct per proof type so signatures can't be reinterpreted across types)
+const DOMAIN_SEPARATORS: Record<number, string> = {
+  1: 'vouch_dev_attestation_v3',   // 24 bytes
+  2: 'vouch_whale_attestation_v3', // 26 bytes
+};
 
 // Proof type values must match Anchor program's ProofType enum
 const PROOF_TYPE_VALUES: Record<string, number> = {
@@ -77,6 +81,14 @@ const PROOF_TYPE_VALUES: Record<string, number> = {
   whale: 2,     // ProofType::WhaleTrading
 };
 
+// How long a signed attestation may wait before on-chain submission
+const ATTESTATION_TTL_SECONDS = 10 * 60;
+
+// Program the attestation is bound to; must match the deployment's declare_id!
+const VOUCH_PROGRAM_ID = new PublicKey(
+  process.env.VOUCH_PROGRAM_ID || 'EhSkCuohWP8Sdfq6yHoKih6r2rsNoYYPZZSfpnyELuaD'
+);
+
 /**
  * Create a signed attestation from a verification result
  *
@@ -96,6 +108,13 @@ export function signAttestation(result: VerificationResult): SignedAttestation {
   const crypto = require('crypto');
   const nacl = require('tweetnacl');
 
+  // Pin the proof generation time so the client submits exactly what was signed
+  result = {
+    ...result,
+    proofGeneratedAt: proofGeneratedAtOf(result),
+    expiresAt: expiresAtOf(result),
+  };
+
   // First compute attestation hash from verification metadata
   const metadataForHash = `${result.isValid}|${result.proofType}|${result.verifiedAt}`;
   const attestationHash = crypto
@@ -103,9 +122,9 @@ export function signAttestation(result: VerificationResult): SignedAttestation {
     .update(metadataForHash)
     .digest();
 
-  // Build binary message matching Anchor's build_attestation_message_v2
-  // Format: domain (20) | proof_type (1) | nullifier (32) | epoch (8) | data_hash (32) | attestation_hash (32) = 125 bytes
-  const messageBytes = buildAttestationMessageV2(result, attestationHash);
+  // Build binary message matching Anchor's build_attestation_message_v3
+  // Format: domain (per proof type) | program_id (32) | proof_type (1) | nullifier (32) | epoch (8) | data_hash (32) | attestation_hash (32) | proof_generated_at (8) | expires_at (8) | has_proof_commitment (1) [| proof_commitment (32)] | has_wallet_commitment (1) [| wallet_commitment (32)]
+  const messageBytes = buildAttestationMessageV3(result, attestationHash);
 
   // Sign with Ed25519 using nacl
   const signatureBytes = nacl.sign.detached(messageBytes, verifierKeypair!.secretKey);
@@ -123,45 +142,131 @@ export function signAttestation(result: VerificationResult): SignedAttestation {
 }
 
 /**
- * Build binary attestation message matching Anchor's build_attestation_message_v2
+ * Build binary attestation message matching Anchor's build_attestation_message_v3
  *
- * Format (125 bytes total):
- * - Domain separator: "vouch_attestation_v2" (20 bytes)
+ * Format (variable length, domain depends on proof type):
+ * - Domain separator: "vouch_dev_attestation_v3" (24 bytes) or "vouch_whale_attestation_v3" (26 bytes)
+ * - Program ID: [u8; 32] (32 bytes, the deployment the signature is valid for)
  * - Proof type: u8 (1 byte)
  * - Nullifier: [u8; 32] (32 bytes)
  * - Epoch: u64 big-endian (8 bytes)
  * - Data hash: [u8; 32] (32 bytes)
  * - Attestation hash: [u8; 32] (32 bytes)
+ * - Proof generated at: i64 big-endian unix seconds (8 bytes)
+ * - Expires at: i64 big-endian unix seconds (8 bytes)
+ * - Has proof commitment: u8 (1 byte, 0 or 1)
+ * - Proof commitment: [u8; 32] (32 bytes, only when present)
+ * - Has wallet commitment: u8 (1 byte, 0 or 1)
+ * - Wallet commitment: [u8; 32] (32 bytes, only in commitment-binding mode)
  */
-function buildAttestationMessageV2(
+function buildAttestationMessageV3(
   result: VerificationResult,
   attestationHash: Uint8Array
 ): Uint8Array {
-  const message = new Uint8Array(125);
+  const proofTypeValue = PROOF_TYPE_VALUES[result.proofType] ?? 0;
+  return encodeAttestationMessageV3(
+    proofTypeValue,
+    hexToBytes(result.nullifier),
+    BigInt(result.epoch),
+    hexToBytes(result.dataHash),
+    attestationHash,
+    BigInt(proofGeneratedAtOf(result)),
+    BigInt(expiresAtOf(result)),
+    result.proofCommitment ? hexToBytes(result.proofCommitment) : undefined,
+    result.walletCommitment ? hexToBytes(result.walletCommitment) : undefined
+  );
+}
 
-  // Domain separator (20 bytes)
-  const domainBytes = new TextEncoder().encode(DOMAIN_SEPARATOR);
-  message.set(domainBytes, 0);
+/**
+ * Proof generation time in unix seconds (falls back to verification time)
+ */
+function proofGeneratedAtOf(result: VerificationResult): number {
+  return result.proofGeneratedAt ?? Math.floor(result.verifiedAt / 1000);
+}
+
+/**
+ * Submission deadline in unix seconds (falls back to verification time + TTL)
+ */
+function expiresAtOf(result: VerificationResult): number {
+  return result.expiresAt ?? Math.floor(result.verifiedAt / 1000) + ATTESTATION_TTL_SECONDS;
+}
+
+/**
+ * Encode the v3 attestation message from raw fields
+ */
+function encodeAttestationMessageV3(
+  proofTypeValue: number,
+  nullifier: Uint8Array,
+  epoch: bigint,
+  dataHash: Uint8Array,
+  attestationHash: Uint8Array,
+  proofGeneratedAt: bigint,
+  expiresAt: bigint,
+  proofCommitment?: Uint8Array,
+  walletCommitment?: Uint8Array
+): Uint8Array {
+  const domain = DOMAIN_SEPARATORS[proofTypeValue];
+  if (!domain) {
+    throw new Error(`Unknown proof type value: ${proofTypeValue}`);
+  }
+  const domainBytes = new TextEncoder().encode(domain);
+  const message = new Uint8Array(
+    domainBytes.length + 32 + 1 + 32 + 8 + 32 + 32 + 8 + 8 +
+      1 + (proofCommitment ? 32 : 0) +
+      1 + (walletCommitment ? 32 : 0)
+  );
+  let offset = 0;
+
+  // Domain separator (per proof type)
+  message.set(domainBytes, offset);
+  offset += domainBytes.length;
+
+  // Program ID (32 bytes)
+  message.set(VOUCH_PROGRAM_ID.toBytes(), offset);
+  offset += 32;
 
   // Proof type (1 byte)
-  const proofTypeValue = PROOF_TYPE_VALUES[result.proofType] ?? 0;
-  message[20] = proofTypeValue;
+  message[offset] = proofTypeValue;
+  offset += 1;
 
-  // Nullifier (32 bytes) - convert hex to bytes
-  const nullifierBytes = hexToBytes(result.nullifier);
-  message.set(nullifierBytes, 21);
+  // Nullifier (32 bytes)
+  message.set(nullifier.slice(0, 32), offset);
+  offset += 32;
 
   // Epoch (8 bytes, big-endian)
-  const epochBigInt = BigInt(result.epoch);
-  const epochBytes = bigIntToBytes8BE(epochBigInt);
-  message.set(epochBytes, 53);
+  message.set(bigIntToBytes8BE(epoch), offset);
+  offset += 8;
 
-  // Data hash (32 bytes) - convert hex to bytes
-  const dataHashBytes = hexToBytes(result.dataHash);
-  message.set(dataHashBytes, 61);
+  // Data hash (32 bytes)
+  message.set(dataHash.slice(0, 32), offset);
+  offset += 32;
 
   // Attestation hash (32 bytes)
-  message.set(attestationHash, 93);
+  message.set(attestationHash.slice(0, 32), offset);
+  offset += 32;
+
+  // Proof generated at (8 bytes, big-endian, two's complement)
+  message.set(bigIntToBytes8BE(BigInt.asUintN(64, proofGeneratedAt)), offset);
+  offset += 8;
+
+  // Expires at (8 bytes, big-endian, two's complement)
+  message.set(bigIntToBytes8BE(BigInt.asUintN(64, expiresAt)), offset);
+  offset += 8;
+
+  // Proof commitment (presence byte + 32 bytes, optional)
+  message[offset] = proofCommitment ? 1 : 0;
+  offset += 1;
+  if (proofCommitment) {
+    message.set(proofCommitment.slice(0, 32), offset);
+    offset += 32;
+  }
+
+  // Wallet commitment (presence byte + 32 bytes, commitment-binding mode only)
+  message[offset] = walletCommitment ? 1 : 0;
+  offset += 1;
+  if (walletCommitment) {
+    message.set(walletCommitment.slice(0, 32), offset);
+  }
 
   return message;
 }
@@ -211,7 +316,7 @@ export function verifyAttestationSignature(attestation: SignedAttestation): bool
     .digest();
 
   // Rebuild the message
-  const messageBytes = buildAttestationMessageV2(attestation.result, attestationHash);
+  const messageBytes = buildAttestationMessageV3(attestation.result, attestationHash);
 
   // Handle both array (from JSON) and Uint8Array formats for signature
   const signature = attestation.signatureBytes
@@ -238,35 +343,32 @@ export function buildAttestationMessage(
   nullifier: Uint8Array,
   attestationHash: Uint8Array,
   epoch?: bigint,
-  dataHash?: Uint8Array
+  dataHash?: Uint8Array,
+  proofGeneratedAt?: bigint,
+  expiresAt?: bigint,
+  proofCommitment?: Uint8Array,
+  walletCommitment?: Uint8Array
 ): Uint8Array {
-  // If epoch and dataHash provided, use v2 format
-  if (epoch !== undefined && dataHash !== undefined) {
-    const message = new Uint8Array(125);
-
-    // Domain separator (20 bytes)
-    const domainBytes = new TextEncoder().encode(DOMAIN_SEPARATOR);
-    message.set(domainBytes, 0);
-
-    // Proof type (1 byte)
-    message[20] = proofTypeValue;
-
-    // Nullifier (32 bytes)
-    message.set(nullifier.slice(0, 32), 21);
-
-    // Epoch (8 bytes, big-endian)
-    const epochBytes = bigIntToBytes8BE(epoch);
-    message.set(epochBytes, 53);
-
-    // Data hash (32 bytes)
-    message.set(dataHash.slice(0, 32), 61);
-
-    // Attestation hash (32 bytes)
-    message.set(attestationHash.slice(0, 32), 93);
-
-    return message;
+  // If epoch, dataHash, proofGeneratedAt and expiresAt provided, use v3 format
+  if (
+    epoch !== undefined &&
+    dataHash !== undefined &&
+    proofGeneratedAt !== undefined &&
+    expiresAt !== undefined
+  ) {
+    return encodeAttestationMessageV3(
+      proofTypeValue,
+      nullifier,
+      epoch,
+      dataHash,
+      attestationHash,
+      proofGeneratedAt,
+      expiresAt,
+      proofCommitment,
+      walletCommitment
+    );
   }
 
   // Legacy v1 format (82 bytes) - should not be used anymore
-  throw new Error('Legacy v1 attestation format is no longer supported. Epoch and dataHash are required.');
+  throw new Error('Legacy v1 attestation format is no longer supported. Epoch, dataHash, proofGeneratedAt and expiresAt are required.');
 }
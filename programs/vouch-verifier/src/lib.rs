@@ -2,8 +2,9 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::sysvar::instructions::{
     load_current_index_checked, load_instruction_at_checked,
 };
-use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use solana_sha256_hasher::hashv;
 use solana_sdk_ids::ed25519_program;
 
 declare_id!("EhSkCuohWP8Sdfq6yHoKih6r2rsNoYYPZZSfpnyELuaD");
@@ -14,12 +15,119 @@ pub const ED25519_SIGNATURE_SIZE: usize = 64;
 
 /// Default rate limit: 10 proofs per day
 pub const DEFAULT_MAX_PROOFS_PER_DAY: u32 = 10;
+/// Default attestation floor: at least one active verifier
+pub const DEFAULT_MIN_ACTIVE_VERIFIERS: u32 = 1;
 /// Default cooldown: 60 seconds between proofs
 pub const DEFAULT_COOLDOWN_SECONDS: i64 = 60;
 /// Seconds in a day for rate limit reset
 pub const SECONDS_PER_DAY: i64 = 86400;
+/// Maximum clock-skew tolerance for the rolling rate-limit window: 5 minutes
+pub const MAX_RESET_SKEW_TOLERANCE_SECONDS: i64 = 300;
+/// Proof timestamps kept per wallet for the rolling 24h window
+/// Daily limits above this cannot be enforced and are rejected by update_rate_limits.
+pub const RATE_LIMIT_WINDOW_SIZE: usize = 32;
+/// Default Ed25519 scan distance: the verify instruction must be adjacent
+pub const DEFAULT_ED25519_SCAN_DISTANCE: u8 = 1;
+/// Maximum preceding instructions inspected for the Ed25519 verify instruction
+pub const MAX_ED25519_SCAN_DISTANCE: u8 = 8;
+/// Bounds for the configured slot duration used by slot-based time
+pub const MIN_MS_PER_SLOT: u64 = 100;
+pub const MAX_MS_PER_SLOT: u64 = 2_000;
 /// Default max epoch age: 7 days (proofs older than this are rejected)
 pub const DEFAULT_MAX_EPOCH_AGE: u64 = 7;
+/// Default retention before a used nullifier can be closed: 30 days
+pub const DEFAULT_MIN_NULLIFIER_AGE_SECONDS: i64 = 30 * SECONDS_PER_DAY;
+/// Config account size (with discriminator) of the v2 layout ending at `bump`
+pub const CONFIG_V2_SPACE: usize = 106;
+
+/// Basis point denominator (100%)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+/// Maximum per-campaign claim fee: 10%
+pub const MAX_CAMPAIGN_CLAIM_FEE_BPS: u16 = 1_000;
+/// Maximum early claim penalty: 50%
+pub const MAX_EARLY_CLAIM_PENALTY_BPS: u16 = 5_000;
+/// Maximum total registration deadline extension from funding: 90 days
+pub const MAX_DEADLINE_EXTENSION_SECONDS: i64 = 90 * SECONDS_PER_DAY;
+/// Current AirdropRegistrationAccount layout version
+pub const REGISTRATION_ACCOUNT_VERSION: u8 = 1;
+/// Current AirdropCampaign layout version (0 = LegacyAirdropCampaign)
+pub const CAMPAIGN_ACCOUNT_VERSION: u8 = 1;
+/// Current NullifierAccount layout version
+pub const NULLIFIER_ACCOUNT_VERSION: u8 = 1;
+/// Layout version of the VerifierProfile view; bump when its fields change
+pub const VERIFIER_PROFILE_VERSION: u8 = 1;
+/// Maximum registrations per mark_airdrop_distributed_batch call (compute bound)
+pub const MAX_DISTRIBUTION_BATCH: usize = 20;
+/// Maximum length of a creator memo on a registration
+pub const MAX_REGISTRATION_MEMO_LEN: usize = 100;
+/// Maximum number of additional mints in a multi-asset campaign
+pub const MAX_CAMPAIGN_ASSETS: usize = 3;
+/// Maximum nullifiers per init_nullifiers_batch call (compute bound)
+pub const MAX_NULLIFIER_BATCH: usize = 10;
+/// Maximum verifiers per import_signed_verifier_set call (compute bound)
+pub const MAX_VERIFIER_SET_SIZE: usize = 10;
+/// Entries kept in a campaign's activity ring buffer
+pub const CAMPAIGN_ACTIVITY_LOG_SIZE: usize = 16;
+/// Mints tracked individually in ProtocolStats (later mints count as untracked)
+pub const MAX_TVL_TRACKED_MINTS: usize = 8;
+/// Maximum merkle proof depth (supports 2^32 leaves)
+pub const MAX_MERKLE_PROOF_DEPTH: usize = 32;
+/// Maximum entries a single attestation batch may authorize
+pub const MAX_ATTESTATION_BATCH_ENTRIES: u16 = 256;
+/// Maximum lifetime of an open attestation batch: 15 minutes
+pub const MAX_ATTESTATION_BATCH_LIFETIME_SECONDS: i64 = 15 * 60;
+/// Maximum registrations tracked in a WalletRegistrationIndex
+pub const MAX_WALLET_INDEX_ENTRIES: usize = 32;
+/// Maximum verifier removal grace period: 7 days
+pub const MAX_VERIFIER_REMOVAL_DELAY_SECONDS: i64 = 7 * SECONDS_PER_DAY;
+/// Default share of reaped registration rent paid to the caller: 20%
+pub const DEFAULT_REAPER_BOUNTY_BPS: u16 = 2_000;
+/// Wait after campaign completion before unclaimed tokens can be reclaimed: 30 days
+pub const AIRDROP_RECLAIM_GRACE_SECONDS: i64 = 30 * SECONDS_PER_DAY;
+/// Fixed-point scale of the auto-pause attestation counter (one attestation)
+pub const ATTESTATION_RATE_SCALE: u64 = 1_000;
+/// Lowest registry proof-type ID; 1 and 2 are the built-in ProofType values
+pub const FIRST_REGISTERED_PROOF_TYPE_ID: u16 = 3;
+/// Maximum proof types in the ProofTypeRegistry
+pub const MAX_REGISTERED_PROOF_TYPES: usize = 16;
+/// Maximum length of a registered proof type's name
+pub const MAX_PROOF_TYPE_NAME_LEN: usize = 32;
+/// Maximum entries in a campaign's proof_type_bonuses table
+pub const MAX_CAMPAIGN_PROOF_TYPE_BONUSES: usize = 8;
+
+// validate_config violation bits
+/// A daily proof limit is zero (or the global one exceeds RATE_LIMIT_WINDOW_SIZE),
+/// or cooldown_seconds is negative
+pub const CONFIG_ISSUE_RATE_LIMIT: u32 = 1 << 0;
+/// reset_skew_tolerance_seconds outside 0..=MAX_RESET_SKEW_TOLERANCE_SECONDS
+pub const CONFIG_ISSUE_RESET_SKEW: u32 = 1 << 1;
+/// global_min_hold_seconds or max_proof_age_seconds is negative
+pub const CONFIG_ISSUE_NEGATIVE_DURATION: u32 = 1 << 2;
+/// reaper_bounty_bps exceeds 100%
+pub const CONFIG_ISSUE_REAPER_BOUNTY: u32 = 1 << 3;
+/// max_ed25519_scan_distance exceeds MAX_ED25519_SCAN_DISTANCE
+pub const CONFIG_ISSUE_SCAN_DISTANCE: u32 = 1 << 4;
+/// Slot-based time enabled with ms_per_slot outside MIN..=MAX_MS_PER_SLOT
+pub const CONFIG_ISSUE_TIME_SOURCE: u32 = 1 << 5;
+/// Concurrent campaign cap is below the current active campaign count
+pub const CONFIG_ISSUE_CAMPAIGN_CAP: u32 = 1 << 6;
+/// pause_authority is unset, so the protocol can never be paused or unpaused
+pub const CONFIG_ISSUE_PAUSE_AUTHORITY: u32 = 1 << 7;
+/// Protocol is active with fewer verifiers than attestations require (at least one)
+pub const CONFIG_ISSUE_NO_VERIFIERS: u32 = 1 << 8;
+
+/// Like require!, but first logs a parseable diagnostic for the failure:
+/// `VOUCH_ERR:<ErrorName> key=value key=value ...`
+/// Failing instructions can't emit events, so this is the only way to surface
+/// the values (current vs required) behind an error code.
+macro_rules! require_diag {
+    ($cond:expr, $err:ident, $fmt:literal $(, $arg:expr)* $(,)?) => {
+        if !($cond) {
+            msg!(concat!("VOUCH_ERR:", stringify!($err), " ", $fmt) $(, $arg)*);
+            return Err(VouchError::$err.into());
+        }
+    };
+}
 
 /// Vouch Protocol - ZK Proof Verifier
 ///
@@ -37,17 +145,102 @@ pub mod vouch_verifier {
     // === Configuration ===
 
     /// Initialize the protocol configuration
-    /// Must be called once by the deployer
-    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+    /// Must be called once by the deployer with config_epoch 0.
+    ///
+    /// Resetting a deployment without redeploying: call again with the next
+    /// config_epoch, passing the current config as previous_config and signing
+    /// as its admin. A fresh config is created under
+    /// [b"config", config_epoch_seed(config_epoch)] with default settings and
+    /// the previous one is marked retired, after which every instruction rejects
+    /// it with ConfigRetired. Epoch 0 keeps the original [b"config"] address.
+    ///
+    /// Migration notes:
+    /// - Clients must derive the config PDA from the new epoch (or read
+    ///   config_epoch from the retired config) before the rotation lands.
+    /// - Only the config is reset. Verifier, nullifier, rate-limit, campaign
+    ///   and other PDAs are not epoch-scoped and stay as they are, so the new
+    ///   config starts with verifier_count = 0 while old VerifierAccounts still
+    ///   exist; verifier_count and active_campaign_count drift until those
+    ///   accounts are re-registered or closed.
+    /// - Settings (limits, pause authority, fee and time-source options) are not
+    ///   copied; reapply them with the admin setters after rotating.
+    pub fn initialize_config(ctx: Context<InitializeConfig>, config_epoch: u8) -> Result<()> {
+        if config_epoch > 0 {
+            let previous = ctx
+                .accounts
+                .previous_config
+                .as_mut()
+                .ok_or(VouchError::InvalidConfigEpoch)?;
+            require_diag!(
+                !previous.retired && previous.config_epoch < config_epoch,
+                InvalidConfigEpoch,
+                "previous_epoch={} retired={} config_epoch={}",
+                previous.config_epoch,
+                previous.retired,
+                config_epoch
+            );
+            previous.retired = true;
+
+            emit!(ConfigEpochRotated {
+                admin: ctx.accounts.admin.key(),
+                previous_config: previous.key(),
+                previous_epoch: previous.config_epoch,
+                config: ctx.accounts.config.key(),
+                config_epoch,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+
         let config = &mut ctx.accounts.config;
         config.admin = ctx.accounts.admin.key();
         config.verifier_count = 0;
         config.is_paused = false;
         config.pause_authority = ctx.accounts.admin.key();
         config.max_proofs_per_day = DEFAULT_MAX_PROOFS_PER_DAY;
+        config.max_dev_proofs_per_day = DEFAULT_MAX_PROOFS_PER_DAY;
+        config.max_whale_proofs_per_day = DEFAULT_MAX_PROOFS_PER_DAY;
         config.cooldown_seconds = DEFAULT_COOLDOWN_SECONDS;
         config.max_epoch_age = DEFAULT_MAX_EPOCH_AGE;
         config.total_proofs_verified = 0;
+        config.require_commitment = false;
+        config.commitment_proof_types = 0;
+        config.reset_skew_tolerance_seconds = 0;
+        config.global_min_hold_seconds = 0;
+        config.require_proof_commitment = false;
+        config.max_concurrent_open_campaigns = 0;
+        config.active_campaign_count = 0;
+        config.governance_key = Pubkey::default();
+        config.governance_nonce = 0;
+        config.allow_credential_upgrades = false;
+        config.max_ed25519_scan_distance = DEFAULT_ED25519_SCAN_DISTANCE;
+        config.use_slot_based_time = false;
+        config.ms_per_slot = 0;
+        config.time_anchor_slot = 0;
+        config.time_anchor_timestamp = 0;
+        config.max_proof_age_seconds = 0;
+        config.exclusive_ed25519_instructions = true;
+        config.verifier_removal_delay_seconds = 0;
+        config.campaigns_respect_protocol_pause = true;
+        config.reaper_bounty_bps = DEFAULT_REAPER_BOUNTY_BPS;
+        config.pending_admin = Pubkey::default();
+        config.min_active_verifiers = DEFAULT_MIN_ACTIVE_VERIFIERS;
+        config.protocol_stats_enabled = false;
+        config.required_signatures = 1;
+        config.global_claim_cooldown_seconds = 0;
+        config.min_nullifier_age_seconds = DEFAULT_MIN_NULLIFIER_AGE_SECONDS;
+        config.bind_commitment = false;
+        config.shadow_wire_program_id = Pubkey::default();
+        config.max_attestations_per_window = 0;
+        config.attestation_window_seconds = 0;
+        config.attestation_rate_level = 0;
+        config.attestation_rate_updated_at = 0;
+        config.enforce_recipient_blocklist = false;
+        config.config_epoch = config_epoch;
+        config.retired = false;
+        config.track_used_signatures = false;
+        config.max_global_proofs_per_day = 0;
+        config.global_proofs_today = 0;
+        config.global_day_start = 0;
         config.bump = ctx.bumps.config;
 
         emit!(ConfigInitialized {
@@ -65,14 +258,14 @@ pub mod vouch_verifier {
 
     /// Pause the protocol (emergency stop)
     /// Only pause_authority can call this
-    pub fn pause_protocol(ctx: Context<AdminControl>) -> Result<()> {
+    pub fn pause_protocol(ctx: Context<PauseControl>) -> Result<()> {
         let config = &mut ctx.accounts.config;
         require!(!config.is_paused, VouchError::AlreadyPaused);
 
         config.is_paused = true;
 
         emit!(ProtocolPaused {
-            admin: ctx.accounts.admin.key(),
+            admin: ctx.accounts.pause_authority.key(),
             timestamp: Clock::get()?.unix_timestamp,
         });
 
@@ -81,14 +274,39 @@ pub mod vouch_verifier {
 
     /// Unpause the protocol (resume operations)
     /// Only pause_authority can call this
-    pub fn unpause_protocol(ctx: Context<AdminControl>) -> Result<()> {
+    pub fn unpause_protocol(ctx: Context<PauseControl>) -> Result<()> {
         let config = &mut ctx.accounts.config;
         require!(config.is_paused, VouchError::NotPaused);
 
         config.is_paused = false;
 
         emit!(ProtocolUnpaused {
+            admin: ctx.accounts.pause_authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Delegate emergency pause powers (e.g. to a hot key)
+    /// Only admin can call this; admin keeps all other controls
+    pub fn set_pause_authority(
+        ctx: Context<AdminControl>,
+        new_pause_authority: Pubkey,
+    ) -> Result<()> {
+        require!(
+            new_pause_authority != Pubkey::default(),
+            VouchError::InvalidPauseAuthority
+        );
+
+        let config = &mut ctx.accounts.config;
+        let old_pause_authority = config.pause_authority;
+        config.pause_authority = new_pause_authority;
+
+        emit!(PauseAuthorityUpdated {
             admin: ctx.accounts.admin.key(),
+            old_pause_authority,
+            new_pause_authority,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
@@ -96,26 +314,44 @@ pub mod vouch_verifier {
     }
 
     /// Update rate limit configuration
-    /// Only admin can call this
+    /// Only admin can call this. max_proofs_per_day is a global cap that applies
+    /// on top of the per-proof-type daily limits.
     pub fn update_rate_limits(
         ctx: Context<AdminControl>,
         max_proofs_per_day: u32,
+        max_dev_proofs_per_day: u32,
+        max_whale_proofs_per_day: u32,
         cooldown_seconds: i64,
     ) -> Result<()> {
-        require!(max_proofs_per_day > 0, VouchError::InvalidRateLimit);
+        require!(
+            (1..=RATE_LIMIT_WINDOW_SIZE as u32).contains(&max_proofs_per_day),
+            VouchError::InvalidRateLimit
+        );
+        require!(
+            max_dev_proofs_per_day > 0 && max_whale_proofs_per_day > 0,
+            VouchError::InvalidRateLimit
+        );
         require!(cooldown_seconds >= 0, VouchError::InvalidRateLimit);
 
         let config = &mut ctx.accounts.config;
         let old_max = config.max_proofs_per_day;
+        let old_max_dev = config.max_dev_proofs_per_day;
+        let old_max_whale = config.max_whale_proofs_per_day;
         let old_cooldown = config.cooldown_seconds;
 
         config.max_proofs_per_day = max_proofs_per_day;
+        config.max_dev_proofs_per_day = max_dev_proofs_per_day;
+        config.max_whale_proofs_per_day = max_whale_proofs_per_day;
         config.cooldown_seconds = cooldown_seconds;
 
         emit!(RateLimitsUpdated {
             admin: ctx.accounts.admin.key(),
             old_max_proofs_per_day: old_max,
             new_max_proofs_per_day: max_proofs_per_day,
+            old_max_dev_proofs_per_day: old_max_dev,
+            new_max_dev_proofs_per_day: max_dev_proofs_per_day,
+            old_max_whale_proofs_per_day: old_max_whale,
+            new_max_whale_proofs_per_day: max_whale_proofs_per_day,
             old_cooldown_seconds: old_cooldown,
             new_cooldown_seconds: cooldown_seconds,
             timestamp: Clock::get()?.unix_timestamp,
@@ -124,1652 +360,12663 @@ pub mod vouch_verifier {
         Ok(())
     }
 
-    /// Transfer admin authority to a new address
-    /// Only current admin can call this
-    pub fn transfer_admin(ctx: Context<AdminControl>, new_admin: Pubkey) -> Result<()> {
+    /// Update the protocol-wide cap on attestations per UTC day
+    /// Only admin can call this. Zero means unlimited.
+    pub fn set_max_global_proofs_per_day(
+        ctx: Context<AdminControl>,
+        max_global_proofs_per_day: u32,
+    ) -> Result<()> {
         let config = &mut ctx.accounts.config;
-        let old_admin = config.admin;
-
-        config.admin = new_admin;
-        config.pause_authority = new_admin;
+        let old_max_global_proofs_per_day = config.max_global_proofs_per_day;
+        config.max_global_proofs_per_day = max_global_proofs_per_day;
 
-        emit!(AdminTransferred {
-            old_admin,
-            new_admin,
+        emit!(GlobalProofLimitUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_max_global_proofs_per_day,
+            new_max_global_proofs_per_day: max_global_proofs_per_day,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Migrate config account to add max_epoch_age field
-    /// Only admin can call this. This is a one-time migration for v2.
-    /// Uses raw account manipulation to bypass Anchor's deserialization which fails
-    /// when the account size doesn't match the new struct definition.
-    pub fn migrate_config(ctx: Context<MigrateConfig>, max_epoch_age: u64) -> Result<()> {
-        let config_info = &ctx.accounts.config;
-        let admin = &ctx.accounts.admin;
+    /// Update the clock-skew tolerance that shortens the rolling rate limit window
+    /// Only admin can call this. Zero keeps the window at exactly 24h.
+    pub fn set_reset_skew_tolerance(
+        ctx: Context<AdminControl>,
+        reset_skew_tolerance_seconds: i64,
+    ) -> Result<()> {
+        require!(
+            (0..=MAX_RESET_SKEW_TOLERANCE_SECONDS).contains(&reset_skew_tolerance_seconds),
+            VouchError::InvalidRateLimit
+        );
 
-        // Verify admin from raw account data
-        // Admin pubkey is stored at offset 8 (after discriminator), 32 bytes
-        {
-            let data = config_info.try_borrow_data()?;
-            require!(data.len() >= 40, VouchError::InvalidCommitment); // Need at least discriminator + admin
-            let stored_admin = Pubkey::try_from(&data[8..40]).map_err(|_| VouchError::Unauthorized)?;
-            require!(stored_admin == admin.key(), VouchError::Unauthorized);
-        }
+        let config = &mut ctx.accounts.config;
+        let old_tolerance = config.reset_skew_tolerance_seconds;
+        config.reset_skew_tolerance_seconds = reset_skew_tolerance_seconds;
 
-        // Calculate new space: 8 (discriminator) + ConfigAccount size
-        let new_space = 8 + ConfigAccount::INIT_SPACE;
-        let current_space = config_info.data_len();
+        emit!(ResetSkewToleranceUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_tolerance_seconds: old_tolerance,
+            new_tolerance_seconds: reset_skew_tolerance_seconds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        msg!("Migrating config: current size = {}, new size = {}", current_space, new_space);
+        Ok(())
+    }
 
-        // If already at correct size, just update max_epoch_age
-        if current_space >= new_space {
-            msg!("Config already at correct size, updating max_epoch_age");
-            let mut data = config_info.try_borrow_mut_data()?;
-            // max_epoch_age is at offset: 8 (discriminator) + 32 (admin) + 32 (pause_authority) + 4 (verifier_count) + 1 (is_paused) + 4 (max_proofs_per_day) + 8 (cooldown_seconds) = 89
-            let offset = 89;
-            data[offset..offset + 8].copy_from_slice(&max_epoch_age.to_le_bytes());
-            msg!("Config migrated: max_epoch_age set to {}", max_epoch_age);
-            return Ok(());
-        }
+    /// Choose between validator wall-clock time and slot-derived time
+    /// Only admin can call this. Time is re-anchored at the current slot to the
+    /// current effective time, so switching sources never moves time backwards
+    /// by more than the clocks disagree. ms_per_slot is ignored when disabling.
+    pub fn set_time_source(
+        ctx: Context<AdminControl>,
+        use_slot_based_time: bool,
+        ms_per_slot: u64,
+    ) -> Result<()> {
+        require!(
+            !use_slot_based_time || (MIN_MS_PER_SLOT..=MAX_MS_PER_SLOT).contains(&ms_per_slot),
+            VouchError::InvalidTimeSource
+        );
 
-        // Reallocate the account
-        let rent = Rent::get()?;
-        let new_minimum_balance = rent.minimum_balance(new_space);
-        let lamports_diff = new_minimum_balance.saturating_sub(config_info.lamports());
+        let anchor_timestamp = effective_now(&ctx.accounts.config)?;
+        let config = &mut ctx.accounts.config;
+        config.use_slot_based_time = use_slot_based_time;
+        config.ms_per_slot = if use_slot_based_time { ms_per_slot } else { 0 };
+        config.time_anchor_slot = Clock::get()?.slot;
+        config.time_anchor_timestamp = anchor_timestamp;
 
-        if lamports_diff > 0 {
-            // Transfer lamports from admin to config account
-            let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
-                admin.key,
-                config_info.key,
-                lamports_diff,
-            );
-            anchor_lang::solana_program::program::invoke(
-                &transfer_ix,
-                &[
-                    admin.to_account_info(),
-                    config_info.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-            )?;
-        }
+        emit!(TimeSourceUpdated {
+            admin: ctx.accounts.admin.key(),
+            use_slot_based_time,
+            ms_per_slot: config.ms_per_slot,
+            anchor_slot: config.time_anchor_slot,
+            anchor_timestamp,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Reallocate
-        config_info.realloc(new_space, false)?;
+        Ok(())
+    }
 
-        // Write max_epoch_age at the correct offset
-        let mut data = config_info.try_borrow_mut_data()?;
-        let offset = 89; // After admin(32) + pause_authority(32) + verifier_count(4) + is_paused(1) + max_proofs_per_day(4) + cooldown_seconds(8) + discriminator(8)
-        data[offset..offset + 8].copy_from_slice(&max_epoch_age.to_le_bytes());
+    /// Set how many preceding instructions are searched for the Ed25519 verify instruction
+    /// Only admin can call this. 1 requires strict adjacency.
+    pub fn set_ed25519_scan_distance(
+        ctx: Context<AdminControl>,
+        max_ed25519_scan_distance: u8,
+    ) -> Result<()> {
+        require!(
+            (1..=MAX_ED25519_SCAN_DISTANCE).contains(&max_ed25519_scan_distance),
+            VouchError::InvalidScanDistance
+        );
 
-        msg!("Config migrated: max_epoch_age set to {}", max_epoch_age);
+        let config = &mut ctx.accounts.config;
+        let old_distance = config.max_ed25519_scan_distance;
+        config.max_ed25519_scan_distance = max_ed25519_scan_distance;
+
+        emit!(Ed25519ScanDistanceUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_scan_distance: old_distance,
+            new_scan_distance: max_ed25519_scan_distance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         Ok(())
     }
 
-    /// Fix config layout after broken migration
-    /// This is a one-time fix for the max_epoch_age migration that didn't shift existing fields.
-    /// The previous migration wrote max_epoch_age at offset 89 but didn't move total_proofs_verified
-    /// and bump to their new positions (97-104 and 105 respectively).
-    pub fn fix_config_layout(ctx: Context<MigrateConfig>) -> Result<()> {
-        let config_info = &ctx.accounts.config;
-        let admin = &ctx.accounts.admin;
-
-        // Verify admin from raw account data
-        {
-            let data = config_info.try_borrow_data()?;
-            require!(data.len() >= 40, VouchError::InvalidCommitment);
-            let stored_admin = Pubkey::try_from(&data[8..40]).map_err(|_| VouchError::Unauthorized)?;
-            require!(stored_admin == admin.key(), VouchError::Unauthorized);
-        }
-
-        // Verify account is at correct size (106 bytes)
-        require!(config_info.data_len() == 106, VouchError::InvalidCommitment);
+    /// Require each Ed25519 verify instruction to back at most one program instruction
+    /// Only admin can call this. When enabled, no other vouch-verifier
+    /// instruction may sit between the matched Ed25519 instruction and its
+    /// consumer, so one signature can't satisfy several instructions in a
+    /// multi-attestation transaction when the scan distance is above 1.
+    pub fn set_exclusive_ed25519_instructions(
+        ctx: Context<AdminControl>,
+        exclusive_ed25519_instructions: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.exclusive_ed25519_instructions = exclusive_ed25519_instructions;
 
-        // Compute correct bump
-        let (expected_pda, bump) = Pubkey::find_program_address(&[b"config"], &crate::ID);
-        require!(expected_pda == config_info.key(), VouchError::InvalidCommitment);
+        emit!(Ed25519ExclusivityUpdated {
+            admin: ctx.accounts.admin.key(),
+            exclusive_ed25519_instructions,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        msg!("Fixing config layout: computed bump = {}", bump);
+        Ok(())
+    }
 
-        // Read current values for logging
-        {
-            let data = config_info.try_borrow_data()?;
-            msg!("Current value at offset 97 (old bump stuck there): {}", data[97]);
-            msg!("Current value at offset 105 (where bump should be): {}", data[105]);
-        }
+    /// Set the protocol-wide minimum hold between registration and claim
+    /// Only admin can call this. Applied as a floor over each campaign's min_hold_seconds.
+    /// Zero means no global floor.
+    pub fn set_global_min_hold(
+        ctx: Context<AdminControl>,
+        global_min_hold_seconds: i64,
+    ) -> Result<()> {
+        require!(global_min_hold_seconds >= 0, VouchError::InvalidHoldPeriod);
 
-        // Fix the layout:
-        // - Offset 89-96: max_epoch_age (keep as is - was written by migrate_config)
-        // - Offset 97-104: total_proofs_verified (set to 0, was lost during migration)
-        // - Offset 105: bump (write the correct value)
-        {
-            let mut data = config_info.try_borrow_mut_data()?;
-            // Set total_proofs_verified to 0
-            data[97..105].copy_from_slice(&0u64.to_le_bytes());
-            // Set bump to correct value
-            data[105] = bump;
-        }
+        let config = &mut ctx.accounts.config;
+        let old_hold = config.global_min_hold_seconds;
+        config.global_min_hold_seconds = global_min_hold_seconds;
 
-        msg!("Config layout fixed: total_proofs_verified=0, bump={}", bump);
+        emit!(GlobalMinHoldUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_min_hold_seconds: old_hold,
+            new_min_hold_seconds: global_min_hold_seconds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         Ok(())
     }
 
-    // === Rate Limiting ===
+    /// Set the minimum gap between any two claims by the same wallet, across campaigns
+    /// Only admin can call this. Zero disables the cooldown; while nonzero,
+    /// claim_airdrop requires the claimer's WalletClaimLimit account.
+    pub fn set_global_claim_cooldown(
+        ctx: Context<AdminControl>,
+        global_claim_cooldown_seconds: i64,
+    ) -> Result<()> {
+        require!(global_claim_cooldown_seconds >= 0, VouchError::InvalidRateLimit);
 
-    /// Initialize rate limit tracking for a wallet
-    /// Creates a WalletRateLimit PDA for the wallet
-    pub fn init_rate_limit(ctx: Context<InitRateLimit>) -> Result<()> {
-        let rate_limit = &mut ctx.accounts.rate_limit;
-        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.config;
+        let old_cooldown = config.global_claim_cooldown_seconds;
+        config.global_claim_cooldown_seconds = global_claim_cooldown_seconds;
 
-        rate_limit.wallet = ctx.accounts.wallet.key();
-        rate_limit.proofs_today = 0;
-        rate_limit.last_proof_at = 0;
-        rate_limit.day_start = now;
-        rate_limit.total_proofs = 0;
-        rate_limit.bump = ctx.bumps.rate_limit;
+        emit!(GlobalClaimCooldownUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_cooldown_seconds: old_cooldown,
+            new_cooldown_seconds: global_claim_cooldown_seconds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        emit!(RateLimitInitialized {
-            wallet: rate_limit.wallet,
-            timestamp: now,
+        Ok(())
+    }
+
+    /// Toggle whether record_attestation requires a commitment owned by the recipient
+    /// Only admin can call this
+    pub fn set_require_commitment(
+        ctx: Context<AdminControl>,
+        require_commitment: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.require_commitment = require_commitment;
+
+        emit!(CommitmentRequirementUpdated {
+            admin: ctx.accounts.admin.key(),
+            require_commitment,
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    // === Verifier Management ===
+    /// Scope require_commitment to a set of built-in proof types
+    /// commitment_proof_types is a proof_type_bit mask (0 = every attestation).
+    /// Only admin can call this
+    pub fn set_commitment_proof_types(
+        ctx: Context<AdminControl>,
+        commitment_proof_types: u8,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_commitment_proof_types = config.commitment_proof_types;
+        config.commitment_proof_types = commitment_proof_types;
 
-    /// Add an authorized verifier
-    /// Only admin can add verifiers
-    pub fn add_verifier(ctx: Context<AddVerifier>, verifier_pubkey: Pubkey) -> Result<()> {
-        // Check protocol is not paused
-        require!(!ctx.accounts.config.is_paused, VouchError::ProtocolPaused);
+        emit!(CommitmentProofTypesUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_commitment_proof_types,
+            new_commitment_proof_types: commitment_proof_types,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        let verifier_account = &mut ctx.accounts.verifier_account;
-        verifier_account.verifier = verifier_pubkey;
-        verifier_account.is_active = true;
-        verifier_account.added_at = Clock::get()?.unix_timestamp;
-        verifier_account.attestation_count = 0;
-        verifier_account.bump = ctx.bumps.verifier_account;
+        Ok(())
+    }
 
+    /// Toggle commitment-binding mode for attestations
+    /// When enabled, record_attestation requires the recipient's CommitmentAccount and
+    /// the verifier must sign its commitment bytes as part of the attestation message.
+    /// Only admin can call this
+    pub fn set_bind_commitment(ctx: Context<AdminControl>, bind_commitment: bool) -> Result<()> {
         let config = &mut ctx.accounts.config;
-        config.verifier_count = config.verifier_count.checked_add(1).ok_or(VouchError::Overflow)?;
+        config.bind_commitment = bind_commitment;
 
-        emit!(VerifierAdded {
-            verifier: verifier_pubkey,
+        emit!(CommitmentBindingUpdated {
             admin: ctx.accounts.admin.key(),
-            timestamp: verifier_account.added_at,
+            bind_commitment,
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Remove an authorized verifier
-    pub fn remove_verifier(ctx: Context<RemoveVerifier>) -> Result<()> {
-        // Check protocol is not paused
-        require!(!ctx.accounts.config.is_paused, VouchError::ProtocolPaused);
+    /// Configure the attestation-rate circuit breaker
+    /// Only admin can call this. Once more than max_attestations_per_window
+    /// attestations arrive within window_seconds, the next attestation pauses the
+    /// protocol instead of being recorded. Zero disables the breaker. Changing
+    /// the threshold resets the counter.
+    pub fn set_auto_pause_threshold(
+        ctx: Context<AdminControl>,
+        max_attestations_per_window: u32,
+        window_seconds: i64,
+    ) -> Result<()> {
+        require!(
+            max_attestations_per_window == 0 || window_seconds > 0,
+            VouchError::InvalidRateLimit
+        );
 
-        let verifier_account = &mut ctx.accounts.verifier_account;
-        verifier_account.is_active = false;
+        let config = &mut ctx.accounts.config;
+        let old_max = config.max_attestations_per_window;
+        let old_window = config.attestation_window_seconds;
+        config.max_attestations_per_window = max_attestations_per_window;
+        config.attestation_window_seconds = window_seconds;
+        config.attestation_rate_level = 0;
+        config.attestation_rate_updated_at = effective_now(config)?;
+
+        emit!(AutoPauseThresholdUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_max_attestations_per_window: old_max,
+            new_max_attestations_per_window: max_attestations_per_window,
+            old_window_seconds: old_window,
+            new_window_seconds: window_seconds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
 
+    /// Turn enforcement of the recipient blocklist on or off
+    /// Only admin can call this. While enabled, signed attestations and claims
+    /// reject recipients with a BlockedRecipient entry. Off by
+    /// default so permissionless deployments are unaffected.
+    pub fn set_enforce_recipient_blocklist(
+        ctx: Context<AdminControl>,
+        enforce_recipient_blocklist: bool,
+    ) -> Result<()> {
         let config = &mut ctx.accounts.config;
-        config.verifier_count = config.verifier_count.saturating_sub(1);
+        config.enforce_recipient_blocklist = enforce_recipient_blocklist;
 
-        emit!(VerifierRemoved {
-            verifier: verifier_account.verifier,
+        emit!(RecipientBlocklistEnforcementUpdated {
             admin: ctx.accounts.admin.key(),
+            enforce_recipient_blocklist,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    // === Attestation Recording ===
+    /// Turn per-signature replay records on or off
+    /// Only admin can call this. While enabled, every instruction that accepts a
+    /// verifier signature creates a UsedSignature PDA for it and rejects a
+    /// signature that already has one. Off by default: the nullifier already stops
+    /// replays, so the extra account is opt-in.
+    pub fn set_track_used_signatures(
+        ctx: Context<AdminControl>,
+        track_used_signatures: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.track_used_signatures = track_used_signatures;
 
-    /// Record a verified attestation from an authorized verifier
-    /// This is the production-ready verification flow:
-    /// 1. Client generates proof
-    /// 2. Off-chain verifier verifies proof and signs attestation
-    /// 3. Client submits attestation to this instruction
-    /// 4. On-chain program validates signature and records result
-    ///
-    /// New parameters (v2):
-    /// - epoch: Day number since Unix epoch (prevents replay attacks)
-    /// - data_hash: Hash of private data (ensures data integrity)
-    pub fn record_attestation(
-        ctx: Context<RecordAttestation>,
-        attestation_hash: [u8; 32],
-        proof_type_value: u8,
-        nullifier: [u8; 32],
-        epoch: u64,
-        data_hash: [u8; 32],
-        signature: [u8; 64],
-    ) -> Result<()> {
-        let config = &ctx.accounts.config;
+        emit!(UsedSignatureTrackingUpdated {
+            admin: ctx.accounts.admin.key(),
+            track_used_signatures,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Block a recipient from receiving attestations and claiming airdrops
+    /// Only admin can call this. Takes effect while
+    /// config.enforce_recipient_blocklist is set.
+    pub fn block_recipient(ctx: Context<BlockRecipient>, recipient: Pubkey) -> Result<()> {
         let now = Clock::get()?.unix_timestamp;
+        let blocked = &mut ctx.accounts.blocked_recipient;
+        blocked.recipient = recipient;
+        blocked.blocked_by = ctx.accounts.admin.key();
+        blocked.blocked_at = now;
+        blocked.bump = ctx.bumps.blocked_recipient;
 
-        // Check protocol is not paused
-        require!(!config.is_paused, VouchError::ProtocolPaused);
+        emit!(RecipientBlocked {
+            admin: ctx.accounts.admin.key(),
+            recipient,
+            timestamp: now,
+        });
 
-        // Verify the verifier is authorized
-        let verifier_account = &ctx.accounts.verifier_account;
-        require!(verifier_account.is_active, VouchError::VerifierNotAuthorized);
+        Ok(())
+    }
 
-        // Validate epoch is recent (within max_epoch_age days)
-        let current_epoch = (now as u64) / 86400; // Day number since Unix epoch
-        let epoch_age = current_epoch.saturating_sub(epoch);
-        require!(epoch_age <= config.max_epoch_age, VouchError::EpochTooOld);
-        // Also reject future epochs (clock manipulation attempt)
-        require!(epoch <= current_epoch + 1, VouchError::EpochInFuture);
+    /// Remove a recipient from the blocklist (closes the entry to the admin)
+    /// Only admin can call this
+    pub fn unblock_recipient(ctx: Context<UnblockRecipient>) -> Result<()> {
+        emit!(RecipientUnblocked {
+            admin: ctx.accounts.admin.key(),
+            recipient: ctx.accounts.blocked_recipient.recipient,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Check and update rate limits
-        let rate_limit = &mut ctx.accounts.rate_limit;
-        check_and_update_rate_limit(rate_limit, config, now)?;
+        Ok(())
+    }
 
-        // Build the attestation message that was signed (v2 format with epoch and data_hash)
-        let message = build_attestation_message_v2(
-            proof_type_value,
-            &nullifier,
-            epoch,
-            &data_hash,
-            &attestation_hash,
-        );
+    /// Cap the number of campaigns that may be active (not yet completed) at once
+    /// Only admin can call this. Zero means unlimited.
+    pub fn set_max_concurrent_open_campaigns(
+        ctx: Context<AdminControl>,
+        max_concurrent_open_campaigns: u32,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_max = config.max_concurrent_open_campaigns;
+        config.max_concurrent_open_campaigns = max_concurrent_open_campaigns;
 
-        // Verify the Ed25519 signature using instruction introspection
-        // The transaction must include an Ed25519Program verify instruction
-        // immediately before this instruction
-        verify_ed25519_signature(
-            &ctx.accounts.instructions_sysvar.to_account_info(),
-            &verifier_account.verifier,
-            &signature,
-            &message,
-        )?;
+        emit!(MaxConcurrentCampaignsUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_max_concurrent_open_campaigns: old_max,
+            new_max_concurrent_open_campaigns: max_concurrent_open_campaigns,
+            active_campaign_count: config.active_campaign_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Check nullifier hasn't been used
-        let nullifier_account = &ctx.accounts.nullifier_account;
-        require!(!nullifier_account.is_used, VouchError::NullifierAlreadyUsed);
+        Ok(())
+    }
 
-        // Mark nullifier as used and store epoch/data_hash
-        let nullifier_account = &mut ctx.accounts.nullifier_account;
-        nullifier_account.is_used = true;
-        nullifier_account.used_at = now;
-        nullifier_account.epoch = epoch;
-        nullifier_account.data_hash = data_hash;
-        nullifier_account.proof_type = match proof_type_value {
-            1 => ProofType::DeveloperReputation,
-            2 => ProofType::WhaleTrading,
-            _ => return Err(VouchError::InvalidProofType.into()),
-        };
+    /// Toggle whether verifiers may upgrade a used nullifier's proof type in place
+    /// Only admin can call this
+    pub fn set_allow_credential_upgrades(
+        ctx: Context<AdminControl>,
+        allow_credential_upgrades: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.allow_credential_upgrades = allow_credential_upgrades;
 
-        // Update verifier stats
-        let verifier_account = &mut ctx.accounts.verifier_account;
-        verifier_account.attestation_count = verifier_account
-            .attestation_count
-            .checked_add(1)
-            .ok_or(VouchError::Overflow)?;
+        emit!(CredentialUpgradePolicyUpdated {
+            admin: ctx.accounts.admin.key(),
+            allow_credential_upgrades,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Update global stats
+        Ok(())
+    }
+
+    /// Choose whether an emergency pause also freezes the airdrop subsystem
+    /// Only admin can call this. When true, registration, funding and claims
+    /// fail with ProtocolPaused while the protocol is paused.
+    pub fn set_campaigns_respect_protocol_pause(
+        ctx: Context<AdminControl>,
+        campaigns_respect_protocol_pause: bool,
+    ) -> Result<()> {
         let config = &mut ctx.accounts.config;
-        config.total_proofs_verified = config
-            .total_proofs_verified
-            .checked_add(1)
-            .ok_or(VouchError::Overflow)?;
+        config.campaigns_respect_protocol_pause = campaigns_respect_protocol_pause;
 
-        emit!(AttestationRecorded {
-            nullifier,
-            attestation_hash,
-            epoch,
-            data_hash,
-            verifier: verifier_account.verifier,
-            proof_type: nullifier_account.proof_type,
-            recipient: ctx.accounts.recipient.key(),
-            timestamp: nullifier_account.used_at,
-            signature,
+        emit!(CampaignPausePolicyUpdated {
+            admin: ctx.accounts.admin.key(),
+            campaigns_respect_protocol_pause,
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    // === Commitment & Nullifier Management ===
+    /// Set the share of reaped registration rent paid to the reaper
+    /// Only admin can call this. The remainder goes to the campaign creator.
+    pub fn set_reaper_bounty(ctx: Context<AdminControl>, reaper_bounty_bps: u16) -> Result<()> {
+        require!(
+            (reaper_bounty_bps as u64) <= BPS_DENOMINATOR,
+            VouchError::InvalidFeeSplit
+        );
 
-    /// Initialize a new commitment for a wallet
-    /// The commitment = hash(wallet_pubkey + secret) is stored on-chain
-    pub fn create_commitment(ctx: Context<CreateCommitment>, commitment: [u8; 32]) -> Result<()> {
-        let commitment_account = &mut ctx.accounts.commitment_account;
-        commitment_account.commitment = commitment;
-        commitment_account.owner = ctx.accounts.owner.key();
-        commitment_account.created_at = Clock::get()?.unix_timestamp;
-        commitment_account.bump = ctx.bumps.commitment_account;
+        let config = &mut ctx.accounts.config;
+        let old_reaper_bounty_bps = config.reaper_bounty_bps;
+        config.reaper_bounty_bps = reaper_bounty_bps;
 
-        emit!(CommitmentCreated {
-            owner: ctx.accounts.owner.key(),
-            commitment,
-            timestamp: commitment_account.created_at,
+        emit!(ReaperBountyUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_reaper_bounty_bps,
+            new_reaper_bounty_bps: reaper_bounty_bps,
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Initialize a nullifier account (must be called before record_attestation)
-    /// This separates account creation from verification for security
-    pub fn init_nullifier(ctx: Context<InitNullifier>, nullifier: [u8; 32]) -> Result<()> {
-        let nullifier_account = &mut ctx.accounts.nullifier_account;
-        nullifier_account.nullifier = nullifier;
-        nullifier_account.is_used = false;
-        nullifier_account.used_at = 0;
-        nullifier_account.epoch = 0;
-        nullifier_account.data_hash = [0u8; 32];
-        nullifier_account.proof_type = ProofType::Unset;
-        nullifier_account.bump = ctx.bumps.nullifier_account;
+    /// Set the maximum age of the underlying proof at attestation time
+    /// Only admin can call this. Zero disables the check.
+    pub fn set_max_proof_age(ctx: Context<AdminControl>, max_proof_age_seconds: i64) -> Result<()> {
+        require!(max_proof_age_seconds >= 0, VouchError::InvalidProofAge);
+
+        let config = &mut ctx.accounts.config;
+        let old_max_proof_age_seconds = config.max_proof_age_seconds;
+        config.max_proof_age_seconds = max_proof_age_seconds;
+
+        emit!(MaxProofAgeUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_max_proof_age_seconds,
+            new_max_proof_age_seconds: max_proof_age_seconds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         Ok(())
     }
 
-    // NOTE: Direct on-chain proof verification instructions (verify_dev_reputation, verify_whale_trading)
-    // have been removed. UltraHonk proofs cannot be verified natively on Solana.
-    //
-    // For production use, the correct flow is:
-    // 1. Client generates UltraHonk proof in browser
-    // 2. Client sends proof to off-chain verifier service
-    // 3. Verifier cryptographically verifies proof and signs attestation
-    // 4. Client calls record_attestation with the signed attestation
-    // 5. On-chain program verifies Ed25519 signature and records result
-    //
-    // See: https://github.com/solana-foundation/noir-examples for Groth16 alternative
+    /// Toggle whether record_attestation must bind a proof commitment
+    /// Only admin can call this. Paths that carry no proof commitment (registry,
+    /// merkle and batched attestations) are rejected while it is set.
+    pub fn set_require_proof_commitment(
+        ctx: Context<AdminControl>,
+        require_proof_commitment: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.require_proof_commitment = require_proof_commitment;
 
-    // === Private Airdrop Registry ===
-    // Enables privacy-preserving airdrops where:
-    // - Recipients prove eligibility via Vouch credentials
-    // - Distribution amounts are hidden via ShadowWire
-    // - No one can link real wallets to airdrop amounts
+        emit!(ProofCommitmentRequirementUpdated {
+            admin: ctx.accounts.admin.key(),
+            require_proof_commitment,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-    /// Create a new airdrop campaign
-    /// Only the campaign creator can distribute to registered addresses
-    /// Create a tiered airdrop campaign
-    /// - base_amount: Everyone gets this (open registration)
-    /// - dev_bonus: Additional amount for verified developers
-    /// - whale_bonus: Additional amount for verified whales
-    pub fn create_airdrop_campaign(
-        ctx: Context<CreateAirdropCampaign>,
-        campaign_id: [u8; 32],
-        name: String,
-        token_mint: Pubkey,
-        base_amount: u64,
-        dev_bonus: u64,
-        whale_bonus: u64,
-        registration_deadline: i64,
-    ) -> Result<()> {
-        require!(name.len() <= 64, VouchError::NameTooLong);
-        require!(registration_deadline > Clock::get()?.unix_timestamp, VouchError::InvalidDeadline);
-        // At least base amount must be set (tiered model requires base)
-        require!(base_amount > 0, VouchError::InvalidAmount);
+        Ok(())
+    }
 
-        let campaign = &mut ctx.accounts.campaign;
-        campaign.campaign_id = campaign_id;
-        campaign.creator = ctx.accounts.creator.key();
-        campaign.name = name;
-        campaign.token_mint = token_mint;
-        campaign.base_amount = base_amount;
-        campaign.dev_bonus = dev_bonus;
-        campaign.whale_bonus = whale_bonus;
-        campaign.registration_deadline = registration_deadline;
-        campaign.status = CampaignStatus::Open;
-        campaign.total_registrations = 0;
-        campaign.open_registrations = 0;
-        campaign.dev_registrations = 0;
-        campaign.whale_registrations = 0;
-        campaign.created_at = Clock::get()?.unix_timestamp;
-        campaign.vault_balance = 0;
-        campaign.total_claimed = 0;
-        campaign.bump = ctx.bumps.campaign;
+    /// Set the off-chain governance key allowed to sign verifier sets
+    /// Only admin can call this. Pubkey::default() disables signed imports.
+    pub fn set_governance_key(ctx: Context<AdminControl>, governance_key: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_governance_key = config.governance_key;
+        config.governance_key = governance_key;
 
-        emit!(AirdropCampaignCreated {
-            campaign_id,
-            creator: campaign.creator,
-            name: campaign.name.clone(),
-            token_mint,
-            base_amount,
-            dev_bonus,
-            whale_bonus,
-            registration_deadline,
-            timestamp: campaign.created_at,
+        emit!(GovernanceKeyUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_governance_key,
+            new_governance_key: governance_key,
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Register for an airdrop campaign using a verified Vouch credential
-    /// Gets base_amount + bonus (dev_bonus or whale_bonus based on credential)
-    /// Links the user's nullifier to their ShadowWire address for private distribution
-    pub fn register_for_airdrop(
-        ctx: Context<RegisterForAirdrop>,
-        shadow_wire_address: String,
+    /// Record the ShadowWire program that airdrop distributions go through
+    /// Only admin can call this. Pubkey::default() means not configured.
+    pub fn set_shadow_wire_program(
+        ctx: Context<AdminControl>,
+        shadow_wire_program_id: Pubkey,
     ) -> Result<()> {
-        let campaign = &ctx.accounts.campaign;
-        let nullifier_account = &ctx.accounts.nullifier_account;
-        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.config;
+        let old_program_id = config.shadow_wire_program_id;
+        config.shadow_wire_program_id = shadow_wire_program_id;
 
-        // Verify campaign is open
-        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
-        require!(now < campaign.registration_deadline, VouchError::RegistrationClosed);
+        emit!(ShadowWireProgramUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_program_id,
+            new_program_id: shadow_wire_program_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Verify nullifier is used (proves user has Vouch credential)
-        require!(nullifier_account.is_used, VouchError::NullifierNotVerified);
+        Ok(())
+    }
 
-        // Validate ShadowWire address format (base58, 32-44 chars)
+    /// Nominate a new admin (step one of a two-step transfer)
+    /// Only current admin can call this. Authority moves once the nominee
+    /// calls accept_admin; nominating again replaces the pending admin.
+    pub fn transfer_admin(ctx: Context<AdminControl>, new_admin: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.pending_admin = new_admin;
+
+        emit!(AdminTransferInitiated {
+            admin: config.admin,
+            pending_admin: new_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Accept a pending admin transfer (step two)
+    /// Must be signed by config.pending_admin. pause_authority follows the admin
+    /// only if it had not been delegated to a separate key.
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require!(config.pending_admin != Pubkey::default(), VouchError::NoPendingAdmin);
         require!(
-            shadow_wire_address.len() >= 32 && shadow_wire_address.len() <= 44,
-            VouchError::InvalidShadowWireAddress
+            config.pending_admin == ctx.accounts.new_admin.key(),
+            VouchError::Unauthorized
         );
 
-        // Create registration
-        let registration = &mut ctx.accounts.registration;
-        registration.campaign = campaign.key();
-        registration.nullifier = nullifier_account.nullifier;
-        registration.shadow_wire_address = shadow_wire_address.clone();
-        registration.proof_type = nullifier_account.proof_type;
-        registration.registered_at = now;
-        registration.is_distributed = false;
-        registration.is_claimed = false;
-        registration.claimed_at = 0;
-        registration.claimed_amount = 0;
-        registration.bump = ctx.bumps.registration;
+        let old_admin = config.admin;
+        let new_admin = config.pending_admin;
+        if config.pause_authority == old_admin {
+            config.pause_authority = new_admin;
+        }
+        config.admin = new_admin;
+        config.pending_admin = Pubkey::default();
 
-        // Update campaign stats
-        let campaign = &mut ctx.accounts.campaign;
-        campaign.total_registrations = campaign
-            .total_registrations
-            .checked_add(1)
-            .ok_or(VouchError::Overflow)?;
+        emit!(AdminTransferred {
+            old_admin,
+            new_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        match nullifier_account.proof_type {
-            ProofType::DeveloperReputation => {
-                campaign.dev_registrations = campaign
-                    .dev_registrations
-                    .checked_add(1)
-                    .ok_or(VouchError::Overflow)?;
-            }
-            ProofType::WhaleTrading => {
-                campaign.whale_registrations = campaign
-                    .whale_registrations
-                    .checked_add(1)
-                    .ok_or(VouchError::Overflow)?;
-            }
-            _ => return Err(VouchError::InvalidProofType.into()),
-        }
+        Ok(())
+    }
 
-        emit!(AirdropRegistration {
-            campaign_id: campaign.campaign_id,
-            nullifier: nullifier_account.nullifier,
-            shadow_wire_address,
-            proof_type: nullifier_account.proof_type,
-            timestamp: now,
+    /// Cancel a pending admin transfer
+    /// Only current admin can call this
+    pub fn cancel_admin_transfer(ctx: Context<AdminControl>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require!(config.pending_admin != Pubkey::default(), VouchError::NoPendingAdmin);
+
+        let cancelled_admin = config.pending_admin;
+        config.pending_admin = Pubkey::default();
+
+        emit!(AdminTransferCancelled {
+            admin: config.admin,
+            cancelled_admin,
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Register for an airdrop campaign without verification (open registration)
-    /// Gets only base_amount (no bonus)
-    /// Uses wallet pubkey hash as unique identifier to prevent double registration
-    pub fn register_for_airdrop_open(
-        ctx: Context<RegisterForAirdropOpen>,
-        shadow_wire_address: String,
-    ) -> Result<()> {
-        let campaign = &ctx.accounts.campaign;
-        let now = Clock::get()?.unix_timestamp;
+    /// Migrate config account to add max_epoch_age field
+    /// Only admin can call this. This is a one-time migration for v2.
+    /// Uses raw account manipulation to bypass Anchor's deserialization which fails
+    /// when the account size doesn't match the new struct definition.
+    pub fn migrate_config(ctx: Context<MigrateConfig>, max_epoch_age: u64) -> Result<()> {
+        let config_info = &ctx.accounts.config;
+        let admin = &ctx.accounts.admin;
 
-        // Verify campaign is open
-        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
-        require!(now < campaign.registration_deadline, VouchError::RegistrationClosed);
+        // Verify admin from raw account data
+        // Admin pubkey is stored at offset 8 (after discriminator), 32 bytes
+        {
+            let data = config_info.try_borrow_data()?;
+            require!(data.len() >= 40, VouchError::InvalidCommitment); // Need at least discriminator + admin
+            let stored_admin = Pubkey::try_from(&data[8..40]).map_err(|_| VouchError::Unauthorized)?;
+            require!(stored_admin == admin.key(), VouchError::Unauthorized);
+        }
 
-        // Validate ShadowWire address format (base58, 32-44 chars)
-        require!(
-            shadow_wire_address.len() >= 32 && shadow_wire_address.len() <= 44,
-            VouchError::InvalidShadowWireAddress
-        );
+        // Calculate new space: 8 (discriminator) + ConfigAccount size
+        let new_space = 8 + ConfigAccount::INIT_SPACE;
+        let current_space = config_info.data_len();
 
-        // Create unique identifier from wallet pubkey (hash to 32 bytes)
-        let wallet_id = ctx.accounts.payer.key().to_bytes();
+        msg!("Migrating config: current size = {}, new size = {}", current_space, new_space);
 
-        // Create registration
-        let registration = &mut ctx.accounts.registration;
-        registration.campaign = campaign.key();
-        registration.nullifier = wallet_id; // Use wallet pubkey as identifier
-        registration.shadow_wire_address = shadow_wire_address.clone();
-        registration.proof_type = ProofType::Unset; // No verification
-        registration.registered_at = now;
-        registration.is_distributed = false;
-        registration.is_claimed = false;
-        registration.claimed_at = 0;
-        registration.claimed_amount = 0;
-        registration.bump = ctx.bumps.registration;
+        // If already at correct size, just update max_epoch_age
+        if current_space >= new_space {
+            msg!("Config already at correct size, updating max_epoch_age");
+            let mut data = config_info.try_borrow_mut_data()?;
+            // max_epoch_age is at offset: 8 (discriminator) + 32 (admin) + 32 (pause_authority) + 4 (verifier_count) + 1 (is_paused) + 4 (max_proofs_per_day) + 8 (cooldown_seconds) = 89
+            let offset = 89;
+            data[offset..offset + 8].copy_from_slice(&max_epoch_age.to_le_bytes());
+            msg!("Config migrated: max_epoch_age set to {}", max_epoch_age);
+            return Ok(());
+        }
 
-        // Update campaign stats
-        let campaign = &mut ctx.accounts.campaign;
-        campaign.total_registrations = campaign
-            .total_registrations
-            .checked_add(1)
-            .ok_or(VouchError::Overflow)?;
-        campaign.open_registrations = campaign
-            .open_registrations
-            .checked_add(1)
-            .ok_or(VouchError::Overflow)?;
+        // Reallocate the account
+        let rent = Rent::get()?;
+        let new_minimum_balance = rent.minimum_balance(new_space);
+        let lamports_diff = new_minimum_balance.saturating_sub(config_info.lamports());
 
-        emit!(AirdropRegistration {
-            campaign_id: campaign.campaign_id,
-            nullifier: wallet_id,
-            shadow_wire_address,
-            proof_type: ProofType::Unset,
-            timestamp: now,
-        });
+        if lamports_diff > 0 {
+            // Transfer lamports from admin to config account
+            let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+                admin.key,
+                config_info.key,
+                lamports_diff,
+            );
+            anchor_lang::solana_program::program::invoke(
+                &transfer_ix,
+                &[
+                    admin.to_account_info(),
+                    config_info.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        // Reallocate
+        config_info.resize(new_space)?;
+
+        // Write max_epoch_age at the correct offset
+        let mut data = config_info.try_borrow_mut_data()?;
+        let offset = 89; // After admin(32) + pause_authority(32) + verifier_count(4) + is_paused(1) + max_proofs_per_day(4) + cooldown_seconds(8) + discriminator(8)
+        data[offset..offset + 8].copy_from_slice(&max_epoch_age.to_le_bytes());
+
+        // Fields appended after `bump` were just zero-filled
+        if current_space <= CONFIG_V2_SPACE {
+            let mut config = ConfigAccount::try_deserialize(&mut &data[..])?;
+            apply_appended_config_defaults(&mut config);
+            config.try_serialize(&mut &mut data[..])?;
+        }
+
+        msg!("Config migrated: max_epoch_age set to {}", max_epoch_age);
 
         Ok(())
     }
 
-    /// Close registration for a campaign (prevents new registrations)
-    /// Only campaign creator can close
-    pub fn close_airdrop_registration(ctx: Context<CloseAirdropRegistration>) -> Result<()> {
-        let campaign = &mut ctx.accounts.campaign;
+    /// Fix config layout after broken migration
+    /// This is a one-time fix for the max_epoch_age migration that didn't shift existing fields.
+    /// The previous migration wrote max_epoch_age at offset 89 but didn't move total_proofs_verified
+    /// and bump to their new positions (97-104 and 105 respectively).
+    pub fn fix_config_layout(ctx: Context<MigrateConfig>) -> Result<()> {
+        let config_info = &ctx.accounts.config;
+        let admin = &ctx.accounts.admin;
 
-        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+        // Verify admin from raw account data
+        {
+            let data = config_info.try_borrow_data()?;
+            require!(data.len() >= 40, VouchError::InvalidCommitment);
+            let stored_admin = Pubkey::try_from(&data[8..40]).map_err(|_| VouchError::Unauthorized)?;
+            require!(stored_admin == admin.key(), VouchError::Unauthorized);
+        }
 
-        campaign.status = CampaignStatus::RegistrationClosed;
+        // Verify account is at correct size (106 bytes)
+        require!(
+            config_info.data_len() == CONFIG_V2_SPACE,
+            VouchError::InvalidCommitment
+        );
 
-        emit!(AirdropRegistrationClosed {
-            campaign_id: campaign.campaign_id,
-            total_registrations: campaign.total_registrations,
-            dev_registrations: campaign.dev_registrations,
-            whale_registrations: campaign.whale_registrations,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+        // Compute correct bump
+        let (expected_pda, bump) = Pubkey::find_program_address(&[b"config"], &crate::ID);
+        require!(expected_pda == config_info.key(), VouchError::InvalidCommitment);
+
+        msg!("Fixing config layout: computed bump = {}", bump);
+
+        // Read current values for logging
+        {
+            let data = config_info.try_borrow_data()?;
+            msg!("Current value at offset 97 (old bump stuck there): {}", data[97]);
+            msg!("Current value at offset 105 (where bump should be): {}", data[105]);
+        }
+
+        // Fix the layout:
+        // - Offset 89-96: max_epoch_age (keep as is - was written by migrate_config)
+        // - Offset 97-104: total_proofs_verified (set to 0, was lost during migration)
+        // - Offset 105: bump (write the correct value)
+        {
+            let mut data = config_info.try_borrow_mut_data()?;
+            // Set total_proofs_verified to 0
+            data[97..105].copy_from_slice(&0u64.to_le_bytes());
+            // Set bump to correct value
+            data[105] = bump;
+        }
+
+        msg!("Config layout fixed: total_proofs_verified=0, bump={}", bump);
 
         Ok(())
     }
 
-    /// Mark a registration as distributed (after sending via ShadowWire)
-    /// Only campaign creator can mark distributions
-    pub fn mark_airdrop_distributed(
-        ctx: Context<MarkAirdropDistributed>,
-        tx_signature: String,
-    ) -> Result<()> {
-        let registration = &mut ctx.accounts.registration;
+    // === Rate Limiting ===
 
-        require!(!registration.is_distributed, VouchError::AlreadyDistributed);
+    /// Initialize rate limit tracking for a wallet
+    /// Creates a WalletRateLimit PDA for the wallet with an empty rolling window.
+    /// day_start stays 0 ("not yet started") until the first proof.
+    pub fn init_rate_limit(ctx: Context<InitRateLimit>) -> Result<()> {
+        let rate_limit = &mut ctx.accounts.rate_limit;
+        let now = Clock::get()?.unix_timestamp;
 
-        registration.is_distributed = true;
-        registration.distributed_at = Clock::get()?.unix_timestamp;
-        registration.distribution_tx = tx_signature.clone();
+        rate_limit.wallet = ctx.accounts.wallet.key();
+        rate_limit.proofs_today = 0;
+        rate_limit.dev_proofs_today = 0;
+        rate_limit.whale_proofs_today = 0;
+        rate_limit.last_proof_at = 0;
+        rate_limit.day_start = 0;
+        rate_limit.total_proofs = 0;
+        rate_limit.recent_proofs = [RecentProof::default(); RATE_LIMIT_WINDOW_SIZE];
+        rate_limit.recent_head = 0;
+        rate_limit.exempt = false;
+        rate_limit.bump = ctx.bumps.rate_limit;
 
-        emit!(AirdropDistributed {
-            campaign_id: ctx.accounts.campaign.campaign_id,
-            nullifier: registration.nullifier,
-            shadow_wire_address: registration.shadow_wire_address.clone(),
-            tx_signature,
-            timestamp: registration.distributed_at,
+        emit!(RateLimitInitialized {
+            wallet: rate_limit.wallet,
+            timestamp: now,
         });
 
         Ok(())
     }
 
-    /// Complete an airdrop campaign (marks as fully distributed)
-    /// Only campaign creator can complete
-    pub fn complete_airdrop_campaign(ctx: Context<CompleteAirdropCampaign>) -> Result<()> {
-        let campaign = &mut ctx.accounts.campaign;
+    /// Initialize cross-campaign claim tracking for a wallet
+    /// Creates a WalletClaimLimit PDA (needed while a global claim cooldown is set)
+    pub fn init_claim_limit(ctx: Context<InitClaimLimit>) -> Result<()> {
+        let claim_limit = &mut ctx.accounts.claim_limit;
+        claim_limit.wallet = ctx.accounts.wallet.key();
+        claim_limit.last_claim_at = 0;
+        claim_limit.total_claims = 0;
+        claim_limit.bump = ctx.bumps.claim_limit;
+
+        emit!(ClaimLimitInitialized {
+            wallet: claim_limit.wallet,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        require!(
-            campaign.status == CampaignStatus::RegistrationClosed,
-            VouchError::CampaignNotClosed
-        );
+        Ok(())
+    }
 
-        campaign.status = CampaignStatus::Completed;
-        campaign.completed_at = Clock::get()?.unix_timestamp;
+    /// Clear a wallet's rolling rate limit window and cooldown (support lever)
+    /// Only admin can call this. total_proofs is kept for historical
+    /// accounting; day_start restarts at now.
+    pub fn reset_wallet_rate_limit(ctx: Context<ResetWalletRateLimit>) -> Result<()> {
+        let now = effective_now(&ctx.accounts.config)?;
+        let rate_limit = &mut ctx.accounts.rate_limit;
+        let previous_proofs_today = rate_limit.proofs_today;
+        let previous_last_proof_at = rate_limit.last_proof_at;
 
-        emit!(AirdropCampaignCompleted {
-            campaign_id: campaign.campaign_id,
-            total_distributed: campaign.total_registrations,
-            timestamp: campaign.completed_at,
+        rate_limit.last_proof_at = 0;
+        rate_limit.proofs_today = 0;
+        rate_limit.dev_proofs_today = 0;
+        rate_limit.whale_proofs_today = 0;
+        rate_limit.day_start = now;
+        rate_limit.recent_proofs = [RecentProof::default(); RATE_LIMIT_WINDOW_SIZE];
+        rate_limit.recent_head = 0;
+
+        emit!(RateLimitReset {
+            wallet: rate_limit.wallet,
+            admin: ctx.accounts.admin.key(),
+            previous_proofs_today,
+            previous_last_proof_at,
+            timestamp: now,
         });
 
         Ok(())
     }
 
-    /// Fund an airdrop campaign's token vault
-    /// Only campaign creator can fund
-    /// Tokens are transferred from creator's ATA to campaign vault
-    pub fn fund_airdrop_campaign(ctx: Context<FundAirdropCampaign>, amount: u64) -> Result<()> {
-        let campaign = &ctx.accounts.campaign;
+    /// Exempt a wallet from the cooldown and daily limits, or revoke the exemption
+    /// Only admin can call this. Meant for trusted relayers submitting on behalf
+    /// of many users; exempt wallets still count towards total_proofs.
+    pub fn set_rate_limit_exempt(ctx: Context<SetRateLimitExempt>, exempt: bool) -> Result<()> {
+        let rate_limit = &mut ctx.accounts.rate_limit;
+        rate_limit.exempt = exempt;
 
-        require!(amount > 0, VouchError::InvalidAmount);
-        require!(
-            campaign.status == CampaignStatus::Open ||
-            campaign.status == CampaignStatus::RegistrationClosed,
-            VouchError::CampaignNotOpen
-        );
-
-        // Transfer tokens from creator to vault
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.creator_token_account.to_account_info(),
-            to: ctx.accounts.campaign_vault.to_account_info(),
-            authority: ctx.accounts.creator.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
-
-        // Update campaign funding stats
-        let campaign = &mut ctx.accounts.campaign;
-        campaign.vault_balance = campaign
-            .vault_balance
-            .checked_add(amount)
-            .ok_or(VouchError::Overflow)?;
-
-        emit!(AirdropCampaignFunded {
-            campaign_id: campaign.campaign_id,
-            funder: ctx.accounts.creator.key(),
-            amount,
-            total_funded: campaign.vault_balance,
+        emit!(RateLimitExemptionUpdated {
+            wallet: rate_limit.wallet,
+            admin: ctx.accounts.admin.key(),
+            exempt,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Claim airdrop tokens from a campaign
-    /// Only registered users can claim
-    /// Tokens are transferred from campaign vault to claimer's ATA
-    pub fn claim_airdrop(ctx: Context<ClaimAirdrop>) -> Result<()> {
-        let registration = &ctx.accounts.registration;
-        let campaign = &ctx.accounts.campaign;
+    // === Verifier Management ===
 
-        // Verify not already claimed
-        require!(!registration.is_claimed, VouchError::AlreadyClaimed);
+    /// Add an authorized verifier
+    /// Only admin can add verifiers. A previously removed verifier is
+    /// reactivated in place, keeping its attestation history.
+    pub fn add_verifier(ctx: Context<AddVerifier>, verifier_pubkey: Pubkey) -> Result<()> {
+        // Check protocol is not paused
+        require!(!ctx.accounts.config.is_paused, VouchError::ProtocolPaused);
 
-        // Calculate claim amount based on proof type
-        let claim_amount = match registration.proof_type {
-            ProofType::DeveloperReputation => {
-                campaign.base_amount.checked_add(campaign.dev_bonus).ok_or(VouchError::Overflow)?
-            }
-            ProofType::WhaleTrading => {
-                campaign.base_amount.checked_add(campaign.whale_bonus).ok_or(VouchError::Overflow)?
-            }
-            ProofType::Unset => campaign.base_amount, // Open registration gets base only
-        };
+        let verifier_account = &mut ctx.accounts.verifier_account;
+        // Only inactive verifiers count towards verifier_count when (re)added
+        require!(!verifier_account.is_active, VouchError::VerifierAlreadyActive);
+        if verifier_account.verifier == Pubkey::default() {
+            verifier_account.verifier = verifier_pubkey;
+            verifier_account.attestation_count = 0;
+            verifier_account.merkle_root = [0u8; 32];
+            verifier_account.merkle_root_published_at = 0;
+            verifier_account.last_attestation_at = 0;
+            verifier_account.bump = ctx.bumps.verifier_account;
+        }
+        verifier_account.is_active = true;
+        verifier_account.added_at = Clock::get()?.unix_timestamp;
+        verifier_account.removal_scheduled_at = 0;
 
-        // Verify vault has enough tokens
-        require!(
-            ctx.accounts.campaign_vault.amount >= claim_amount,
-            VouchError::InsufficientFunds
-        );
+        let config = &mut ctx.accounts.config;
+        config.verifier_count = config.verifier_count.checked_add(1).ok_or(VouchError::Overflow)?;
 
-        // Transfer tokens from vault to claimer
-        let campaign_id = campaign.campaign_id;
-        let bump = campaign.bump;
-        let seeds = &[
-            b"airdrop_campaign".as_ref(),
-            campaign_id.as_ref(),
-            &[bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        emit!(VerifierAdded {
+            verifier: verifier_pubkey,
+            admin: ctx.accounts.admin.key(),
+            timestamp: verifier_account.added_at,
+        });
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.campaign_vault.to_account_info(),
-            to: ctx.accounts.claimer_token_account.to_account_info(),
-            authority: ctx.accounts.campaign.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, claim_amount)?;
+        Ok(())
+    }
 
-        // Update registration
-        let registration = &mut ctx.accounts.registration;
-        registration.is_claimed = true;
-        registration.claimed_at = Clock::get()?.unix_timestamp;
-        registration.claimed_amount = claim_amount;
+    /// Remove an authorized verifier
+    /// Intentionally allowed while the protocol is paused: removing a
+    /// compromised verifier is a core incident-response action during a pause
+    ///
+    /// With a nonzero config.verifier_removal_delay_seconds the removal is only
+    /// scheduled: the verifier stays active for the grace period so in-flight
+    /// attestations can land, then finalize_verifier_removal completes it.
+    /// `immediate` bypasses the grace period for emergency removals.
+    pub fn remove_verifier(ctx: Context<RemoveVerifier>, immediate: bool) -> Result<()> {
+        let delay = ctx.accounts.config.verifier_removal_delay_seconds;
+        let now = effective_now(&ctx.accounts.config)?;
+
+        if !immediate && delay > 0 {
+            let verifier_account = &mut ctx.accounts.verifier_account;
+            require!(verifier_account.is_active, VouchError::VerifierNotAuthorized);
+            require!(
+                verifier_account.removal_scheduled_at == 0,
+                VouchError::VerifierRemovalAlreadyScheduled
+            );
+            verifier_account.removal_scheduled_at =
+                now.checked_add(delay).ok_or(VouchError::Overflow)?;
 
-        // Update campaign stats
-        let campaign = &mut ctx.accounts.campaign;
-        campaign.vault_balance = campaign
-            .vault_balance
-            .saturating_sub(claim_amount);
-        campaign.total_claimed = campaign
-            .total_claimed
-            .checked_add(1)
-            .ok_or(VouchError::Overflow)?;
+            emit!(VerifierRemovalScheduled {
+                verifier: verifier_account.verifier,
+                admin: ctx.accounts.admin.key(),
+                removal_at: verifier_account.removal_scheduled_at,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
 
-        emit!(AirdropClaimed {
-            campaign_id: campaign.campaign_id,
-            claimer: ctx.accounts.claimer.key(),
-            nullifier: registration.nullifier,
-            amount: claim_amount,
-            proof_type: registration.proof_type,
-            timestamp: registration.claimed_at,
+            return Ok(());
+        }
+
+        // Removing an already-inactive verifier would decrement verifier_count twice
+        let verifier_account = &mut ctx.accounts.verifier_account;
+        require!(verifier_account.is_active, VouchError::VerifierNotAuthorized);
+        verifier_account.is_active = false;
+        verifier_account.removal_scheduled_at = 0;
+
+        let config = &mut ctx.accounts.config;
+        config.verifier_count = config.verifier_count.checked_sub(1).ok_or(VouchError::Overflow)?;
+
+        emit!(VerifierRemoved {
+            verifier: verifier_account.verifier,
+            admin: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
-}
 
-// === Helper Functions ===
+    /// Reactivate a previously removed verifier
+    /// Only admin can call this. Restores the existing VerifierAccount in place,
+    /// keeping its attestation history, and counts it towards verifier_count again.
+    pub fn reactivate_verifier(ctx: Context<ReactivateVerifier>) -> Result<()> {
+        // Check protocol is not paused
+        require!(!ctx.accounts.config.is_paused, VouchError::ProtocolPaused);
 
-/// Check and update rate limits for a wallet
-fn check_and_update_rate_limit(
-    rate_limit: &mut WalletRateLimit,
-    config: &ConfigAccount,
-    now: i64,
-) -> Result<()> {
-    // Check cooldown period
-    let time_since_last = now.saturating_sub(rate_limit.last_proof_at);
-    require!(
-        time_since_last >= config.cooldown_seconds,
-        VouchError::RateLimitCooldown
-    );
+        // Reactivating an active verifier would increment verifier_count twice
+        let verifier_account = &mut ctx.accounts.verifier_account;
+        require!(!verifier_account.is_active, VouchError::VerifierAlreadyActive);
+        verifier_account.is_active = true;
+        verifier_account.removal_scheduled_at = 0;
 
-    // Reset daily counter if new day
-    if now.saturating_sub(rate_limit.day_start) >= SECONDS_PER_DAY {
-        rate_limit.day_start = now;
-        rate_limit.proofs_today = 0;
+        let config = &mut ctx.accounts.config;
+        config.verifier_count = config.verifier_count.checked_add(1).ok_or(VouchError::Overflow)?;
+
+        emit!(VerifierReactivated {
+            verifier: verifier_account.verifier,
+            admin: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
     }
 
-    // Check daily limit
-    require!(
-        rate_limit.proofs_today < config.max_proofs_per_day,
-        VouchError::DailyRateLimitExceeded
-    );
+    /// Rotate a verifier to a new signing key, keeping its attestation history
+    /// Only admin can call this. The new key's VerifierAccount inherits
+    /// attestation_count, added_at and last_attestation_at; the
+    /// old account is deactivated, so attestations the old key signed but that
+    /// were not yet submitted are refused. verifier_count is unchanged.
+    /// Allowed while paused: rotating a leaked key is incident response.
+    pub fn rotate_verifier(
+        ctx: Context<RotateVerifier>,
+        old_verifier: Pubkey,
+        new_verifier: Pubkey,
+    ) -> Result<()> {
+        let old_account = &mut ctx.accounts.old_verifier_account;
+        let new_account = &mut ctx.accounts.new_verifier_account;
+
+        new_account.verifier = new_verifier;
+        new_account.is_active = true;
+        new_account.added_at = old_account.added_at;
+        new_account.attestation_count = old_account.attestation_count;
+        // The old key signed the published root; the new key publishes its own
+        new_account.merkle_root = [0u8; 32];
+        new_account.merkle_root_published_at = 0;
+        new_account.last_attestation_at = old_account.last_attestation_at;
+        new_account.removal_scheduled_at = 0;
+        new_account.bump = ctx.bumps.new_verifier_account;
+
+        old_account.is_active = false;
+        old_account.removal_scheduled_at = 0;
+
+        emit!(VerifierRotated {
+            old_verifier,
+            new_verifier,
+            admin: ctx.accounts.admin.key(),
+            attestation_count: new_account.attestation_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-    // Update rate limit counters
-    rate_limit.proofs_today = rate_limit
-        .proofs_today
-        .checked_add(1)
-        .ok_or(VouchError::Overflow)?;
-    rate_limit.last_proof_at = now;
-    rate_limit.total_proofs = rate_limit
-        .total_proofs
-        .checked_add(1)
-        .ok_or(VouchError::Overflow)?;
+        Ok(())
+    }
 
-    Ok(())
-}
+    /// Recompute config.verifier_count from the verifier registry
+    /// Only admin can call this. remaining_accounts: every VerifierAccount PDA,
+    /// each at most once; verifier_count becomes the number that are active.
+    /// Repairs drift, e.g. after initialize_config rotates to a new config epoch
+    /// while old verifiers remain active.
+    pub fn recount_verifiers<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AdminControl<'info>>,
+    ) -> Result<()> {
+        let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+        let mut active: u32 = 0;
+        for info in ctx.remaining_accounts.iter() {
+            let verifier_account: Account<'info, VerifierAccount> = Account::try_from(info)?;
+            let (expected, _) = Pubkey::find_program_address(
+                &[b"verifier", verifier_account.verifier.as_ref()],
+                &crate::ID,
+            );
+            require!(expected == *info.key, VouchError::InvalidVerifierSet);
+            require!(!seen.contains(info.key), VouchError::InvalidVerifierSet);
+            seen.push(*info.key);
 
-/// Build the attestation message that the verifier signs (v1 - deprecated)
-/// Format: "vouch_attestation" | proof_type (1 byte) | nullifier (32 bytes) | attestation_hash (32 bytes)
-#[allow(dead_code)]
-pub fn build_attestation_message(
-    proof_type_value: u8,
-    nullifier: &[u8; 32],
-    attestation_hash: &[u8; 32],
-) -> [u8; 82] {
-    let mut message = [0u8; 82];
-    // Domain separator: "vouch_attestation" (17 bytes)
-    message[0..17].copy_from_slice(b"vouch_attestation");
-    // Proof type (1 byte)
-    message[17] = proof_type_value;
-    // Nullifier (32 bytes)
-    message[18..50].copy_from_slice(nullifier);
-    // Attestation hash (32 bytes)
-    message[50..82].copy_from_slice(attestation_hash);
-    message
-}
+            if verifier_account.is_active {
+                active = active.checked_add(1).ok_or(VouchError::Overflow)?;
+            }
+        }
 
-/// Build the attestation message that the verifier signs (v2 - with epoch and data_hash)
-/// Format: "vouch_attestation_v2" | proof_type (1 byte) | nullifier (32 bytes) | epoch (8 bytes) | data_hash (32 bytes) | attestation_hash (32 bytes)
-/// Total: 20 + 1 + 32 + 8 + 32 + 32 = 125 bytes
-pub fn build_attestation_message_v2(
-    proof_type_value: u8,
-    nullifier: &[u8; 32],
-    epoch: u64,
-    data_hash: &[u8; 32],
-    attestation_hash: &[u8; 32],
-) -> [u8; 125] {
-    let mut message = [0u8; 125];
-    // Domain separator: "vouch_attestation_v2" (20 bytes)
-    message[0..20].copy_from_slice(b"vouch_attestation_v2");
-    // Proof type (1 byte)
-    message[20] = proof_type_value;
-    // Nullifier (32 bytes)
-    message[21..53].copy_from_slice(nullifier);
-    // Epoch (8 bytes, big-endian)
-    message[53..61].copy_from_slice(&epoch.to_be_bytes());
-    // Data hash (32 bytes)
-    message[61..93].copy_from_slice(data_hash);
-    // Attestation hash (32 bytes)
-    message[93..125].copy_from_slice(attestation_hash);
-    message
-}
+        let config = &mut ctx.accounts.config;
+        let old_count = config.verifier_count;
+        config.verifier_count = active;
 
-/// Verify Ed25519 signature using instruction introspection
-/// This function checks that a valid Ed25519Program instruction was included
-/// in the transaction that verifies the signature over the attestation message
-pub fn verify_ed25519_signature(
-    instructions_sysvar: &AccountInfo,
-    verifier_pubkey: &Pubkey,
-    signature: &[u8; 64],
-    message: &[u8],
-) -> Result<()> {
-    // Get the current instruction index
-    let current_index = load_current_index_checked(instructions_sysvar)
-        .map_err(|_| VouchError::InvalidSignature)?;
+        emit!(VerifierCountRecounted {
+            admin: ctx.accounts.admin.key(),
+            old_count,
+            new_count: active,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-    // We expect the Ed25519 instruction to be right before this instruction
-    // (index = current_index - 1)
-    if current_index == 0 {
-        return Err(VouchError::InvalidSignature.into());
+        Ok(())
     }
 
-    let ed25519_ix_index = current_index - 1;
+    /// Complete a scheduled verifier removal once its grace period has passed
+    /// Permissionless: the removal was already authorized by remove_verifier.
+    /// Attestations are refused from the scheduled time even before this runs.
+    pub fn finalize_verifier_removal(ctx: Context<FinalizeVerifierRemoval>) -> Result<()> {
+        let now = effective_now(&ctx.accounts.config)?;
+        let verifier_account = &mut ctx.accounts.verifier_account;
 
-    // Load the Ed25519 instruction
-    let ed25519_ix = load_instruction_at_checked(ed25519_ix_index as usize, instructions_sysvar)
-        .map_err(|_| VouchError::InvalidSignature)?;
+        require!(verifier_account.is_active, VouchError::VerifierNotAuthorized);
+        require!(
+            verifier_account.removal_scheduled_at != 0
+                && now >= verifier_account.removal_scheduled_at,
+            VouchError::VerifierRemovalNotDue
+        );
 
-    // Verify it's an Ed25519 program instruction
-    if ed25519_ix.program_id != ed25519_program::ID {
-        return Err(VouchError::InvalidSignature.into());
-    }
+        verifier_account.is_active = false;
+        verifier_account.removal_scheduled_at = 0;
 
-    // Parse and verify the Ed25519 instruction data
-    // Ed25519 instruction format:
-    // - 1 byte: number of signatures
-    // - 1 byte: padding
-    // For each signature:
-    // - 2 bytes: signature offset
-    // - 2 bytes: signature instruction index
-    // - 2 bytes: public key offset
-    // - 2 bytes: public key instruction index
-    // - 2 bytes: message data offset
-    // - 2 bytes: message data size
-    // - 2 bytes: message instruction index
-    // Then the actual data (signature, pubkey, message)
+        let config = &mut ctx.accounts.config;
+        config.verifier_count = config.verifier_count.checked_sub(1).ok_or(VouchError::Overflow)?;
 
-    let ix_data = &ed25519_ix.data;
+        emit!(VerifierRemovalFinalized {
+            verifier: verifier_account.verifier,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-    if ix_data.len() < 2 {
-        return Err(VouchError::InvalidSignature.into());
+        Ok(())
     }
 
-    let num_signatures = ix_data[0];
-    if num_signatures != 1 {
-        return Err(VouchError::InvalidSignature.into());
-    }
+    /// Close an inactive verifier's account and refund its rent to the admin
+    /// Only admin can call this. VerifierClosed snapshots the verifier's
+    /// attestation history first; add_verifier can recreate the account later.
+    pub fn close_verifier(ctx: Context<CloseVerifier>) -> Result<()> {
+        let verifier_account = &ctx.accounts.verifier_account;
 
-    // Parse offsets (bytes 2-15)
-    if ix_data.len() < 16 {
-        return Err(VouchError::InvalidSignature.into());
+        emit!(VerifierClosed {
+            verifier: verifier_account.verifier,
+            admin: ctx.accounts.admin.key(),
+            attestation_count: verifier_account.attestation_count,
+            added_at: verifier_account.added_at,
+            last_attestation_at: verifier_account.last_attestation_at,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
     }
 
-    let sig_offset = u16::from_le_bytes([ix_data[2], ix_data[3]]) as usize;
-    let pubkey_offset = u16::from_le_bytes([ix_data[6], ix_data[7]]) as usize;
-    let msg_offset = u16::from_le_bytes([ix_data[10], ix_data[11]]) as usize;
-    let msg_size = u16::from_le_bytes([ix_data[12], ix_data[13]]) as usize;
+    /// Set the grace period between remove_verifier and the removal taking effect
+    /// Only admin can call this. Zero makes removals immediate.
+    pub fn set_verifier_removal_delay(
+        ctx: Context<AdminControl>,
+        verifier_removal_delay_seconds: i64,
+    ) -> Result<()> {
+        require!(
+            (0..=MAX_VERIFIER_REMOVAL_DELAY_SECONDS).contains(&verifier_removal_delay_seconds),
+            VouchError::InvalidRemovalDelay
+        );
 
-    // Verify the instruction contains the expected data at the specified offsets
-    if ix_data.len() < sig_offset + ED25519_SIGNATURE_SIZE {
-        return Err(VouchError::InvalidSignature.into());
-    }
-    if ix_data.len() < pubkey_offset + ED25519_PUBKEY_SIZE {
-        return Err(VouchError::InvalidSignature.into());
+        let config = &mut ctx.accounts.config;
+        let old_delay_seconds = config.verifier_removal_delay_seconds;
+        config.verifier_removal_delay_seconds = verifier_removal_delay_seconds;
+
+        emit!(VerifierRemovalDelayUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_delay_seconds,
+            new_delay_seconds: verifier_removal_delay_seconds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
     }
-    if ix_data.len() < msg_offset + msg_size {
-        return Err(VouchError::InvalidSignature.into());
+
+    /// Set the minimum number of active verifiers required to record attestations
+    /// Only admin can call this. Attestations fail with InsufficientVerifiers
+    /// while config.verifier_count is below this floor.
+    pub fn set_min_active_verifiers(
+        ctx: Context<AdminControl>,
+        min_active_verifiers: u32,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_min_active_verifiers = config.min_active_verifiers;
+        config.min_active_verifiers = min_active_verifiers;
+
+        emit!(MinActiveVerifiersUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_min_active_verifiers,
+            new_min_active_verifiers: min_active_verifiers,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
     }
 
-    // Extract and verify signature matches
-    let ix_signature = &ix_data[sig_offset..sig_offset + ED25519_SIGNATURE_SIZE];
-    if ix_signature != signature.as_slice() {
-        return Err(VouchError::InvalidSignature.into());
+    /// View: check the config for combinations individual setters allow
+    /// Returns a bitmask of CONFIG_ISSUE_* flags via return data; zero means
+    /// the config is fully consistent. Read-only diagnostic for operators.
+    pub fn validate_config(ctx: Context<ValidateConfig>) -> Result<u32> {
+        let config = &ctx.accounts.config;
+        let mut issues = 0u32;
+
+        if !(1..=RATE_LIMIT_WINDOW_SIZE as u32).contains(&config.max_proofs_per_day)
+            || config.max_dev_proofs_per_day == 0
+            || config.max_whale_proofs_per_day == 0
+            || config.cooldown_seconds < 0
+        {
+            issues |= CONFIG_ISSUE_RATE_LIMIT;
+        }
+        if !(0..=MAX_RESET_SKEW_TOLERANCE_SECONDS).contains(&config.reset_skew_tolerance_seconds) {
+            issues |= CONFIG_ISSUE_RESET_SKEW;
+        }
+        if config.global_min_hold_seconds < 0 || config.max_proof_age_seconds < 0 {
+            issues |= CONFIG_ISSUE_NEGATIVE_DURATION;
+        }
+        if config.reaper_bounty_bps as u64 > BPS_DENOMINATOR {
+            issues |= CONFIG_ISSUE_REAPER_BOUNTY;
+        }
+        if config.max_ed25519_scan_distance > MAX_ED25519_SCAN_DISTANCE {
+            issues |= CONFIG_ISSUE_SCAN_DISTANCE;
+        }
+        if config.use_slot_based_time
+            && !(MIN_MS_PER_SLOT..=MAX_MS_PER_SLOT).contains(&config.ms_per_slot)
+        {
+            issues |= CONFIG_ISSUE_TIME_SOURCE;
+        }
+        if config.max_concurrent_open_campaigns > 0
+            && config.active_campaign_count > config.max_concurrent_open_campaigns
+        {
+            issues |= CONFIG_ISSUE_CAMPAIGN_CAP;
+        }
+        if config.pause_authority == Pubkey::default() {
+            issues |= CONFIG_ISSUE_PAUSE_AUTHORITY;
+        }
+        if !config.is_paused && config.verifier_count < config.min_active_verifiers.max(1) {
+            issues |= CONFIG_ISSUE_NO_VERIFIERS;
+        }
+
+        Ok(issues)
     }
 
-    // Extract and verify public key matches the verifier
-    let ix_pubkey = &ix_data[pubkey_offset..pubkey_offset + ED25519_PUBKEY_SIZE];
-    if ix_pubkey != verifier_pubkey.as_ref() {
-        return Err(VouchError::InvalidSignature.into());
+    /// View: the configured ShadowWire program id (Pubkey::default() if unset)
+    pub fn get_shadow_wire_program(ctx: Context<ValidateConfig>) -> Result<Pubkey> {
+        Ok(ctx.accounts.config.shadow_wire_program_id)
     }
 
-    // Extract and verify message matches
-    let ix_message = &ix_data[msg_offset..msg_offset + msg_size];
-    if ix_message != message {
-        return Err(VouchError::InvalidSignature.into());
+    /// View: the rate limits that apply to an attestation of the given proof type
+    /// Returns EffectiveLimits via return data. max_per_day is the tighter of the
+    /// type's daily limit and the global max_proofs_per_day cap.
+    pub fn get_effective_limits(
+        ctx: Context<ValidateConfig>,
+        proof_type_value: u8,
+    ) -> Result<EffectiveLimits> {
+        let proof_type = proof_type_from_value(proof_type_value)?;
+        let config = &ctx.accounts.config;
+
+        Ok(EffectiveLimits {
+            max_per_day: daily_limit_for(config, proof_type),
+            cooldown_seconds: config.cooldown_seconds,
+        })
     }
 
-    // If we get here, the Ed25519 program has verified the signature is valid
-    // for the given public key and message
-    Ok(())
-}
+    /// View: a verifier's profile as a stable, versioned struct
+    /// Returns VerifierProfile via return data so clients don't depend on the
+    /// raw VerifierAccount layout. Works for active and removed verifiers.
+    pub fn get_verifier_profile(ctx: Context<GetVerifierProfile>) -> Result<VerifierProfile> {
+        let verifier_account = &ctx.accounts.verifier_account;
 
-// === Accounts ===
+        Ok(VerifierProfile {
+            version: VERIFIER_PROFILE_VERSION,
+            verifier: verifier_account.verifier,
+            is_active: verifier_account.is_active,
+            added_at: verifier_account.added_at,
+            attestation_count: verifier_account.attestation_count,
+            last_attestation_at: verifier_account.last_attestation_at,
+            merkle_root_published_at: verifier_account.merkle_root_published_at,
+            // Neither is tracked on VerifierAccount yet
+            allowed_proof_types: None,
+            staked_amount: None,
+        })
+    }
 
-#[derive(Accounts)]
-pub struct InitializeConfig<'info> {
-    #[account(
-        init,
-        payer = admin,
-        space = 8 + ConfigAccount::INIT_SPACE,
-        seeds = [b"config"],
-        bump
-    )]
-    pub config: Account<'info, ConfigAccount>,
+    /// Import a governance-signed set of verifiers
+    /// Anyone can submit; the governance key's Ed25519 signature over
+    /// build_verifier_set_message (in the preceding instruction) authorizes it.
+    /// remaining_accounts: the verifier PDA for each pubkey, in the same order.
+    /// Missing verifier accounts are created, inactive ones reactivated. The
+    /// governance nonce is bound into the message so a set can't be replayed.
+    pub fn import_signed_verifier_set<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ImportSignedVerifierSet<'info>>,
+        verifiers: Vec<Pubkey>,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(!config.is_paused, VouchError::ProtocolPaused);
+        require!(
+            config.governance_key != Pubkey::default(),
+            VouchError::GovernanceKeyNotSet
+        );
+        require!(
+            !verifiers.is_empty() && verifiers.len() <= MAX_VERIFIER_SET_SIZE,
+            VouchError::InvalidBatchSize
+        );
+        require!(
+            ctx.remaining_accounts.len() == verifiers.len(),
+            VouchError::InvalidVerifierSet
+        );
 
-    #[account(mut)]
-    pub admin: Signer<'info>,
+        let nonce = config.governance_nonce;
+        let message = build_verifier_set_message(nonce, &verifiers);
+        verify_ed25519_signature(
+            &ctx.accounts.instructions_sysvar.to_account_info(),
+            &config.governance_key,
+            &signature,
+            &message,
+            ctx.accounts.config.max_ed25519_scan_distance,
+            ctx.accounts.config.exclusive_ed25519_instructions,
+        )?;
 
-    pub system_program: Program<'info, System>,
-}
+        let now = Clock::get()?.unix_timestamp;
+        let governance_key = config.governance_key;
+        let mut activated: u32 = 0;
+
+        for (verifier_pubkey, info) in verifiers.iter().zip(ctx.remaining_accounts.iter()) {
+            let (expected, bump) =
+                Pubkey::find_program_address(&[b"verifier", verifier_pubkey.as_ref()], &crate::ID);
+            require!(expected == *info.key, VouchError::InvalidVerifierSet);
+
+            if info.owner == &crate::ID {
+                // Existing verifier: reactivate if needed
+                let mut verifier_account: Account<'info, VerifierAccount> =
+                    Account::try_from(info)?;
+                if verifier_account.is_active {
+                    // Still in its removal grace period: re-approval cancels the removal
+                    if verifier_account.removal_scheduled_at != 0 {
+                        verifier_account.removal_scheduled_at = 0;
+                        verifier_account.exit(&crate::ID)?;
+                    }
+                    continue;
+                }
+                verifier_account.is_active = true;
+                verifier_account.exit(&crate::ID)?;
+            } else {
+                create_pda_account(
+                    &ctx.accounts.payer.to_account_info(),
+                    info,
+                    &ctx.accounts.system_program.to_account_info(),
+                    8 + VerifierAccount::INIT_SPACE,
+                    &[b"verifier", verifier_pubkey.as_ref(), &[bump]],
+                )?;
+                let verifier_account = VerifierAccount {
+                    verifier: *verifier_pubkey,
+                    is_active: true,
+                    added_at: now,
+                    attestation_count: 0,
+                    merkle_root: [0u8; 32],
+                    merkle_root_published_at: 0,
+                    last_attestation_at: 0,
+                    removal_scheduled_at: 0,
+                    bump,
+                };
+                let mut data = info.try_borrow_mut_data()?;
+                verifier_account.try_serialize(&mut &mut data[..])?;
+            }
 
-/// Admin control context for pause/unpause and config updates
-#[derive(Accounts)]
-pub struct AdminControl<'info> {
-    #[account(
-        mut,
-        seeds = [b"config"],
-        bump = config.bump,
-        constraint = config.admin == admin.key() @ VouchError::Unauthorized
-    )]
-    pub config: Account<'info, ConfigAccount>,
+            activated = activated.checked_add(1).ok_or(VouchError::Overflow)?;
+            emit!(VerifierAdded {
+                verifier: *verifier_pubkey,
+                admin: governance_key,
+                timestamp: now,
+            });
+        }
 
-    #[account(mut)]
-    pub admin: Signer<'info>,
-}
+        let config = &mut ctx.accounts.config;
+        config.verifier_count = config
+            .verifier_count
+            .checked_add(activated)
+            .ok_or(VouchError::Overflow)?;
+        config.governance_nonce = nonce.checked_add(1).ok_or(VouchError::Overflow)?;
 
-/// Migrate config account to new size (adds max_epoch_age field)
-/// Uses UncheckedAccount to bypass Anchor's automatic deserialization
-/// which fails when the account size doesn't match the expected struct size.
-#[derive(Accounts)]
-pub struct MigrateConfig<'info> {
-    /// CHECK: Manual verification - this is the config PDA being migrated.
-    /// We use UncheckedAccount because the old account format (98 bytes)
-    /// cannot be deserialized as ConfigAccount (106 bytes).
-    #[account(
-        mut,
-        seeds = [b"config"],
-        bump
-    )]
-    pub config: UncheckedAccount<'info>,
+        emit!(VerifierSetImported {
+            governance_key,
+            nonce,
+            verifier_count: verifiers.len() as u32,
+            activated_count: activated,
+            timestamp: now,
+        });
 
-    /// Admin must be the one stored in the config account
-    /// We verify this manually in the instruction handler
-    #[account(mut)]
-    pub admin: Signer<'info>,
+        Ok(())
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    // === Attestation Recording ===
 
-/// Initialize rate limit tracking for a wallet
-#[derive(Accounts)]
-pub struct InitRateLimit<'info> {
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + WalletRateLimit::INIT_SPACE,
-        seeds = [b"rate_limit", wallet.key().as_ref()],
-        bump
-    )]
-    pub rate_limit: Account<'info, WalletRateLimit>,
+    /// Record a verified attestation from an authorized verifier
+    /// This is the production-ready verification flow:
+    /// 1. Client generates proof
+    /// 2. Off-chain verifier verifies proof and signs attestation
+    /// 3. Client submits attestation to this instruction
+    /// 4. On-chain program validates signature and records result
+    ///
+    /// New parameters (v2):
+    /// - epoch: Day number since Unix epoch (prevents replay attacks)
+    /// - data_hash: Hash of private data (ensures data integrity)
+    ///
+    /// - proof_commitment: Hash of the raw ZK proof, bound into the signed message
+    ///   and stored on the nullifier for auditing (required when
+    ///   config.require_proof_commitment is set)
+    /// - expires_at: Signed deadline for submission; later submissions fail with
+    ///   AttestationExpired, so a leaked signature can't be used months later
+    ///
+    /// In commitment-binding mode (config.bind_commitment) the recipient's
+    /// CommitmentAccount.commitment is appended to the signed message, so the
+    /// attestation only verifies for a wallet that committed on-chain.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_attestation(
+        ctx: Context<RecordAttestation>,
+        attestation_hash: [u8; 32],
+        proof_type_value: u8,
+        nullifier: [u8; 32],
+        epoch: u64,
+        data_hash: [u8; 32],
+        signature: [u8; 64],
+        proof_commitment: Option<[u8; 32]>,
+        proof_generated_at: i64,
+        expires_at: i64,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let now = effective_now(config)?;
 
-    /// CHECK: The wallet to track rate limits for
-    pub wallet: UncheckedAccount<'info>,
+        // Check protocol is not paused
+        require_diag!(!config.is_paused, ProtocolPaused, "paused=true");
+        require_diag!(
+            config.verifier_count >= config.min_active_verifiers,
+            InsufficientVerifiers,
+            "verifier_count={} min_active_verifiers={}",
+            config.verifier_count,
+            config.min_active_verifiers
+        );
+        require_diag!(
+            config.required_signatures <= 1,
+            InsufficientSignatures,
+            "signatures=1 required_signatures={}",
+            config.required_signatures
+        );
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+        // Verify the verifier is authorized
+        let verifier_account = &ctx.accounts.verifier_account;
+        require_diag!(
+            verifier_is_active(verifier_account, now),
+            VerifierNotAuthorized,
+            "verifier={} is_active={} removal_scheduled_at={} now={}",
+            verifier_account.verifier,
+            verifier_account.is_active,
+            verifier_account.removal_scheduled_at,
+            now
+        );
 
-    pub system_program: Program<'info, System>,
-}
+        // Validate epoch is recent (within max_epoch_age days)
+        let current_epoch = (now as u64) / 86400; // Day number since Unix epoch
+        let epoch_age = current_epoch.saturating_sub(epoch);
+        require_diag!(
+            epoch_age <= config.max_epoch_age,
+            EpochTooOld,
+            "epoch={} current_epoch={} max_epoch_age={}",
+            epoch,
+            current_epoch,
+            config.max_epoch_age
+        );
+        // Also reject future epochs (clock manipulation attempt)
+        require_diag!(
+            epoch <= current_epoch + 1,
+            EpochInFuture,
+            "epoch={} current_epoch={}",
+            epoch,
+            current_epoch
+        );
 
-#[derive(Accounts)]
-#[instruction(verifier_pubkey: Pubkey)]
-pub struct AddVerifier<'info> {
-    #[account(
-        mut,
-        seeds = [b"config"],
-        bump = config.bump,
-        constraint = config.admin == admin.key() @ VouchError::Unauthorized
-    )]
-    pub config: Account<'info, ConfigAccount>,
+        // Reject fresh signatures over stale proofs (0 = no proof age limit)
+        require_diag!(
+            config.max_proof_age_seconds == 0
+                || now.saturating_sub(proof_generated_at) <= config.max_proof_age_seconds,
+            ProofTooOld,
+            "proof_generated_at={} now={} max_proof_age_seconds={}",
+            proof_generated_at,
+            now,
+            config.max_proof_age_seconds
+        );
+        // Bound the window between off-chain signing and on-chain submission
+        require_diag!(
+            now <= expires_at,
+            AttestationExpired,
+            "expires_at={} now={}",
+            expires_at,
+            now
+        );
 
-    #[account(
-        init,
-        payer = admin,
-        space = 8 + VerifierAccount::INIT_SPACE,
-        seeds = [b"verifier", verifier_pubkey.as_ref()],
-        bump
-    )]
-    pub verifier_account: Account<'info, VerifierAccount>,
+        // Enforce wallet <-> proof binding when commitments are required
+        check_commitment_requirement(
+            config,
+            ctx.accounts.commitment_account.as_ref(),
+            &ctx.accounts.recipient.key(),
+            Some(proof_type_value),
+        )?;
+        check_recipient_not_blocked(
+            config,
+            ctx.accounts.blocked_recipient.as_ref(),
+            &ctx.accounts.recipient.key(),
+        )?;
+        let bound_commitment = bound_wallet_commitment(
+            config,
+            ctx.accounts.commitment_account.as_ref(),
+            &ctx.accounts.recipient.key(),
+        )?;
 
-    #[account(mut)]
-    pub admin: Signer<'info>,
+        require_diag!(
+            proof_commitment.is_some() || !config.require_proof_commitment,
+            ProofCommitmentRequired,
+            "proof_commitment=none require_proof_commitment=true"
+        );
 
-    pub system_program: Program<'info, System>,
-}
+        // Build the attestation message that was signed (v3 format with per-type domain)
+        let message = build_attestation_message_v3(
+            proof_type_value,
+            &nullifier,
+            epoch,
+            &data_hash,
+            &attestation_hash,
+            proof_generated_at,
+            expires_at,
+            proof_commitment.as_ref(),
+            bound_commitment.as_ref(),
+        )?;
 
-#[derive(Accounts)]
-pub struct RemoveVerifier<'info> {
-    #[account(
-        mut,
-        seeds = [b"config"],
-        bump = config.bump,
-        constraint = config.admin == admin.key() @ VouchError::Unauthorized
-    )]
-    pub config: Account<'info, ConfigAccount>,
+        // Verify the Ed25519 signature using instruction introspection
+        // The transaction must include an Ed25519Program verify instruction
+        // immediately before this instruction
+        verify_ed25519_signature(
+            &ctx.accounts.instructions_sysvar.to_account_info(),
+            &verifier_account.verifier,
+            &signature,
+            &message,
+            ctx.accounts.config.max_ed25519_scan_distance,
+            ctx.accounts.config.exclusive_ed25519_instructions,
+        )?;
 
-    #[account(
-        mut,
-        seeds = [b"verifier", verifier_account.verifier.as_ref()],
-        bump = verifier_account.bump
-    )]
-    pub verifier_account: Account<'info, VerifierAccount>,
+        // Check nullifier hasn't been used
+        let nullifier_account = &ctx.accounts.nullifier_account;
+        require_diag!(
+            !nullifier_account.is_used,
+            NullifierAlreadyUsed,
+            "used_at={}",
+            nullifier_account.used_at
+        );
 
-    #[account(mut)]
-    pub admin: Signer<'info>,
-}
+        // An anomalous attestation rate pauses the protocol instead of recording
+        if trip_attestation_circuit_breaker(&mut ctx.accounts.config, now)? {
+            return Ok(());
+        }
 
-#[derive(Accounts)]
-#[instruction(attestation_hash: [u8; 32], proof_type_value: u8, nullifier: [u8; 32], epoch: u64, data_hash: [u8; 32])]
-pub struct RecordAttestation<'info> {
-    #[account(
-        mut,
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, ConfigAccount>,
+        // Check and update rate limits
+        check_and_update_rate_limit(
+            &mut ctx.accounts.rate_limit,
+            &ctx.accounts.config,
+            proof_type_from_value(proof_type_value)?,
+            now,
+        )?;
 
+        record_used_signature(
+            &ctx.accounts.config,
+            ctx.accounts.used_signature.as_ref(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &signature,
+            &nullifier,
+            &verifier_account.verifier,
+            now,
+        )?;
+
+        // Mark nullifier as used and store epoch/data_hash
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        nullifier_account.is_used = true;
+        nullifier_account.used_at = now;
+        nullifier_account.epoch = epoch;
+        nullifier_account.data_hash = data_hash;
+        nullifier_account.proof_type = proof_type_from_value(proof_type_value)?;
+        nullifier_account.proof_commitment = proof_commitment.unwrap_or_default();
+        nullifier_account.credential_types = proof_type_bit(proof_type_value);
+        nullifier_account.credential_id =
+            build_credential_id(&nullifier, proof_type_value, &verifier_account.verifier);
+        nullifier_account.verifier = verifier_account.verifier;
+        nullifier_account.recipient = ctx.accounts.recipient.key();
+
+        // Update verifier stats
+        let verifier_account = &mut ctx.accounts.verifier_account;
+        verifier_account.attestation_count = verifier_account
+            .attestation_count
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+        verifier_account.last_attestation_at = now;
+
+        // Update global stats
+        let config = &mut ctx.accounts.config;
+        record_verified_proof(config, now)?;
+
+        emit!(AttestationRecorded {
+            nullifier,
+            attestation_hash,
+            epoch,
+            data_hash,
+            verifier: verifier_account.verifier,
+            proof_type: nullifier_account.proof_type,
+            recipient: ctx.accounts.recipient.key(),
+            timestamp: nullifier_account.used_at,
+            signature,
+            proof_commitment: nullifier_account.proof_commitment,
+            credential_id: nullifier_account.credential_id,
+            signers: vec![verifier_account.verifier],
+        });
+
+        Ok(())
+    }
+
+    /// Create the proof-type registry
+    /// Only admin can call this, once.
+    pub fn init_proof_type_registry(ctx: Context<InitProofTypeRegistry>) -> Result<()> {
+        let registry = &mut ctx.accounts.proof_type_registry;
+        registry.entries = Vec::new();
+        registry.bump = ctx.bumps.proof_type_registry;
+
+        Ok(())
+    }
+
+    /// Register a new credential category without a program upgrade
+    /// Only admin can call this. IDs below FIRST_REGISTERED_PROOF_TYPE_ID are
+    /// reserved: 1 and 2 are the built-in DeveloperReputation and WhaleTrading
+    /// types. airdrop_bonus is added to a campaign's base_amount for
+    /// registrations holding this type (the registry counterpart of
+    /// dev_bonus/whale_bonus).
+    pub fn register_proof_type(
+        ctx: Context<UpdateProofTypeRegistry>,
+        proof_type_id: u16,
+        name: String,
+        airdrop_bonus: u64,
+    ) -> Result<()> {
+        require!(
+            proof_type_id >= FIRST_REGISTERED_PROOF_TYPE_ID,
+            VouchError::InvalidProofType
+        );
+        require!(
+            !name.is_empty() && name.len() <= MAX_PROOF_TYPE_NAME_LEN,
+            VouchError::InvalidProofTypeName
+        );
+
+        let registry = &mut ctx.accounts.proof_type_registry;
+        require!(
+            !registry.entries.iter().any(|entry| entry.id == proof_type_id),
+            VouchError::ProofTypeAlreadyRegistered
+        );
+        require!(
+            registry.entries.len() < MAX_REGISTERED_PROOF_TYPES,
+            VouchError::ProofTypeRegistryFull
+        );
+
+        registry.entries.push(RegisteredProofType {
+            id: proof_type_id,
+            name: name.clone(),
+            airdrop_bonus,
+            is_active: true,
+        });
+
+        emit!(ProofTypeRegistered {
+            admin: ctx.accounts.admin.key(),
+            proof_type_id,
+            name,
+            airdrop_bonus,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Update a registered proof type's bonus or retire it
+    /// Only admin can call this. Inactive types can't be attested or used to
+    /// register for airdrops; existing credentials keep their ID.
+    pub fn update_proof_type(
+        ctx: Context<UpdateProofTypeRegistry>,
+        proof_type_id: u16,
+        airdrop_bonus: u64,
+        is_active: bool,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.proof_type_registry;
+        let entry = registry
+            .entries
+            .iter_mut()
+            .find(|entry| entry.id == proof_type_id)
+            .ok_or(VouchError::ProofTypeNotRegistered)?;
+        entry.airdrop_bonus = airdrop_bonus;
+        entry.is_active = is_active;
+
+        emit!(ProofTypeUpdated {
+            admin: ctx.accounts.admin.key(),
+            proof_type_id,
+            airdrop_bonus,
+            is_active,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Record an attestation for a registry proof type
+    /// Same checks as record_attestation, but the credential category is an
+    /// active ProofTypeRegistry ID and the verifier signs
+    /// build_registered_attestation_message. Rate limiting uses the global
+    /// max_proofs_per_day. The nullifier stores the ID in proof_type_id and
+    /// keeps proof_type = Unset.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_registered_attestation(
+        ctx: Context<RecordRegisteredAttestation>,
+        attestation_hash: [u8; 32],
+        proof_type_id: u16,
+        nullifier: [u8; 32],
+        epoch: u64,
+        data_hash: [u8; 32],
+        signature: [u8; 64],
+        proof_generated_at: i64,
+        expires_at: i64,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let now = effective_now(config)?;
+
+        require_diag!(!config.is_paused, ProtocolPaused, "paused=true");
+        require_diag!(
+            config.verifier_count >= config.min_active_verifiers,
+            InsufficientVerifiers,
+            "verifier_count={} min_active_verifiers={}",
+            config.verifier_count,
+            config.min_active_verifiers
+        );
+        require_diag!(
+            config.required_signatures <= 1,
+            InsufficientSignatures,
+            "signatures=1 required_signatures={}",
+            config.required_signatures
+        );
+        // Registry attestations carry no proof commitment
+        require_diag!(
+            !config.require_proof_commitment,
+            ProofCommitmentRequired,
+            "proof_commitment=none require_proof_commitment=true"
+        );
+
+        let verifier_account = &ctx.accounts.verifier_account;
+        require_diag!(
+            verifier_is_active(verifier_account, now),
+            VerifierNotAuthorized,
+            "verifier={} is_active={} removal_scheduled_at={} now={}",
+            verifier_account.verifier,
+            verifier_account.is_active,
+            verifier_account.removal_scheduled_at,
+            now
+        );
+
+        registered_proof_type(&ctx.accounts.proof_type_registry, proof_type_id)?;
+
+        let current_epoch = (now as u64) / 86400;
+        let epoch_age = current_epoch.saturating_sub(epoch);
+        require_diag!(
+            epoch_age <= config.max_epoch_age,
+            EpochTooOld,
+            "epoch={} current_epoch={} max_epoch_age={}",
+            epoch,
+            current_epoch,
+            config.max_epoch_age
+        );
+        require_diag!(
+            epoch <= current_epoch + 1,
+            EpochInFuture,
+            "epoch={} current_epoch={}",
+            epoch,
+            current_epoch
+        );
+        require_diag!(
+            config.max_proof_age_seconds == 0
+                || now.saturating_sub(proof_generated_at) <= config.max_proof_age_seconds,
+            ProofTooOld,
+            "proof_generated_at={} now={} max_proof_age_seconds={}",
+            proof_generated_at,
+            now,
+            config.max_proof_age_seconds
+        );
+        require_diag!(
+            now <= expires_at,
+            AttestationExpired,
+            "expires_at={} now={}",
+            expires_at,
+            now
+        );
+
+        check_commitment_requirement(
+            config,
+            ctx.accounts.commitment_account.as_ref(),
+            &ctx.accounts.recipient.key(),
+            None,
+        )?;
+        check_recipient_not_blocked(
+            config,
+            ctx.accounts.blocked_recipient.as_ref(),
+            &ctx.accounts.recipient.key(),
+        )?;
+        let bound_commitment = bound_wallet_commitment(
+            config,
+            ctx.accounts.commitment_account.as_ref(),
+            &ctx.accounts.recipient.key(),
+        )?;
+
+        let message = build_registered_attestation_message(
+            proof_type_id,
+            &nullifier,
+            epoch,
+            &data_hash,
+            &attestation_hash,
+            proof_generated_at,
+            expires_at,
+            bound_commitment.as_ref(),
+        );
+        verify_ed25519_signature(
+            &ctx.accounts.instructions_sysvar.to_account_info(),
+            &verifier_account.verifier,
+            &signature,
+            &message,
+            config.max_ed25519_scan_distance,
+            config.exclusive_ed25519_instructions,
+        )?;
+
+        if trip_attestation_circuit_breaker(&mut ctx.accounts.config, now)? {
+            return Ok(());
+        }
+
+        check_and_update_rate_limit(
+            &mut ctx.accounts.rate_limit,
+            &ctx.accounts.config,
+            ProofType::Unset,
+            now,
+        )?;
+
+        let verifier = ctx.accounts.verifier_account.verifier;
+        record_used_signature(
+            &ctx.accounts.config,
+            ctx.accounts.used_signature.as_ref(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &signature,
+            &nullifier,
+            &verifier,
+            now,
+        )?;
+
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        nullifier_account.is_used = true;
+        nullifier_account.used_at = now;
+        nullifier_account.epoch = epoch;
+        nullifier_account.data_hash = data_hash;
+        nullifier_account.proof_type = ProofType::Unset;
+        nullifier_account.proof_type_id = proof_type_id;
+        nullifier_account.proof_commitment = [0u8; 32];
+        nullifier_account.credential_types = 0;
+        nullifier_account.credential_id =
+            build_registered_credential_id(&nullifier, proof_type_id, &verifier);
+        nullifier_account.verifier = verifier;
+        nullifier_account.recipient = ctx.accounts.recipient.key();
+
+        let verifier_account = &mut ctx.accounts.verifier_account;
+        verifier_account.attestation_count = verifier_account
+            .attestation_count
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+        verifier_account.last_attestation_at = now;
+
+        let config = &mut ctx.accounts.config;
+        record_verified_proof(config, now)?;
+
+        emit!(RegisteredAttestationRecorded {
+            nullifier,
+            attestation_hash,
+            proof_type_id,
+            verifier,
+            recipient: ctx.accounts.recipient.key(),
+            credential_id: nullifier_account.credential_id,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Record an attestation co-signed by several verifiers (m-of-n)
+    /// Each signature must have its own preceding Ed25519 instruction over the
+    /// same v3 attestation message. At least config.required_signatures distinct
+    /// active verifiers must sign; their VerifierAccounts are passed via
+    /// remaining_accounts in the same order as `args.signatures`, followed (when
+    /// config.track_used_signatures is set) by each signature's UsedSignature PDA
+    /// in the same order. The first signer is stored as the nullifier's verifier.
+    pub fn record_attestation_threshold<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordAttestationThreshold<'info>>,
+        args: ThresholdAttestationArgs,
+    ) -> Result<()> {
+        let ThresholdAttestationArgs {
+            attestation_hash,
+            proof_type_value,
+            nullifier,
+            epoch,
+            data_hash,
+            signatures,
+            proof_commitment,
+            proof_generated_at,
+            expires_at,
+        } = args;
+        let config = &ctx.accounts.config;
+        let now = effective_now(config)?;
+
+        require!(!config.is_paused, VouchError::ProtocolPaused);
+        require!(
+            config.verifier_count >= config.min_active_verifiers,
+            VouchError::InsufficientVerifiers
+        );
+        require!(
+            !signatures.is_empty() && signatures.len() <= MAX_VERIFIER_SET_SIZE,
+            VouchError::InvalidBatchSize
+        );
+        require!(
+            signatures.len() >= config.required_signatures.max(1) as usize,
+            VouchError::InsufficientSignatures
+        );
+        let used_signature_count = if config.track_used_signatures {
+            signatures.len()
+        } else {
+            0
+        };
+        require!(
+            ctx.remaining_accounts.len() == signatures.len() + used_signature_count,
+            VouchError::InvalidVerifierSet
+        );
+        let (verifier_infos, used_signature_infos) =
+            ctx.remaining_accounts.split_at(signatures.len());
+        for (i, entry) in signatures.iter().enumerate() {
+            require!(
+                !signatures[..i].iter().any(|other| other.verifier == entry.verifier),
+                VouchError::DuplicateSigner
+            );
+        }
+
+        // Validate epoch is recent (within max_epoch_age days) and not in the future
+        let current_epoch = (now as u64) / 86400;
+        let epoch_age = current_epoch.saturating_sub(epoch);
+        require!(epoch_age <= config.max_epoch_age, VouchError::EpochTooOld);
+        require!(epoch <= current_epoch + 1, VouchError::EpochInFuture);
+
+        require!(
+            config.max_proof_age_seconds == 0
+                || now.saturating_sub(proof_generated_at) <= config.max_proof_age_seconds,
+            VouchError::ProofTooOld
+        );
+        require!(now <= expires_at, VouchError::AttestationExpired);
+
+        check_commitment_requirement(
+            config,
+            ctx.accounts.commitment_account.as_ref(),
+            &ctx.accounts.recipient.key(),
+            Some(proof_type_value),
+        )?;
+        check_recipient_not_blocked(
+            config,
+            ctx.accounts.blocked_recipient.as_ref(),
+            &ctx.accounts.recipient.key(),
+        )?;
+        let bound_commitment = bound_wallet_commitment(
+            config,
+            ctx.accounts.commitment_account.as_ref(),
+            &ctx.accounts.recipient.key(),
+        )?;
+        require!(
+            proof_commitment.is_some() || !config.require_proof_commitment,
+            VouchError::ProofCommitmentRequired
+        );
+
+        let proof_type = proof_type_from_value(proof_type_value)?;
+
+        let message = build_attestation_message_v3(
+            proof_type_value,
+            &nullifier,
+            epoch,
+            &data_hash,
+            &attestation_hash,
+            proof_generated_at,
+            expires_at,
+            proof_commitment.as_ref(),
+            bound_commitment.as_ref(),
+        )?;
+
+        // Each signer needs its own Ed25519 instruction, so scan at least that far back
+        let scan_distance = config.max_ed25519_scan_distance.max(signatures.len() as u8);
+        let exclusive = config.exclusive_ed25519_instructions;
+        let mut signers = Vec::with_capacity(signatures.len());
+        let mut verifier_accounts = Vec::with_capacity(signatures.len());
+
+        for (entry, info) in signatures.iter().zip(verifier_infos.iter()) {
+            let (expected, _) =
+                Pubkey::find_program_address(&[b"verifier", entry.verifier.as_ref()], &crate::ID);
+            require!(expected == *info.key, VouchError::InvalidVerifierSet);
+
+            let verifier_account: Account<'info, VerifierAccount> = Account::try_from(info)?;
+            require!(
+                verifier_is_active(&verifier_account, now),
+                VouchError::VerifierNotAuthorized
+            );
+
+            verify_ed25519_signature(
+                &ctx.accounts.instructions_sysvar.to_account_info(),
+                &entry.verifier,
+                &entry.signature,
+                &message,
+                scan_distance,
+                exclusive,
+            )?;
+
+            signers.push(entry.verifier);
+            verifier_accounts.push(verifier_account);
+        }
+
+        // Nothing may be mutated before this: a trip returns Ok and commits
+        if trip_attestation_circuit_breaker(&mut ctx.accounts.config, now)? {
+            return Ok(());
+        }
+
+        check_and_update_rate_limit(
+            &mut ctx.accounts.rate_limit,
+            &ctx.accounts.config,
+            proof_type,
+            now,
+        )?;
+
+        for verifier_account in verifier_accounts.iter_mut() {
+            verifier_account.attestation_count = verifier_account
+                .attestation_count
+                .checked_add(1)
+                .ok_or(VouchError::Overflow)?;
+            verifier_account.last_attestation_at = now;
+            verifier_account.exit(&crate::ID)?;
+        }
+
+        for (i, entry) in signatures.iter().enumerate() {
+            let used_signature = used_signature_infos.get(i).map(UncheckedAccount::try_from);
+            record_used_signature(
+                &ctx.accounts.config,
+                used_signature.as_ref(),
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &entry.signature,
+                &nullifier,
+                &entry.verifier,
+                now,
+            )?;
+        }
+
+        let primary = signatures[0];
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        nullifier_account.is_used = true;
+        nullifier_account.used_at = now;
+        nullifier_account.epoch = epoch;
+        nullifier_account.data_hash = data_hash;
+        nullifier_account.proof_type = proof_type;
+        nullifier_account.proof_commitment = proof_commitment.unwrap_or_default();
+        nullifier_account.credential_types = proof_type_bit(proof_type_value);
+        nullifier_account.credential_id =
+            build_credential_id(&nullifier, proof_type_value, &primary.verifier);
+        nullifier_account.verifier = primary.verifier;
+        nullifier_account.recipient = ctx.accounts.recipient.key();
+
+        let config = &mut ctx.accounts.config;
+        record_verified_proof(config, now)?;
+
+        emit!(AttestationRecorded {
+            nullifier,
+            attestation_hash,
+            epoch,
+            data_hash,
+            verifier: primary.verifier,
+            proof_type,
+            recipient: ctx.accounts.recipient.key(),
+            timestamp: now,
+            signature: primary.signature,
+            proof_commitment: nullifier_account.proof_commitment,
+            credential_id: nullifier_account.credential_id,
+            signers,
+        });
+
+        Ok(())
+    }
+
+    /// Set how many distinct verifiers must co-sign an attestation
+    /// Only admin can call this. Above 1, single-verifier paths (record_attestation,
+    /// record_attestation_merkle) are disabled in favor of record_attestation_threshold.
+    pub fn set_required_signatures(
+        ctx: Context<AdminControl>,
+        required_signatures: u8,
+    ) -> Result<()> {
+        require!(
+            (1..=MAX_VERIFIER_SET_SIZE as u8).contains(&required_signatures),
+            VouchError::InvalidSignatureThreshold
+        );
+
+        let config = &mut ctx.accounts.config;
+        let old_required_signatures = config.required_signatures;
+        config.required_signatures = required_signatures;
+
+        emit!(RequiredSignaturesUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_required_signatures,
+            new_required_signatures: required_signatures,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // === Attestation Batches ===
+    // A verifier signs one batch descriptor (merkle root over the batch's
+    // (proof_type, nullifier, recipient) entries). begin_attestation_batch checks
+    // that single signature and opens a short-lived AttestationBatch; each
+    // apply_batched_attestation then only needs a merkle inclusion proof.
+
+    /// Open an attestation batch from a verifier-signed descriptor
+    /// The signature covers build_attestation_batch_message and must be verified
+    /// via a preceding Ed25519 instruction.
+    pub fn begin_attestation_batch(
+        ctx: Context<BeginAttestationBatch>,
+        batch_id: [u8; 32],
+        entries_root: [u8; 32],
+        entry_count: u16,
+        expires_at: i64,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(!config.is_paused, VouchError::ProtocolPaused);
+        require!(config.required_signatures <= 1, VouchError::InsufficientSignatures);
+        let now = effective_now(config)?;
+
+        let verifier_account = &ctx.accounts.verifier_account;
+        require!(
+            verifier_is_active(verifier_account, now),
+            VouchError::VerifierNotAuthorized
+        );
+        require!(
+            entry_count > 0 && entry_count <= MAX_ATTESTATION_BATCH_ENTRIES,
+            VouchError::InvalidBatchSize
+        );
+        require!(
+            expires_at > now && expires_at - now <= MAX_ATTESTATION_BATCH_LIFETIME_SECONDS,
+            VouchError::InvalidBatchExpiry
+        );
+
+        let message = build_attestation_batch_message(
+            &verifier_account.verifier,
+            &batch_id,
+            &entries_root,
+            entry_count,
+            expires_at,
+        );
+        verify_ed25519_signature(
+            &ctx.accounts.instructions_sysvar.to_account_info(),
+            &verifier_account.verifier,
+            &signature,
+            &message,
+            config.max_ed25519_scan_distance,
+            config.exclusive_ed25519_instructions,
+        )?;
+
+        record_used_signature(
+            &ctx.accounts.config,
+            ctx.accounts.used_signature.as_ref(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &signature,
+            &batch_id,
+            &verifier_account.verifier,
+            now,
+        )?;
+
+        let batch = &mut ctx.accounts.batch;
+        batch.verifier = verifier_account.verifier;
+        batch.batch_id = batch_id;
+        batch.entries_root = entries_root;
+        batch.entry_count = entry_count;
+        batch.applied_count = 0;
+        batch.expires_at = expires_at;
+        batch.payer = ctx.accounts.payer.key();
+        batch.bump = ctx.bumps.batch;
+
+        emit!(AttestationBatchOpened {
+            batch: batch.key(),
+            verifier: batch.verifier,
+            batch_id,
+            entries_root,
+            entry_count,
+            expires_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Record one attestation from an open batch
+    /// No signature check: the entry is authorized by its merkle inclusion in the
+    /// batch's verifier-signed entries_root. The recipient is part of the leaf;
+    /// no proof commitment is, so this path is closed while require_proof_commitment
+    /// is set.
+    pub fn apply_batched_attestation(
+        ctx: Context<ApplyBatchedAttestation>,
+        proof_type_value: u8,
+        nullifier: [u8; 32],
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let now = effective_now(config)?;
+
+        require!(!config.is_paused, VouchError::ProtocolPaused);
+        require!(
+            config.verifier_count >= config.min_active_verifiers,
+            VouchError::InsufficientVerifiers
+        );
+        require!(config.required_signatures <= 1, VouchError::InsufficientSignatures);
+        // Batch entries carry no proof commitment
+        require_diag!(
+            !config.require_proof_commitment,
+            ProofCommitmentRequired,
+            "proof_commitment=none require_proof_commitment=true"
+        );
+
+        let verifier_account = &ctx.accounts.verifier_account;
+        require!(
+            verifier_is_active(verifier_account, now),
+            VouchError::VerifierNotAuthorized
+        );
+
+        let batch = &ctx.accounts.batch;
+        require!(now < batch.expires_at, VouchError::AttestationBatchExpired);
+        require!(
+            batch.applied_count < batch.entry_count,
+            VouchError::AttestationBatchExhausted
+        );
+
+        check_commitment_requirement(
+            config,
+            ctx.accounts.commitment_account.as_ref(),
+            &ctx.accounts.recipient.key(),
+            Some(proof_type_value),
+        )?;
+        check_recipient_not_blocked(
+            config,
+            ctx.accounts.blocked_recipient.as_ref(),
+            &ctx.accounts.recipient.key(),
+        )?;
+        let bound_commitment = bound_wallet_commitment(
+            config,
+            ctx.accounts.commitment_account.as_ref(),
+            &ctx.accounts.recipient.key(),
+        )?;
+
+        // Entries outside the signed batch descriptor fail inclusion
+        require!(proof.len() <= MAX_MERKLE_PROOF_DEPTH, VouchError::InvalidMerkleProof);
+        let proof_type = proof_type_from_value(proof_type_value)?;
+        let leaf = build_batch_attestation_leaf(
+            proof_type_value,
+            &nullifier,
+            &ctx.accounts.recipient.key(),
+            bound_commitment.as_ref(),
+        );
+        require!(
+            verify_merkle_proof(&proof, &batch.entries_root, &leaf),
+            VouchError::InvalidMerkleProof
+        );
+
+        if trip_attestation_circuit_breaker(&mut ctx.accounts.config, now)? {
+            return Ok(());
+        }
+
+        check_and_update_rate_limit(
+            &mut ctx.accounts.rate_limit,
+            &ctx.accounts.config,
+            proof_type,
+            now,
+        )?;
+
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        nullifier_account.is_used = true;
+        nullifier_account.used_at = now;
+        nullifier_account.epoch = (now as u64) / 86400;
+        nullifier_account.data_hash = [0u8; 32];
+        nullifier_account.proof_type = proof_type;
+        nullifier_account.proof_commitment = [0u8; 32];
+        nullifier_account.credential_types = proof_type_bit(proof_type_value);
+        nullifier_account.credential_id =
+            build_credential_id(&nullifier, proof_type_value, &verifier_account.verifier);
+        nullifier_account.verifier = verifier_account.verifier;
+        nullifier_account.recipient = ctx.accounts.recipient.key();
+
+        let verifier_account = &mut ctx.accounts.verifier_account;
+        verifier_account.attestation_count = verifier_account
+            .attestation_count
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+        verifier_account.last_attestation_at = now;
+
+        let batch = &mut ctx.accounts.batch;
+        batch.applied_count = batch
+            .applied_count
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+
+        let config = &mut ctx.accounts.config;
+        record_verified_proof(config, now)?;
+
+        emit!(BatchedAttestationRecorded {
+            batch: batch.key(),
+            nullifier,
+            verifier: verifier_account.verifier,
+            proof_type,
+            recipient: ctx.accounts.recipient.key(),
+            credential_id: nullifier_account.credential_id,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Close an attestation batch and refund its rent to the opener
+    /// Permissionless once every entry is applied or the batch has expired.
+    pub fn close_attestation_batch(ctx: Context<CloseAttestationBatch>) -> Result<()> {
+        let now = effective_now(&ctx.accounts.config)?;
+        let batch = &ctx.accounts.batch;
+        require!(
+            batch.applied_count >= batch.entry_count || now >= batch.expires_at,
+            VouchError::AttestationBatchOpen
+        );
+
+        emit!(AttestationBatchClosed {
+            batch: batch.key(),
+            verifier: batch.verifier,
+            applied_count: batch.applied_count,
+            entry_count: batch.entry_count,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Upgrade a used nullifier to a new proof type without minting a new nullifier
+    /// Requires config.allow_credential_upgrades and an active verifier's Ed25519
+    /// signature over build_credential_upgrade_message (in the preceding instruction).
+    /// proof_type becomes the new type; every type ever held is kept in
+    /// credential_types. Airdrop registrations snapshot proof_type when created,
+    /// so existing registrations keep their original tier and only registrations
+    /// made after the upgrade use the new one.
+    pub fn upgrade_credential(
+        ctx: Context<UpgradeCredential>,
+        nullifier: [u8; 32],
+        new_proof_type_value: u8,
+        epoch: u64,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let now = effective_now(config)?;
+
+        require!(!config.is_paused, VouchError::ProtocolPaused);
+        require!(
+            config.allow_credential_upgrades,
+            VouchError::CredentialUpgradesDisabled
+        );
+
+        let verifier_account = &ctx.accounts.verifier_account;
+        require!(
+            verifier_is_active(verifier_account, now),
+            VouchError::VerifierNotAuthorized
+        );
+
+        // Same freshness window as record_attestation
+        let current_epoch = (now as u64) / 86400;
+        require!(
+            current_epoch.saturating_sub(epoch) <= config.max_epoch_age,
+            VouchError::EpochTooOld
+        );
+        require!(epoch <= current_epoch + 1, VouchError::EpochInFuture);
+
+        let new_proof_type = proof_type_from_value(new_proof_type_value)?;
+        let nullifier_account = &ctx.accounts.nullifier_account;
+        let old_proof_type = nullifier_account.proof_type;
+        require!(
+            old_proof_type != ProofType::Unset && old_proof_type != new_proof_type,
+            VouchError::InvalidCredentialUpgrade
+        );
+
+        let message = build_credential_upgrade_message(
+            &nullifier,
+            old_proof_type as u8,
+            new_proof_type_value,
+            epoch,
+        );
+        verify_ed25519_signature(
+            &ctx.accounts.instructions_sysvar.to_account_info(),
+            &verifier_account.verifier,
+            &signature,
+            &message,
+            ctx.accounts.config.max_ed25519_scan_distance,
+            ctx.accounts.config.exclusive_ed25519_instructions,
+        )?;
+
+        record_used_signature(
+            &ctx.accounts.config,
+            ctx.accounts.used_signature.as_ref(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &signature,
+            &nullifier,
+            &verifier_account.verifier,
+            now,
+        )?;
+
+        // Nullifiers recorded before credential_types existed only know their current type
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        nullifier_account.credential_types |=
+            proof_type_bit(old_proof_type as u8) | proof_type_bit(new_proof_type_value);
+        nullifier_account.proof_type = new_proof_type;
+
+        emit!(CredentialUpgraded {
+            nullifier,
+            verifier: verifier_account.verifier,
+            old_proof_type,
+            new_proof_type,
+            credential_types: nullifier_account.credential_types,
+            epoch,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Update the reputation score stored on a used credential
+    /// Requires the issuing verifier's Ed25519 signature over
+    /// build_score_update_message (in the preceding instruction). `timestamp` must
+    /// be newer than the last update and not in the future, so an older signed
+    /// score can't be replayed over a newer one. Scores are unsigned: an update may
+    /// lower a score, but never below zero.
+    pub fn update_credential_score(
+        ctx: Context<UpdateCredentialScore>,
+        nullifier: [u8; 32],
+        new_score: u64,
+        timestamp: i64,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let now = effective_now(config)?;
+        require!(!config.is_paused, VouchError::ProtocolPaused);
+
+        let verifier_account = &ctx.accounts.verifier_account;
+        require!(
+            verifier_is_active(verifier_account, now),
+            VouchError::VerifierNotAuthorized
+        );
+
+        let nullifier_account = &ctx.accounts.nullifier_account;
+        require_diag!(
+            nullifier_account.verifier == verifier_account.verifier,
+            VerifierNotAuthorized,
+            "verifier={} issuing_verifier={}",
+            verifier_account.verifier,
+            nullifier_account.verifier
+        );
+        require_diag!(
+            timestamp > nullifier_account.score_updated_at && timestamp <= now,
+            StaleScoreUpdate,
+            "timestamp={} score_updated_at={} now={}",
+            timestamp,
+            nullifier_account.score_updated_at,
+            now
+        );
+
+        let message = build_score_update_message(&nullifier, new_score, timestamp);
+        verify_ed25519_signature(
+            &ctx.accounts.instructions_sysvar.to_account_info(),
+            &verifier_account.verifier,
+            &signature,
+            &message,
+            config.max_ed25519_scan_distance,
+            config.exclusive_ed25519_instructions,
+        )?;
+
+        record_used_signature(
+            &ctx.accounts.config,
+            ctx.accounts.used_signature.as_ref(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &signature,
+            &nullifier,
+            &verifier_account.verifier,
+            now,
+        )?;
+
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        let old_score = nullifier_account.score;
+        nullifier_account.score = new_score;
+        nullifier_account.score_updated_at = timestamp;
+
+        emit!(CredentialScoreUpdated {
+            nullifier,
+            verifier: verifier_account.verifier,
+            old_score,
+            new_score,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Publish a verifier-signed merkle root of eligible (nullifier, proof_type) pairs
+    /// Anyone can submit the root; the verifier's Ed25519 signature over
+    /// build_verifier_root_message (in the preceding instruction) authorizes it.
+    /// Replaces any previously published root for this verifier.
+    pub fn publish_verifier_root(
+        ctx: Context<PublishVerifierRoot>,
+        merkle_root: [u8; 32],
+        signature: [u8; 64],
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused, VouchError::ProtocolPaused);
+        let now = effective_now(&ctx.accounts.config)?;
+
+        let verifier_account = &ctx.accounts.verifier_account;
+        require!(
+            verifier_is_active(verifier_account, now),
+            VouchError::VerifierNotAuthorized
+        );
+
+        let message = build_verifier_root_message(&verifier_account.verifier, &merkle_root);
+        verify_ed25519_signature(
+            &ctx.accounts.instructions_sysvar.to_account_info(),
+            &verifier_account.verifier,
+            &signature,
+            &message,
+            ctx.accounts.config.max_ed25519_scan_distance,
+            ctx.accounts.config.exclusive_ed25519_instructions,
+        )?;
+
+        record_used_signature(
+            &ctx.accounts.config,
+            ctx.accounts.used_signature.as_ref(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &signature,
+            &merkle_root,
+            &verifier_account.verifier,
+            now,
+        )?;
+
+        let verifier_account = &mut ctx.accounts.verifier_account;
+        verifier_account.merkle_root = merkle_root;
+        verifier_account.merkle_root_published_at = now;
+
+        emit!(VerifierRootPublished {
+            verifier: verifier_account.verifier,
+            merkle_root,
+            timestamp: verifier_account.merkle_root_published_at,
+        });
+
+        Ok(())
+    }
+
+    /// Record an attestation by proving inclusion in a verifier's published merkle root
+    /// The leaf is build_merkle_attestation_leaf(proof_type, nullifier, recipient,
+    /// expires_at, bound_commitment), so an entry only credits the wallet it names
+    /// and lapses at expires_at. Roots older than max_epoch_age days are rejected,
+    /// mirroring the signed-attestation freshness rule. The leaf carries no proof
+    /// commitment, so this path is closed while require_proof_commitment is set.
+    pub fn record_attestation_merkle(
+        ctx: Context<RecordAttestationMerkle>,
+        proof_type_value: u8,
+        nullifier: [u8; 32],
+        expires_at: i64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let now = effective_now(config)?;
+
+        // Check protocol is not paused
+        require!(!config.is_paused, VouchError::ProtocolPaused);
+        require!(
+            config.verifier_count >= config.min_active_verifiers,
+            VouchError::InsufficientVerifiers
+        );
+        require!(config.required_signatures <= 1, VouchError::InsufficientSignatures);
+
+        // Verify the verifier is authorized and has a fresh root
+        let verifier_account = &ctx.accounts.verifier_account;
+        require!(
+            verifier_is_active(verifier_account, now),
+            VouchError::VerifierNotAuthorized
+        );
+        require!(
+            verifier_account.merkle_root != [0u8; 32],
+            VouchError::InvalidMerkleProof
+        );
+        let root_age_days = (now.saturating_sub(verifier_account.merkle_root_published_at)
+            / SECONDS_PER_DAY) as u64;
+        require!(root_age_days <= config.max_epoch_age, VouchError::EpochTooOld);
+        require_diag!(
+            now <= expires_at,
+            AttestationExpired,
+            "expires_at={} now={}",
+            expires_at,
+            now
+        );
+
+        check_commitment_requirement(
+            config,
+            ctx.accounts.commitment_account.as_ref(),
+            &ctx.accounts.recipient.key(),
+            Some(proof_type_value),
+        )?;
+        check_recipient_not_blocked(
+            config,
+            ctx.accounts.blocked_recipient.as_ref(),
+            &ctx.accounts.recipient.key(),
+        )?;
+        let bound_commitment = bound_wallet_commitment(
+            config,
+            ctx.accounts.commitment_account.as_ref(),
+            &ctx.accounts.recipient.key(),
+        )?;
+
+        // Merkle leaves carry no proof commitment
+        require_diag!(
+            !config.require_proof_commitment,
+            ProofCommitmentRequired,
+            "proof_commitment=none require_proof_commitment=true"
+        );
+
+        // Verify inclusion of the recipient-bound entry in the signed root
+        require!(proof.len() <= MAX_MERKLE_PROOF_DEPTH, VouchError::InvalidMerkleProof);
+        let proof_type = proof_type_from_value(proof_type_value)?;
+        let leaf = build_merkle_attestation_leaf(
+            proof_type_value,
+            &nullifier,
+            &ctx.accounts.recipient.key(),
+            expires_at,
+            bound_commitment.as_ref(),
+        );
+        require!(
+            verify_merkle_proof(&proof, &verifier_account.merkle_root, &leaf),
+            VouchError::InvalidMerkleProof
+        );
+
+        let merkle_root = verifier_account.merkle_root;
+        require!(
+            !ctx.accounts.nullifier_account.is_used,
+            VouchError::NullifierAlreadyUsed
+        );
+        if trip_attestation_circuit_breaker(&mut ctx.accounts.config, now)? {
+            return Ok(());
+        }
+
+        // Check and update rate limits
+        check_and_update_rate_limit(
+            &mut ctx.accounts.rate_limit,
+            &ctx.accounts.config,
+            proof_type,
+            now,
+        )?;
+
+        // Mark nullifier as used
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        nullifier_account.is_used = true;
+        nullifier_account.used_at = now;
+        nullifier_account.epoch = (now as u64) / 86400;
+        nullifier_account.data_hash = [0u8; 32];
+        nullifier_account.proof_type = proof_type;
+        nullifier_account.proof_commitment = [0u8; 32];
+        nullifier_account.credential_types = proof_type_bit(proof_type_value);
+        nullifier_account.credential_id =
+            build_credential_id(&nullifier, proof_type_value, &verifier_account.verifier);
+        nullifier_account.verifier = verifier_account.verifier;
+        nullifier_account.recipient = ctx.accounts.recipient.key();
+
+        // Update verifier stats
+        let verifier_account = &mut ctx.accounts.verifier_account;
+        verifier_account.attestation_count = verifier_account
+            .attestation_count
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+        verifier_account.last_attestation_at = now;
+
+        // Update global stats
+        let config = &mut ctx.accounts.config;
+        record_verified_proof(config, now)?;
+
+        emit!(MerkleAttestationRecorded {
+            nullifier,
+            merkle_root,
+            verifier: verifier_account.verifier,
+            proof_type,
+            recipient: ctx.accounts.recipient.key(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // === Commitment & Nullifier Management ===
+
+    /// Initialize a new commitment for a wallet
+    /// The commitment = hash(wallet_pubkey + secret) is stored on-chain
+    pub fn create_commitment(ctx: Context<CreateCommitment>, commitment: [u8; 32]) -> Result<()> {
+        let commitment_account = &mut ctx.accounts.commitment_account;
+        commitment_account.commitment = commitment;
+        commitment_account.owner = ctx.accounts.owner.key();
+        commitment_account.created_at = Clock::get()?.unix_timestamp;
+        commitment_account.bump = ctx.bumps.commitment_account;
+
+        emit!(CommitmentCreated {
+            owner: ctx.accounts.owner.key(),
+            commitment,
+            timestamp: commitment_account.created_at,
+        });
+
+        Ok(())
+    }
+
+    /// Revoke a recorded credential found to be fraudulent
+    /// Callable by admin, or by the active verifier that issued the credential.
+    /// The nullifier stays used (it can't be re-attested or closed) but no longer
+    /// registers for airdrops, claims via merkle, or upgrades. Registrations made
+    /// before the revocation can't be claimed either (claim_airdrop,
+    /// claim_airdrop_to_custody and claim_airdrop_multi check the credential);
+    /// ShadowWire distributions to them are left to the campaign creator.
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>, reason: u8) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let now = effective_now(config)?;
+        let authority = ctx.accounts.authority.key();
+        let nullifier_account = &ctx.accounts.nullifier_account;
+
+        let is_issuing_verifier = match ctx.accounts.verifier_account.as_ref() {
+            Some(verifier_account) => {
+                verifier_account.verifier == authority
+                    && nullifier_account.verifier == authority
+                    && verifier_is_active(verifier_account, now)
+            }
+            None => false,
+        };
+        require!(
+            authority == config.admin || is_issuing_verifier,
+            VouchError::Unauthorized
+        );
+
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        nullifier_account.is_revoked = true;
+
+        emit!(AttestationRevoked {
+            nullifier: nullifier_account.nullifier,
+            credential_id: nullifier_account.credential_id,
+            revoked_by: authority,
+            reason,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Initialize a nullifier account (must be called before record_attestation)
+    /// This separates account creation from verification for security
+    ///
+    /// `namespace` is the proof type value the nullifier will be attested under
+    /// (see nullifier_namespace_seed); use 0 for record_registered_attestation.
+    ///
+    /// Migration: nullifier PDAs used to be derived from [b"nullifier", nullifier]
+    /// alone, which is now namespace 0. Used credentials stay there and remain
+    /// valid for airdrops, closing and revocation (those instructions derive the
+    /// PDA from the account's stored namespace). Unused nullifiers created for a
+    /// built-in type before namespacing can no longer be attested and should be
+    /// re-initialized under the type's namespace.
+    pub fn init_nullifier(
+        ctx: Context<InitNullifier>,
+        nullifier: [u8; 32],
+        namespace: u8,
+    ) -> Result<()> {
+        validate_nullifier_namespace(namespace)?;
+
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        nullifier_account.nullifier = nullifier;
+        nullifier_account.is_used = false;
+        nullifier_account.used_at = 0;
+        nullifier_account.epoch = 0;
+        nullifier_account.data_hash = [0u8; 32];
+        nullifier_account.proof_type = ProofType::Unset;
+        nullifier_account.proof_type_id = 0;
+        nullifier_account.proof_commitment = [0u8; 32];
+        nullifier_account.credential_types = 0;
+        nullifier_account.credential_id = [0u8; 32];
+        nullifier_account.verifier = Pubkey::default();
+        nullifier_account.pinned = false;
+        nullifier_account.payer = ctx.accounts.payer.key();
+        nullifier_account.score = 0;
+        nullifier_account.score_updated_at = 0;
+        nullifier_account.is_revoked = false;
+        nullifier_account.namespace = namespace;
+        nullifier_account.recipient = Pubkey::default();
+        nullifier_account.bump = ctx.bumps.nullifier_account;
+        nullifier_account.version = NULLIFIER_ACCOUNT_VERSION;
+
+        Ok(())
+    }
+
+    /// Pre-create many nullifier accounts in one instruction
+    /// remaining_accounts: the nullifier PDA for each nullifier, in the same order.
+    /// Nullifiers whose account already exists are skipped. All nullifiers in
+    /// the batch share one namespace (see init_nullifier).
+    pub fn init_nullifiers_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitNullifiersBatch<'info>>,
+        nullifiers: Vec<[u8; 32]>,
+        namespace: u8,
+    ) -> Result<()> {
+        require!(
+            !nullifiers.is_empty() && nullifiers.len() <= MAX_NULLIFIER_BATCH,
+            VouchError::InvalidBatchSize
+        );
+        require!(
+            ctx.remaining_accounts.len() == nullifiers.len(),
+            VouchError::InvalidNullifierAccount
+        );
+        validate_nullifier_namespace(namespace)?;
+        let namespace_seed = nullifier_namespace_seed(&namespace);
+
+        let now = Clock::get()?.unix_timestamp;
+
+        for (nullifier, info) in nullifiers.iter().zip(ctx.remaining_accounts.iter()) {
+            let (expected, bump) = Pubkey::find_program_address(
+                &[b"nullifier", namespace_seed, nullifier.as_ref()],
+                &crate::ID,
+            );
+            require!(expected == *info.key, VouchError::InvalidNullifierAccount);
+
+            if info.owner == &crate::ID {
+                continue;
+            }
+
+            create_pda_account(
+                &ctx.accounts.payer.to_account_info(),
+                info,
+                &ctx.accounts.system_program.to_account_info(),
+                8 + NullifierAccount::INIT_SPACE,
+                &[b"nullifier", namespace_seed, nullifier.as_ref(), &[bump]],
+            )?;
+            let nullifier_account = NullifierAccount {
+                nullifier: *nullifier,
+                is_used: false,
+                used_at: 0,
+                epoch: 0,
+                data_hash: [0u8; 32],
+                proof_type: ProofType::Unset,
+                proof_commitment: [0u8; 32],
+                credential_types: 0,
+                credential_id: [0u8; 32],
+                verifier: Pubkey::default(),
+                pinned: false,
+                payer: ctx.accounts.payer.key(),
+                proof_type_id: 0,
+                score: 0,
+                score_updated_at: 0,
+                is_revoked: false,
+                namespace,
+                recipient: Pubkey::default(),
+                bump,
+                version: NULLIFIER_ACCOUNT_VERSION,
+            };
+            let mut data = info.try_borrow_mut_data()?;
+            nullifier_account.try_serialize(&mut &mut data[..])?;
+
+            emit!(NullifierInitialized {
+                nullifier: *nullifier,
+                payer: ctx.accounts.payer.key(),
+                timestamp: now,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Close a used nullifier and refund its rent to `receiver`
+    /// Only the nullifier's original payer or admin can close it, and only once
+    /// it has been used for at least the retention period. The retention is never
+    /// shorter than the attestation replay window (see nullifier_retention_seconds):
+    /// after that, a closed-and-recreated nullifier needs a fresh verifier attestation.
+    pub fn close_nullifier(ctx: Context<CloseNullifier>) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let nullifier_account = &ctx.accounts.nullifier_account;
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == nullifier_account.payer || authority == config.admin,
+            VouchError::Unauthorized
+        );
+        require!(nullifier_account.is_used, VouchError::NullifierNotVerified);
+        require!(!nullifier_account.pinned, VouchError::PinnedNullifier);
+        // Closing would let a revoked nullifier be attested again
+        require!(!nullifier_account.is_revoked, VouchError::CredentialRevoked);
+
+        let now = effective_now(config)?;
+        require!(
+            now.saturating_sub(nullifier_account.used_at) >= nullifier_retention_seconds(config),
+            VouchError::NullifierRetentionActive
+        );
+
+        emit!(NullifierClosed {
+            nullifier: nullifier_account.nullifier,
+            authority,
+            receiver: ctx.accounts.receiver.key(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Set how long a used nullifier must be retained before close_nullifier
+    /// Only admin can call this. Values below the replay window are allowed but
+    /// have no effect (the replay window always applies).
+    pub fn set_min_nullifier_age(
+        ctx: Context<AdminControl>,
+        min_nullifier_age_seconds: i64,
+    ) -> Result<()> {
+        require!(min_nullifier_age_seconds >= 0, VouchError::InvalidHoldPeriod);
+
+        let config = &mut ctx.accounts.config;
+        let old_min_age = config.min_nullifier_age_seconds;
+        config.min_nullifier_age_seconds = min_nullifier_age_seconds;
+
+        emit!(MinNullifierAgeUpdated {
+            admin: ctx.accounts.admin.key(),
+            old_min_age_seconds: old_min_age,
+            new_min_age_seconds: min_nullifier_age_seconds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pin a nullifier so it is retained during an investigation
+    /// Only admin can call this. close_nullifier refuses pinned nullifiers.
+    pub fn pin_nullifier(ctx: Context<SetNullifierPin>) -> Result<()> {
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        require!(!nullifier_account.pinned, VouchError::NullifierAlreadyPinned);
+        nullifier_account.pinned = true;
+
+        emit!(NullifierPinned {
+            nullifier: nullifier_account.nullifier,
+            admin: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Release a pinned nullifier
+    /// Only admin can call this
+    pub fn unpin_nullifier(ctx: Context<SetNullifierPin>) -> Result<()> {
+        let nullifier_account = &mut ctx.accounts.nullifier_account;
+        require!(nullifier_account.pinned, VouchError::NullifierNotPinned);
+        nullifier_account.pinned = false;
+
+        emit!(NullifierUnpinned {
+            nullifier: nullifier_account.nullifier,
+            admin: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// View: derive the nullifier PDA and report its state in one call
+    /// Returns NullifierAccountStatus via return data. The PDA is derived
+    /// on-chain so clients can't drift from the program's actual seeds; a
+    /// never-created nullifier reports is_initialized = false.
+    pub fn nullifier_account_info(
+        ctx: Context<GetNullifierAccountInfo>,
+        nullifier: [u8; 32],
+        namespace: u8,
+    ) -> Result<NullifierAccountStatus> {
+        let (pda, bump) = Pubkey::find_program_address(
+            &[
+                b"nullifier",
+                nullifier_namespace_seed(&namespace),
+                nullifier.as_ref(),
+            ],
+            &crate::ID,
+        );
+
+        let info = &ctx.accounts.nullifier_account;
+        require!(info.key() == pda, VouchError::InvalidNullifierAccount);
+
+        let (is_initialized, is_used, is_revoked) =
+            if info.owner == &crate::ID && !info.data_is_empty() {
+                let data = info.try_borrow_data()?;
+                let account = NullifierAccount::try_deserialize(&mut &data[..])?;
+                (true, account.is_used, account.is_revoked)
+            } else {
+                (false, false, false)
+            };
+
+        Ok(NullifierAccountStatus {
+            pda,
+            bump,
+            is_initialized,
+            is_used,
+            is_revoked,
+        })
+    }
+
+    /// View: whether a verifier signature has been recorded
+    /// Returns the usage status via return data. The UsedSignature PDA is
+    /// derived on-chain from the signature hash; only signatures accepted while
+    /// config.track_used_signatures was set have a record.
+    pub fn is_signature_used(ctx: Context<GetSignatureUsage>, signature: [u8; 64]) -> Result<bool> {
+        let (pda, _) = Pubkey::find_program_address(
+            &[b"used_signature", used_signature_seed(&signature).as_ref()],
+            &crate::ID,
+        );
+
+        let info = &ctx.accounts.used_signature;
+        require!(info.key() == pda, VouchError::InvalidUsedSignatureAccount);
+
+        Ok(info.owner == &crate::ID && !info.data_is_empty())
+    }
+
+    // NOTE: Direct on-chain proof verification instructions (verify_dev_reputation, verify_whale_trading)
+    // have been removed. UltraHonk proofs cannot be verified natively on Solana.
+    //
+    // For production use, the correct flow is:
+    // 1. Client generates UltraHonk proof in browser
+    // 2. Client sends proof to off-chain verifier service
+    // 3. Verifier cryptographically verifies proof and signs attestation
+    // 4. Client calls record_attestation with the signed attestation
+    // 5. On-chain program verifies Ed25519 signature and records result
+    //
+    // See: https://github.com/solana-foundation/noir-examples for Groth16 alternative
+
+    // === Private Airdrop Registry ===
+    // Enables privacy-preserving airdrops where:
+    // - Recipients prove eligibility via Vouch credentials
+    // - Distribution amounts are hidden via ShadowWire
+    // - No one can link real wallets to airdrop amounts
+
+    /// Create a new airdrop campaign
+    /// Only the campaign creator can distribute to registered addresses
+    /// Create a tiered airdrop campaign
+    /// - base_amount: Everyone gets this (open registration)
+    /// - dev_bonus: Additional amount for verified developers
+    /// - whale_bonus: Additional amount for verified whales
+    /// - claim_deadline: Last moment registrants can claim (ClaimPeriodEnded after
+    ///   it); must be after the registration deadline unless 0, which means no
+    ///   deadline (settable later via set_claim_deadline)
+    /// - campaign_claim_fee_bps: Creator fee taken from each claim (0 = no fee)
+    /// - claim_fee_account: Token account receiving the creator fee
+    /// - proof_type_bonuses: Per-proof-type bonus table (up to 8 unique IDs);
+    ///   a listed ID gets base + its bonus instead of dev_bonus/whale_bonus or
+    ///   the registry bonus, and registry types listed here are eligible
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_airdrop_campaign(
+        ctx: Context<CreateAirdropCampaign>,
+        campaign_id: [u8; 32],
+        name: String,
+        token_mint: Pubkey,
+        base_amount: u64,
+        dev_bonus: u64,
+        whale_bonus: u64,
+        registration_deadline: i64,
+        claim_deadline: i64,
+        campaign_claim_fee_bps: u16,
+        claim_fee_account: Pubkey,
+        proof_type_bonuses: Vec<ProofTypeBonus>,
+    ) -> Result<()> {
+        let params = CampaignParams {
+            token_mint,
+            base_amount,
+            dev_bonus,
+            whale_bonus,
+            campaign_claim_fee_bps,
+            claim_fee_account,
+        };
+        init_airdrop_campaign(
+            &mut ctx.accounts.config,
+            &mut ctx.accounts.campaign,
+            ctx.accounts.creator.key(),
+            campaign_id,
+            name,
+            &params,
+            proof_type_bonuses,
+            registration_deadline,
+            claim_deadline,
+        )?;
+        ctx.accounts.campaign.bump = ctx.bumps.campaign;
+
+        Ok(())
+    }
+
+    /// Save a reusable campaign parameter set for recurring airdrops
+    /// Each campaign created from it registers for registration_window_seconds,
+    /// then accepts claims for claim_window_seconds past the registration
+    /// deadline (0 = no claim deadline). allowed_proof_types is a proof_type_bit
+    /// mask (bit 0 = open registration, 0 = everyone eligible);
+    /// proof_type_bonuses is copied as in create_airdrop_campaign.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_campaign_template(
+        ctx: Context<CreateCampaignTemplate>,
+        template_id: [u8; 32],
+        params: CampaignParams,
+        registration_window_seconds: i64,
+        allowed_proof_types: u8,
+        proof_type_bonuses: Vec<ProofTypeBonus>,
+        claim_window_seconds: i64,
+    ) -> Result<()> {
+        validate_campaign_params(&params)?;
+        validate_template_windows(registration_window_seconds, claim_window_seconds)?;
+        validate_proof_type_bonuses(&proof_type_bonuses)?;
+
+        let template = &mut ctx.accounts.template;
+        template.creator = ctx.accounts.creator.key();
+        template.template_id = template_id;
+        template.params = params;
+        template.registration_window_seconds = registration_window_seconds;
+        template.allowed_proof_types = allowed_proof_types;
+        template.proof_type_bonuses = proof_type_bonuses;
+        template.claim_window_seconds = claim_window_seconds;
+        template.campaigns_created = 0;
+        template.bump = ctx.bumps.template;
+
+        emit!(CampaignTemplateSaved {
+            template: template.key(),
+            creator: template.creator,
+            template_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Replace a template's parameter set
+    /// Only the template creator can update it; existing campaigns are unaffected.
+    pub fn update_campaign_template(
+        ctx: Context<UpdateCampaignTemplate>,
+        params: CampaignParams,
+        registration_window_seconds: i64,
+        allowed_proof_types: u8,
+        proof_type_bonuses: Vec<ProofTypeBonus>,
+        claim_window_seconds: i64,
+    ) -> Result<()> {
+        validate_campaign_params(&params)?;
+        validate_template_windows(registration_window_seconds, claim_window_seconds)?;
+        validate_proof_type_bonuses(&proof_type_bonuses)?;
+
+        let template = &mut ctx.accounts.template;
+        template.params = params;
+        template.registration_window_seconds = registration_window_seconds;
+        template.allowed_proof_types = allowed_proof_types;
+        template.proof_type_bonuses = proof_type_bonuses;
+        template.claim_window_seconds = claim_window_seconds;
+
+        emit!(CampaignTemplateSaved {
+            template: template.key(),
+            creator: template.creator,
+            template_id: template.template_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create a campaign from a template with a fresh campaign_id
+    /// The registration deadline is now + template.registration_window_seconds,
+    /// and the claim deadline that + template.claim_window_seconds (none when 0).
+    pub fn create_campaign_from_template(
+        ctx: Context<CreateCampaignFromTemplate>,
+        campaign_id: [u8; 32],
+        name: String,
+    ) -> Result<()> {
+        let template = &ctx.accounts.template;
+        let registration_deadline = effective_now(&ctx.accounts.config)?
+            .checked_add(template.registration_window_seconds)
+            .ok_or(VouchError::Overflow)?;
+        let claim_deadline = if template.claim_window_seconds == 0 {
+            0
+        } else {
+            registration_deadline
+                .checked_add(template.claim_window_seconds)
+                .ok_or(VouchError::Overflow)?
+        };
+        let params = template.params;
+        let allowed_proof_types = template.allowed_proof_types;
+        let proof_type_bonuses = template.proof_type_bonuses.clone();
+
+        init_airdrop_campaign(
+            &mut ctx.accounts.config,
+            &mut ctx.accounts.campaign,
+            ctx.accounts.creator.key(),
+            campaign_id,
+            name,
+            &params,
+            proof_type_bonuses,
+            registration_deadline,
+            claim_deadline,
+        )?;
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.allowed_proof_types = allowed_proof_types;
+        campaign.bump = ctx.bumps.campaign;
+
+        let template = &mut ctx.accounts.template;
+        template.campaigns_created = template
+            .campaigns_created
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+
+        emit!(CampaignCreatedFromTemplate {
+            campaign_id,
+            template: template.key(),
+            template_id: template.template_id,
+            creator: template.creator,
+            registration_deadline,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Register for an airdrop campaign using a verified Vouch credential
+    /// Gets base_amount + bonus (dev_bonus or whale_bonus based on credential)
+    /// Links the user's nullifier to their ShadowWire address for private distribution
+    pub fn register_for_airdrop(
+        ctx: Context<RegisterForAirdrop>,
+        shadow_wire_address: String,
+    ) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let nullifier_account = &ctx.accounts.nullifier_account;
+        require_campaigns_unpaused(&ctx.accounts.config)?;
+        let now = effective_now(&ctx.accounts.config)?;
+
+        require_diag!(!campaign.is_paused, CampaignPaused, "is_paused=true");
+
+        // Verify campaign is open
+        require_diag!(
+            campaign.status == CampaignStatus::Open,
+            CampaignNotOpen,
+            "status={}",
+            campaign.status as u8
+        );
+        require_diag!(
+            now < campaign.registration_deadline,
+            RegistrationClosed,
+            "now={} registration_deadline={}",
+            now,
+            campaign.registration_deadline
+        );
+
+        // Verify nullifier is used (proves user has Vouch credential)
+        require_diag!(nullifier_account.is_used, NullifierNotVerified, "is_used=false");
+        require_diag!(!nullifier_account.is_revoked, CredentialRevoked, "is_revoked=true");
+
+        // Registry proof types carry their own bonus (unless the campaign's
+        // table overrides it) and are only eligible on campaigns that accept
+        // any proof type or list them in proof_type_bonuses
+        let proof_type_id = effective_proof_type_id(nullifier_account);
+        let registered_bonus = if proof_type_id >= FIRST_REGISTERED_PROOF_TYPE_ID {
+            require_diag!(
+                ctx.accounts.proof_type_registry.is_some(),
+                ProofTypeRegistryRequired,
+                "proof_type_id={} proof_type_registry=none",
+                proof_type_id
+            );
+            let registry = ctx
+                .accounts
+                .proof_type_registry
+                .as_ref()
+                .ok_or(VouchError::ProofTypeRegistryRequired)?;
+            let registry_bonus = registered_proof_type(registry, proof_type_id)?.airdrop_bonus;
+            Some(campaign_proof_type_bonus(campaign, proof_type_id).unwrap_or(registry_bonus))
+        } else {
+            None
+        };
+        require_diag!(
+            match registered_bonus {
+                Some(_) => {
+                    campaign.allowed_proof_types == 0
+                        || campaign_proof_type_bonus(campaign, proof_type_id).is_some()
+                }
+                None => proof_type_eligible(campaign, nullifier_account.proof_type),
+            },
+            ProofTypeNotEligible,
+            "proof_type_id={} allowed_proof_types={}",
+            proof_type_id,
+            campaign.allowed_proof_types
+        );
+
+        // Campaigns may scope eligibility to a single trusted issuer
+        if let Some(required_verifier) = campaign.required_verifier {
+            require_diag!(
+                nullifier_account.verifier == required_verifier,
+                WrongVerifier,
+                "verifier={} required_verifier={}",
+                nullifier_account.verifier,
+                required_verifier
+            );
+        }
+
+        // Campaigns may require the registrant to be the credential's owner,
+        // not just someone who knows the nullifier
+        if campaign.require_credential_owner {
+            require_diag!(
+                nullifier_account.recipient == ctx.accounts.payer.key(),
+                NotCredentialOwner,
+                "recipient={} payer={}",
+                nullifier_account.recipient,
+                ctx.accounts.payer.key()
+            );
+        }
+
+        // Validate ShadowWire address format (base58, decodes to 32 bytes)
+        validate_shadow_wire_address(&shadow_wire_address)?;
+
+        // Anti-sybil: cap registrations funneling to one ShadowWire address
+        enforce_shadow_wire_reuse_limit(
+            campaign,
+            ctx.accounts
+                .shadow_wire_usage
+                .as_ref()
+                .map(|usage| usage.to_account_info())
+                .as_ref(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &shadow_wire_address,
+        )?;
+
+        // Create registration
+        let registration = &mut ctx.accounts.registration;
+        registration.campaign = campaign.key();
+        registration.nullifier = nullifier_account.nullifier;
+        registration.shadow_wire_address = shadow_wire_address.clone();
+        registration.proof_type = nullifier_account.proof_type;
+        registration.proof_type_id = proof_type_id;
+        registration.is_open_registration = false;
+        // Credentials recorded before recipients were stored bind to the registrant
+        registration.recipient = if nullifier_account.recipient != Pubkey::default() {
+            nullifier_account.recipient
+        } else {
+            ctx.accounts.payer.key()
+        };
+        registration.registered_at = now;
+        registration.is_distributed = false;
+        registration.is_claimed = false;
+        registration.claimed_at = 0;
+        registration.claimed_amount = 0;
+        registration.bump = ctx.bumps.registration;
+        registration.version = REGISTRATION_ACCOUNT_VERSION;
+        registration.memo = String::new();
+        registration.claim_in_progress = false;
+
+        // Update campaign stats
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.total_registrations = campaign
+            .total_registrations
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+
+        match (nullifier_account.proof_type, registered_bonus) {
+            (_, Some(_)) => {
+                campaign.registry_registrations = campaign
+                    .registry_registrations
+                    .checked_add(1)
+                    .ok_or(VouchError::Overflow)?;
+            }
+            (ProofType::DeveloperReputation, None) => {
+                campaign.dev_registrations = campaign
+                    .dev_registrations
+                    .checked_add(1)
+                    .ok_or(VouchError::Overflow)?;
+            }
+            (ProofType::WhaleTrading, None) => {
+                campaign.whale_registrations = campaign
+                    .whale_registrations
+                    .checked_add(1)
+                    .ok_or(VouchError::Overflow)?;
+            }
+            _ => return Err(VouchError::InvalidProofType.into()),
+        }
+
+        let allocation = match registered_bonus {
+            Some(bonus) => campaign
+                .base_amount
+                .checked_add(bonus)
+                .and_then(|amount| amount.checked_add(decayed_bonus(campaign, now)))
+                .ok_or(VouchError::Overflow)?,
+            None => registration_allocation(campaign, nullifier_account.proof_type, now)?,
+        };
+        ctx.accounts.registration.allocation = allocation;
+        campaign.total_allocated = campaign
+            .total_allocated
+            .checked_add(allocation)
+            .ok_or(VouchError::Overflow)?;
+
+        record_campaign_activity(
+            campaign,
+            ctx.accounts.activity_log.as_mut(),
+            CampaignActivityKind::Registration,
+            nullifier_account.nullifier,
+            allocation,
+            now,
+        )?;
+
+        record_wallet_registration(
+            ctx.accounts.registration_index.as_mut(),
+            campaign.key(),
+            ctx.accounts.registration.key(),
+        )?;
+
+        emit!(AirdropRegistration {
+            campaign_id: campaign.campaign_id,
+            nullifier: nullifier_account.nullifier,
+            shadow_wire_address,
+            proof_type: nullifier_account.proof_type,
+            proof_type_id,
+            total_allocated: campaign.total_allocated,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Save the caller's default ShadowWire address for future registrations
+    /// Creates the WalletProfile PDA on first use; later calls overwrite it.
+    pub fn set_default_shadow_wire(
+        ctx: Context<SetDefaultShadowWire>,
+        shadow_wire_address: String,
+    ) -> Result<()> {
+        validate_shadow_wire_address(&shadow_wire_address)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let wallet_profile = &mut ctx.accounts.wallet_profile;
+        wallet_profile.wallet = ctx.accounts.wallet.key();
+        wallet_profile.default_shadow_wire_address = shadow_wire_address.clone();
+        wallet_profile.updated_at = now;
+        wallet_profile.bump = ctx.bumps.wallet_profile;
+
+        emit!(DefaultShadowWireUpdated {
+            wallet: wallet_profile.wallet,
+            shadow_wire_address,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Register for an airdrop campaign using the payer's saved ShadowWire address
+    /// Same as register_for_airdrop, with the address read from the payer's
+    /// WalletProfile (see set_default_shadow_wire) instead of an argument.
+    pub fn register_for_airdrop_with_default(ctx: Context<RegisterForAirdrop>) -> Result<()> {
+        let shadow_wire_address = ctx
+            .accounts
+            .wallet_profile
+            .as_ref()
+            .ok_or(VouchError::WalletProfileRequired)?
+            .default_shadow_wire_address
+            .clone();
+
+        register_for_airdrop(ctx, shadow_wire_address)
+    }
+
+    /// Register for an airdrop campaign without verification (open registration)
+    /// Gets only base_amount (no bonus)
+    /// Uses wallet pubkey hash as unique identifier to prevent double registration
+    pub fn register_for_airdrop_open(
+        ctx: Context<RegisterForAirdropOpen>,
+        shadow_wire_address: String,
+    ) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        require_campaigns_unpaused(&ctx.accounts.config)?;
+        let now = effective_now(&ctx.accounts.config)?;
+
+        require!(!campaign.is_paused, VouchError::CampaignPaused);
+
+        // Verify campaign is open
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+        require!(now < campaign.registration_deadline, VouchError::RegistrationClosed);
+        require!(
+            !campaign.open_registration_disabled,
+            VouchError::OpenRegistrationDisabled
+        );
+        require!(
+            proof_type_eligible(campaign, ProofType::Unset),
+            VouchError::ProofTypeNotEligible
+        );
+
+        // Validate ShadowWire address format (base58, decodes to 32 bytes)
+        validate_shadow_wire_address(&shadow_wire_address)?;
+
+        // Anti-sybil: cap registrations funneling to one ShadowWire address
+        enforce_shadow_wire_reuse_limit(
+            campaign,
+            ctx.accounts
+                .shadow_wire_usage
+                .as_ref()
+                .map(|usage| usage.to_account_info())
+                .as_ref(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &shadow_wire_address,
+        )?;
+
+        // Create unique identifier from wallet pubkey (hash to 32 bytes)
+        let wallet_id = ctx.accounts.payer.key().to_bytes();
+
+        // Create registration
+        let registration = &mut ctx.accounts.registration;
+        registration.campaign = campaign.key();
+        registration.nullifier = wallet_id; // Use wallet pubkey as identifier
+        registration.shadow_wire_address = shadow_wire_address.clone();
+        registration.proof_type = ProofType::Unset; // No verification
+        registration.is_open_registration = true;
+        registration.recipient = Pubkey::default(); // Unbound: already keyed by payer
+        registration.registered_at = now;
+        registration.is_distributed = false;
+        registration.is_claimed = false;
+        registration.claimed_at = 0;
+        registration.claimed_amount = 0;
+        registration.bump = ctx.bumps.registration;
+        registration.version = REGISTRATION_ACCOUNT_VERSION;
+        registration.memo = String::new();
+        registration.claim_in_progress = false;
+
+        // Update campaign stats
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.total_registrations = campaign
+            .total_registrations
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+        campaign.open_registrations = campaign
+            .open_registrations
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+
+        let allocation = registration_allocation(campaign, ProofType::Unset, now)?;
+        ctx.accounts.registration.allocation = allocation;
+        campaign.total_allocated = campaign
+            .total_allocated
+            .checked_add(allocation)
+            .ok_or(VouchError::Overflow)?;
+
+        record_campaign_activity(
+            campaign,
+            ctx.accounts.activity_log.as_mut(),
+            CampaignActivityKind::Registration,
+            wallet_id,
+            allocation,
+            now,
+        )?;
+
+        record_wallet_registration(
+            ctx.accounts.registration_index.as_mut(),
+            campaign.key(),
+            ctx.accounts.registration.key(),
+        )?;
+
+        emit!(AirdropRegistration {
+            campaign_id: campaign.campaign_id,
+            nullifier: wallet_id,
+            shadow_wire_address,
+            proof_type: ProofType::Unset,
+            // Open registrations carry no credential
+            proof_type_id: 0,
+            total_allocated: campaign.total_allocated,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Migrate a nullifier account to the current layout
+    /// Nullifiers created before versioning end at `bump`; every field added
+    /// since is appended after it, so the zero-filled tail decodes as "not
+    /// recorded" (namespace 0 is the legacy PDA, credential_types is derived
+    /// from proof_type on the next upgrade). Existing fields are never rewritten.
+    /// Idempotent: a nullifier already at the current version is a no-op.
+    pub fn migrate_nullifier(ctx: Context<MigrateNullifier>) -> Result<()> {
+        let nullifier_info = ctx.accounts.nullifier_account.to_account_info();
+        let payer = &ctx.accounts.payer;
+
+        // Verify this is a nullifier account from raw data
+        {
+            let data = nullifier_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && &data[..8] == NullifierAccount::DISCRIMINATOR,
+                VouchError::InvalidNullifierAccount
+            );
+        }
+
+        let new_space = 8 + NullifierAccount::INIT_SPACE;
+        let old_space = nullifier_info.data_len();
+
+        if old_space < new_space {
+            let rent = Rent::get()?;
+            let lamports_diff = rent
+                .minimum_balance(new_space)
+                .saturating_sub(nullifier_info.lamports());
+
+            if lamports_diff > 0 {
+                let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+                    payer.key,
+                    nullifier_info.key,
+                    lamports_diff,
+                );
+                anchor_lang::solana_program::program::invoke(
+                    &transfer_ix,
+                    &[
+                        payer.to_account_info(),
+                        nullifier_info.clone(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+            }
+
+            // Zero-init the appended tail so new fields decode as defaults
+            nullifier_info.resize(new_space)?;
+        }
+
+        let mut nullifier_account = {
+            let data = nullifier_info.try_borrow_data()?;
+            NullifierAccount::try_deserialize(&mut &data[..])?
+        };
+
+        let old_version = nullifier_account.version;
+        require!(
+            old_version <= NULLIFIER_ACCOUNT_VERSION,
+            VouchError::InvalidNullifierAccount
+        );
+
+        if old_version == NULLIFIER_ACCOUNT_VERSION && old_space >= new_space {
+            msg!("Nullifier already at version {}", old_version);
+            return Ok(());
+        }
+
+        nullifier_account.version = NULLIFIER_ACCOUNT_VERSION;
+        {
+            let mut data = nullifier_info.try_borrow_mut_data()?;
+            nullifier_account.try_serialize(&mut &mut data[..])?;
+        }
+
+        emit!(NullifierMigrated {
+            nullifier_account: *nullifier_info.key,
+            nullifier: nullifier_account.nullifier,
+            old_version,
+            new_version: NULLIFIER_ACCOUNT_VERSION,
+            old_size: old_space as u32,
+            new_size: nullifier_info.data_len() as u32,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Migrate a campaign account to the current layout
+    /// Campaigns created before versioning (LegacyAirdropCampaign) have u32
+    /// counters, so they are decoded and re-encoded with every added field at
+    /// its create_airdrop_campaign default (all zero). Later versions only
+    /// append after `version` and are zero-extended like migrate_registration.
+    /// Idempotent: a campaign already at the current version is a no-op.
+    pub fn migrate_campaign(ctx: Context<MigrateCampaign>) -> Result<()> {
+        let campaign_info = ctx.accounts.campaign.to_account_info();
+        let payer = &ctx.accounts.payer;
+
+        // Verify this is a campaign account from raw data
+        let legacy = {
+            let data = campaign_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && &data[..8] == AirdropCampaign::DISCRIMINATOR,
+                VouchError::InvalidCampaign
+            );
+            if data.len() == 8 + LegacyAirdropCampaign::INIT_SPACE {
+                Some(
+                    LegacyAirdropCampaign::deserialize(&mut &data[8..])
+                        .map_err(|_| VouchError::InvalidCampaign)?,
+                )
+            } else {
+                None
+            }
+        };
+
+        let new_space = 8 + AirdropCampaign::INIT_SPACE;
+        let old_space = campaign_info.data_len();
+
+        if old_space < new_space {
+            let rent = Rent::get()?;
+            let lamports_diff = rent
+                .minimum_balance(new_space)
+                .saturating_sub(campaign_info.lamports());
+
+            if lamports_diff > 0 {
+                let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+                    payer.key,
+                    campaign_info.key,
+                    lamports_diff,
+                );
+                anchor_lang::solana_program::program::invoke(
+                    &transfer_ix,
+                    &[
+                        payer.to_account_info(),
+                        campaign_info.clone(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+            }
+
+            // Zero-init the appended tail so new fields decode as defaults
+            campaign_info.resize(new_space)?;
+        }
+
+        let mut campaign = {
+            let mut data = campaign_info.try_borrow_mut_data()?;
+            if legacy.is_some() {
+                data[8..].fill(0);
+            }
+            AirdropCampaign::try_deserialize(&mut &data[..])?
+        };
+
+        if let Some(legacy) = legacy {
+            apply_legacy_campaign(&mut campaign, legacy);
+        }
+
+        let old_version = campaign.version;
+        require!(
+            old_version <= CAMPAIGN_ACCOUNT_VERSION,
+            VouchError::InvalidCampaign
+        );
+
+        if old_version == CAMPAIGN_ACCOUNT_VERSION && old_space >= new_space {
+            msg!("Campaign already at version {}", old_version);
+            return Ok(());
+        }
+
+        campaign.version = CAMPAIGN_ACCOUNT_VERSION;
+        {
+            let mut data = campaign_info.try_borrow_mut_data()?;
+            campaign.try_serialize(&mut &mut data[..])?;
+        }
+
+        emit!(CampaignMigrated {
+            campaign: *campaign_info.key,
+            campaign_id: campaign.campaign_id,
+            old_version,
+            new_version: CAMPAIGN_ACCOUNT_VERSION,
+            old_size: old_space as u32,
+            new_size: campaign_info.data_len() as u32,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Migrate a registration account to the current layout
+    /// Reallocs to the current INIT_SPACE and stamps the layout version.
+    /// New fields are only ever appended after `version`, so the zero-filled
+    /// tail decodes as safe defaults and existing fields (including
+    /// claimed_amount and is_claimed) are never rewritten with new values.
+    /// Verified registrations made before recipients were stored have no
+    /// owner and can't be claimed; passing the credential's nullifier account
+    /// backfills `recipient` from it (see backfill_registration_recipient);
+    /// when the credential has none either, see bind_registration_recipient.
+    /// Idempotent: a registration already at the current version (with nothing
+    /// to backfill) is a no-op.
+    pub fn migrate_registration(ctx: Context<MigrateRegistration>) -> Result<()> {
+        let registration_info = ctx.accounts.registration.to_account_info();
+        let payer = &ctx.accounts.payer;
+
+        // Verify this is a registration account from raw data
+        {
+            let data = registration_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && &data[..8] == AirdropRegistrationAccount::DISCRIMINATOR,
+                VouchError::InvalidRegistration
+            );
+        }
+
+        let new_space = 8 + AirdropRegistrationAccount::INIT_SPACE;
+        let old_space = registration_info.data_len();
+
+        if old_space < new_space {
+            let rent = Rent::get()?;
+            let lamports_diff = rent
+                .minimum_balance(new_space)
+                .saturating_sub(registration_info.lamports());
+
+            if lamports_diff > 0 {
+                let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+                    payer.key,
+                    registration_info.key,
+                    lamports_diff,
+                );
+                anchor_lang::solana_program::program::invoke(
+                    &transfer_ix,
+                    &[
+                        payer.to_account_info(),
+                        registration_info.clone(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+            }
+
+            // Zero-init the appended tail so new fields decode as defaults
+            registration_info.resize(new_space)?;
+        }
+
+        let mut registration = {
+            let data = registration_info.try_borrow_data()?;
+            AirdropRegistrationAccount::try_deserialize(&mut &data[..])?
+        };
+
+        let old_version = registration.version;
+        require!(
+            old_version <= REGISTRATION_ACCOUNT_VERSION,
+            VouchError::InvalidRegistration
+        );
+
+        let recipient_backfilled = match ctx.accounts.nullifier_account.as_ref() {
+            Some(nullifier_account) => {
+                // Legacy/registry namespace or the registration's built-in type
+                require!(
+                    nullifier_account.namespace == 0
+                        || nullifier_account.namespace == registration.proof_type as u8,
+                    VouchError::InvalidNullifierAccount
+                );
+                let expected = Pubkey::create_program_address(
+                    &[
+                        b"nullifier",
+                        nullifier_namespace_seed(&nullifier_account.namespace),
+                        nullifier_account.nullifier.as_ref(),
+                        &[nullifier_account.bump],
+                    ],
+                    &crate::ID,
+                )
+                .map_err(|_| VouchError::InvalidNullifierAccount)?;
+                require!(
+                    expected == nullifier_account.key(),
+                    VouchError::InvalidNullifierAccount
+                );
+                backfill_registration_recipient(&mut registration, nullifier_account)?
+            }
+            None => false,
+        };
+
+        if old_version == REGISTRATION_ACCOUNT_VERSION
+            && old_space >= new_space
+            && !recipient_backfilled
+        {
+            msg!("Registration already at version {}", old_version);
+            return Ok(());
+        }
+
+        // Only the layout version is written; financial fields pass through unchanged
+        registration.version = REGISTRATION_ACCOUNT_VERSION;
+        {
+            let mut data = registration_info.try_borrow_mut_data()?;
+            registration.try_serialize(&mut &mut data[..])?;
+        }
+
+        emit!(RegistrationMigrated {
+            registration: *registration_info.key,
+            campaign: registration.campaign,
+            recipient: registration.recipient,
+            old_version,
+            new_version: REGISTRATION_ACCOUNT_VERSION,
+            old_size: old_space as u32,
+            new_size: registration_info.data_len() as u32,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Bind an ownerless verified registration to a recipient
+    /// Campaign creator or admin only. For registrations migrate_registration
+    /// can't backfill: the credential was recorded before recipients were
+    /// stored, so there is nothing to copy. One-time; a bound registration
+    /// is never rebound.
+    pub fn bind_registration_recipient(
+        ctx: Context<BindRegistrationRecipient>,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == ctx.accounts.campaign.creator || authority == ctx.accounts.config.admin,
+            VouchError::Unauthorized
+        );
+
+        let nullifier_account = &ctx.accounts.nullifier_account;
+        let registration = &mut ctx.accounts.registration;
+        // Legacy/registry namespace or the registration's built-in type
+        require!(
+            nullifier_account.namespace == 0
+                || nullifier_account.namespace == registration.proof_type as u8,
+            VouchError::InvalidNullifierAccount
+        );
+        bind_ownerless_registration(registration, nullifier_account, recipient)?;
+
+        emit!(RegistrationRecipientBound {
+            registration: registration.key(),
+            campaign: registration.campaign,
+            nullifier: registration.nullifier,
+            recipient,
+            authority,
+            timestamp: effective_now(&ctx.accounts.config)?,
+        });
+
+        Ok(())
+    }
+
+    /// Temporarily halt a campaign's registrations and claims
+    /// Only campaign creator can pause. Unlike close_airdrop_registration this is
+    /// reversible via resume_campaign (e.g. while investigating a bug mid-distribution).
+    pub fn pause_campaign(ctx: Context<UpdateAirdropCampaign>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        require!(!campaign.is_paused, VouchError::AlreadyPaused);
+
+        campaign.is_paused = true;
+
+        emit!(CampaignPaused {
+            campaign_id: campaign.campaign_id,
+            creator: ctx.accounts.creator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Resume a paused campaign
+    /// Only campaign creator can resume
+    pub fn resume_campaign(ctx: Context<UpdateAirdropCampaign>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        require!(campaign.is_paused, VouchError::NotPaused);
+
+        campaign.is_paused = false;
+
+        emit!(CampaignResumed {
+            campaign_id: campaign.campaign_id,
+            creator: ctx.accounts.creator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Allow or disallow unverified registrations (see register_for_airdrop_open)
+    /// Only campaign creator can change this. Existing open registrations are kept.
+    pub fn set_open_registration_enabled(
+        ctx: Context<UpdateAirdropCampaign>,
+        enabled: bool,
+    ) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.open_registration_disabled = !enabled;
+
+        emit!(CampaignOpenRegistrationUpdated {
+            campaign_id: campaign.campaign_id,
+            enabled,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Close registration for a campaign (prevents new registrations)
+    /// Campaign creator or a campaign manager can close
+    pub fn close_airdrop_registration(ctx: Context<CloseAirdropRegistration>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+
+        campaign.status = CampaignStatus::RegistrationClosed;
+
+        emit!(AirdropRegistrationClosed {
+            campaign_id: campaign.campaign_id,
+            total_registrations: campaign.total_registrations,
+            dev_registrations: campaign.dev_registrations,
+            whale_registrations: campaign.whale_registrations,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set the ShadowWire relay key whose signed receipts prove distribution
+    /// Only campaign creator can set this, and only while the campaign is open,
+    /// so registrants know the distribution proof requirement up front
+    pub fn set_distribution_relay(
+        ctx: Context<UpdateAirdropCampaign>,
+        distribution_relay: Pubkey,
+    ) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+
+        campaign.distribution_relay = distribution_relay;
+
+        emit!(DistributionRelayUpdated {
+            campaign_id: campaign.campaign_id,
+            distribution_relay,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Cap how many registrations may share one ShadowWire address
+    /// Only campaign creator can set this, and only while the campaign is open.
+    /// Zero means unlimited. When set, registrations must pass the
+    /// ShadowWireUsage counter PDA for their address.
+    pub fn set_shadow_wire_reuse_limit(
+        ctx: Context<UpdateAirdropCampaign>,
+        max_registrations_per_shadow_wire: u16,
+    ) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+
+        campaign.max_registrations_per_shadow_wire = max_registrations_per_shadow_wire;
+
+        emit!(ShadowWireReuseLimitUpdated {
+            campaign_id: campaign.campaign_id,
+            max_registrations_per_shadow_wire,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Restrict registration to credentials issued by one verifier
+    /// Only campaign creator can set this, and only while the campaign is open.
+    /// None accepts credentials from any protocol verifier.
+    pub fn set_required_verifier(
+        ctx: Context<UpdateAirdropCampaign>,
+        required_verifier: Option<Pubkey>,
+    ) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+
+        campaign.required_verifier = required_verifier;
+
+        emit!(RequiredVerifierUpdated {
+            campaign_id: campaign.campaign_id,
+            required_verifier,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Require a creator-controlled signature authorizing each claim
+    /// Only campaign creator can set this, at any point before completion, so
+    /// claims can be gated after reviewing registrants. claim_signer may be the
+    /// creator itself or a dedicated signing key.
+    pub fn set_claim_signature_requirement(
+        ctx: Context<UpdateAirdropCampaign>,
+        require_claim_signature: bool,
+        claim_signer: Pubkey,
+    ) -> Result<()> {
+        require!(
+            !require_claim_signature || claim_signer != Pubkey::default(),
+            VouchError::ClaimNotAuthorized
+        );
+
+        let campaign = &mut ctx.accounts.campaign;
+        require!(
+            campaign.status != CampaignStatus::Completed,
+            VouchError::CampaignNotOpen
+        );
+
+        campaign.require_claim_signature = require_claim_signature;
+        campaign.claim_signer = claim_signer;
+
+        emit!(ClaimSignatureRequirementUpdated {
+            campaign_id: campaign.campaign_id,
+            require_claim_signature,
+            claim_signer,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set the deadline after which unclaimed registrations can no longer claim
+    /// Only campaign creator can set this, and only while the campaign is open,
+    /// so registrants know the claim window up front. Zero means no deadline.
+    /// Once anyone has registered the deadline can only be extended, so the
+    /// creator can't cut short a window registrants signed up under.
+    /// Once passed on a completed campaign, unclaimed registrations are reapable.
+    pub fn set_claim_deadline(ctx: Context<UpdateAirdropCampaign>, claim_deadline: i64) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+        require!(
+            campaign.total_registrations == 0
+                || extends_claim_deadline(campaign.claim_deadline, claim_deadline),
+            VouchError::CampaignHasRegistrations
+        );
+        require!(
+            claim_deadline == 0 || claim_deadline > campaign.registration_deadline,
+            VouchError::InvalidDeadline
+        );
+        require!(
+            claim_deadline == 0 || claim_deadline >= vesting_end(campaign),
+            VouchError::InvalidVestingSchedule
+        );
+
+        campaign.claim_deadline = claim_deadline;
+
+        emit!(ClaimDeadlineUpdated {
+            campaign_id: campaign.campaign_id,
+            claim_deadline,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set the minimum hold between registration and claim for a campaign
+    /// Only campaign creator can set this, and only while the campaign is open
+    /// with no registrations, so a hold can't be raised on existing registrants.
+    /// The protocol-wide global_min_hold_seconds still applies as a floor.
+    pub fn set_campaign_min_hold(
+        ctx: Context<UpdateAirdropCampaign>,
+        min_hold_seconds: i64,
+    ) -> Result<()> {
+        require!(min_hold_seconds >= 0, VouchError::InvalidHoldPeriod);
+
+        let campaign = &mut ctx.accounts.campaign;
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+        require!(campaign.total_registrations == 0, VouchError::CampaignHasRegistrations);
+
+        campaign.min_hold_seconds = min_hold_seconds;
+
+        emit!(CampaignMinHoldUpdated {
+            campaign_id: campaign.campaign_id,
+            min_hold_seconds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Enable registration deadline extension on funding top-ups
+    /// Only campaign creator can set this, and only while the campaign is open.
+    /// Each fund_airdrop_campaign call pushes the deadline forward by
+    /// deadline_bump_per_fund until max_deadline_extension is used up.
+    /// A zero bump disables the feature (immutable deadline).
+    pub fn set_deadline_extension(
+        ctx: Context<UpdateAirdropCampaign>,
+        deadline_bump_per_fund: i64,
+        max_deadline_extension: i64,
+    ) -> Result<()> {
+        require!(
+            deadline_bump_per_fund >= 0
+                && (0..=MAX_DEADLINE_EXTENSION_SECONDS).contains(&max_deadline_extension),
+            VouchError::InvalidDeadline
+        );
+
+        let campaign = &mut ctx.accounts.campaign;
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+
+        campaign.deadline_bump_per_fund = deadline_bump_per_fund;
+        campaign.max_deadline_extension = max_deadline_extension;
+
+        emit!(DeadlineExtensionUpdated {
+            campaign_id: campaign.campaign_id,
+            deadline_bump_per_fund,
+            max_deadline_extension,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Configure the early-bird bonus for a campaign
+    /// Only campaign creator can set this, while open and before any registrations.
+    /// Each registrant's bonus decays linearly from max_bonus at campaign creation
+    /// to min_bonus at the registration deadline and is fixed on the registration
+    /// (on top of base + tier bonus). Zero for both disables the decay bonus.
+    pub fn set_bonus_decay(
+        ctx: Context<UpdateAirdropCampaign>,
+        max_bonus: u64,
+        min_bonus: u64,
+    ) -> Result<()> {
+        require!(min_bonus <= max_bonus, VouchError::InvalidBonusDecay);
+
+        let campaign = &mut ctx.accounts.campaign;
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+        require!(campaign.total_registrations == 0, VouchError::CampaignHasRegistrations);
+
+        campaign.decay_max_bonus = max_bonus;
+        campaign.decay_min_bonus = min_bonus;
+
+        emit!(BonusDecayUpdated {
+            campaign_id: campaign.campaign_id,
+            max_bonus,
+            min_bonus,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Correct a campaign's base amount and tier bonuses during setup
+    /// Only campaign creator can call this, while open, unfunded and before any
+    /// registrations: once tokens are deposited or allocations fixed, registrants
+    /// rely on the advertised amounts.
+    pub fn update_campaign_amounts(
+        ctx: Context<UpdateAirdropCampaign>,
+        base_amount: u64,
+        dev_bonus: u64,
+        whale_bonus: u64,
+    ) -> Result<()> {
+        require!(base_amount > 0, VouchError::InvalidAmount);
+
+        let campaign = &mut ctx.accounts.campaign;
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+        require_diag!(
+            campaign.vault_balance == 0,
+            CampaignAlreadyFunded,
+            "vault_balance={}",
+            campaign.vault_balance
+        );
+        require!(campaign.total_registrations == 0, VouchError::CampaignHasRegistrations);
+
+        let old_base_amount = campaign.base_amount;
+        let old_dev_bonus = campaign.dev_bonus;
+        let old_whale_bonus = campaign.whale_bonus;
+        campaign.base_amount = base_amount;
+        campaign.dev_bonus = dev_bonus;
+        campaign.whale_bonus = whale_bonus;
+
+        emit!(CampaignAmountsUpdated {
+            campaign_id: campaign.campaign_id,
+            old_base_amount,
+            old_dev_bonus,
+            old_whale_bonus,
+            new_base_amount: base_amount,
+            new_dev_bonus: dev_bonus,
+            new_whale_bonus: whale_bonus,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set the early claim penalty for a campaign
+    /// Only campaign creator can set this, and only while the campaign is open.
+    /// Zero keeps the default mode (claims inside the hold period are rejected);
+    /// a non-zero value lets early claims succeed with the penalty returned to the pool.
+    pub fn set_early_claim_penalty(
+        ctx: Context<UpdateAirdropCampaign>,
+        early_claim_penalty_bps: u16,
+    ) -> Result<()> {
+        require!(
+            early_claim_penalty_bps <= MAX_EARLY_CLAIM_PENALTY_BPS,
+            VouchError::InvalidEarlyClaimPenalty
+        );
+
+        let campaign = &mut ctx.accounts.campaign;
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+        // Vesting already paces claims; the two modes don't compose
+        require!(
+            early_claim_penalty_bps == 0 || campaign.vesting_duration == 0,
+            VouchError::InvalidEarlyClaimPenalty
+        );
+
+        campaign.early_claim_penalty_bps = early_claim_penalty_bps;
+
+        emit!(EarlyClaimPenaltyUpdated {
+            campaign_id: campaign.campaign_id,
+            early_claim_penalty_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Configure linear vesting of claims for a campaign
+    /// Only campaign creator can set this, and only while the campaign is open,
+    /// so registrants know the schedule up front. Entitlements vest linearly over
+    /// vesting_duration from vesting_start; nothing is claimable before the cliff.
+    /// claim_airdrop may then be called repeatedly, each time releasing the newly
+    /// vested delta. A zero duration disables vesting.
+    pub fn set_vesting_schedule(
+        ctx: Context<UpdateAirdropCampaign>,
+        vesting_start: i64,
+        vesting_duration: i64,
+        cliff_seconds: i64,
+    ) -> Result<()> {
+        require!(
+            vesting_duration >= 0 && (0..=vesting_duration).contains(&cliff_seconds),
+            VouchError::InvalidVestingSchedule
+        );
+        require!(
+            vesting_duration == 0 || vesting_start > 0,
+            VouchError::InvalidVestingSchedule
+        );
+
+        let campaign = &mut ctx.accounts.campaign;
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+        require!(
+            vesting_duration == 0 || campaign.early_claim_penalty_bps == 0,
+            VouchError::InvalidVestingSchedule
+        );
+
+        campaign.vesting_start = vesting_start;
+        campaign.vesting_duration = vesting_duration;
+        campaign.cliff_seconds = cliff_seconds;
+        require!(
+            campaign.claim_deadline == 0 || campaign.claim_deadline >= vesting_end(campaign),
+            VouchError::InvalidVestingSchedule
+        );
+
+        emit!(VestingScheduleUpdated {
+            campaign_id: campaign.campaign_id,
+            vesting_start,
+            vesting_duration,
+            cliff_seconds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set the eligibility root for merkle claims (see claim_airdrop_merkle)
+    /// Only campaign creator can set this, and only while the campaign is open.
+    /// Leaves are build_airdrop_claim_leaf(nullifier, amount, proof_type); an
+    /// all-zero root disables merkle claims.
+    pub fn set_merkle_root(ctx: Context<UpdateAirdropCampaign>, merkle_root: [u8; 32]) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+
+        let old_merkle_root = campaign.merkle_root;
+        campaign.merkle_root = merkle_root;
+
+        emit!(CampaignMerkleRootUpdated {
+            campaign_id: campaign.campaign_id,
+            old_merkle_root,
+            new_merkle_root: merkle_root,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Allow or disallow attestation-authorized claims (see claim_airdrop_private)
+    /// Only campaign creator can change this. While enabled, any active
+    /// verifier (or campaign.required_verifier when set) can authorize payouts
+    /// from the vault.
+    pub fn set_private_claims_enabled(
+        ctx: Context<UpdateAirdropCampaign>,
+        enabled: bool,
+    ) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.private_claims_enabled = enabled;
+
+        emit!(CampaignPrivateClaimsUpdated {
+            campaign_id: campaign.campaign_id,
+            enabled,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Require registrants to own the credential they register with
+    /// Only campaign creator can change this. While enabled, register_for_airdrop
+    /// only accepts a payer equal to the nullifier's recorded recipient, so
+    /// credentials recorded before recipients were stored can't register.
+    pub fn set_require_credential_owner(
+        ctx: Context<UpdateAirdropCampaign>,
+        required: bool,
+    ) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.require_credential_owner = required;
+
+        emit!(CampaignCredentialOwnerRequirementUpdated {
+            campaign_id: campaign.campaign_id,
+            required,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Mark a registration as distributed (after sending via ShadowWire)
+    /// Campaign creator or a campaign manager can mark distributions
+    ///
+    /// If the campaign has a distribution relay configured, the transaction must
+    /// include an Ed25519 instruction (immediately before this one) in which the
+    /// relay signs build_distribution_receipt_message over the delivered amount
+    pub fn mark_airdrop_distributed(
+        ctx: Context<MarkAirdropDistributed>,
+        tx_signature: String,
+        amount: u64,
+        receipt_signature: Option<[u8; 64]>,
+    ) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let registration = &ctx.accounts.registration;
+        require_campaigns_unpaused(&ctx.accounts.config)?;
+
+        require!(!registration.is_distributed, VouchError::AlreadyDistributed);
+
+        // Verify the relay's signed receipt when the campaign requires one
+        let receipt_verified = if campaign.distribution_relay != Pubkey::default() {
+            let receipt_signature =
+                receipt_signature.ok_or(VouchError::DistributionProofInvalid)?;
+            let message = build_distribution_receipt_message(
+                &campaign.campaign_id,
+                &registration.nullifier,
+                amount,
+                &registration.shadow_wire_address,
+            );
+            verify_ed25519_signature(
+                &ctx.accounts.instructions_sysvar.to_account_info(),
+                &campaign.distribution_relay,
+                &receipt_signature,
+                &message,
+                DEFAULT_ED25519_SCAN_DISTANCE,
+                true,
+            )
+            .map_err(|_| VouchError::DistributionProofInvalid)?;
+            true
+        } else {
+            false
+        };
+
+        let registration = &mut ctx.accounts.registration;
+        registration.is_distributed = true;
+        registration.distributed_at = Clock::get()?.unix_timestamp;
+        registration.distribution_tx = tx_signature.clone();
+        registration.distributed_amount = amount;
+        registration.distribution_receipt_verified = receipt_verified;
+
+        emit!(AirdropDistributed {
+            campaign_id: ctx.accounts.campaign.campaign_id,
+            nullifier: registration.nullifier,
+            shadow_wire_address: registration.shadow_wire_address.clone(),
+            tx_signature,
+            amount,
+            receipt_verified,
+            timestamp: registration.distributed_at,
+        });
+
+        Ok(())
+    }
+
+    /// Mark many registrations as distributed in one instruction
+    /// Campaign creator or a campaign manager can mark distributions
+    /// remaining_accounts: registration accounts (writable), max MAX_DISTRIBUTION_BATCH
+    /// tx_signatures: one shared signature, or one per registration
+    /// amounts: amount delivered per registration
+    /// Already-distributed registrations are skipped rather than failing the batch.
+    /// Campaigns that require relay receipts must use mark_airdrop_distributed.
+    pub fn mark_airdrop_distributed_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MarkAirdropDistributedBatch<'info>>,
+        tx_signatures: Vec<String>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let registrations = ctx.remaining_accounts;
+        require_campaigns_unpaused(&ctx.accounts.config)?;
+
+        require!(
+            !registrations.is_empty() && registrations.len() <= MAX_DISTRIBUTION_BATCH,
+            VouchError::InvalidBatchSize
+        );
+        require!(
+            tx_signatures.len() == 1 || tx_signatures.len() == registrations.len(),
+            VouchError::InvalidBatchSize
+        );
+        require!(amounts.len() == registrations.len(), VouchError::InvalidBatchSize);
+        require!(
+            campaign.distribution_relay == Pubkey::default(),
+            VouchError::DistributionProofInvalid
+        );
+
+        let campaign_key = campaign.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        for (i, info) in registrations.iter().enumerate() {
+            require!(info.is_writable, VouchError::InvalidRegistration);
+            let mut registration: Account<'info, AirdropRegistrationAccount> =
+                Account::try_from(info)?;
+
+            // Validate the registration belongs to this campaign
+            require!(registration.campaign == campaign_key, VouchError::InvalidCampaign);
+            let expected = Pubkey::create_program_address(
+                &[
+                    b"airdrop_registration",
+                    campaign_key.as_ref(),
+                    registration.nullifier.as_ref(),
+                    &[registration.bump],
+                ],
+                &crate::ID,
+            )
+            .map_err(|_| VouchError::InvalidRegistration)?;
+            require!(expected == *info.key, VouchError::InvalidRegistration);
+
+            if registration.is_distributed {
+                continue;
+            }
+
+            let tx_signature = if tx_signatures.len() == 1 {
+                tx_signatures[0].clone()
+            } else {
+                tx_signatures[i].clone()
+            };
+            require!(tx_signature.len() <= 88, VouchError::InvalidBatchSize);
+
+            registration.is_distributed = true;
+            registration.distributed_at = now;
+            registration.distribution_tx = tx_signature.clone();
+            registration.distributed_amount = amounts[i];
+            registration.distribution_receipt_verified = false;
+            registration.exit(&crate::ID)?;
+
+            emit!(AirdropDistributed {
+                campaign_id: campaign.campaign_id,
+                nullifier: registration.nullifier,
+                shadow_wire_address: registration.shadow_wire_address.clone(),
+                tx_signature,
+                amount: amounts[i],
+                receipt_verified: false,
+                timestamp: now,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Attach an internal creator note to a registration (e.g. "flagged for review")
+    /// Campaign creator or a campaign manager can set memos. Registrations created before the
+    /// memo field existed must be migrated with migrate_registration first.
+    pub fn set_registration_memo(ctx: Context<SetRegistrationMemo>, memo: String) -> Result<()> {
+        require!(memo.len() <= MAX_REGISTRATION_MEMO_LEN, VouchError::MemoTooLong);
+
+        let registration = &mut ctx.accounts.registration;
+        registration.memo = memo.clone();
+
+        emit!(RegistrationMemoSet {
+            campaign_id: ctx.accounts.campaign.campaign_id,
+            nullifier: registration.nullifier,
+            memo,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Correct the ShadowWire address of an undistributed registration
+    /// Only the registration owner can change it (see registration_owner), and
+    /// only before it is distributed or claimed. The new address is validated
+    /// and counted against the campaign's reuse cap like a fresh registration,
+    /// and the old address's counter is released.
+    pub fn update_registration_address(
+        ctx: Context<UpdateRegistrationAddress>,
+        shadow_wire_address: String,
+    ) -> Result<()> {
+        let registration = &ctx.accounts.registration;
+        let owner = ctx.accounts.owner.key();
+        require!(
+            registration_owner(registration) == Some(owner),
+            VouchError::Unauthorized
+        );
+        require!(!registration.is_distributed, VouchError::AlreadyDistributed);
+        require!(!registration.is_claimed, VouchError::AlreadyClaimed);
+        validate_shadow_wire_address(&shadow_wire_address)?;
+
+        if shadow_wire_address != registration.shadow_wire_address {
+            release_shadow_wire_usage(
+                &ctx.accounts.campaign,
+                ctx.accounts
+                    .old_shadow_wire_usage
+                    .as_ref()
+                    .map(|usage| usage.to_account_info())
+                    .as_ref(),
+                &ctx.accounts.owner.to_account_info(),
+                &registration.shadow_wire_address,
+            )?;
+            enforce_shadow_wire_reuse_limit(
+                &ctx.accounts.campaign,
+                ctx.accounts
+                    .shadow_wire_usage
+                    .as_ref()
+                    .map(|usage| usage.to_account_info())
+                    .as_ref(),
+                &ctx.accounts.owner.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &shadow_wire_address,
+            )?;
+        }
+
+        let registration = &mut ctx.accounts.registration;
+        let old_address = registration.shadow_wire_address.clone();
+        registration.shadow_wire_address = shadow_wire_address.clone();
+
+        emit!(RegistrationAddressUpdated {
+            campaign_id: ctx.accounts.campaign.campaign_id,
+            nullifier: registration.nullifier,
+            owner,
+            old_address,
+            new_address: shadow_wire_address,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Grant a wallet shared management rights over a campaign
+    /// Only campaign creator can add managers. Managers may fund the campaign,
+    /// close registration, mark distributions and set registration memos; campaign
+    /// settings, completion and the manager set itself stay creator-only.
+    pub fn add_campaign_manager(ctx: Context<AddCampaignManager>, manager: Pubkey) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        require!(
+            manager != Pubkey::default() && manager != campaign.creator,
+            VouchError::InvalidCampaignManager
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let campaign_manager = &mut ctx.accounts.campaign_manager;
+        campaign_manager.campaign = campaign.key();
+        campaign_manager.manager = manager;
+        campaign_manager.added_at = now;
+        campaign_manager.bump = ctx.bumps.campaign_manager;
+
+        emit!(CampaignManagerAdded {
+            campaign_id: campaign.campaign_id,
+            manager,
+            creator: ctx.accounts.creator.key(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Revoke a campaign manager (closes the CampaignManager PDA)
+    /// Only campaign creator can remove managers
+    pub fn remove_campaign_manager(ctx: Context<RemoveCampaignManager>) -> Result<()> {
+        emit!(CampaignManagerRemoved {
+            campaign_id: ctx.accounts.campaign.campaign_id,
+            manager: ctx.accounts.campaign_manager.manager,
+            creator: ctx.accounts.creator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Complete an airdrop campaign (marks as fully distributed)
+    /// Only campaign creator can complete
+    pub fn complete_airdrop_campaign(ctx: Context<CompleteAirdropCampaign>) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+
+        require!(
+            campaign.status == CampaignStatus::RegistrationClosed,
+            VouchError::CampaignNotClosed
+        );
+
+        campaign.status = CampaignStatus::Completed;
+        campaign.completed_at = Clock::get()?.unix_timestamp;
+
+        // Campaigns created before the counter existed were never counted
+        let config = &mut ctx.accounts.config;
+        config.active_campaign_count = config.active_campaign_count.saturating_sub(1);
+
+        emit!(AirdropCampaignCompleted {
+            campaign_id: campaign.campaign_id,
+            total_distributed: campaign.total_registrations,
+            timestamp: campaign.completed_at,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a campaign and refund its vault to the creator
+    /// Only campaign creator can cancel, and only before completion and before
+    /// any claim has been paid. The whole vault balance is returned and the
+    /// campaign stops accepting registrations, funding and claims for good.
+    /// Additional asset vaults are refunded too: when the campaign has an asset
+    /// basket, remaining_accounts must hold, for each asset in order,
+    /// [asset_vault (mut), creator_token_account (mut)].
+    pub fn cancel_campaign<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelAirdropCampaign<'info>>,
+    ) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        require!(
+            campaign.status == CampaignStatus::Open
+                || campaign.status == CampaignStatus::RegistrationClosed,
+            VouchError::CampaignNotOpen
+        );
+        require_diag!(
+            campaign.total_claimed == 0,
+            CampaignHasClaims,
+            "total_claimed={}",
+            campaign.total_claimed
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let amount = ctx.accounts.campaign_vault.amount;
+
+        if amount > 0 {
+            let campaign_id = campaign.campaign_id;
+            let bump = campaign.bump;
+            let seeds = &[
+                b"airdrop_campaign".as_ref(),
+                campaign_id.as_ref(),
+                &[bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.campaign_vault.to_account_info(),
+                to: ctx.accounts.creator_token_account.to_account_info(),
+                authority: ctx.accounts.campaign.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        // Refund the additional asset vaults; campaigns without a basket pass none
+        let basket_info = ctx.accounts.asset_basket.to_account_info();
+        let has_basket = basket_info.owner == &crate::ID && !basket_info.data_is_empty();
+        if !has_basket {
+            require!(
+                ctx.remaining_accounts.is_empty(),
+                VouchError::InvalidAssetAccounts
+            );
+        } else {
+            let mut basket = {
+                let data = basket_info.try_borrow_data()?;
+                CampaignAssetBasket::try_deserialize(&mut &data[..])?
+            };
+            require!(
+                ctx.remaining_accounts.len() == basket.assets.len() * 2,
+                VouchError::InvalidAssetAccounts
+            );
+
+            let campaign = &ctx.accounts.campaign;
+            let campaign_key = campaign.key();
+            let campaign_id = campaign.campaign_id;
+            let bump = campaign.bump;
+            let seeds = &[
+                b"airdrop_campaign".as_ref(),
+                campaign_id.as_ref(),
+                &[bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+            let creator_key = ctx.accounts.creator.key();
+
+            for (i, asset) in basket.assets.iter_mut().enumerate() {
+                let vault_info = &ctx.remaining_accounts[i * 2];
+                let creator_token_info = &ctx.remaining_accounts[i * 2 + 1];
+
+                // Vault must be the campaign's ATA for this mint; an uncreated one is empty
+                require!(
+                    *vault_info.key == get_associated_token_address(&campaign_key, &asset.mint),
+                    VouchError::InvalidAssetAccounts
+                );
+                if vault_info.owner != &token::ID {
+                    continue;
+                }
+                let asset_amount = load_token_account(vault_info)?.amount;
+                if asset_amount == 0 {
+                    continue;
+                }
+
+                let creator_token = load_token_account(creator_token_info)?;
+                require!(
+                    creator_token.mint == asset.mint && creator_token.owner == creator_key,
+                    VouchError::InvalidAssetAccounts
+                );
+
+                let cpi_accounts = Transfer {
+                    from: vault_info.clone(),
+                    to: creator_token_info.clone(),
+                    authority: ctx.accounts.campaign.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token::transfer(cpi_ctx, asset_amount)?;
+
+                asset.vault_balance = 0;
+                record_tvl_flow(
+                    &ctx.accounts.config,
+                    ctx.accounts.protocol_stats.as_mut(),
+                    asset.mint,
+                    0,
+                    asset_amount,
+                )?;
+
+                emit!(CampaignAssetRefunded {
+                    campaign_id,
+                    mint: asset.mint,
+                    creator: creator_key,
+                    amount: asset_amount,
+                    timestamp: now,
+                });
+            }
+
+            let mut data = basket_info.try_borrow_mut_data()?;
+            basket.try_serialize(&mut &mut data[..])?;
+        }
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.status = CampaignStatus::Cancelled;
+        campaign.vault_balance = 0;
+
+        record_tvl_flow(
+            &ctx.accounts.config,
+            ctx.accounts.protocol_stats.as_mut(),
+            campaign.token_mint,
+            0,
+            amount,
+        )?;
+
+        // Campaigns created before the counter existed were never counted
+        let config = &mut ctx.accounts.config;
+        config.active_campaign_count = config.active_campaign_count.saturating_sub(1);
+
+        emit!(AirdropCampaignCancelled {
+            campaign_id: campaign.campaign_id,
+            creator: ctx.accounts.creator.key(),
+            refunded_amount: amount,
+            total_registrations: campaign.total_registrations,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Close an unclaimed registration whose claim window has ended
+    /// Permissionless. Only registrations in a Completed campaign past its
+    /// claim_deadline that never claimed can be reaped. The reclaimed rent is
+    /// split: config.reaper_bounty_bps to the caller, the rest to the creator.
+    pub fn reap_expired_registration(ctx: Context<ReapExpiredRegistration>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let registration = &ctx.accounts.registration;
+        let now = effective_now(&ctx.accounts.config)?;
+
+        require!(
+            campaign.status == CampaignStatus::Completed,
+            VouchError::CampaignNotCompleted
+        );
+        // No claim deadline means the registration stays claimable forever
+        require!(
+            campaign.claim_deadline != 0 && now > campaign.claim_deadline,
+            VouchError::ClaimWindowOpen
+        );
+        require!(!registration.is_claimed, VouchError::AlreadyClaimed);
+
+        let campaign_id = campaign.campaign_id;
+        let nullifier = registration.nullifier;
+
+        // Keep the registrant's index in step when the reaper supplies it
+        if let Some(registration_index) = ctx.accounts.registration_index.as_mut() {
+            let registration_key = ctx.accounts.registration.key();
+            registration_index
+                .entries
+                .retain(|entry| entry.registration != registration_key);
+        }
+
+        let registration_info = ctx.accounts.registration.to_account_info();
+        let rent_lamports = registration_info.lamports();
+        let bounty_lamports = rent_lamports
+            .checked_mul(ctx.accounts.config.reaper_bounty_bps as u64)
+            .ok_or(VouchError::Overflow)?
+            / BPS_DENOMINATOR;
+        let creator_lamports = rent_lamports - bounty_lamports;
+
+        if bounty_lamports > 0 {
+            let reaper_info = ctx.accounts.reaper.to_account_info();
+            **registration_info.try_borrow_mut_lamports()? -= bounty_lamports;
+            **reaper_info.try_borrow_mut_lamports()? = reaper_info
+                .lamports()
+                .checked_add(bounty_lamports)
+                .ok_or(VouchError::Overflow)?;
+        }
+
+        // Remaining rent goes to the creator; the account is zeroed and handed back
+        ctx.accounts
+            .registration
+            .close(ctx.accounts.creator.to_account_info())?;
+
+        emit!(RegistrationReaped {
+            campaign_id,
+            nullifier,
+            reaper: ctx.accounts.reaper.key(),
+            bounty_lamports,
+            creator_lamports,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Opt in to an on-chain index of the caller's airdrop registrations
+    /// Creates the WalletRegistrationIndex PDA; from then on registrations
+    /// that pass it are appended (up to MAX_WALLET_INDEX_ENTRIES).
+    pub fn init_wallet_registration_index(ctx: Context<InitWalletRegistrationIndex>) -> Result<()> {
+        let registration_index = &mut ctx.accounts.registration_index;
+        registration_index.wallet = ctx.accounts.wallet.key();
+        registration_index.entries = Vec::new();
+        registration_index.dropped_entries = 0;
+        registration_index.bump = ctx.bumps.registration_index;
+
+        Ok(())
+    }
+
+    /// Enable the on-chain recent-activity feed for a campaign
+    /// Only campaign creator can enable. Creates the CampaignActivityLog ring
+    /// buffer; from then on registrations, claims and funding must pass it.
+    pub fn enable_campaign_activity_log(ctx: Context<EnableCampaignActivityLog>) -> Result<()> {
+        let activity_log = &mut ctx.accounts.activity_log;
+        activity_log.campaign = ctx.accounts.campaign.key();
+        activity_log.head = 0;
+        activity_log.len = 0;
+        activity_log.total_entries = 0;
+        activity_log.entries = [CampaignActivityEntry::default(); CAMPAIGN_ACTIVITY_LOG_SIZE];
+        activity_log.bump = ctx.bumps.activity_log;
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.activity_log_enabled = true;
+
+        emit!(CampaignActivityLogEnabled {
+            campaign_id: campaign.campaign_id,
+            creator: ctx.accounts.creator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// View: aggregate claim progress for a campaign
+    /// Returns CampaignClaimProgress via return data. progress_bps is
+    /// total_claimed_amount / total_allocated in basis points (0 when nothing
+    /// is allocated). Campaigns created before total_allocated was tracked
+    /// only count registrations made since.
+    pub fn campaign_claim_progress(
+        ctx: Context<GetCampaignClaimProgress>,
+    ) -> Result<CampaignClaimProgress> {
+        let campaign = &ctx.accounts.campaign;
+
+        let progress_bps = if campaign.total_allocated == 0 {
+            0
+        } else {
+            let bps = (campaign.total_claimed_amount as u128)
+                .checked_mul(BPS_DENOMINATOR as u128)
+                .ok_or(VouchError::Overflow)?
+                / campaign.total_allocated as u128;
+            bps.min(BPS_DENOMINATOR as u128) as u16
+        };
+
+        Ok(CampaignClaimProgress {
+            total_allocated: campaign.total_allocated,
+            total_claimed_amount: campaign.total_claimed_amount,
+            total_claimed: campaign.total_claimed,
+            total_registrations: campaign.total_registrations,
+            progress_bps,
+        })
+    }
+
+    /// View: registrations per tier for a campaign
+    /// Returns CampaignRegistrationBreakdown via return data. `consistent` is
+    /// false when the tier counters don't sum to total_registrations (counter
+    /// drift, or registrations made before open_registrations was tracked).
+    pub fn campaign_registration_breakdown(
+        ctx: Context<GetCampaignClaimProgress>,
+    ) -> Result<CampaignRegistrationBreakdown> {
+        let campaign = &ctx.accounts.campaign;
+
+        let tier_sum = campaign
+            .open_registrations
+            .checked_add(campaign.dev_registrations)
+            .and_then(|sum| sum.checked_add(campaign.whale_registrations))
+            .and_then(|sum| sum.checked_add(campaign.registry_registrations))
+            .ok_or(VouchError::Overflow)?;
+        let consistent = tier_sum == campaign.total_registrations;
+        if !consistent {
+            msg!(
+                "Registration counter drift: tiers sum to {}, total is {}",
+                tier_sum,
+                campaign.total_registrations
+            );
+        }
+
+        Ok(CampaignRegistrationBreakdown {
+            open: campaign.open_registrations,
+            dev: campaign.dev_registrations,
+            whale: campaign.whale_registrations,
+            registry: campaign.registry_registrations,
+            total: campaign.total_registrations,
+            consistent,
+        })
+    }
+
+    /// View: the allocation a registration of the given proof type would be
+    /// fixed at if made at `registered_at` (base + tier bonus + early-bird bonus)
+    pub fn preview_registration_allocation(
+        ctx: Context<GetCampaignClaimProgress>,
+        proof_type_value: u8,
+        registered_at: i64,
+    ) -> Result<u64> {
+        let proof_type = if proof_type_value == 0 {
+            ProofType::Unset
+        } else {
+            proof_type_from_value(proof_type_value)?
+        };
+        registration_allocation(&ctx.accounts.campaign, proof_type, registered_at)
+    }
+
+    /// Start protocol-wide TVL tracking
+    /// Only admin can call this. Once enabled, campaign funding and claim
+    /// instructions must pass the ProtocolStats account. Flows from before
+    /// tracking started are not counted, so TVL is a lower bound for those mints.
+    pub fn init_protocol_stats(ctx: Context<InitProtocolStats>) -> Result<()> {
+        let stats = &mut ctx.accounts.protocol_stats;
+        stats.tracked_mints = Vec::new();
+        stats.untracked_flows = 0;
+        stats.bump = ctx.bumps.protocol_stats;
+
+        ctx.accounts.config.protocol_stats_enabled = true;
+
+        emit!(ProtocolStatsInitialized {
+            admin: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// View: tokens currently locked in campaign vaults, per tracked mint
+    /// Returns ProtocolTvl via return data.
+    pub fn protocol_tvl(ctx: Context<GetProtocolTvl>) -> Result<ProtocolTvl> {
+        let stats = &ctx.accounts.protocol_stats;
+
+        let mints = stats
+            .tracked_mints
+            .iter()
+            .map(|entry| MintTvl {
+                mint: entry.mint,
+                total_funded: entry.total_funded,
+                total_claimed: entry.total_claimed,
+                total_value_locked: entry.total_funded.saturating_sub(entry.total_claimed),
+            })
+            .collect();
+
+        Ok(ProtocolTvl {
+            mints,
+            untracked_flows: stats.untracked_flows,
+        })
+    }
+
+    /// Fund an airdrop campaign's token vault
+    /// Campaign creator or a campaign manager can fund
+    /// Tokens are transferred from the signer's ATA to campaign vault. With a
+    /// separate `funder` (e.g. a DAO treasury PDA signing via CPI), they come
+    /// from any token account the funder owns instead.
+    pub fn fund_airdrop_campaign(ctx: Context<FundAirdropCampaign>, amount: u64) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        require_campaigns_unpaused(&ctx.accounts.config)?;
+
+        require!(amount > 0, VouchError::InvalidAmount);
+        require!(
+            campaign.status == CampaignStatus::Open ||
+            campaign.status == CampaignStatus::RegistrationClosed,
+            VouchError::CampaignNotOpen
+        );
+
+        // The funding authority owns the source account: the funder when set,
+        // otherwise the signer
+        let funding_authority = match ctx.accounts.funder.as_ref() {
+            Some(funder) => funder.to_account_info(),
+            None => ctx.accounts.creator.to_account_info(),
+        };
+        require!(
+            ctx.accounts.creator_token_account.owner == funding_authority.key(),
+            VouchError::InvalidFundingAccount
+        );
+
+        // Transfer tokens from the funding authority to vault
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.creator_token_account.to_account_info(),
+            to: ctx.accounts.campaign_vault.to_account_info(),
+            authority: funding_authority.clone(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        // Update campaign funding stats
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.vault_balance = campaign
+            .vault_balance
+            .checked_add(amount)
+            .ok_or(VouchError::Overflow)?;
+
+        // Opt-in: each top-up buys more registration time, up to the extension cap
+        if campaign.status == CampaignStatus::Open && campaign.deadline_bump_per_fund > 0 {
+            let remaining = campaign
+                .max_deadline_extension
+                .saturating_sub(campaign.total_deadline_extension);
+            let extension = campaign.deadline_bump_per_fund.min(remaining).max(0);
+            campaign.registration_deadline = campaign
+                .registration_deadline
+                .checked_add(extension)
+                .ok_or(VouchError::Overflow)?;
+            campaign.total_deadline_extension = campaign
+                .total_deadline_extension
+                .checked_add(extension)
+                .ok_or(VouchError::Overflow)?;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        record_campaign_activity(
+            campaign,
+            ctx.accounts.activity_log.as_mut(),
+            CampaignActivityKind::Funding,
+            funding_authority.key().to_bytes(),
+            amount,
+            now,
+        )?;
+        record_tvl_flow(
+            &ctx.accounts.config,
+            ctx.accounts.protocol_stats.as_mut(),
+            campaign.token_mint,
+            amount,
+            0,
+        )?;
+
+        emit!(AirdropCampaignFunded {
+            campaign_id: campaign.campaign_id,
+            funder: funding_authority.key(),
+            amount,
+            total_funded: campaign.vault_balance,
+            registration_deadline: campaign.registration_deadline,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Claim airdrop tokens from a campaign
+    /// Only registered users can claim, and only until campaign.claim_deadline
+    /// (ClaimPeriodEnded after it; 0 = no deadline)
+    /// Tokens are transferred from campaign vault to claimer's ATA,
+    /// minus the campaign claim fee which goes to the campaign's fee account
+    ///
+    /// Registrations made with a credential must pass its nullifier account, and
+    /// fail with CredentialRevoked once it has been revoked.
+    ///
+    /// When campaign.require_claim_signature is set, claim_signature must be the
+    /// claim signer's Ed25519 signature over build_claim_authorization_message
+    /// (verified via a preceding Ed25519 instruction)
+    ///
+    /// Follows checks-effects-interactions: the registration is marked claimed and
+    /// campaign stats updated before any token transfer. registration.claim_in_progress
+    /// is persisted for the duration of the transfers so a re-entrant claim (e.g. from
+    /// a token transfer hook) fails even before it reaches the is_claimed check.
+    ///
+    /// On vesting campaigns (see set_vesting_schedule) each call releases only the
+    /// newly vested portion of the entitlement; is_claimed is set once fully vested.
+    /// requested_amount claims only part of the unclaimed vested balance (None = all
+    /// of it); it is rejected on campaigns with an early claim penalty.
+    pub fn claim_airdrop(
+        ctx: Context<ClaimAirdrop>,
+        claim_signature: Option<[u8; 64]>,
+        requested_amount: Option<u64>,
+    ) -> Result<()> {
+        let registration = &ctx.accounts.registration;
+        let campaign = &ctx.accounts.campaign;
+        check_claim_not_in_progress(registration)?;
+        require_campaigns_unpaused(&ctx.accounts.config)?;
+        let now = effective_now(&ctx.accounts.config)?;
+        require_diag!(!campaign.is_paused, CampaignPaused, "is_paused=true");
+        require_diag!(
+            campaign.status != CampaignStatus::Cancelled,
+            CampaignCancelled,
+            "status={}",
+            campaign.status as u8
+        );
+        check_recipient_not_blocked(
+            &ctx.accounts.config,
+            ctx.accounts.blocked_recipient.as_ref(),
+            &ctx.accounts.claimer.key(),
+        )?;
+        check_registration_claimer(registration, &ctx.accounts.claimer.key())?;
+        check_registration_credential(registration, ctx.accounts.nullifier_account.as_deref())?;
+        require_unclaimed_marker(&ctx.accounts.private_claim_marker)?;
+
+        // Verify not already claimed
+        require_diag!(
+            !registration.is_claimed,
+            AlreadyClaimed,
+            "claimed_at={}",
+            registration.claimed_at
+        );
+        require_diag!(
+            campaign.claim_deadline == 0 || now <= campaign.claim_deadline,
+            ClaimPeriodEnded,
+            "now={} claim_deadline={}",
+            now,
+            campaign.claim_deadline
+        );
+
+        // Dampen cross-campaign claim bursts from one wallet
+        check_and_update_claim_cooldown(
+            &ctx.accounts.config,
+            ctx.accounts.claim_limit.as_mut(),
+            now,
+        )?;
+
+        // Enforce the hold period (campaign setting, floored by the global minimum)
+        // Campaigns in penalty mode allow early claims at a reduced amount instead
+        let effective_hold_seconds = effective_min_hold(campaign, &ctx.accounts.config);
+        let is_early =
+            now < registration.registered_at.saturating_add(effective_hold_seconds);
+        require_diag!(
+            !is_early || campaign.early_claim_penalty_bps > 0,
+            ClaimHoldActive,
+            "now={} registered_at={} hold_seconds={}",
+            now,
+            registration.registered_at,
+            effective_hold_seconds
+        );
+
+        // Claim the allocation fixed at registration; older registrations
+        // derive it from the proof type
+        let claim_amount = registration_claim_amount(campaign, registration)?;
+
+        // Creator gate: the claim signer must have authorized this specific claim
+        if campaign.require_claim_signature {
+            let claim_signature = claim_signature.ok_or(VouchError::ClaimNotAuthorized)?;
+            let message = build_claim_authorization_message(
+                &campaign.campaign_id,
+                &registration.nullifier,
+                claim_amount,
+            );
+            verify_ed25519_signature(
+                &ctx.accounts.instructions_sysvar.to_account_info(),
+                &campaign.claim_signer,
+                &claim_signature,
+                &message,
+                ctx.accounts.config.max_ed25519_scan_distance,
+                ctx.accounts.config.exclusive_ed25519_instructions,
+            )
+            .map_err(|_| VouchError::ClaimNotAuthorized)?;
+        }
+
+        // Vesting campaigns release only what has vested since the last claim
+        if campaign.vesting_duration > 0 {
+            let cliff_at = campaign.vesting_start.saturating_add(campaign.cliff_seconds);
+            require_diag!(
+                now >= cliff_at,
+                VestingCliffActive,
+                "now={} cliff_at={}",
+                now,
+                cliff_at
+            );
+        }
+        let vested = vested_amount(campaign, claim_amount, now)?;
+        let remaining = vested.saturating_sub(registration.claimed_amount);
+        require_diag!(
+            remaining > 0,
+            NothingVested,
+            "vested={} claimed_amount={}",
+            vested,
+            registration.claimed_amount
+        );
+
+        // Partial claims: the penalty would otherwise be re-claimable later
+        let claimable = match requested_amount {
+            Some(requested_amount) => {
+                require_diag!(
+                    requested_amount > 0 && campaign.early_claim_penalty_bps == 0,
+                    InvalidAmount,
+                    "requested_amount={} early_claim_penalty_bps={}",
+                    requested_amount,
+                    campaign.early_claim_penalty_bps
+                );
+                require_diag!(
+                    requested_amount <= remaining,
+                    ExceedsVested,
+                    "requested_amount={} vested={} claimed_amount={}",
+                    requested_amount,
+                    vested,
+                    registration.claimed_amount
+                );
+                requested_amount
+            }
+            None => remaining,
+        };
+
+        // Early claim penalty stays in the vault (returned to the pool)
+        let early_claim_penalty = if is_early {
+            compute_bps(claimable, campaign.early_claim_penalty_bps)?
+        } else {
+            0
+        };
+        let released_amount = claimable
+            .checked_sub(early_claim_penalty)
+            .ok_or(VouchError::Overflow)?;
+
+        // Verify vault has enough tokens
+        require_diag!(
+            ctx.accounts.campaign_vault.amount >= released_amount,
+            InsufficientFunds,
+            "vault_amount={} required={}",
+            ctx.accounts.campaign_vault.amount,
+            released_amount
+        );
+
+        let (campaign_fee, net_amount) =
+            split_campaign_claim_fee(released_amount, campaign.campaign_claim_fee_bps)?;
+
+        // Effects before interactions: mark claimed and update campaign stats
+        let registration = &mut ctx.accounts.registration;
+        let first_claim = registration.claimed_amount == 0;
+        registration.is_claimed = vested >= claim_amount && claimable == remaining;
+        registration.claimed_at = now;
+        registration.claimed_amount = registration
+            .claimed_amount
+            .checked_add(released_amount)
+            .ok_or(VouchError::Overflow)?;
+        registration.claim_in_progress = true;
+        record_registration_claim(
+            &mut ctx.accounts.claim_status,
+            ctx.bumps.claim_status,
+            registration,
+            ctx.accounts.claimer.key(),
+            released_amount,
+            now,
+        )?;
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.vault_balance = campaign
+            .vault_balance
+            .saturating_sub(released_amount);
+        // Counts claimants; later vesting installments don't add to it
+        if first_claim {
+            campaign.total_claimed = campaign
+                .total_claimed
+                .checked_add(1)
+                .ok_or(VouchError::Overflow)?;
+        }
+        campaign.total_claimed_amount = campaign
+            .total_claimed_amount
+            .checked_add(released_amount)
+            .ok_or(VouchError::Overflow)?;
+
+        // Persist the guard (and claimed state) so re-entrant calls observe it
+        ctx.accounts.registration.exit(&crate::ID)?;
+        ctx.accounts.campaign.exit(&crate::ID)?;
+
+        // Transfer tokens from vault to claimer
+        let campaign_id = ctx.accounts.campaign.campaign_id;
+        let bump = ctx.accounts.campaign.bump;
+        let seeds = &[
+            b"airdrop_campaign".as_ref(),
+            campaign_id.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.campaign_vault.to_account_info(),
+            to: ctx.accounts.claimer_token_account.to_account_info(),
+            authority: ctx.accounts.campaign.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, net_amount)?;
+
+        // Route the campaign fee to the creator's fee account
+        if campaign_fee > 0 {
+            let fee_account = ctx
+                .accounts
+                .claim_fee_account
+                .as_ref()
+                .ok_or(VouchError::InvalidClaimFee)?;
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.campaign_vault.to_account_info(),
+                to: fee_account.to_account_info(),
+                authority: ctx.accounts.campaign.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, campaign_fee)?;
+        }
+
+        let registration = &mut ctx.accounts.registration;
+        registration.claim_in_progress = false;
+
+        record_tvl_flow(
+            &ctx.accounts.config,
+            ctx.accounts.protocol_stats.as_mut(),
+            ctx.accounts.campaign.token_mint,
+            0,
+            released_amount,
+        )?;
+
+        let campaign = &mut ctx.accounts.campaign;
+        record_campaign_activity(
+            campaign,
+            ctx.accounts.activity_log.as_mut(),
+            CampaignActivityKind::Claim,
+            registration.nullifier,
+            released_amount,
+            now,
+        )?;
+
+        emit!(AirdropClaimed {
+            campaign_id: campaign.campaign_id,
+            claimer: ctx.accounts.claimer.key(),
+            nullifier: registration.nullifier,
+            amount: released_amount,
+            campaign_fee,
+            net_amount,
+            early_claim_penalty,
+            effective_hold_seconds,
+            vested_amount: vested,
+            claimed_amount: registration.claimed_amount,
+            proof_type: registration.proof_type,
+            timestamp: registration.claimed_at,
+        });
+
+        Ok(())
+    }
+
+    /// Claim airdrop tokens into a custody escrow instead of the claimer's ATA
+    /// The claim follows claim_airdrop's rules (hold period, penalty, vesting,
+    /// claim signature, fees) for the full unclaimed vested balance, but the net
+    /// amount lands in a CustodyEscrow PDA keyed to the claimer (the beneficiary).
+    /// The beneficiary withdraws with release_custody_escrow once release_at has
+    /// passed, or earlier with the custodian's co-signature; at least one of the
+    /// two must be set. Later claims (e.g. vesting installments) top up the same
+    /// escrow and must repeat its release terms.
+    pub fn claim_airdrop_to_custody(
+        ctx: Context<ClaimAirdropToCustody>,
+        claim_signature: Option<[u8; 64]>,
+        custodian: Pubkey,
+        release_at: i64,
+    ) -> Result<()> {
+        let registration = &ctx.accounts.registration;
+        let campaign = &ctx.accounts.campaign;
+        check_claim_not_in_progress(registration)?;
+        require_campaigns_unpaused(&ctx.accounts.config)?;
+        let now = effective_now(&ctx.accounts.config)?;
+        require_diag!(!campaign.is_paused, CampaignPaused, "is_paused=true");
+        require!(campaign.status != CampaignStatus::Cancelled, VouchError::CampaignCancelled);
+        check_recipient_not_blocked(
+            &ctx.accounts.config,
+            ctx.accounts.blocked_recipient.as_ref(),
+            &ctx.accounts.claimer.key(),
+        )?;
+        check_registration_claimer(registration, &ctx.accounts.claimer.key())?;
+        check_registration_credential(registration, ctx.accounts.nullifier_account.as_deref())?;
+        require_unclaimed_marker(&ctx.accounts.private_claim_marker)?;
+
+        require_diag!(
+            !registration.is_claimed,
+            AlreadyClaimed,
+            "claimed_at={}",
+            registration.claimed_at
+        );
+        require_diag!(
+            campaign.claim_deadline == 0 || now <= campaign.claim_deadline,
+            ClaimPeriodEnded,
+            "now={} claim_deadline={}",
+            now,
+            campaign.claim_deadline
+        );
+
+        // Release terms are fixed when the escrow is first funded
+        let escrow = &ctx.accounts.custody_escrow;
+        if escrow.funded_at == 0 {
+            require_diag!(
+                custodian != Pubkey::default() || release_at > now,
+                InvalidCustodyRelease,
+                "custodian=none release_at={} now={}",
+                release_at,
+                now
+            );
+        } else {
+            require_diag!(
+                escrow.custodian == custodian && escrow.release_at == release_at,
+                InvalidCustodyRelease,
+                "custodian={} release_at={} escrow_custodian={} escrow_release_at={}",
+                custodian,
+                release_at,
+                escrow.custodian,
+                escrow.release_at
+            );
+        }
+
+        check_and_update_claim_cooldown(
+            &ctx.accounts.config,
+            ctx.accounts.claim_limit.as_mut(),
+            now,
+        )?;
+
+        let effective_hold_seconds = effective_min_hold(campaign, &ctx.accounts.config);
+        let is_early =
+            now < registration.registered_at.saturating_add(effective_hold_seconds);
+        require_diag!(
+            !is_early || campaign.early_claim_penalty_bps > 0,
+            ClaimHoldActive,
+            "now={} registered_at={} hold_seconds={}",
+            now,
+            registration.registered_at,
+            effective_hold_seconds
+        );
+
+        let claim_amount = registration_claim_amount(campaign, registration)?;
+
+        if campaign.require_claim_signature {
+            let claim_signature = claim_signature.ok_or(VouchError::ClaimNotAuthorized)?;
+            let message = build_claim_authorization_message(
+                &campaign.campaign_id,
+                &registration.nullifier,
+                claim_amount,
+            );
+            verify_ed25519_signature(
+                &ctx.accounts.instructions_sysvar.to_account_info(),
+                &campaign.claim_signer,
+                &claim_signature,
+                &message,
+                ctx.accounts.config.max_ed25519_scan_distance,
+                ctx.accounts.config.exclusive_ed25519_instructions,
+            )
+            .map_err(|_| VouchError::ClaimNotAuthorized)?;
+        }
+
+        if campaign.vesting_duration > 0 {
+            let cliff_at = campaign.vesting_start.saturating_add(campaign.cliff_seconds);
+            require_diag!(
+                now >= cliff_at,
+                VestingCliffActive,
+                "now={} cliff_at={}",
+                now,
+                cliff_at
+            );
+        }
+        let vested = vested_amount(campaign, claim_amount, now)?;
+        let remaining = vested.saturating_sub(registration.claimed_amount);
+        require_diag!(
+            remaining > 0,
+            NothingVested,
+            "vested={} claimed_amount={}",
+            vested,
+            registration.claimed_amount
+        );
+
+        let early_claim_penalty = if is_early {
+            compute_bps(remaining, campaign.early_claim_penalty_bps)?
+        } else {
+            0
+        };
+        let released_amount = remaining
+            .checked_sub(early_claim_penalty)
+            .ok_or(VouchError::Overflow)?;
+
+        require_diag!(
+            ctx.accounts.campaign_vault.amount >= released_amount,
+            InsufficientFunds,
+            "vault_amount={} required={}",
+            ctx.accounts.campaign_vault.amount,
+            released_amount
+        );
+
+        let (campaign_fee, net_amount) =
+            split_campaign_claim_fee(released_amount, campaign.campaign_claim_fee_bps)?;
+
+        // Effects before interactions, as in claim_airdrop
+        let registration = &mut ctx.accounts.registration;
+        let first_claim = registration.claimed_amount == 0;
+        registration.is_claimed = vested >= claim_amount;
+        registration.claimed_at = now;
+        registration.claimed_amount = registration
+            .claimed_amount
+            .checked_add(released_amount)
+            .ok_or(VouchError::Overflow)?;
+        registration.claim_in_progress = true;
+        record_registration_claim(
+            &mut ctx.accounts.claim_status,
+            ctx.bumps.claim_status,
+            registration,
+            ctx.accounts.claimer.key(),
+            released_amount,
+            now,
+        )?;
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.vault_balance = campaign
+            .vault_balance
+            .saturating_sub(released_amount);
+        if first_claim {
+            campaign.total_claimed = campaign
+                .total_claimed
+                .checked_add(1)
+                .ok_or(VouchError::Overflow)?;
+        }
+        campaign.total_claimed_amount = campaign
+            .total_claimed_amount
+            .checked_add(released_amount)
+            .ok_or(VouchError::Overflow)?;
+
+        let escrow = &mut ctx.accounts.custody_escrow;
+        if escrow.funded_at == 0 {
+            escrow.campaign = campaign.key();
+            escrow.beneficiary = ctx.accounts.claimer.key();
+            escrow.custodian = custodian;
+            escrow.release_at = release_at;
+            escrow.token_mint = campaign.token_mint;
+            escrow.bump = ctx.bumps.custody_escrow;
+        }
+        escrow.amount = escrow
+            .amount
+            .checked_add(net_amount)
+            .ok_or(VouchError::Overflow)?;
+        escrow.funded_at = now;
+
+        ctx.accounts.registration.exit(&crate::ID)?;
+        ctx.accounts.campaign.exit(&crate::ID)?;
+
+        let campaign_id = ctx.accounts.campaign.campaign_id;
+        let bump = ctx.accounts.campaign.bump;
+        let seeds = &[
+            b"airdrop_campaign".as_ref(),
+            campaign_id.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.campaign_vault.to_account_info(),
+            to: ctx.accounts.escrow_vault.to_account_info(),
+            authority: ctx.accounts.campaign.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, net_amount)?;
+
+        if campaign_fee > 0 {
+            let fee_account = ctx
+                .accounts
+                .claim_fee_account
+                .as_ref()
+                .ok_or(VouchError::InvalidClaimFee)?;
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.campaign_vault.to_account_info(),
+                to: fee_account.to_account_info(),
+                authority: ctx.accounts.campaign.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, campaign_fee)?;
+        }
+
+        let registration = &mut ctx.accounts.registration;
+        registration.claim_in_progress = false;
+
+        // Escrowed tokens have left the campaign vault
+        record_tvl_flow(
+            &ctx.accounts.config,
+            ctx.accounts.protocol_stats.as_mut(),
+            ctx.accounts.campaign.token_mint,
+            0,
+            released_amount,
+        )?;
+
+        let campaign = &mut ctx.accounts.campaign;
+        record_campaign_activity(
+            campaign,
+            ctx.accounts.activity_log.as_mut(),
+            CampaignActivityKind::Claim,
+            registration.nullifier,
+            released_amount,
+            now,
+        )?;
+
+        emit!(AirdropClaimed {
+            campaign_id: campaign.campaign_id,
+            claimer: ctx.accounts.claimer.key(),
+            nullifier: registration.nullifier,
+            amount: released_amount,
+            campaign_fee,
+            net_amount,
+            early_claim_penalty,
+            effective_hold_seconds,
+            vested_amount: vested,
+            claimed_amount: registration.claimed_amount,
+            proof_type: registration.proof_type,
+            timestamp: registration.claimed_at,
+        });
+
+        let escrow = &ctx.accounts.custody_escrow;
+        emit!(CustodyEscrowFunded {
+            campaign_id: campaign.campaign_id,
+            escrow: escrow.key(),
+            beneficiary: escrow.beneficiary,
+            custodian: escrow.custodian,
+            release_at: escrow.release_at,
+            amount: net_amount,
+            escrow_amount: escrow.amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw a custody escrow to its beneficiary
+    /// The beneficiary signs; the release needs either release_at to have passed
+    /// or the escrow's custodian co-signing. The full balance is paid out and the
+    /// escrow and its token account are closed to the beneficiary.
+    pub fn release_custody_escrow(ctx: Context<ReleaseCustodyEscrow>) -> Result<()> {
+        let escrow = &ctx.accounts.custody_escrow;
+        let now = Clock::get()?.unix_timestamp;
+        let released_by_custodian = ctx.accounts.custodian.is_some();
+        let time_lock_elapsed = escrow.release_at > 0 && now >= escrow.release_at;
+        require_diag!(
+            released_by_custodian || time_lock_elapsed,
+            CustodyLocked,
+            "now={} release_at={}",
+            now,
+            escrow.release_at
+        );
+
+        let amount = ctx.accounts.escrow_vault.amount;
+        let campaign = escrow.campaign;
+        let beneficiary = escrow.beneficiary;
+        let bump = escrow.bump;
+        let seeds = &[
+            b"custody_escrow".as_ref(),
+            campaign.as_ref(),
+            beneficiary.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        if amount > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.escrow_vault.to_account_info(),
+                to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                authority: ctx.accounts.custody_escrow.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.escrow_vault.to_account_info(),
+            destination: ctx.accounts.beneficiary.to_account_info(),
+            authority: ctx.accounts.custody_escrow.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::close_account(cpi_ctx)?;
+
+        emit!(CustodyEscrowReleased {
+            campaign,
+            escrow: ctx.accounts.custody_escrow.key(),
+            beneficiary,
+            amount,
+            released_by_custodian,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Return unclaimed tokens in a completed campaign's vault to the creator
+    /// Only campaign creator can reclaim, and only once AIRDROP_RECLAIM_GRACE_SECONDS
+    /// have passed since completion and any claim_deadline has ended, so
+    /// registrants still inside the claim window keep their allocation.
+    pub fn reclaim_unclaimed_airdrop(ctx: Context<ReclaimUnclaimedAirdrop>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        require!(
+            campaign.status == CampaignStatus::Completed,
+            VouchError::CampaignNotCompleted
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let reclaim_at = airdrop_reclaim_at(campaign);
+        require_diag!(
+            now > reclaim_at,
+            ReclaimGracePeriodActive,
+            "now={} reclaim_at={}",
+            now,
+            reclaim_at
+        );
+
+        let amount = ctx.accounts.campaign_vault.amount;
+        require!(amount > 0, VouchError::InsufficientFunds);
+
+        let campaign_id = campaign.campaign_id;
+        let bump = campaign.bump;
+        let seeds = &[
+            b"airdrop_campaign".as_ref(),
+            campaign_id.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.campaign_vault.to_account_info(),
+            to: ctx.accounts.creator_token_account.to_account_info(),
+            authority: ctx.accounts.campaign.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)?;
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.vault_balance = 0;
+
+        record_tvl_flow(
+            &ctx.accounts.config,
+            ctx.accounts.protocol_stats.as_mut(),
+            campaign.token_mint,
+            0,
+            amount,
+        )?;
+
+        emit!(AirdropReclaimed {
+            campaign_id,
+            creator: ctx.accounts.creator.key(),
+            amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Claim airdrop tokens by proving inclusion in the campaign's merkle root
+    /// No registration is needed: the claimer proves their verified credential's
+    /// leaf build_airdrop_claim_leaf(nullifier, amount, proof_type) is in
+    /// campaign.merkle_root, and must be the credential's recipient. The
+    /// (campaign, nullifier) ClaimStatus is shared with claim_airdrop and
+    /// claim_airdrop_to_custody, and claim_airdrop_private's marker for the
+    /// nullifier must not exist, so a credential pays out through one path only.
+    /// The leaf amount is released in full (minus the campaign claim fee); hold
+    /// periods and vesting don't apply.
+    pub fn claim_airdrop_merkle(
+        ctx: Context<ClaimAirdropMerkle>,
+        leaf: [u8; 32],
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let nullifier_account = &ctx.accounts.nullifier_account;
+        require_campaigns_unpaused(&ctx.accounts.config)?;
+        let now = effective_now(&ctx.accounts.config)?;
+
+        require!(!campaign.is_paused, VouchError::CampaignPaused);
+        require!(campaign.status != CampaignStatus::Cancelled, VouchError::CampaignCancelled);
+        require!(campaign.merkle_root != [0u8; 32], VouchError::MerkleClaimsDisabled);
+        require_diag!(
+            campaign.claim_deadline == 0 || now <= campaign.claim_deadline,
+            ClaimPeriodEnded,
+            "now={} claim_deadline={}",
+            now,
+            campaign.claim_deadline
+        );
+        require!(amount > 0, VouchError::InvalidAmount);
+        check_credential_claimer(nullifier_account, &ctx.accounts.claimer.key())?;
+        require_unclaimed_marker(&ctx.accounts.private_claim_marker)?;
+        check_recipient_not_blocked(
+            &ctx.accounts.config,
+            ctx.accounts.blocked_recipient.as_ref(),
+            &ctx.accounts.claimer.key(),
+        )?;
+        require_diag!(
+            ctx.accounts.claim_status.claimed_at == 0,
+            AlreadyClaimed,
+            "claimed_at={}",
+            ctx.accounts.claim_status.claimed_at
+        );
+
+        // The leaf must commit to this credential and amount, and be in the tree
+        let expected_leaf = build_airdrop_claim_leaf(
+            &nullifier_account.nullifier,
+            amount,
+            nullifier_account.proof_type as u8,
+        );
+        require!(leaf == expected_leaf, VouchError::InvalidMerkleProof);
+        require!(proof.len() <= MAX_MERKLE_PROOF_DEPTH, VouchError::InvalidMerkleProof);
+        require!(
+            verify_merkle_proof(&proof, &campaign.merkle_root, &leaf),
+            VouchError::InvalidMerkleProof
+        );
+
+        require_diag!(
+            ctx.accounts.campaign_vault.amount >= amount,
+            InsufficientFunds,
+            "vault_amount={} required={}",
+            ctx.accounts.campaign_vault.amount,
+            amount
+        );
+        let (campaign_fee, net_amount) =
+            split_campaign_claim_fee(amount, campaign.campaign_claim_fee_bps)?;
+
+        // Effects before interactions: the claim status marks the nullifier spent
+        let claim_status = &mut ctx.accounts.claim_status;
+        claim_status.campaign = campaign.key();
+        claim_status.nullifier = nullifier_account.nullifier;
+        claim_status.leaf = leaf;
+        claim_status.claimer = ctx.accounts.claimer.key();
+        claim_status.amount = amount;
+        claim_status.claimed_at = now;
+        claim_status.bump = ctx.bumps.claim_status;
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.vault_balance = campaign.vault_balance.saturating_sub(amount);
+        campaign.total_claimed = campaign
+            .total_claimed
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+        campaign.total_claimed_amount = campaign
+            .total_claimed_amount
+            .checked_add(amount)
+            .ok_or(VouchError::Overflow)?;
+
+        let campaign_id = campaign.campaign_id;
+        let bump = campaign.bump;
+        let seeds = &[
+            b"airdrop_campaign".as_ref(),
+            campaign_id.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.campaign_vault.to_account_info(),
+            to: ctx.accounts.claimer_token_account.to_account_info(),
+            authority: ctx.accounts.campaign.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, net_amount)?;
+
+        // Route the campaign fee to the creator's fee account
+        if campaign_fee > 0 {
+            let fee_account = ctx
+                .accounts
+                .claim_fee_account
+                .as_ref()
+                .ok_or(VouchError::InvalidClaimFee)?;
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.campaign_vault.to_account_info(),
+                to: fee_account.to_account_info(),
+                authority: ctx.accounts.campaign.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, campaign_fee)?;
+        }
+
+        record_tvl_flow(
+            &ctx.accounts.config,
+            ctx.accounts.protocol_stats.as_mut(),
+            ctx.accounts.campaign.token_mint,
+            0,
+            amount,
+        )?;
+
+        let nullifier = ctx.accounts.nullifier_account.nullifier;
+        record_campaign_activity(
+            &ctx.accounts.campaign,
+            ctx.accounts.activity_log.as_mut(),
+            CampaignActivityKind::Claim,
+            nullifier,
+            amount,
+            now,
+        )?;
+
+        emit!(AirdropMerkleClaimed {
+            campaign_id,
+            claimer: ctx.accounts.claimer.key(),
+            nullifier,
+            leaf,
+            amount,
+            campaign_fee,
+            net_amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Claim airdrop tokens with a fresh verifier attestation instead of a registration
+    /// The transaction must include an Ed25519 instruction in which a verifier
+    /// signs build_private_claim_message(campaign_id, nullifier, amount,
+    /// claimer, expires_at). No registration or NullifierAccount is involved, so
+    /// nothing on-chain links the wallet to a stored credential; the signed
+    /// claimer binds the payout to one wallet, so an observed signature can't be
+    /// submitted by another signer. A PrivateClaimMarker keyed by
+    /// (campaign, nullifier) prevents double claims, and the nullifier's
+    /// ClaimStatus must not exist, so a credential already paid out through a
+    /// registration or claim_airdrop_merkle can't claim here too (and vice versa).
+    /// Requires campaign.private_claims_enabled. The amount is released in full
+    /// (minus the campaign claim fee); hold periods and vesting don't apply.
+    pub fn claim_airdrop_private(
+        ctx: Context<ClaimAirdropPrivate>,
+        nullifier: [u8; 32],
+        amount: u64,
+        expires_at: i64,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        let config = &ctx.accounts.config;
+        require_campaigns_unpaused(config)?;
+        let now = effective_now(config)?;
+
+        require!(!campaign.is_paused, VouchError::CampaignPaused);
+        require!(campaign.status != CampaignStatus::Cancelled, VouchError::CampaignCancelled);
+        require!(campaign.private_claims_enabled, VouchError::PrivateClaimsDisabled);
+        require_diag!(
+            campaign.claim_deadline == 0 || now <= campaign.claim_deadline,
+            ClaimPeriodEnded,
+            "now={} claim_deadline={}",
+            now,
+            campaign.claim_deadline
+        );
+        require!(amount > 0, VouchError::InvalidAmount);
+        require_diag!(
+            now <= expires_at,
+            AttestationExpired,
+            "expires_at={} now={}",
+            expires_at,
+            now
+        );
+        check_recipient_not_blocked(
+            config,
+            ctx.accounts.blocked_recipient.as_ref(),
+            &ctx.accounts.claimer.key(),
+        )?;
+        require_unclaimed_marker(&ctx.accounts.claim_status)?;
+
+        let verifier_account = &ctx.accounts.verifier_account;
+        require!(
+            verifier_is_active(verifier_account, now),
+            VouchError::VerifierNotAuthorized
+        );
+        if let Some(required_verifier) = campaign.required_verifier {
+            require_diag!(
+                verifier_account.verifier == required_verifier,
+                WrongVerifier,
+                "verifier={} required_verifier={}",
+                verifier_account.verifier,
+                required_verifier
+            );
+        }
+
+        let message = build_private_claim_message(
+            &campaign.campaign_id,
+            &nullifier,
+            amount,
+            &ctx.accounts.claimer.key(),
+            expires_at,
+        );
+        verify_ed25519_signature(
+            &ctx.accounts.instructions_sysvar.to_account_info(),
+            &verifier_account.verifier,
+            &signature,
+            &message,
+            config.max_ed25519_scan_distance,
+            config.exclusive_ed25519_instructions,
+        )?;
+
+        record_used_signature(
+            &ctx.accounts.config,
+            ctx.accounts.used_signature.as_ref(),
+            &ctx.accounts.claimer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &signature,
+            &nullifier,
+            &verifier_account.verifier,
+            now,
+        )?;
+
+        require_diag!(
+            ctx.accounts.campaign_vault.amount >= amount,
+            InsufficientFunds,
+            "vault_amount={} required={}",
+            ctx.accounts.campaign_vault.amount,
+            amount
+        );
+        let (campaign_fee, net_amount) =
+            split_campaign_claim_fee(amount, campaign.campaign_claim_fee_bps)?;
+
+        // Effects before interactions: the marker consumes the nullifier for this campaign
+        let marker = &mut ctx.accounts.claim_marker;
+        marker.campaign = campaign.key();
+        marker.claimed_at = now;
+        marker.bump = ctx.bumps.claim_marker;
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.vault_balance = campaign.vault_balance.saturating_sub(amount);
+        campaign.total_claimed = campaign
+            .total_claimed
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+        campaign.total_claimed_amount = campaign
+            .total_claimed_amount
+            .checked_add(amount)
+            .ok_or(VouchError::Overflow)?;
+
+        let campaign_id = campaign.campaign_id;
+        let bump = campaign.bump;
+        let seeds = &[
+            b"airdrop_campaign".as_ref(),
+            campaign_id.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.campaign_vault.to_account_info(),
+            to: ctx.accounts.claimer_token_account.to_account_info(),
+            authority: ctx.accounts.campaign.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, net_amount)?;
+
+        // Route the campaign fee to the creator's fee account
+        if campaign_fee > 0 {
+            let fee_account = ctx
+                .accounts
+                .claim_fee_account
+                .as_ref()
+                .ok_or(VouchError::InvalidClaimFee)?;
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.campaign_vault.to_account_info(),
+                to: fee_account.to_account_info(),
+                authority: ctx.accounts.campaign.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, campaign_fee)?;
+        }
+
+        record_tvl_flow(
+            &ctx.accounts.config,
+            ctx.accounts.protocol_stats.as_mut(),
+            ctx.accounts.campaign.token_mint,
+            0,
+            amount,
+        )?;
+        record_campaign_activity(
+            &ctx.accounts.campaign,
+            ctx.accounts.activity_log.as_mut(),
+            CampaignActivityKind::Claim,
+            nullifier,
+            amount,
+            now,
+        )?;
+
+        emit!(AirdropPrivateClaimed {
+            campaign_id,
+            nullifier,
+            verifier: ctx.accounts.verifier_account.verifier,
+            amount,
+            campaign_fee,
+            net_amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // === Multi-Asset Campaigns ===
+    // A campaign can distribute a basket of up to MAX_CAMPAIGN_ASSETS additional
+    // mints alongside its primary token_mint. Each asset has its own vault (ATA
+    // owned by the campaign PDA) and a flat per-registrant amount. Claims are
+    // tracked per asset on the registration so a failed asset can be retried.
+
+    /// Configure the additional assets for a campaign
+    /// Only campaign creator can configure, while open and before any registrations
+    pub fn configure_campaign_assets(
+        ctx: Context<ConfigureCampaignAssets>,
+        mints: Vec<Pubkey>,
+        amounts_per_claim: Vec<u64>,
+    ) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+
+        require!(campaign.status == CampaignStatus::Open, VouchError::CampaignNotOpen);
+        require!(campaign.total_registrations == 0, VouchError::CampaignHasRegistrations);
+        require!(
+            !mints.is_empty()
+                && mints.len() <= MAX_CAMPAIGN_ASSETS
+                && mints.len() == amounts_per_claim.len(),
+            VouchError::InvalidAssetConfig
+        );
+
+        let mut assets = Vec::with_capacity(mints.len());
+        for (i, (mint, amount)) in mints.iter().zip(amounts_per_claim.iter()).enumerate() {
+            require!(*amount > 0, VouchError::InvalidAmount);
+            // The primary mint already has the campaign's vault
+            require!(*mint != campaign.token_mint, VouchError::InvalidAssetConfig);
+            require!(!mints[..i].contains(mint), VouchError::InvalidAssetConfig);
+            assets.push(CampaignAsset {
+                mint: *mint,
+                amount_per_claim: *amount,
+                vault_balance: 0,
+                total_claimed: 0,
+            });
+        }
+
+        let asset_basket = &mut ctx.accounts.asset_basket;
+        asset_basket.campaign = campaign.key();
+        asset_basket.assets = assets;
+        asset_basket.bump = ctx.bumps.asset_basket;
+
+        emit!(CampaignAssetsConfigured {
+            campaign_id: campaign.campaign_id,
+            mints,
+            amounts_per_claim,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Fund one of a campaign's additional asset vaults
+    /// Campaign creator or a campaign manager can fund
+    pub fn fund_campaign_asset(
+        ctx: Context<FundCampaignAsset>,
+        asset_index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        let campaign = &ctx.accounts.campaign;
+        require_campaigns_unpaused(&ctx.accounts.config)?;
+
+        require!(amount > 0, VouchError::InvalidAmount);
+        require!(
+            campaign.status == CampaignStatus::Open ||
+            campaign.status == CampaignStatus::RegistrationClosed,
+            VouchError::CampaignNotOpen
+        );
+
+        let asset = ctx
+            .accounts
+            .asset_basket
+            .assets
+            .get(asset_index as usize)
+            .ok_or(VouchError::InvalidAssetIndex)?;
+        require!(asset.mint == ctx.accounts.token_mint.key(), VouchError::InvalidMint);
+
+        // Transfer tokens from creator to the asset vault
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.creator_token_account.to_account_info(),
+            to: ctx.accounts.asset_vault.to_account_info(),
+            authority: ctx.accounts.creator.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        let asset = &mut ctx.accounts.asset_basket.assets[asset_index as usize];
+        asset.vault_balance = asset
+            .vault_balance
+            .checked_add(amount)
+            .ok_or(VouchError::Overflow)?;
+        record_tvl_flow(
+            &ctx.accounts.config,
+            ctx.accounts.protocol_stats.as_mut(),
+            asset.mint,
+            amount,
+            0,
+        )?;
+
+        emit!(CampaignAssetFunded {
+            campaign_id: ctx.accounts.campaign.campaign_id,
+            mint: asset.mint,
+            funder: ctx.accounts.creator.key(),
+            amount,
+            total_funded: asset.vault_balance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Claim a registration's allocation of one or more additional assets
+    /// remaining_accounts: for each entry in asset_indices, in order,
+    /// [asset_vault (mut), claimer_token_account (mut)]
+    /// Already-claimed assets are rejected; unclaimed ones can be retried later.
+    pub fn claim_airdrop_multi<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimAirdropMulti<'info>>,
+        asset_indices: Vec<u8>,
+    ) -> Result<()> {
+        let registration = &ctx.accounts.registration;
+        let campaign = &ctx.accounts.campaign;
+        require_campaigns_unpaused(&ctx.accounts.config)?;
+        let now = effective_now(&ctx.accounts.config)?;
+
+        require!(!campaign.is_paused, VouchError::CampaignPaused);
+        require!(campaign.status != CampaignStatus::Cancelled, VouchError::CampaignCancelled);
+        require!(!asset_indices.is_empty(), VouchError::InvalidAssetIndex);
+        check_registration_claimer(registration, &ctx.accounts.claimer.key())?;
+        check_registration_credential(registration, ctx.accounts.nullifier_account.as_deref())?;
+        check_recipient_not_blocked(
+            &ctx.accounts.config,
+            ctx.accounts.blocked_recipient.as_ref(),
+            &ctx.accounts.claimer.key(),
+        )?;
+        require!(
+            campaign.claim_deadline == 0 || now <= campaign.claim_deadline,
+            VouchError::ClaimPeriodEnded
+        );
+        require!(
+            ctx.remaining_accounts.len() == asset_indices.len() * 2,
+            VouchError::InvalidAssetAccounts
+        );
+
+        // Enforce the same hold period as the primary claim
+        let effective_hold_seconds = effective_min_hold(campaign, &ctx.accounts.config);
+        require!(
+            now >= registration.registered_at.saturating_add(effective_hold_seconds),
+            VouchError::ClaimHoldActive
+        );
+
+        // Signature-gated campaigns: extra assets follow an authorized primary claim
+        require!(
+            !campaign.require_claim_signature || registration.claimed_amount > 0,
+            VouchError::ClaimNotAuthorized
+        );
+
+        let campaign_key = campaign.key();
+        let campaign_id = campaign.campaign_id;
+        let bump = campaign.bump;
+        let seeds = &[
+            b"airdrop_campaign".as_ref(),
+            campaign_id.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let claimer_key = ctx.accounts.claimer.key();
+        let nullifier = registration.nullifier;
+        let mut assets_claimed = registration.assets_claimed;
+
+        for (i, &asset_index) in asset_indices.iter().enumerate() {
+            let asset = ctx
+                .accounts
+                .asset_basket
+                .assets
+                .get(asset_index as usize)
+                .ok_or(VouchError::InvalidAssetIndex)?;
+            let mint = asset.mint;
+            let amount = asset.amount_per_claim;
+
+            let flag = 1u8 << asset_index;
+            require!(assets_claimed & flag == 0, VouchError::AlreadyClaimed);
+
+            let vault_info = &ctx.remaining_accounts[i * 2];
+            let claimer_token_info = &ctx.remaining_accounts[i * 2 + 1];
+
+            // Vault must be the campaign's ATA for this mint
+            require!(
+                *vault_info.key == get_associated_token_address(&campaign_key, &mint),
+                VouchError::InvalidAssetAccounts
+            );
+            let vault = load_token_account(vault_info)?;
+            require!(vault.amount >= amount, VouchError::InsufficientFunds);
+
+            let claimer_token = load_token_account(claimer_token_info)?;
+            require!(
+                claimer_token.mint == mint && claimer_token.owner == claimer_key,
+                VouchError::InvalidAssetAccounts
+            );
+
+            let cpi_accounts = Transfer {
+                from: vault_info.clone(),
+                to: claimer_token_info.clone(),
+                authority: ctx.accounts.campaign.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, amount)?;
+
+            assets_claimed |= flag;
+
+            let asset = &mut ctx.accounts.asset_basket.assets[asset_index as usize];
+            asset.vault_balance = asset.vault_balance.saturating_sub(amount);
+            asset.total_claimed = asset
+                .total_claimed
+                .checked_add(1)
+                .ok_or(VouchError::Overflow)?;
+            record_tvl_flow(
+                &ctx.accounts.config,
+                ctx.accounts.protocol_stats.as_mut(),
+                mint,
+                0,
+                amount,
+            )?;
+
+            emit!(AirdropAssetClaimed {
+                campaign_id,
+                claimer: claimer_key,
+                nullifier,
+                mint,
+                amount,
+                timestamp: now,
+            });
+        }
+
+        ctx.accounts.registration.assets_claimed = assets_claimed;
+
+        Ok(())
+    }
+}
+
+// === Helper Functions ===
+
+/// Extra config PDA seed for a config epoch
+/// Epoch 0 contributes an empty seed, so its address is the original
+/// [b"config"] PDA and existing deployments keep their config.
+pub fn config_epoch_seed(config_epoch: &u8) -> &[u8] {
+    if *config_epoch == 0 {
+        &[]
+    } else {
+        std::slice::from_ref(config_epoch)
+    }
+}
+
+/// Extra nullifier PDA seed for a proof type namespace
+/// Namespace 0 contributes an empty seed, so its address is the original
+/// [b"nullifier", nullifier] PDA: credentials recorded before namespacing, and
+/// registry attestations (whose u16 ids don't fit the byte), live there. The
+/// built-in types use their proof type value, so the same 32 bytes used as a
+/// nullifier for two different types no longer collide on one account.
+pub fn nullifier_namespace_seed(namespace: &u8) -> &[u8] {
+    if *namespace == 0 {
+        &[]
+    } else {
+        std::slice::from_ref(namespace)
+    }
+}
+
+/// Check a nullifier namespace is 0 (legacy/registry) or a built-in proof type
+pub fn validate_nullifier_namespace(namespace: u8) -> Result<()> {
+    if namespace != 0 {
+        proof_type_from_value(namespace)?;
+    }
+    Ok(())
+}
+
+/// Give zero-filled appended config fields their initialize_config defaults
+/// Only fields whose zero value differs from the default are set; everything
+/// else already reads as "disabled" or "none".
+fn apply_appended_config_defaults(config: &mut ConfigAccount) {
+    config.max_dev_proofs_per_day = config.max_proofs_per_day;
+    config.max_whale_proofs_per_day = config.max_proofs_per_day;
+    config.max_ed25519_scan_distance = DEFAULT_ED25519_SCAN_DISTANCE;
+    config.exclusive_ed25519_instructions = true;
+    config.campaigns_respect_protocol_pause = true;
+    config.reaper_bounty_bps = DEFAULT_REAPER_BOUNTY_BPS;
+    config.min_active_verifiers = DEFAULT_MIN_ACTIVE_VERIFIERS;
+    config.required_signatures = 1;
+    config.min_nullifier_age_seconds = DEFAULT_MIN_NULLIFIER_AGE_SECONDS;
+}
+
+/// Daily limit for a proof type, capped by the global max_proofs_per_day
+fn daily_limit_for(config: &ConfigAccount, proof_type: ProofType) -> u32 {
+    let type_limit = match proof_type {
+        ProofType::DeveloperReputation => config.max_dev_proofs_per_day,
+        ProofType::WhaleTrading => config.max_whale_proofs_per_day,
+        ProofType::Unset => config.max_proofs_per_day,
+    };
+    type_limit.min(config.max_proofs_per_day)
+}
+
+/// Feed one attestation into the auto-pause circuit breaker
+/// The counter is a leaky bucket draining max_attestations_per_window per
+/// attestation_window_seconds, so bursts up to the threshold pass and only a
+/// sustained excess trips it. Returns true when this attestation paused the
+/// protocol; callers must then return Ok without recording, since an error
+/// would roll the pause back.
+fn trip_attestation_circuit_breaker(config: &mut ConfigAccount, now: i64) -> Result<bool> {
+    if config.max_attestations_per_window == 0 {
+        return Ok(false);
+    }
+
+    let threshold = (config.max_attestations_per_window as u64)
+        .checked_mul(ATTESTATION_RATE_SCALE)
+        .ok_or(VouchError::Overflow)?;
+    let elapsed = now.saturating_sub(config.attestation_rate_updated_at).max(0) as u128;
+    let window = config.attestation_window_seconds.max(1) as u128;
+    let drained = (elapsed * threshold as u128 / window).min(u64::MAX as u128) as u64;
+    let level = config
+        .attestation_rate_level
+        .saturating_sub(drained)
+        .checked_add(ATTESTATION_RATE_SCALE)
+        .ok_or(VouchError::Overflow)?;
+    config.attestation_rate_updated_at = now;
+
+    if level <= threshold {
+        config.attestation_rate_level = level;
+        return Ok(false);
+    }
+
+    // Start from an empty bucket once the pause authority unpauses
+    config.attestation_rate_level = 0;
+    config.is_paused = true;
+    msg!(
+        "Auto-pause: attestation rate exceeded {} per {}s",
+        config.max_attestations_per_window,
+        config.attestation_window_seconds
+    );
+
+    emit!(AutoPauseTriggered {
+        max_attestations_per_window: config.max_attestations_per_window,
+        window_seconds: config.attestation_window_seconds,
+        timestamp: now,
+    });
+
+    Ok(true)
+}
+
+/// Check and update rate limits for a wallet
+/// Enforces the proof type's daily limit and the global daily cap over a rolling
+/// 24h window of the wallet's recent proof timestamps.
+fn check_and_update_rate_limit(
+    rate_limit: &mut WalletRateLimit,
+    config: &ConfigAccount,
+    proof_type: ProofType,
+    now: i64,
+) -> Result<()> {
+    // Exempt wallets (trusted relayers) skip the limits but keep accounting
+    if rate_limit.exempt {
+        rate_limit.last_proof_at = now;
+        rate_limit.total_proofs = rate_limit
+            .total_proofs
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+        return Ok(());
+    }
+
+    // Check cooldown period
+    let time_since_last = now.saturating_sub(rate_limit.last_proof_at);
+    require_diag!(
+        time_since_last >= config.cooldown_seconds,
+        RateLimitCooldown,
+        "elapsed={} cooldown_seconds={}",
+        time_since_last,
+        config.cooldown_seconds
+    );
+
+    let RateLimitWindow {
+        in_window,
+        mut dev_in_window,
+        mut whale_in_window,
+        oldest_in_window,
+    } = rate_limit_window(rate_limit, config, now);
+
+    // Check the global daily cap, then the proof type's own limit
+    // The buffer holds RATE_LIMIT_WINDOW_SIZE >= max_proofs_per_day entries, so the
+    // slot overwritten below is never one still inside the window.
+    require_diag!(
+        in_window < daily_proof_limit(config),
+        DailyRateLimitExceeded,
+        "scope=global in_window={} limit={}",
+        in_window,
+        config.max_proofs_per_day
+    );
+    match proof_type {
+        ProofType::DeveloperReputation => {
+            require_diag!(
+                dev_in_window < config.max_dev_proofs_per_day,
+                DailyRateLimitExceeded,
+                "scope=developer in_window={} limit={}",
+                dev_in_window,
+                config.max_dev_proofs_per_day
+            );
+            dev_in_window += 1;
+        }
+        ProofType::WhaleTrading => {
+            require_diag!(
+                whale_in_window < config.max_whale_proofs_per_day,
+                DailyRateLimitExceeded,
+                "scope=whale in_window={} limit={}",
+                whale_in_window,
+                config.max_whale_proofs_per_day
+            );
+            whale_in_window += 1;
+        }
+        ProofType::Unset => {}
+    }
+
+    let head = rate_limit.recent_head as usize % RATE_LIMIT_WINDOW_SIZE;
+    rate_limit.recent_proofs[head] = RecentProof {
+        timestamp: now,
+        proof_type,
+    };
+    rate_limit.recent_head = ((head + 1) % RATE_LIMIT_WINDOW_SIZE) as u8;
+
+    // Update rate limit counters (window snapshot as of this proof)
+    rate_limit.proofs_today = in_window + 1;
+    rate_limit.dev_proofs_today = dev_in_window;
+    rate_limit.whale_proofs_today = whale_in_window;
+    rate_limit.day_start = oldest_in_window;
+    rate_limit.last_proof_at = now;
+    rate_limit.total_proofs = rate_limit
+        .total_proofs
+        .checked_add(1)
+        .ok_or(VouchError::Overflow)?;
+
+    Ok(())
+}
+
+/// Proofs a wallet made inside the current rolling rate limit window
+pub struct RateLimitWindow {
+    pub in_window: u32,
+    pub dev_in_window: u32,
+    pub whale_in_window: u32,
+    /// Oldest proof in the window (`now` when the window is empty)
+    pub oldest_in_window: i64,
+}
+
+/// Count a wallet's proofs in the rolling 24h window ending at `now`
+/// Not a fixed day boundary, so a burst just before a reset can't be followed
+/// by a full allotment right after. The skew tolerance shortens the window
+/// slightly to absorb validator clock skew. Shared by
+/// check_and_update_rate_limit and remaining_quota.
+pub fn rate_limit_window(
+    rate_limit: &WalletRateLimit,
+    config: &ConfigAccount,
+    now: i64,
+) -> RateLimitWindow {
+    let window = SECONDS_PER_DAY.saturating_sub(config.reset_skew_tolerance_seconds);
+    let window_start = now.saturating_sub(window);
+    let mut counts = RateLimitWindow {
+        in_window: 0,
+        dev_in_window: 0,
+        whale_in_window: 0,
+        oldest_in_window: now,
+    };
+    for entry in rate_limit.recent_proofs.iter() {
+        if entry.timestamp == 0 || entry.timestamp <= window_start {
+            continue;
+        }
+        counts.in_window += 1;
+        match entry.proof_type {
+            ProofType::DeveloperReputation => counts.dev_in_window += 1,
+            ProofType::WhaleTrading => counts.whale_in_window += 1,
+            ProofType::Unset => {}
+        }
+        counts.oldest_in_window = counts.oldest_in_window.min(entry.timestamp);
+    }
+    counts
+}
+
+/// Effective per-wallet daily proof limit (capped by the ring buffer size)
+fn daily_proof_limit(config: &ConfigAccount) -> u32 {
+    config.max_proofs_per_day.min(RATE_LIMIT_WINDOW_SIZE as u32)
+}
+
+/// Proofs a wallet may still submit in the current rolling window
+/// Off-chain callers can deserialize the WalletRateLimit and ConfigAccount and
+/// pass the current unix time. Exempt wallets report u32::MAX. The cooldown
+/// and per-type limits are not reflected.
+pub fn remaining_quota(rate_limit: &WalletRateLimit, config: &ConfigAccount, now: i64) -> u32 {
+    if rate_limit.exempt {
+        return u32::MAX;
+    }
+    daily_proof_limit(config).saturating_sub(rate_limit_window(rate_limit, config, now).in_window)
+}
+
+/// Count a verified proof in total_proofs_verified and the global daily cap
+/// config.max_global_proofs_per_day (0 = unlimited) is enforced per UTC day;
+/// the counter resets on the first proof of a new day.
+fn record_verified_proof(config: &mut ConfigAccount, now: i64) -> Result<()> {
+    let day_start = now - now.rem_euclid(SECONDS_PER_DAY);
+    if config.global_day_start != day_start {
+        config.global_day_start = day_start;
+        config.global_proofs_today = 0;
+    }
+    require_diag!(
+        config.max_global_proofs_per_day == 0
+            || config.global_proofs_today < config.max_global_proofs_per_day,
+        GlobalRateLimitExceeded,
+        "global_proofs_today={} limit={}",
+        config.global_proofs_today,
+        config.max_global_proofs_per_day
+    );
+    config.global_proofs_today = config
+        .global_proofs_today
+        .checked_add(1)
+        .ok_or(VouchError::Overflow)?;
+    config.total_proofs_verified = config
+        .total_proofs_verified
+        .checked_add(1)
+        .ok_or(VouchError::Overflow)?;
+    Ok(())
+}
+
+/// Minimum age of a used nullifier before it may be closed
+/// The larger of config.min_nullifier_age_seconds and the attestation replay
+/// window: a signature for epoch E (<= current epoch + 1) stays acceptable until
+/// max_epoch_age days after E, so max_epoch_age + 2 days covers it.
+fn nullifier_retention_seconds(config: &ConfigAccount) -> i64 {
+    let replay_window = (config.max_epoch_age.min(i64::MAX as u64) as i64)
+        .saturating_add(2)
+        .saturating_mul(SECONDS_PER_DAY);
+    config.min_nullifier_age_seconds.max(replay_window)
+}
+
+/// Enforce config.global_claim_cooldown_seconds between a wallet's claims
+/// No-op while the cooldown is zero; otherwise the claim limit account is required.
+fn check_and_update_claim_cooldown(
+    config: &ConfigAccount,
+    claim_limit: Option<&mut Account<WalletClaimLimit>>,
+    now: i64,
+) -> Result<()> {
+    if config.global_claim_cooldown_seconds == 0 {
+        return Ok(());
+    }
+    let claim_limit = claim_limit.ok_or(VouchError::ClaimLimitRequired)?;
+
+    require_diag!(
+        claim_limit.last_claim_at == 0
+            || now.saturating_sub(claim_limit.last_claim_at) >= config.global_claim_cooldown_seconds,
+        ClaimCooldownActive,
+        "last_claim_at={} now={} cooldown_seconds={}",
+        claim_limit.last_claim_at,
+        now,
+        config.global_claim_cooldown_seconds
+    );
+
+    claim_limit.last_claim_at = now;
+    claim_limit.total_claims = claim_limit
+        .total_claims
+        .checked_add(1)
+        .ok_or(VouchError::Overflow)?;
+    Ok(())
+}
+
+/// Reject campaign operations during a protocol pause when config opts in
+/// (config.campaigns_respect_protocol_pause, on by default)
+/// Applies to campaign creation, registration, funding, claims and
+/// distribution marking. Creator settings, pause/resume, completion, reaping,
+/// closes, reclaim_unclaimed_airdrop and release_custody_escrow stay exempt so
+/// funds can't be trapped by an indefinite pause.
+fn require_campaigns_unpaused(config: &ConfigAccount) -> Result<()> {
+    require!(
+        !(config.is_paused && config.campaigns_respect_protocol_pause),
+        VouchError::ProtocolPaused
+    );
+    Ok(())
+}
+
+/// Whether a verifier may attest: active and not past a scheduled removal
+/// (a due removal applies even before finalize_verifier_removal runs)
+fn verifier_is_active(verifier_account: &VerifierAccount, now: i64) -> bool {
+    verifier_account.is_active
+        && (verifier_account.removal_scheduled_at == 0 || now < verifier_account.removal_scheduled_at)
+}
+
+/// Map a proof type value from a signed message to ProofType
+pub fn proof_type_from_value(proof_type_value: u8) -> Result<ProofType> {
+    match proof_type_value {
+        1 => Ok(ProofType::DeveloperReputation),
+        2 => Ok(ProofType::WhaleTrading),
+        _ => Err(VouchError::InvalidProofType.into()),
+    }
+}
+
+/// Current protocol time used for cooldowns, deadlines and hold periods
+///
+/// By default this is the validator clock's unix_timestamp: wall-clock aligned,
+/// but validator-reported and only loosely bounded by stake-weighted consensus.
+/// With config.use_slot_based_time it is time_anchor_timestamp plus elapsed
+/// slots * ms_per_slot: monotonic and harder to skew, but it drifts from wall
+/// time whenever real slot times differ from ms_per_slot (skipped slots,
+/// congestion), so long deadlines can land noticeably early or late.
+/// Event timestamps always use the validator clock.
+pub fn effective_now(config: &ConfigAccount) -> Result<i64> {
+    let clock = Clock::get()?;
+    if !config.use_slot_based_time {
+        return Ok(clock.unix_timestamp);
+    }
+
+    let elapsed_slots = clock.slot.saturating_sub(config.time_anchor_slot);
+    let elapsed_seconds = (elapsed_slots as u128)
+        .checked_mul(config.ms_per_slot as u128)
+        .ok_or(VouchError::Overflow)?
+        / 1_000;
+    let elapsed_seconds = i64::try_from(elapsed_seconds).map_err(|_| VouchError::Overflow)?;
+    config
+        .time_anchor_timestamp
+        .checked_add(elapsed_seconds)
+        .ok_or_else(|| VouchError::Overflow.into())
+}
+
+/// Bit for a proof type value in NullifierAccount.credential_types
+pub fn proof_type_bit(proof_type_value: u8) -> u8 {
+    1u8.checked_shl(proof_type_value as u32).unwrap_or(0)
+}
+
+/// Require a recipient-owned commitment when config.require_commitment is set
+/// proof_type_value is None for registered proof types, which are gated whenever
+/// the requirement is on; built-in types are gated only if in config.commitment_proof_types.
+fn check_commitment_requirement(
+    config: &ConfigAccount,
+    commitment_account: Option<&Account<CommitmentAccount>>,
+    recipient: &Pubkey,
+    proof_type_value: Option<u8>,
+) -> Result<()> {
+    let in_scope = match proof_type_value {
+        Some(value) if config.commitment_proof_types != 0 => {
+            config.commitment_proof_types & proof_type_bit(value) != 0
+        }
+        _ => true,
+    };
+    if config.require_commitment && in_scope {
+        let commitment_account = commitment_account.ok_or(VouchError::CommitmentRequired)?;
+        require!(
+            commitment_account.owner == *recipient,
+            VouchError::CommitmentRequired
+        );
+    }
+    Ok(())
+}
+
+/// Wallet that controls a registration
+/// The bound recipient when set; open registrations are keyed by the
+/// registrant's pubkey. Verified registrations made before recipients were
+/// stored have no known owner until migrate_registration backfills one
+/// (or bind_registration_recipient binds one, when the credential has none).
+fn registration_owner(registration: &AirdropRegistrationAccount) -> Option<Pubkey> {
+    if registration.recipient != Pubkey::default() {
+        Some(registration.recipient)
+    } else if !registration_has_credential(registration) {
+        Some(Pubkey::new_from_array(registration.nullifier))
+    } else {
+        None
+    }
+}
+
+/// Whether a registration was made with a credential (anything but an open registration)
+fn registration_has_credential(registration: &AirdropRegistrationAccount) -> bool {
+    !(registration.is_open_registration
+        || (registration.proof_type == ProofType::Unset && registration.proof_type_id == 0))
+}
+
+/// Reject a claim on a registration whose credential has since been revoked
+/// Open registrations have no credential; any other registration must pass the
+/// nullifier account it was made with, as claim_airdrop_merkle does, so a
+/// revocation after registering still stops the payout.
+fn check_registration_credential(
+    registration: &AirdropRegistrationAccount,
+    nullifier_account: Option<&NullifierAccount>,
+) -> Result<()> {
+    if !registration_has_credential(registration) {
+        return Ok(());
+    }
+    let nullifier_account = nullifier_account.ok_or(VouchError::InvalidNullifierAccount)?;
+    // Legacy/registry namespace or the registration's built-in type
+    require!(
+        nullifier_account.nullifier == registration.nullifier
+            && (nullifier_account.namespace == 0
+                || nullifier_account.namespace == registration.proof_type as u8),
+        VouchError::InvalidNullifierAccount
+    );
+    require_diag!(
+        !nullifier_account.is_revoked,
+        CredentialRevoked,
+        "nullifier_namespace={}",
+        nullifier_account.namespace
+    );
+    Ok(())
+}
+
+/// Bind an ownerless verified registration to its credential's recipient
+/// Returns whether `recipient` was written. Registrations that already have
+/// an owner are left alone; credentials recorded before recipients were
+/// stored have none to copy, so those registrations stay unbound until
+/// bind_registration_recipient is used.
+fn backfill_registration_recipient(
+    registration: &mut AirdropRegistrationAccount,
+    nullifier_account: &NullifierAccount,
+) -> Result<bool> {
+    require!(
+        nullifier_account.is_used && nullifier_account.nullifier == registration.nullifier,
+        VouchError::InvalidNullifierAccount
+    );
+    if registration_owner(registration).is_some()
+        || nullifier_account.recipient == Pubkey::default()
+    {
+        return Ok(false);
+    }
+    registration.recipient = nullifier_account.recipient;
+    Ok(true)
+}
+
+/// Bind an ownerless registration whose credential has no recipient to copy
+/// Registrations the credential could backfill go through migrate_registration
+/// instead, so the credential's own recipient always wins when it exists.
+fn bind_ownerless_registration(
+    registration: &mut AirdropRegistrationAccount,
+    nullifier_account: &NullifierAccount,
+    recipient: Pubkey,
+) -> Result<()> {
+    require!(
+        nullifier_account.is_used && nullifier_account.nullifier == registration.nullifier,
+        VouchError::InvalidNullifierAccount
+    );
+    require!(!nullifier_account.is_revoked, VouchError::CredentialRevoked);
+    require_diag!(
+        registration_owner(registration).is_none(),
+        RegistrationAlreadyBound,
+        "recipient={}",
+        registration.recipient
+    );
+    require_diag!(
+        nullifier_account.recipient == Pubkey::default(),
+        RecipientBackfillAvailable,
+        "credential_recipient={}",
+        nullifier_account.recipient
+    );
+    registration.recipient = recipient;
+    Ok(())
+}
+
+/// Reject a claimer other than the credential's recipient
+/// Credentials recorded before recipients were stored have no recipient and
+/// can't be claimed without a registration.
+fn check_credential_claimer(nullifier_account: &NullifierAccount, claimer: &Pubkey) -> Result<()> {
+    require_diag!(
+        nullifier_account.recipient == *claimer,
+        ClaimerNotOwner,
+        "recipient={} claimer={}",
+        nullifier_account.recipient,
+        claimer
+    );
+    Ok(())
+}
+
+/// Reject a claim once the other claim family has paid this nullifier out
+/// claim_airdrop_private keeps its own PrivateClaimMarker (it must not store a
+/// credential link), while the registration and merkle paths share ClaimStatus;
+/// each side requires the other's (campaign, nullifier) PDA to be uninitialized.
+fn require_unclaimed_marker(marker: &AccountInfo) -> Result<()> {
+    require_diag!(
+        marker.data_is_empty(),
+        AlreadyClaimed,
+        "marker={}",
+        marker.key()
+    );
+    Ok(())
+}
+
+/// Record a registration claim on the ClaimStatus shared with claim_airdrop_merkle
+/// A leaf on the marker means the merkle path already paid this nullifier out;
+/// later vesting installments add to the recorded amount.
+fn record_registration_claim(
+    claim_status: &mut ClaimStatus,
+    bump: u8,
+    registration: &AirdropRegistrationAccount,
+    claimer: Pubkey,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    require_diag!(
+        claim_status.leaf == [0u8; 32],
+        AlreadyClaimed,
+        "claimed_at={}",
+        claim_status.claimed_at
+    );
+    claim_status.campaign = registration.campaign;
+    claim_status.nullifier = registration.nullifier;
+    claim_status.claimer = claimer;
+    claim_status.amount = claim_status
+        .amount
+        .checked_add(amount)
+        .ok_or(VouchError::Overflow)?;
+    claim_status.claimed_at = now;
+    claim_status.bump = bump;
+    Ok(())
+}
+
+/// Reject a claimer other than the registration's owner (see registration_owner)
+/// Registrations with no known owner can't be claimed by any wallet.
+fn check_registration_claimer(
+    registration: &AirdropRegistrationAccount,
+    claimer: &Pubkey,
+) -> Result<()> {
+    let owner = registration_owner(registration);
+    require_diag!(
+        owner == Some(*claimer),
+        ClaimerNotOwner,
+        "owner={:?} claimer={}",
+        owner,
+        claimer
+    );
+    Ok(())
+}
+
+/// Reject a claim while another claim on the same registration is mid-transfer
+/// claim_in_progress is only set between a claim's effects and its transfers,
+/// so seeing it here means the call re-entered (e.g. from a transfer hook).
+fn check_claim_not_in_progress(registration: &AirdropRegistrationAccount) -> Result<()> {
+    require_diag!(
+        !registration.claim_in_progress,
+        ClaimInProgress,
+        "claim_in_progress=true"
+    );
+    Ok(())
+}
+
+/// Time after which a completed campaign's creator may reclaim its vault
+/// The later of the grace period past completed_at and any claim_deadline.
+fn airdrop_reclaim_at(campaign: &AirdropCampaign) -> i64 {
+    campaign
+        .completed_at
+        .saturating_add(AIRDROP_RECLAIM_GRACE_SECONDS)
+        .max(campaign.claim_deadline)
+}
+
+/// Reject a blocked recipient when config.enforce_recipient_blocklist is set
+/// The recipient's BlockedRecipient PDA must be passed while enforcement is on;
+/// it is unchecked so an absent entry (not blocked) can be passed too.
+fn check_recipient_not_blocked(
+    config: &ConfigAccount,
+    blocked_recipient: Option<&UncheckedAccount>,
+    recipient: &Pubkey,
+) -> Result<()> {
+    if !config.enforce_recipient_blocklist {
+        return Ok(());
+    }
+    let info = blocked_recipient.ok_or(VouchError::BlocklistAccountRequired)?;
+    require_diag!(
+        info.owner != &crate::ID || info.data_is_empty(),
+        RecipientBlocked,
+        "recipient={}",
+        recipient
+    );
+    Ok(())
+}
+
+/// UsedSignature PDA seed: a 64-byte signature exceeds the 32-byte seed limit, so it is hashed
+pub fn used_signature_seed(signature: &[u8; 64]) -> [u8; 32] {
+    hashv(&[signature.as_ref()]).to_bytes()
+}
+
+/// Record an accepted signature when config.track_used_signatures is set
+/// The signature's UsedSignature PDA must be passed while tracking is on; an
+/// existing record means the signature was already accepted, so it is rejected.
+#[allow(clippy::too_many_arguments)]
+fn record_used_signature<'info>(
+    config: &ConfigAccount,
+    used_signature: Option<&UncheckedAccount<'info>>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signature: &[u8; 64],
+    nullifier: &[u8; 32],
+    verifier: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    if !config.track_used_signatures {
+        return Ok(());
+    }
+    let info = used_signature.ok_or(VouchError::UsedSignatureAccountRequired)?;
+
+    let seed = used_signature_seed(signature);
+    let (pda, bump) = Pubkey::find_program_address(&[b"used_signature", seed.as_ref()], &crate::ID);
+    require!(info.key() == pda, VouchError::InvalidUsedSignatureAccount);
+    require!(
+        info.owner != &crate::ID || info.data_is_empty(),
+        VouchError::SignatureAlreadyUsed
+    );
+
+    create_pda_account(
+        payer,
+        info,
+        system_program,
+        8 + UsedSignature::INIT_SPACE,
+        &[b"used_signature", seed.as_ref(), &[bump]],
+    )?;
+    let record = UsedSignature {
+        signature: *signature,
+        nullifier: *nullifier,
+        verifier: *verifier,
+        used_at: now,
+        bump,
+    };
+    let mut data = info.try_borrow_mut_data()?;
+    record.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Commitment bytes to bind into the attestation message (commitment-binding mode)
+/// None while config.bind_commitment is off; otherwise the recipient's commitment
+/// account is required and must be owned by the recipient.
+fn bound_wallet_commitment(
+    config: &ConfigAccount,
+    commitment_account: Option<&Account<CommitmentAccount>>,
+    recipient: &Pubkey,
+) -> Result<Option<[u8; 32]>> {
+    if !config.bind_commitment {
+        return Ok(None);
+    }
+    let commitment_account = commitment_account.ok_or(VouchError::CommitmentRequired)?;
+    require_diag!(
+        commitment_account.owner == *recipient,
+        CommitmentMismatch,
+        "owner={} recipient={}",
+        commitment_account.owner,
+        recipient
+    );
+    Ok(Some(commitment_account.commitment))
+}
+
+/// Build the message a verifier signs to upgrade a credential's proof type
+/// Format: "vouch_credential_upgrade" (24 bytes) | nullifier (32 bytes) | old_proof_type (1 byte) | new_proof_type (1 byte) | epoch (8 bytes, big-endian)
+pub fn build_credential_upgrade_message(
+    nullifier: &[u8; 32],
+    old_proof_type_value: u8,
+    new_proof_type_value: u8,
+    epoch: u64,
+) -> [u8; 66] {
+    let mut message = [0u8; 66];
+    message[0..24].copy_from_slice(b"vouch_credential_upgrade");
+    message[24..56].copy_from_slice(nullifier);
+    message[56] = old_proof_type_value;
+    message[57] = new_proof_type_value;
+    message[58..66].copy_from_slice(&epoch.to_be_bytes());
+    message
+}
+
+/// Build the message a verifier signs to update a credential's score
+/// Format: "vouch_score_update" (18 bytes) | program_id (32 bytes) | nullifier (32 bytes) | new_score (8 bytes, big-endian) | timestamp (8 bytes, big-endian)
+pub fn build_score_update_message(
+    nullifier: &[u8; 32],
+    new_score: u64,
+    timestamp: i64,
+) -> [u8; 98] {
+    let mut message = [0u8; 98];
+    message[0..18].copy_from_slice(b"vouch_score_update");
+    message[18..50].copy_from_slice(crate::ID.as_ref());
+    message[50..82].copy_from_slice(nullifier);
+    message[82..90].copy_from_slice(&new_score.to_be_bytes());
+    message[90..98].copy_from_slice(&timestamp.to_be_bytes());
+    message
+}
+
+/// Build the message the governance key signs to import a verifier set
+/// Format: "vouch_verifier_set" (18 bytes) | nonce (8 bytes, big-endian) | sha256(verifier pubkeys) (32 bytes)
+pub fn build_verifier_set_message(nonce: u64, verifiers: &[Pubkey]) -> [u8; 58] {
+    let keys: Vec<&[u8]> = verifiers.iter().map(|v| v.as_ref()).collect();
+    let list_hash = hashv(&keys).to_bytes();
+
+    let mut message = [0u8; 58];
+    message[0..18].copy_from_slice(b"vouch_verifier_set");
+    message[18..26].copy_from_slice(&nonce.to_be_bytes());
+    message[26..58].copy_from_slice(&list_hash);
+    message
+}
+
+/// Create a program-owned PDA account, tolerating lamports pre-funded by a third party
+fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    target: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    use anchor_lang::solana_program::{program::invoke, program::invoke_signed, system_instruction};
+
+    let required = Rent::get()?.minimum_balance(space);
+    if target.lamports() == 0 {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                target.key,
+                required,
+                space as u64,
+                &crate::ID,
+            ),
+            &[payer.clone(), target.clone(), system_program.clone()],
+            &[seeds],
+        )?;
+    } else {
+        let top_up = required.saturating_sub(target.lamports());
+        if top_up > 0 {
+            invoke(
+                &system_instruction::transfer(payer.key, target.key, top_up),
+                &[payer.clone(), target.clone(), system_program.clone()],
+            )?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(target.key, space as u64),
+            &[target.clone(), system_program.clone()],
+            &[seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(target.key, &crate::ID),
+            &[target.clone(), system_program.clone()],
+            &[seeds],
+        )?;
+    }
+    Ok(())
+}
+
+/// Build the message a verifier signs to publish a merkle root
+/// Format: "vouch_verifier_root" (19 bytes) | program_id (32 bytes) | verifier (32 bytes) | merkle_root (32 bytes)
+pub fn build_verifier_root_message(verifier: &Pubkey, merkle_root: &[u8; 32]) -> [u8; 115] {
+    let mut message = [0u8; 115];
+    message[0..19].copy_from_slice(b"vouch_verifier_root");
+    message[19..51].copy_from_slice(crate::ID.as_ref());
+    message[51..83].copy_from_slice(verifier.as_ref());
+    message[83..115].copy_from_slice(merkle_root);
+    message
+}
+
+/// Compute the merkle leaf for an allowlisted credential
+/// Leaf = sha256("vouch_merkle_leaf" | proof_type (1 byte) | nullifier (32 bytes) | recipient (32 bytes) | expires_at (8 bytes, BE) [| bound_commitment (32 bytes)])
+/// The bound commitment is appended only in commitment-binding mode.
+pub fn build_merkle_attestation_leaf(
+    proof_type_value: u8,
+    nullifier: &[u8; 32],
+    recipient: &Pubkey,
+    expires_at: i64,
+    bound_commitment: Option<&[u8; 32]>,
+) -> [u8; 32] {
+    let proof_type_bytes = [proof_type_value];
+    let expires_at_bytes = expires_at.to_be_bytes();
+    let mut parts: Vec<&[u8]> = vec![
+        b"vouch_merkle_leaf".as_ref(),
+        &proof_type_bytes,
+        nullifier,
+        recipient.as_ref(),
+        &expires_at_bytes,
+    ];
+    if let Some(commitment) = bound_commitment {
+        parts.push(commitment);
+    }
+    hashv(&parts).to_bytes()
+}
+
+/// Build the message a verifier signs to authorize claim_airdrop_private
+/// Format: "vouch_private_claim_v1" (22 bytes) | program_id (32 bytes) | campaign_id (32 bytes) | nullifier (32 bytes) | amount (8 bytes, BE) | claimer (32 bytes) | expires_at (8 bytes, BE)
+/// Binding the claimer keeps an observed attestation from being replayed to
+/// another wallet.
+pub fn build_private_claim_message(
+    campaign_id: &[u8; 32],
+    nullifier: &[u8; 32],
+    amount: u64,
+    claimer: &Pubkey,
+    expires_at: i64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(22 + 32 + 32 + 32 + 8 + 32 + 8);
+    message.extend_from_slice(b"vouch_private_claim_v1");
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(campaign_id);
+    message.extend_from_slice(nullifier);
+    message.extend_from_slice(&amount.to_be_bytes());
+    message.extend_from_slice(claimer.as_ref());
+    message.extend_from_slice(&expires_at.to_be_bytes());
+    message
+}
+
+/// Compute the merkle leaf for an airdrop claim (see claim_airdrop_merkle)
+/// Leaf = sha256("vouch_airdrop_leaf" | nullifier (32 bytes) | amount (8 bytes, BE) | proof_type (1 byte))
+pub fn build_airdrop_claim_leaf(nullifier: &[u8; 32], amount: u64, proof_type_value: u8) -> [u8; 32] {
+    hashv(&[
+        b"vouch_airdrop_leaf".as_ref(),
+        nullifier,
+        &amount.to_be_bytes(),
+        &[proof_type_value],
+    ])
+    .to_bytes()
+}
+
+/// Build the descriptor a verifier signs to open an attestation batch
+/// Format: "vouch_attestation_batch" (23) | verifier (32) | batch_id (32) |
+/// entries_root (32) | entry_count (2, BE) | expires_at (8, BE) = 129 bytes
+pub fn build_attestation_batch_message(
+    verifier: &Pubkey,
+    batch_id: &[u8; 32],
+    entries_root: &[u8; 32],
+    entry_count: u16,
+    expires_at: i64,
+) -> [u8; 129] {
+    let mut message = [0u8; 129];
+    message[0..23].copy_from_slice(b"vouch_attestation_batch");
+    message[23..55].copy_from_slice(verifier.as_ref());
+    message[55..87].copy_from_slice(batch_id);
+    message[87..119].copy_from_slice(entries_root);
+    message[119..121].copy_from_slice(&entry_count.to_be_bytes());
+    message[121..129].copy_from_slice(&expires_at.to_be_bytes());
+    message
+}
+
+/// Build a batch entry leaf: sha256("vouch_batch_leaf" | proof_type | nullifier | recipient [| bound_commitment])
+/// The bound commitment is appended only in commitment-binding mode, as in
+/// build_merkle_attestation_leaf.
+pub fn build_batch_attestation_leaf(
+    proof_type_value: u8,
+    nullifier: &[u8; 32],
+    recipient: &Pubkey,
+    bound_commitment: Option<&[u8; 32]>,
+) -> [u8; 32] {
+    let proof_type_bytes = [proof_type_value];
+    let mut parts: Vec<&[u8]> = vec![
+        b"vouch_batch_leaf".as_ref(),
+        &proof_type_bytes,
+        nullifier,
+        recipient.as_ref(),
+    ];
+    if let Some(commitment) = bound_commitment {
+        parts.push(commitment);
+    }
+    hashv(&parts).to_bytes()
+}
+
+/// Decode a base58 (Bitcoin alphabet) string that encodes exactly 32 bytes
+/// Returns None for characters outside the alphabet or any other decoded length.
+pub fn decode_base58_32(encoded: &str) -> Option<[u8; 32]> {
+    const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    let mut decoded = [0u8; 32];
+    for c in encoded.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in decoded.iter_mut().rev() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        // Value needs more than 32 bytes
+        if carry != 0 {
+            return None;
+        }
+    }
+
+    // Each leading '1' encodes one leading zero byte; any other zero prefix
+    // means the value decodes to fewer than 32 bytes
+    let leading_ones = encoded.bytes().take_while(|&c| c == b'1').count();
+    let leading_zeros = decoded.iter().take_while(|&&b| b == 0).count();
+    if leading_ones != leading_zeros {
+        return None;
+    }
+    Some(decoded)
+}
+
+/// Check a ShadowWire address is 32-44 base58 characters decoding to 32 bytes
+pub fn is_valid_shadow_wire_address(shadow_wire_address: &str) -> bool {
+    (32..=44).contains(&shadow_wire_address.len())
+        && decode_base58_32(shadow_wire_address).is_some()
+}
+
+/// Reject a malformed ShadowWire address (see is_valid_shadow_wire_address)
+pub fn validate_shadow_wire_address(shadow_wire_address: &str) -> Result<()> {
+    require_diag!(
+        is_valid_shadow_wire_address(shadow_wire_address),
+        InvalidShadowWireAddress,
+        "len={}",
+        shadow_wire_address.len()
+    );
+    Ok(())
+}
+
+/// Append a registration to the registrant's index, if they opted in
+/// A full index never blocks registration; the miss is counted in dropped_entries.
+fn record_wallet_registration(
+    registration_index: Option<&mut Account<WalletRegistrationIndex>>,
+    campaign: Pubkey,
+    registration: Pubkey,
+) -> Result<()> {
+    let registration_index = match registration_index {
+        Some(registration_index) => registration_index,
+        None => return Ok(()),
+    };
+
+    if registration_index.entries.len() < MAX_WALLET_INDEX_ENTRIES {
+        registration_index.entries.push(WalletRegistrationEntry {
+            campaign,
+            registration,
+        });
+    } else {
+        registration_index.dropped_entries = registration_index
+            .dropped_entries
+            .checked_add(1)
+            .ok_or(VouchError::Overflow)?;
+    }
+    Ok(())
+}
+
+/// Compute the canonical credential ID for an issued credential
+/// ID = sha256("vouch_credential_id" | nullifier (32 bytes) | proof_type (1 byte) | verifier (32 bytes))
+pub fn build_credential_id(nullifier: &[u8; 32], proof_type_value: u8, verifier: &Pubkey) -> [u8; 32] {
+    hashv(&[
+        b"vouch_credential_id".as_ref(),
+        nullifier,
+        &[proof_type_value],
+        verifier.as_ref(),
+    ])
+    .to_bytes()
+}
+
+/// Credential reference for a registry proof type
+/// The 2-byte ID keeps it distinct from built-in build_credential_id values.
+pub fn build_registered_credential_id(
+    nullifier: &[u8; 32],
+    proof_type_id: u16,
+    verifier: &Pubkey,
+) -> [u8; 32] {
+    hashv(&[
+        b"vouch_credential_id".as_ref(),
+        nullifier,
+        &proof_type_id.to_be_bytes(),
+        verifier.as_ref(),
+    ])
+    .to_bytes()
+}
+
+/// Registry ID of a nullifier's credential
+/// Built-in types map to their reserved IDs (DeveloperReputation = 1,
+/// WhaleTrading = 2); Unset stays 0.
+pub fn effective_proof_type_id(nullifier_account: &NullifierAccount) -> u16 {
+    if nullifier_account.proof_type_id != 0 {
+        nullifier_account.proof_type_id
+    } else {
+        nullifier_account.proof_type as u16
+    }
+}
+
+/// Look up an active registry proof type
+fn registered_proof_type(
+    registry: &ProofTypeRegistry,
+    proof_type_id: u16,
+) -> Result<&RegisteredProofType> {
+    let entry = registry
+        .entries
+        .iter()
+        .find(|entry| entry.id == proof_type_id)
+        .ok_or(VouchError::ProofTypeNotRegistered)?;
+    require!(entry.is_active, VouchError::ProofTypeNotRegistered);
+    Ok(entry)
+}
+
+/// Verify a merkle inclusion proof using sorted-pair sha256 hashing
+/// Sibling order is implied by byte ordering, so no direction bits are needed.
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: &[u8; 32]) -> bool {
+    let mut computed = *leaf;
+    for sibling in proof {
+        computed = if computed <= *sibling {
+            hashv(&[&computed, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &computed]).to_bytes()
+        };
+    }
+    computed == *root
+}
+
+/// Deserialize an SPL token account passed via remaining_accounts
+fn load_token_account(info: &AccountInfo) -> Result<TokenAccount> {
+    require!(info.owner == &token::ID, VouchError::InvalidAssetAccounts);
+    let data = info.try_borrow_data()?;
+    TokenAccount::try_deserialize(&mut &data[..])
+}
+
+/// Count a registration against its ShadowWire address when the campaign caps reuse
+/// The counter PDA is keyed by sha256(address) and created on first use. It also
+/// stores the full address, so a hash collision can never share a counter.
+fn enforce_shadow_wire_reuse_limit<'info>(
+    campaign: &Account<'info, AirdropCampaign>,
+    usage_info: Option<&AccountInfo<'info>>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    shadow_wire_address: &str,
+) -> Result<()> {
+    if campaign.max_registrations_per_shadow_wire == 0 {
+        return Ok(());
+    }
+    let usage_info = usage_info.ok_or(VouchError::ShadowWireUsageRequired)?;
+
+    let campaign_key = campaign.key();
+    let address_hash = hashv(&[shadow_wire_address.as_bytes()]).to_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"shadow_wire_usage", campaign_key.as_ref(), address_hash.as_ref()],
+        &crate::ID,
+    );
+    require!(expected == *usage_info.key, VouchError::ShadowWireUsageRequired);
+
+    let mut usage = if usage_info.owner == &crate::ID {
+        let data = usage_info.try_borrow_data()?;
+        ShadowWireUsage::try_deserialize(&mut &data[..])?
+    } else {
+        create_pda_account(
+            payer,
+            usage_info,
+            system_program,
+            8 + ShadowWireUsage::INIT_SPACE,
+            &[
+                b"shadow_wire_usage",
+                campaign_key.as_ref(),
+                address_hash.as_ref(),
+                &[bump],
+            ],
+        )?;
+        ShadowWireUsage {
+            campaign: campaign_key,
+            address_hash,
+            shadow_wire_address: shadow_wire_address.to_string(),
+            registrations: 0,
+            bump,
+        }
+    };
+
+    require!(
+        usage.campaign == campaign_key && usage.shadow_wire_address == shadow_wire_address,
+        VouchError::InvalidShadowWireAddress
+    );
+    require!(
+        usage.registrations < campaign.max_registrations_per_shadow_wire,
+        VouchError::ShadowWireReuseLimitExceeded
+    );
+    usage.registrations = usage.registrations.checked_add(1).ok_or(VouchError::Overflow)?;
+
+    let mut data = usage_info.try_borrow_mut_data()?;
+    usage.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Release a registration's count on the ShadowWire address it is leaving
+/// Registrations made while the cap was off were never counted, so a counter
+/// that doesn't exist is skipped and the count never drops below zero. A
+/// counter that reaches zero is closed and its rent sent to rent_recipient.
+fn release_shadow_wire_usage<'info>(
+    campaign: &Account<'info, AirdropCampaign>,
+    usage_info: Option<&AccountInfo<'info>>,
+    rent_recipient: &AccountInfo<'info>,
+    shadow_wire_address: &str,
+) -> Result<()> {
+    let usage_info = match usage_info {
+        Some(usage_info) => usage_info,
+        None => {
+            require!(
+                campaign.max_registrations_per_shadow_wire == 0,
+                VouchError::ShadowWireUsageRequired
+            );
+            return Ok(());
+        }
+    };
+
+    let campaign_key = campaign.key();
+    let address_hash = hashv(&[shadow_wire_address.as_bytes()]).to_bytes();
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"shadow_wire_usage", campaign_key.as_ref(), address_hash.as_ref()],
+        &crate::ID,
+    );
+    require!(expected == *usage_info.key, VouchError::ShadowWireUsageRequired);
+    if usage_info.owner != &crate::ID {
+        return Ok(());
+    }
+
+    let mut usage = {
+        let data = usage_info.try_borrow_data()?;
+        ShadowWireUsage::try_deserialize(&mut &data[..])?
+    };
+    require!(
+        usage.campaign == campaign_key && usage.shadow_wire_address == shadow_wire_address,
+        VouchError::InvalidShadowWireAddress
+    );
+    usage.registrations = usage.registrations.saturating_sub(1);
+
+    if usage.registrations > 0 {
+        let mut data = usage_info.try_borrow_mut_data()?;
+        usage.try_serialize(&mut &mut data[..])?;
+        return Ok(());
+    }
+
+    // Last registration left: close the counter
+    let rent_lamports = usage_info.lamports();
+    **usage_info.try_borrow_mut_lamports()? = 0;
+    **rent_recipient.try_borrow_mut_lamports()? = rent_recipient
+        .lamports()
+        .checked_add(rent_lamports)
+        .ok_or(VouchError::Overflow)?;
+    usage_info.assign(&System::id());
+    usage_info.resize(0)?;
+    Ok(())
+}
+
+/// Append one entry to the campaign's activity ring buffer when the feed is enabled
+/// Writes at most one entry per call; the oldest entry is overwritten once full.
+fn record_campaign_activity(
+    campaign: &AirdropCampaign,
+    activity_log: Option<&mut Account<CampaignActivityLog>>,
+    kind: CampaignActivityKind,
+    subject: [u8; 32],
+    amount: u64,
+    timestamp: i64,
+) -> Result<()> {
+    if !campaign.activity_log_enabled {
+        return Ok(());
+    }
+    let activity_log = activity_log.ok_or(VouchError::ActivityLogRequired)?;
+
+    let head = activity_log.head as usize % CAMPAIGN_ACTIVITY_LOG_SIZE;
+    activity_log.entries[head] = CampaignActivityEntry {
+        kind,
+        subject,
+        amount,
+        timestamp,
+    };
+    activity_log.head = ((head + 1) % CAMPAIGN_ACTIVITY_LOG_SIZE) as u8;
+    if (activity_log.len as usize) < CAMPAIGN_ACTIVITY_LOG_SIZE {
+        activity_log.len += 1;
+    }
+    activity_log.total_entries = activity_log.total_entries.saturating_add(1);
+    Ok(())
+}
+
+/// Add a campaign vault inflow (funded) or outflow (claimed or reclaimed) to ProtocolStats
+/// No-op until TVL tracking is enabled; once enabled the stats account is required.
+fn record_tvl_flow(
+    config: &ConfigAccount,
+    protocol_stats: Option<&mut Account<ProtocolStats>>,
+    mint: Pubkey,
+    funded: u64,
+    claimed: u64,
+) -> Result<()> {
+    if !config.protocol_stats_enabled {
+        return Ok(());
+    }
+    let stats = protocol_stats.ok_or(VouchError::ProtocolStatsRequired)?;
+
+    let index = match stats.tracked_mints.iter().position(|entry| entry.mint == mint) {
+        Some(index) => index,
+        None if stats.tracked_mints.len() < MAX_TVL_TRACKED_MINTS => {
+            stats.tracked_mints.push(TrackedMint {
+                mint,
+                total_funded: 0,
+                total_claimed: 0,
+            });
+            stats.tracked_mints.len() - 1
+        }
+        None => {
+            stats.untracked_flows = stats.untracked_flows.saturating_add(1);
+            return Ok(());
+        }
+    };
+
+    let entry = &mut stats.tracked_mints[index];
+    entry.total_funded = entry
+        .total_funded
+        .checked_add(funded)
+        .ok_or(VouchError::Overflow)?;
+    entry.total_claimed = entry
+        .total_claimed
+        .checked_add(claimed)
+        .ok_or(VouchError::Overflow)?;
+    Ok(())
+}
+
+/// Validate the reusable part of a campaign's parameters
+fn validate_campaign_params(params: &CampaignParams) -> Result<()> {
+    // At least base amount must be set (tiered model requires base)
+    require!(params.base_amount > 0, VouchError::InvalidAmount);
+    require!(
+        params.campaign_claim_fee_bps <= MAX_CAMPAIGN_CLAIM_FEE_BPS,
+        VouchError::InvalidClaimFee
+    );
+    require!(
+        params.campaign_claim_fee_bps == 0 || params.claim_fee_account != Pubkey::default(),
+        VouchError::InvalidClaimFee
+    );
+    Ok(())
+}
+
+/// Validate a campaign's per-proof-type bonus table
+/// IDs must be unique and name a credential (0 is open registration).
+fn validate_proof_type_bonuses(proof_type_bonuses: &[ProofTypeBonus]) -> Result<()> {
+    require!(
+        proof_type_bonuses.len() <= MAX_CAMPAIGN_PROOF_TYPE_BONUSES,
+        VouchError::TooManyProofTypeBonuses
+    );
+    for (i, entry) in proof_type_bonuses.iter().enumerate() {
+        require!(entry.proof_type_id != 0, VouchError::InvalidProofType);
+        require!(
+            !proof_type_bonuses[..i]
+                .iter()
+                .any(|other| other.proof_type_id == entry.proof_type_id),
+            VouchError::DuplicateProofTypeBonus
+        );
+    }
+    Ok(())
+}
+
+/// Check a template's registration window and claim deadline offset
+fn validate_template_windows(
+    registration_window_seconds: i64,
+    claim_window_seconds: i64,
+) -> Result<()> {
+    require!(registration_window_seconds > 0, VouchError::InvalidDeadline);
+    require!(claim_window_seconds >= 0, VouchError::InvalidDeadline);
+    Ok(())
+}
+
+/// Initialize a new campaign (shared by create_airdrop_campaign and templates)
+/// The caller sets campaign.bump. claim_deadline 0 means no deadline (claims
+/// stay open and registrations are never reapable); any other value must fall
+/// after registration_deadline.
+#[allow(clippy::too_many_arguments)]
+fn init_airdrop_campaign(
+    config: &mut Account<ConfigAccount>,
+    campaign: &mut Account<AirdropCampaign>,
+    creator: Pubkey,
+    campaign_id: [u8; 32],
+    name: String,
+    params: &CampaignParams,
+    proof_type_bonuses: Vec<ProofTypeBonus>,
+    registration_deadline: i64,
+    claim_deadline: i64,
+) -> Result<()> {
+    require_campaigns_unpaused(config)?;
+    let now = effective_now(config)?;
+    require!(name.len() <= 64, VouchError::NameTooLong);
+    validate_proof_type_bonuses(&proof_type_bonuses)?;
+    require!(registration_deadline > now, VouchError::InvalidDeadline);
+    require!(
+        claim_deadline == 0 || claim_deadline > registration_deadline,
+        VouchError::InvalidDeadline
+    );
+    validate_campaign_params(params)?;
+
+    // Enforce the protocol-wide cap on active campaigns (0 = unlimited)
+    require!(
+        config.max_concurrent_open_campaigns == 0
+            || config.active_campaign_count < config.max_concurrent_open_campaigns,
+        VouchError::TooManyActiveCampaigns
+    );
+    config.active_campaign_count = config
+        .active_campaign_count
+        .checked_add(1)
+        .ok_or(VouchError::Overflow)?;
+
+    campaign.campaign_id = campaign_id;
+    campaign.creator = creator;
+    campaign.name = name;
+    campaign.token_mint = params.token_mint;
+    campaign.base_amount = params.base_amount;
+    campaign.dev_bonus = params.dev_bonus;
+    campaign.whale_bonus = params.whale_bonus;
+    campaign.registration_deadline = registration_deadline;
+    campaign.campaign_claim_fee_bps = params.campaign_claim_fee_bps;
+    campaign.claim_fee_account = params.claim_fee_account;
+    campaign.distribution_relay = Pubkey::default();
+    campaign.min_hold_seconds = 0;
+    campaign.early_claim_penalty_bps = 0;
+    campaign.deadline_bump_per_fund = 0;
+    campaign.max_deadline_extension = 0;
+    campaign.total_deadline_extension = 0;
+    campaign.status = CampaignStatus::Open;
+    campaign.total_registrations = 0;
+    campaign.open_registrations = 0;
+    campaign.dev_registrations = 0;
+    campaign.whale_registrations = 0;
+    campaign.registry_registrations = 0;
+    campaign.created_at = now;
+    campaign.vault_balance = 0;
+    campaign.total_claimed = 0;
+    campaign.total_allocated = 0;
+    campaign.total_claimed_amount = 0;
+    campaign.activity_log_enabled = false;
+    campaign.require_claim_signature = false;
+    campaign.claim_signer = Pubkey::default();
+    campaign.max_registrations_per_shadow_wire = 0;
+    campaign.required_verifier = None;
+    campaign.claim_deadline = claim_deadline;
+    campaign.allowed_proof_types = 0;
+    campaign.decay_max_bonus = 0;
+    campaign.decay_min_bonus = 0;
+    campaign.vesting_start = 0;
+    campaign.vesting_duration = 0;
+    campaign.cliff_seconds = 0;
+    campaign.merkle_root = [0u8; 32];
+    campaign.is_paused = false;
+    campaign.proof_type_bonuses = proof_type_bonuses;
+    campaign.open_registration_disabled = false;
+    campaign.private_claims_enabled = false;
+    campaign.require_credential_owner = false;
+    campaign.version = CAMPAIGN_ACCOUNT_VERSION;
+
+    emit!(AirdropCampaignCreated {
+        campaign_id,
+        creator,
+        name: campaign.name.clone(),
+        token_mint: params.token_mint,
+        base_amount: params.base_amount,
+        dev_bonus: params.dev_bonus,
+        whale_bonus: params.whale_bonus,
+        proof_type_bonuses: campaign.proof_type_bonuses.clone(),
+        registration_deadline,
+        claim_deadline,
+        campaign_claim_fee_bps: params.campaign_claim_fee_bps,
+        timestamp: campaign.created_at,
+    });
+
+    Ok(())
+}
+
+/// Copy a pre-versioning campaign's fields into a zero-initialized AirdropCampaign
+fn apply_legacy_campaign(campaign: &mut AirdropCampaign, legacy: LegacyAirdropCampaign) {
+    campaign.campaign_id = legacy.campaign_id;
+    campaign.creator = legacy.creator;
+    campaign.name = legacy.name;
+    campaign.token_mint = legacy.token_mint;
+    campaign.base_amount = legacy.base_amount;
+    campaign.dev_bonus = legacy.dev_bonus;
+    campaign.whale_bonus = legacy.whale_bonus;
+    campaign.registration_deadline = legacy.registration_deadline;
+    campaign.status = legacy.status;
+    campaign.total_registrations = legacy.total_registrations as u64;
+    campaign.open_registrations = legacy.open_registrations as u64;
+    campaign.dev_registrations = legacy.dev_registrations as u64;
+    campaign.whale_registrations = legacy.whale_registrations as u64;
+    campaign.created_at = legacy.created_at;
+    campaign.completed_at = legacy.completed_at;
+    campaign.vault_balance = legacy.vault_balance;
+    campaign.total_claimed = legacy.total_claimed as u64;
+    campaign.bump = legacy.bump;
+}
+
+/// Whether a new claim deadline leaves registrants at least as long to claim
+/// (0 = no deadline, the longest possible window)
+fn extends_claim_deadline(current: i64, new: i64) -> bool {
+    new == 0 || (current != 0 && new >= current)
+}
+
+/// Whether a registration of the given tier may join the campaign
+/// (ProofType::Unset = open registration; allowed_proof_types 0 = everyone)
+fn proof_type_eligible(campaign: &AirdropCampaign, proof_type: ProofType) -> bool {
+    campaign.allowed_proof_types == 0
+        || campaign.allowed_proof_types & proof_type_bit(proof_type as u8) != 0
+}
+
+/// Bonus the campaign's proof_type_bonuses table assigns to a proof type ID
+fn campaign_proof_type_bonus(campaign: &AirdropCampaign, proof_type_id: u16) -> Option<u64> {
+    campaign
+        .proof_type_bonuses
+        .iter()
+        .find(|entry| entry.proof_type_id == proof_type_id)
+        .map(|entry| entry.bonus_amount)
+}
+
+/// Claim amount for a registration of the given tier (base + tier bonus)
+/// A proof_type_bonuses entry takes precedence over dev_bonus/whale_bonus.
+/// This is the single source of tier amounts for claims and off-chain callers;
+/// registrations that fixed an allocation claim that instead (see
+/// registration_claim_amount).
+pub fn compute_claim_amount(campaign: &AirdropCampaign, proof_type: ProofType) -> Result<u64> {
+    let bonus = match campaign_proof_type_bonus(campaign, proof_type as u16) {
+        Some(bonus) => bonus,
+        None => match proof_type {
+            ProofType::DeveloperReputation => campaign.dev_bonus,
+            ProofType::WhaleTrading => campaign.whale_bonus,
+            ProofType::Unset => 0,
+        },
+    };
+    campaign
+        .base_amount
+        .checked_add(bonus)
+        .ok_or(VouchError::Overflow.into())
+}
+
+/// Early-bird bonus for a registration made at `registered_at`
+/// Linear from decay_max_bonus at created_at down to decay_min_bonus at the
+/// registration deadline (clamped at both ends).
+pub fn decayed_bonus(campaign: &AirdropCampaign, registered_at: i64) -> u64 {
+    let window = campaign
+        .registration_deadline
+        .saturating_sub(campaign.created_at);
+    if window <= 0 {
+        return campaign.decay_min_bonus;
+    }
+    let elapsed = registered_at
+        .saturating_sub(campaign.created_at)
+        .clamp(0, window);
+    let spread = campaign
+        .decay_max_bonus
+        .saturating_sub(campaign.decay_min_bonus);
+    let decay = (spread as u128) * (elapsed as u128) / (window as u128);
+    campaign.decay_max_bonus - decay as u64
+}
+
+/// Full entitlement of a registration (before fees, penalties and vesting)
+/// The allocation fixed at registration; older registrations derive it from
+/// the proof type.
+pub fn registration_claim_amount(
+    campaign: &AirdropCampaign,
+    registration: &AirdropRegistrationAccount,
+) -> Result<u64> {
+    if registration.allocation > 0 {
+        Ok(registration.allocation)
+    } else {
+        compute_claim_amount(campaign, registration.proof_type)
+    }
+}
+
+/// Allocation fixed on a registration: base + tier bonus + early-bird bonus
+fn registration_allocation(
+    campaign: &AirdropCampaign,
+    proof_type: ProofType,
+    registered_at: i64,
+) -> Result<u64> {
+    compute_claim_amount(campaign, proof_type)?
+        .checked_add(decayed_bonus(campaign, registered_at))
+        .ok_or(VouchError::Overflow.into())
+}
+
+/// Timestamp at which a campaign's vesting completes (0 without vesting)
+pub fn vesting_end(campaign: &AirdropCampaign) -> i64 {
+    if campaign.vesting_duration == 0 {
+        return 0;
+    }
+    campaign
+        .vesting_start
+        .saturating_add(campaign.vesting_duration)
+}
+
+/// Portion of `entitlement` vested at `now` under the campaign's linear schedule
+/// The full entitlement without vesting; nothing before vesting_start.
+pub fn vested_amount(campaign: &AirdropCampaign, entitlement: u64, now: i64) -> Result<u64> {
+    if campaign.vesting_duration == 0 {
+        return Ok(entitlement);
+    }
+    let elapsed = now
+        .saturating_sub(campaign.vesting_start)
+        .clamp(0, campaign.vesting_duration);
+    let vested = (entitlement as u128)
+        .checked_mul(elapsed as u128)
+        .ok_or(VouchError::Overflow)?
+        / campaign.vesting_duration as u128;
+    u64::try_from(vested).map_err(|_| VouchError::Overflow.into())
+}
+
+/// Hold period enforced at claim time: the larger of the campaign and global settings
+pub fn effective_min_hold(campaign: &AirdropCampaign, config: &ConfigAccount) -> i64 {
+    campaign.min_hold_seconds.max(config.global_min_hold_seconds)
+}
+
+/// Compute `amount * bps / 10_000`, floored
+pub fn compute_bps(amount: u64, bps: u16) -> Result<u64> {
+    let value = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(VouchError::Overflow)?
+        / BPS_DENOMINATOR as u128;
+    u64::try_from(value).map_err(|_| VouchError::Overflow.into())
+}
+
+/// Split a gross claim amount into (campaign_fee, net_amount)
+/// The fee is floored so rounding always favors the claimer. Any protocol-level
+/// fee must be deducted from the gross amount before calling this, so the
+/// campaign fee is computed on what remains.
+pub fn split_campaign_claim_fee(gross_amount: u64, fee_bps: u16) -> Result<(u64, u64)> {
+    require!(
+        fee_bps <= MAX_CAMPAIGN_CLAIM_FEE_BPS,
+        VouchError::InvalidClaimFee
+    );
+    let fee = compute_bps(gross_amount, fee_bps)?;
+    let net = gross_amount.checked_sub(fee).ok_or(VouchError::Overflow)?;
+    Ok((fee, net))
+}
+
+/// Build the distribution receipt message that the ShadowWire relay signs
+/// Format: "vouch_distribution_receipt" | campaign_id (32 bytes) | nullifier (32 bytes) | amount (8 bytes, big-endian) | address_len (1 byte) | shadow_wire_address
+pub fn build_distribution_receipt_message(
+    campaign_id: &[u8; 32],
+    nullifier: &[u8; 32],
+    amount: u64,
+    shadow_wire_address: &str,
+) -> Vec<u8> {
+    let address = shadow_wire_address.as_bytes();
+    let mut message = Vec::with_capacity(26 + 32 + 32 + 8 + 1 + address.len());
+    // Domain separator: "vouch_distribution_receipt" (26 bytes)
+    message.extend_from_slice(b"vouch_distribution_receipt");
+    message.extend_from_slice(campaign_id);
+    message.extend_from_slice(nullifier);
+    message.extend_from_slice(&amount.to_be_bytes());
+    message.push(address.len() as u8);
+    message.extend_from_slice(address);
+    message
+}
+
+/// Build the message a campaign's claim signer signs to authorize a claim
+/// Format: "vouch_claim_authorization" (25 bytes) | campaign_id (32 bytes) | nullifier (32 bytes) | amount (8 bytes, big-endian)
+/// amount is the registration's full tier allocation, before any early-claim penalty or fee.
+pub fn build_claim_authorization_message(
+    campaign_id: &[u8; 32],
+    nullifier: &[u8; 32],
+    amount: u64,
+) -> [u8; 97] {
+    let mut message = [0u8; 97];
+    message[0..25].copy_from_slice(b"vouch_claim_authorization");
+    message[25..57].copy_from_slice(campaign_id);
+    message[57..89].copy_from_slice(nullifier);
+    message[89..97].copy_from_slice(&amount.to_be_bytes());
+    message
+}
+
+/// Build the attestation message that the verifier signs (v1 - deprecated)
+/// Format: "vouch_attestation" | proof_type (1 byte) | nullifier (32 bytes) | attestation_hash (32 bytes)
+#[allow(dead_code)]
+pub fn build_attestation_message(
+    proof_type_value: u8,
+    nullifier: &[u8; 32],
+    attestation_hash: &[u8; 32],
+) -> [u8; 82] {
+    let mut message = [0u8; 82];
+    // Domain separator: "vouch_attestation" (17 bytes)
+    message[0..17].copy_from_slice(b"vouch_attestation");
+    // Proof type (1 byte)
+    message[17] = proof_type_value;
+    // Nullifier (32 bytes)
+    message[18..50].copy_from_slice(nullifier);
+    // Attestation hash (32 bytes)
+    message[50..82].copy_from_slice(attestation_hash);
+    message
+}
+
+/// Domain separator for v3 attestation messages, distinct per proof type so a
+/// signature for one credential category can never verify as another
+pub fn attestation_domain_separator(proof_type_value: u8) -> Result<&'static [u8]> {
+    match proof_type_value {
+        1 => Ok(&b"vouch_dev_attestation_v3"[..]),
+        2 => Ok(&b"vouch_whale_attestation_v3"[..]),
+        _ => Err(VouchError::InvalidProofType.into()),
+    }
+}
+
+/// Build the attestation message that the verifier signs (v3 - per-proof-type domain)
+/// Format: domain (per proof type, see attestation_domain_separator) | program_id (32 bytes) | proof_type (1 byte) | nullifier (32 bytes) | epoch (8 bytes) | data_hash (32 bytes) | attestation_hash (32 bytes) | proof_generated_at (8 bytes) | expires_at (8 bytes) | has_proof_commitment (1 byte) [| proof_commitment (32 bytes)] | has_wallet_commitment (1 byte) [| wallet_commitment (32 bytes)]
+/// The proof commitment is present only when one is supplied; the wallet commitment
+/// (CommitmentAccount.commitment) only in commitment-binding mode. Each is preceded
+/// by a presence byte (0 or 1) so one can't be read as the other.
+/// The program ID binds the signature to this deployment, so an attestation
+/// signed for devnet or a forked deployment with the same verifier key can't
+/// be replayed here.
+#[allow(clippy::too_many_arguments)]
+pub fn build_attestation_message_v3(
+    proof_type_value: u8,
+    nullifier: &[u8; 32],
+    epoch: u64,
+    data_hash: &[u8; 32],
+    attestation_hash: &[u8; 32],
+    proof_generated_at: i64,
+    expires_at: i64,
+    proof_commitment: Option<&[u8; 32]>,
+    wallet_commitment: Option<&[u8; 32]>,
+) -> Result<Vec<u8>> {
+    let domain = attestation_domain_separator(proof_type_value)?;
+    let mut message =
+        Vec::with_capacity(domain.len() + 32 + 1 + 32 + 8 + 32 + 32 + 8 + 8 + 1 + 32 + 1 + 32);
+    message.extend_from_slice(domain);
+    message.extend_from_slice(crate::ID.as_ref());
+    message.push(proof_type_value);
+    message.extend_from_slice(nullifier);
+    // Epoch (8 bytes, big-endian)
+    message.extend_from_slice(&epoch.to_be_bytes());
+    message.extend_from_slice(data_hash);
+    message.extend_from_slice(attestation_hash);
+    // Proof generation time (8 bytes, big-endian unix seconds)
+    message.extend_from_slice(&proof_generated_at.to_be_bytes());
+    // Attestation expiry (8 bytes, big-endian unix seconds)
+    message.extend_from_slice(&expires_at.to_be_bytes());
+    for commitment in [proof_commitment, wallet_commitment] {
+        message.push(commitment.is_some() as u8);
+        if let Some(commitment) = commitment {
+            message.extend_from_slice(commitment);
+        }
+    }
+    Ok(message)
+}
+
+/// Build the attestation message a verifier signs for a registry proof type
+/// Format: "vouch_registered_attestation_v3" (31 bytes) | program_id (32 bytes) | proof_type_id (2 bytes, big-endian) | nullifier (32 bytes) | epoch (8 bytes) | data_hash (32 bytes) | attestation_hash (32 bytes) | proof_generated_at (8 bytes) | expires_at (8 bytes) [| wallet_commitment (32 bytes)]
+/// The domain differs from every built-in v3 domain, so built-in signatures
+/// can't verify as registry attestations or vice versa.
+#[allow(clippy::too_many_arguments)]
+pub fn build_registered_attestation_message(
+    proof_type_id: u16,
+    nullifier: &[u8; 32],
+    epoch: u64,
+    data_hash: &[u8; 32],
+    attestation_hash: &[u8; 32],
+    proof_generated_at: i64,
+    expires_at: i64,
+    wallet_commitment: Option<&[u8; 32]>,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(31 + 32 + 2 + 32 + 8 + 32 + 32 + 8 + 8 + 32);
+    message.extend_from_slice(b"vouch_registered_attestation_v3");
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(&proof_type_id.to_be_bytes());
+    message.extend_from_slice(nullifier);
+    message.extend_from_slice(&epoch.to_be_bytes());
+    message.extend_from_slice(data_hash);
+    message.extend_from_slice(attestation_hash);
+    message.extend_from_slice(&proof_generated_at.to_be_bytes());
+    message.extend_from_slice(&expires_at.to_be_bytes());
+    if let Some(commitment) = wallet_commitment {
+        message.extend_from_slice(commitment);
+    }
+    message
+}
+
+/// Build the attestation message that the verifier signs (v2 - deprecated, with epoch and data_hash)
+/// Format: "vouch_attestation_v2" | proof_type (1 byte) | nullifier (32 bytes) | epoch (8 bytes) | data_hash (32 bytes) | attestation_hash (32 bytes)
+/// Total: 20 + 1 + 32 + 8 + 32 + 32 = 125 bytes
+#[allow(dead_code)]
+pub fn build_attestation_message_v2(
+    proof_type_value: u8,
+    nullifier: &[u8; 32],
+    epoch: u64,
+    data_hash: &[u8; 32],
+    attestation_hash: &[u8; 32],
+) -> [u8; 125] {
+    let mut message = [0u8; 125];
+    // Domain separator: "vouch_attestation_v2" (20 bytes)
+    message[0..20].copy_from_slice(b"vouch_attestation_v2");
+    // Proof type (1 byte)
+    message[20] = proof_type_value;
+    // Nullifier (32 bytes)
+    message[21..53].copy_from_slice(nullifier);
+    // Epoch (8 bytes, big-endian)
+    message[53..61].copy_from_slice(&epoch.to_be_bytes());
+    // Data hash (32 bytes)
+    message[61..93].copy_from_slice(data_hash);
+    // Attestation hash (32 bytes)
+    message[93..125].copy_from_slice(attestation_hash);
+    message
+}
+
+/// Verify Ed25519 signature using instruction introspection
+/// This function checks that a valid Ed25519Program instruction was included
+/// in the transaction that verifies the signature over the attestation message
+///
+/// The Ed25519 instruction is searched for among the `max_scan_distance`
+/// instructions immediately preceding this one (1 = must be adjacent), so
+/// compute-budget or other instructions can sit in between while the scan
+/// stays bounded.
+///
+/// With `exclusive`, the matched instruction must be consumed by this one: any
+/// other instruction of this program between the two means an earlier
+/// instruction may already have relied on the same signature.
+pub fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    verifier_pubkey: &Pubkey,
+    signature: &[u8; 64],
+    message: &[u8],
+    max_scan_distance: u8,
+    exclusive: bool,
+) -> Result<()> {
+    // Get the current instruction index
+    let current_index = load_current_index_checked(instructions_sysvar)
+        .map_err(|_| VouchError::InvalidSignature)?;
+
+    // Scan backwards from the instruction right before this one
+    let max_scan_distance = max_scan_distance.max(1) as u16;
+    for distance in 1..=max_scan_distance.min(current_index) {
+        let ed25519_index = current_index - distance;
+        let ed25519_ix = load_instruction_at_checked(ed25519_index as usize, instructions_sysvar)
+            .map_err(|_| VouchError::InvalidSignature)?;
+
+        // Verify it's an Ed25519 program instruction with matching data.
+        // If so, the Ed25519 program has verified the signature is valid
+        // for the given public key and message
+        if ed25519_ix.program_id == ed25519_program::ID
+            && ed25519_instruction_matches(
+                &ed25519_ix.data,
+                ed25519_index,
+                verifier_pubkey,
+                signature,
+                message,
+            )
+        {
+            if exclusive {
+                for between in 1..distance {
+                    let ix = load_instruction_at_checked(
+                        (current_index - between) as usize,
+                        instructions_sysvar,
+                    )
+                    .map_err(|_| VouchError::InvalidSignature)?;
+                    require!(ix.program_id != crate::ID, VouchError::Ed25519InstructionReused);
+                }
+            }
+            return Ok(());
+        }
+    }
+
+    Err(VouchError::Ed25519InstructionNotFound.into())
+}
+
+/// Check that Ed25519Program instruction data covers exactly this pubkey, signature and message
+/// `ix_index` is the instruction's own position; offsets must not point into another instruction
+fn ed25519_instruction_matches(
+    ix_data: &[u8],
+    ix_index: u16,
+    verifier_pubkey: &Pubkey,
+    signature: &[u8; 64],
+    message: &[u8],
+) -> bool {
+    // Parse and verify the Ed25519 instruction data
+    // Ed25519 instruction format:
+    // - 1 byte: number of signatures
+    // - 1 byte: padding
+    // For each signature:
+    // - 2 bytes: signature offset
+    // - 2 bytes: signature instruction index
+    // - 2 bytes: public key offset
+    // - 2 bytes: public key instruction index
+    // - 2 bytes: message data offset
+    // - 2 bytes: message data size
+    // - 2 bytes: message instruction index
+    // Then the actual data (signature, pubkey, message)
+
+    if ix_data.len() < 2 {
+        return false;
+    }
+
+    let num_signatures = ix_data[0];
+    if num_signatures != 1 {
+        return false;
+    }
+
+    // Parse offsets (bytes 2-15)
+    if ix_data.len() < 16 {
+        return false;
+    }
+
+    // The precompile resolves each offset against the instruction named by its
+    // index field (u16::MAX = this instruction). Anything else means the
+    // verified bytes live elsewhere and what we compare below is unchecked.
+    for index_at in [4, 8, 14] {
+        let index = u16::from_le_bytes([ix_data[index_at], ix_data[index_at + 1]]);
+        if index != u16::MAX && index != ix_index {
+            return false;
+        }
+    }
+
+    let sig_offset = u16::from_le_bytes([ix_data[2], ix_data[3]]) as usize;
+    let pubkey_offset = u16::from_le_bytes([ix_data[6], ix_data[7]]) as usize;
+    let msg_offset = u16::from_le_bytes([ix_data[10], ix_data[11]]) as usize;
+    let msg_size = u16::from_le_bytes([ix_data[12], ix_data[13]]) as usize;
+
+    // Verify the instruction contains the expected data at the specified offsets
+    if ix_data.len() < sig_offset + ED25519_SIGNATURE_SIZE {
+        return false;
+    }
+    if ix_data.len() < pubkey_offset + ED25519_PUBKEY_SIZE {
+        return false;
+    }
+    if ix_data.len() < msg_offset + msg_size {
+        return false;
+    }
+
+    // Extract and verify signature matches
+    let ix_signature = &ix_data[sig_offset..sig_offset + ED25519_SIGNATURE_SIZE];
+    if ix_signature != signature.as_slice() {
+        return false;
+    }
+
+    // Extract and verify public key matches the verifier
+    let ix_pubkey = &ix_data[pubkey_offset..pubkey_offset + ED25519_PUBKEY_SIZE];
+    if ix_pubkey != verifier_pubkey.as_ref() {
+        return false;
+    }
+
+    // Extract and verify message matches
+    let ix_message = &ix_data[msg_offset..msg_offset + msg_size];
+    if ix_message != message {
+        return false;
+    }
+
+    true
+}
+
+// === Accounts ===
+
+#[derive(Accounts)]
+#[instruction(config_epoch: u8)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ConfigAccount::INIT_SPACE,
+        seeds = [b"config", config_epoch_seed(&config_epoch)],
+        bump
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Config being retired (required when config_epoch > 0)
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&previous_config.config_epoch)],
+        bump = previous_config.bump,
+        constraint = previous_config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub previous_config: Option<Account<'info, ConfigAccount>>,
+}
+
+/// Admin control context for pause/unpause and config updates
+#[derive(Accounts)]
+pub struct AdminControl<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PauseControl<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.pause_authority == pause_authority.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    pub pause_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    pub new_admin: Signer<'info>,
+}
+
+/// Migrate config account to new size (adds max_epoch_age field)
+/// Uses UncheckedAccount to bypass Anchor's automatic deserialization
+/// which fails when the account size doesn't match the expected struct size.
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: Manual verification - this is the config PDA being migrated.
+    /// We use UncheckedAccount because the old account format (98 bytes)
+    /// cannot be deserialized as ConfigAccount (106 bytes).
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// Admin must be the one stored in the config account
+    /// We verify this manually in the instruction handler
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initialize rate limit tracking for a wallet
+#[derive(Accounts)]
+pub struct InitRateLimit<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + WalletRateLimit::INIT_SPACE,
+        seeds = [b"rate_limit", wallet.key().as_ref()],
+        bump
+    )]
+    pub rate_limit: Account<'info, WalletRateLimit>,
+
+    /// CHECK: The wallet to track rate limits for
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitClaimLimit<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + WalletClaimLimit::INIT_SPACE,
+        seeds = [b"claim_limit", wallet.key().as_ref()],
+        bump
+    )]
+    pub claim_limit: Account<'info, WalletClaimLimit>,
+
+    /// CHECK: The wallet to track claims for
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResetWalletRateLimit<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"rate_limit", wallet.key().as_ref()],
+        bump = rate_limit.bump,
+        constraint = rate_limit.wallet == wallet.key() @ VouchError::RateLimitWalletMismatch
+    )]
+    pub rate_limit: Account<'info, WalletRateLimit>,
+
+    /// CHECK: The wallet whose rate limit is reset
+    pub wallet: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRateLimitExempt<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"rate_limit", wallet.key().as_ref()],
+        bump = rate_limit.bump,
+        constraint = rate_limit.wallet == wallet.key() @ VouchError::RateLimitWalletMismatch
+    )]
+    pub rate_limit: Account<'info, WalletRateLimit>,
+
+    /// CHECK: The wallet whose exemption changes
+    pub wallet: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(verifier_pubkey: Pubkey)]
+pub struct AddVerifier<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + VerifierAccount::INIT_SPACE,
+        seeds = [b"verifier", verifier_pubkey.as_ref()],
+        bump
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct BlockRecipient<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + BlockedRecipient::INIT_SPACE,
+        seeds = [b"blocked_recipient", recipient.as_ref()],
+        bump
+    )]
+    pub blocked_recipient: Account<'info, BlockedRecipient>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnblockRecipient<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"blocked_recipient", blocked_recipient.recipient.as_ref()],
+        bump = blocked_recipient.bump
+    )]
+    pub blocked_recipient: Account<'info, BlockedRecipient>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Read-only config lookup (validate_config, get_effective_limits)
+#[derive(Accounts)]
+pub struct ValidateConfig<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+}
+
+/// Read-only verifier lookup
+#[derive(Accounts)]
+pub struct GetVerifierProfile<'info> {
+    #[account(
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+}
+
+/// Governance-signed verifier import; verifier PDAs are passed via remaining_accounts
+#[derive(Accounts)]
+pub struct ImportSignedVerifierSet<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless completion of a scheduled verifier removal
+#[derive(Accounts)]
+pub struct FinalizeVerifierRemoval<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveVerifier<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReactivateVerifier<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(old_verifier: Pubkey, new_verifier: Pubkey)]
+pub struct RotateVerifier<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"verifier", old_verifier.as_ref()],
+        bump = old_verifier_account.bump,
+        constraint = old_verifier_account.is_active @ VouchError::VerifierNotAuthorized
+    )]
+    pub old_verifier_account: Account<'info, VerifierAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + VerifierAccount::INIT_SPACE,
+        seeds = [b"verifier", new_verifier.as_ref()],
+        bump
+    )]
+    pub new_verifier_account: Account<'info, VerifierAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVerifier<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump,
+        constraint = !verifier_account.is_active @ VouchError::VerifierStillActive
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(attestation_hash: [u8; 32], proof_type_value: u8, nullifier: [u8; 32], epoch: u64, data_hash: [u8; 32])]
+pub struct RecordAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump,
+        constraint = verifier_account.is_active @ VouchError::VerifierNotAuthorized
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"nullifier", nullifier_namespace_seed(&proof_type_value), nullifier.as_ref()],
+        bump = nullifier_account.bump,
+        constraint = !nullifier_account.is_used @ VouchError::NullifierAlreadyUsed
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"rate_limit", recipient.key().as_ref()],
+        bump = rate_limit.bump,
+        constraint = rate_limit.wallet == recipient.key() @ VouchError::RateLimitWalletMismatch
+    )]
+    pub rate_limit: Account<'info, WalletRateLimit>,
+
+    /// The wallet receiving the credential
+    /// CHECK: This is the recipient of the credential NFT
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Recipient's commitment (required when config.require_commitment or config.bind_commitment is set)
+    pub commitment_account: Option<Account<'info, CommitmentAccount>>,
+
+    /// Recipient's blocklist entry (required when config.enforce_recipient_blocklist is set)
+    /// CHECK: May not exist; the recipient is blocked only if it does
+    #[account(seeds = [b"blocked_recipient", recipient.key().as_ref()], bump)]
+    pub blocked_recipient: Option<UncheckedAccount<'info>>,
+
+    /// Signature's UsedSignature PDA (required when config.track_used_signatures is set)
+    /// CHECK: Address is checked against the derived PDA and created in the handler
+    #[account(mut)]
+    pub used_signature: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitProofTypeRegistry<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProofTypeRegistry::INIT_SPACE,
+        seeds = [b"proof_type_registry"],
+        bump
+    )]
+    pub proof_type_registry: Account<'info, ProofTypeRegistry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProofTypeRegistry<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"proof_type_registry"],
+        bump = proof_type_registry.bump
+    )]
+    pub proof_type_registry: Account<'info, ProofTypeRegistry>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(attestation_hash: [u8; 32], proof_type_id: u16, nullifier: [u8; 32])]
+pub struct RecordRegisteredAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        seeds = [b"proof_type_registry"],
+        bump = proof_type_registry.bump
+    )]
+    pub proof_type_registry: Account<'info, ProofTypeRegistry>,
+
+    #[account(
+        mut,
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump,
+        constraint = verifier_account.is_active @ VouchError::VerifierNotAuthorized
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+
+    /// Namespace 0: registry ids don't fit the nullifier namespace byte
+    #[account(
+        mut,
+        seeds = [b"nullifier", nullifier.as_ref()],
+        bump = nullifier_account.bump,
+        constraint = !nullifier_account.is_used @ VouchError::NullifierAlreadyUsed
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"rate_limit", recipient.key().as_ref()],
+        bump = rate_limit.bump,
+        constraint = rate_limit.wallet == recipient.key() @ VouchError::RateLimitWalletMismatch
+    )]
+    pub rate_limit: Account<'info, WalletRateLimit>,
+
+    /// The wallet receiving the credential
+    /// CHECK: This is the recipient of the credential
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Recipient's commitment (required only when config.require_commitment is set)
+    pub commitment_account: Option<Account<'info, CommitmentAccount>>,
+
+    /// Recipient's blocklist entry (required when config.enforce_recipient_blocklist is set)
+    /// CHECK: May not exist; the recipient is blocked only if it does
+    #[account(seeds = [b"blocked_recipient", recipient.key().as_ref()], bump)]
+    pub blocked_recipient: Option<UncheckedAccount<'info>>,
+
+    /// Signature's UsedSignature PDA (required when config.track_used_signatures is set)
+    /// CHECK: Address is checked against the derived PDA and created in the handler
+    #[account(mut)]
+    pub used_signature: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Signing verifiers' VerifierAccounts are passed via remaining_accounts,
+/// followed by their UsedSignature PDAs while config.track_used_signatures is set
+#[derive(Accounts)]
+#[instruction(args: ThresholdAttestationArgs)]
+pub struct RecordAttestationThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"nullifier",
+            nullifier_namespace_seed(&args.proof_type_value),
+            args.nullifier.as_ref()
+        ],
+        bump = nullifier_account.bump,
+        constraint = !nullifier_account.is_used @ VouchError::NullifierAlreadyUsed
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"rate_limit", recipient.key().as_ref()],
+        bump = rate_limit.bump,
+        constraint = rate_limit.wallet == recipient.key() @ VouchError::RateLimitWalletMismatch
+    )]
+    pub rate_limit: Account<'info, WalletRateLimit>,
+
+    /// The wallet receiving the credential
+    /// CHECK: This is the recipient of the credential NFT
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Recipient's commitment (required when config.require_commitment or config.bind_commitment is set)
+    pub commitment_account: Option<Account<'info, CommitmentAccount>>,
+
+    /// Recipient's blocklist entry (required when config.enforce_recipient_blocklist is set)
+    /// CHECK: May not exist; the recipient is blocked only if it does
+    #[account(seeds = [b"blocked_recipient", recipient.key().as_ref()], bump)]
+    pub blocked_recipient: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32])]
+pub struct UpgradeCredential<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump,
+        constraint = verifier_account.is_active @ VouchError::VerifierNotAuthorized
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"nullifier",
+            nullifier_namespace_seed(&nullifier_account.namespace),
+            nullifier.as_ref()
+        ],
+        bump = nullifier_account.bump,
+        constraint = nullifier_account.is_used @ VouchError::NullifierNotVerified,
+        constraint = !nullifier_account.is_revoked @ VouchError::CredentialRevoked
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    /// Signature's UsedSignature PDA (required when config.track_used_signatures is set)
+    /// CHECK: Address is checked against the derived PDA and created in the handler
+    #[account(mut)]
+    pub used_signature: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32])]
+pub struct UpdateCredentialScore<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump,
+        constraint = verifier_account.is_active @ VouchError::VerifierNotAuthorized
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"nullifier",
+            nullifier_namespace_seed(&nullifier_account.namespace),
+            nullifier.as_ref()
+        ],
+        bump = nullifier_account.bump,
+        constraint = nullifier_account.is_used @ VouchError::NullifierNotVerified
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    /// Signature's UsedSignature PDA (required when config.track_used_signatures is set)
+    /// CHECK: Address is checked against the derived PDA and created in the handler
+    #[account(mut)]
+    pub used_signature: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PublishVerifierRoot<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump,
+        constraint = verifier_account.is_active @ VouchError::VerifierNotAuthorized
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+
+    /// Signature's UsedSignature PDA (required when config.track_used_signatures is set)
+    /// CHECK: Address is checked against the derived PDA and created in the handler
+    #[account(mut)]
+    pub used_signature: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proof_type_value: u8, nullifier: [u8; 32])]
+pub struct RecordAttestationMerkle<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump,
+        constraint = verifier_account.is_active @ VouchError::VerifierNotAuthorized
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"nullifier", nullifier_namespace_seed(&proof_type_value), nullifier.as_ref()],
+        bump = nullifier_account.bump,
+        constraint = !nullifier_account.is_used @ VouchError::NullifierAlreadyUsed
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"rate_limit", recipient.key().as_ref()],
+        bump = rate_limit.bump,
+        constraint = rate_limit.wallet == recipient.key() @ VouchError::RateLimitWalletMismatch
+    )]
+    pub rate_limit: Account<'info, WalletRateLimit>,
+
+    /// The wallet receiving the credential
+    /// CHECK: This is the recipient of the credential
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Recipient's commitment (required when config.require_commitment or config.bind_commitment is set)
+    pub commitment_account: Option<Account<'info, CommitmentAccount>>,
+
+    /// Recipient's blocklist entry (required when config.enforce_recipient_blocklist is set)
+    /// CHECK: May not exist; the recipient is blocked only if it does
+    #[account(seeds = [b"blocked_recipient", recipient.key().as_ref()], bump)]
+    pub blocked_recipient: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(batch_id: [u8; 32])]
+pub struct BeginAttestationBatch<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump,
+        constraint = verifier_account.is_active @ VouchError::VerifierNotAuthorized
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AttestationBatch::INIT_SPACE,
+        seeds = [b"attestation_batch", verifier_account.verifier.as_ref(), batch_id.as_ref()],
+        bump
+    )]
+    pub batch: Account<'info, AttestationBatch>,
+
+    /// Signature's UsedSignature PDA (required when config.track_used_signatures is set)
+    /// CHECK: Address is checked against the derived PDA and created in the handler
+    #[account(mut)]
+    pub used_signature: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proof_type_value: u8, nullifier: [u8; 32])]
+pub struct ApplyBatchedAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump,
+        constraint = verifier_account.is_active @ VouchError::VerifierNotAuthorized
+    )]
+    pub verifier_account: Account<'info, VerifierAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"attestation_batch", batch.verifier.as_ref(), batch.batch_id.as_ref()],
+        bump = batch.bump,
+        constraint = batch.verifier == verifier_account.verifier @ VouchError::VerifierNotAuthorized
+    )]
+    pub batch: Account<'info, AttestationBatch>,
+
+    #[account(
+        mut,
+        seeds = [b"nullifier", nullifier_namespace_seed(&proof_type_value), nullifier.as_ref()],
+        bump = nullifier_account.bump,
+        constraint = !nullifier_account.is_used @ VouchError::NullifierAlreadyUsed
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"rate_limit", recipient.key().as_ref()],
+        bump = rate_limit.bump,
+        constraint = rate_limit.wallet == recipient.key() @ VouchError::RateLimitWalletMismatch
+    )]
+    pub rate_limit: Account<'info, WalletRateLimit>,
+
+    /// The wallet receiving the credential (bound into the batch leaf)
+    /// CHECK: This is the recipient of the credential
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Recipient's commitment (required only when config.require_commitment is set)
+    pub commitment_account: Option<Account<'info, CommitmentAccount>>,
+
+    /// Recipient's blocklist entry (required when config.enforce_recipient_blocklist is set)
+    /// CHECK: May not exist; the recipient is blocked only if it does
+    #[account(seeds = [b"blocked_recipient", recipient.key().as_ref()], bump)]
+    pub blocked_recipient: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAttestationBatch<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        close = batch_payer,
+        seeds = [b"attestation_batch", batch.verifier.as_ref(), batch.batch_id.as_ref()],
+        bump = batch.bump
+    )]
+    pub batch: Account<'info, AttestationBatch>,
+
+    /// Rent refund destination (the batch opener)
+    /// CHECK: Address is pinned to batch.payer
+    #[account(mut, address = batch.payer)]
+    pub batch_payer: UncheckedAccount<'info>,
+
+    pub closer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(commitment: [u8; 32])]
+pub struct CreateCommitment<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + CommitmentAccount::INIT_SPACE,
+        seeds = [b"commitment", commitment.as_ref()],
+        bump
+    )]
+    pub commitment_account: Account<'info, CommitmentAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32], namespace: u8)]
+pub struct InitNullifier<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + NullifierAccount::INIT_SPACE,
+        seeds = [b"nullifier", nullifier_namespace_seed(&namespace), nullifier.as_ref()],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"nullifier",
+            nullifier_namespace_seed(&nullifier_account.namespace),
+            nullifier_account.nullifier.as_ref()
+        ],
+        bump = nullifier_account.bump,
+        constraint = nullifier_account.is_used @ VouchError::NullifierNotVerified,
+        constraint = !nullifier_account.is_revoked @ VouchError::CredentialRevoked
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    /// Authority's verifier account (required when the authority is not admin)
+    #[account(
+        seeds = [b"verifier", authority.key().as_ref()],
+        bump = verifier_account.bump
+    )]
+    pub verifier_account: Option<Account<'info, VerifierAccount>>,
+
+    /// Admin or the issuing verifier
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseNullifier<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [
+            b"nullifier",
+            nullifier_namespace_seed(&nullifier_account.namespace),
+            nullifier_account.nullifier.as_ref()
+        ],
+        bump = nullifier_account.bump
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    /// Original payer or admin
+    pub authority: Signer<'info>,
+
+    /// Rent refund destination
+    /// CHECK: Any account may receive the refunded lamports
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+/// Batch nullifier creation; nullifier PDAs are passed via remaining_accounts
+#[derive(Accounts)]
+pub struct InitNullifiersBatch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetNullifierPin<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"nullifier",
+            nullifier_namespace_seed(&nullifier_account.namespace),
+            nullifier_account.nullifier.as_ref()
+        ],
+        bump = nullifier_account.bump
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Read-only signature usage lookup (account may not exist)
+#[derive(Accounts)]
+pub struct GetSignatureUsage<'info> {
+    /// CHECK: Address is checked against the derived PDA in the handler;
+    /// the account may be uninitialized
+    pub used_signature: UncheckedAccount<'info>,
+}
+
+/// Read-only nullifier lookup (account may not exist yet)
+#[derive(Accounts)]
+pub struct GetNullifierAccountInfo<'info> {
+    /// CHECK: Address is checked against the derived PDA in the handler;
+    /// the account may be uninitialized
+    pub nullifier_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnableCampaignActivityLog<'info> {
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CampaignActivityLog::INIT_SPACE,
+        seeds = [b"campaign_activity", campaign.key().as_ref()],
+        bump
+    )]
+    pub activity_log: Account<'info, CampaignActivityLog>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitProtocolStats<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired,
+        constraint = config.admin == admin.key() @ VouchError::Unauthorized
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProtocolStats::INIT_SPACE,
+        seeds = [b"protocol_stats"],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Read-only protocol stats lookup for protocol_tvl
+#[derive(Accounts)]
+pub struct GetProtocolTvl<'info> {
+    #[account(
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+}
+
+#[derive(Accounts)]
+pub struct InitWalletRegistrationIndex<'info> {
+    #[account(
+        init,
+        payer = wallet,
+        space = 8 + WalletRegistrationIndex::INIT_SPACE,
+        seeds = [b"wallet_registrations", wallet.key().as_ref()],
+        bump
+    )]
+    pub registration_index: Account<'info, WalletRegistrationIndex>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Read-only campaign lookup for claim progress
+#[derive(Accounts)]
+pub struct GetCampaignClaimProgress<'info> {
+    #[account(
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+}
+
+// === Airdrop Registry Accounts ===
+
+#[derive(Accounts)]
+#[instruction(template_id: [u8; 32])]
+pub struct CreateCampaignTemplate<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CampaignTemplate::INIT_SPACE,
+        seeds = [b"campaign_template", creator.key().as_ref(), template_id.as_ref()],
+        bump
+    )]
+    pub template: Account<'info, CampaignTemplate>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCampaignTemplate<'info> {
+    #[account(
+        mut,
+        seeds = [b"campaign_template", creator.key().as_ref(), template.template_id.as_ref()],
+        bump = template.bump,
+        constraint = template.creator == creator.key() @ VouchError::Unauthorized
+    )]
+    pub template: Account<'info, CampaignTemplate>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(campaign_id: [u8; 32])]
+pub struct CreateCampaignFromTemplate<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"campaign_template", creator.key().as_ref(), template.template_id.as_ref()],
+        bump = template.bump,
+        constraint = template.creator == creator.key() @ VouchError::Unauthorized
+    )]
+    pub template: Account<'info, CampaignTemplate>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + AirdropCampaign::INIT_SPACE,
+        seeds = [b"airdrop_campaign", campaign_id.as_ref()],
+        bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(campaign_id: [u8; 32])]
+pub struct CreateAirdropCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + AirdropCampaign::INIT_SPACE,
+        seeds = [b"airdrop_campaign", campaign_id.as_ref()],
+        bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterForAirdrop<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.status == CampaignStatus::Open @ VouchError::CampaignNotOpen
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        seeds = [
+            b"nullifier",
+            nullifier_namespace_seed(&nullifier_account.namespace),
+            nullifier_account.nullifier.as_ref()
+        ],
+        bump = nullifier_account.bump,
+        constraint = nullifier_account.is_used @ VouchError::NullifierNotVerified
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AirdropRegistrationAccount::INIT_SPACE,
+        seeds = [b"airdrop_registration", campaign.key().as_ref(), nullifier_account.nullifier.as_ref()],
+        bump
+    )]
+    pub registration: Account<'info, AirdropRegistrationAccount>,
+
+    /// Campaign activity feed (required when campaign.activity_log_enabled)
+    #[account(
+        mut,
+        constraint = activity_log.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub activity_log: Option<Account<'info, CampaignActivityLog>>,
+
+    /// ShadowWire reuse counter (required when campaign.max_registrations_per_shadow_wire > 0)
+    /// CHECK: Address is derived and verified in enforce_shadow_wire_reuse_limit;
+    /// created on first use
+    #[account(mut)]
+    pub shadow_wire_usage: Option<UncheckedAccount<'info>>,
+
+    /// Payer's registration index (opt-in, see init_wallet_registration_index)
+    #[account(
+        mut,
+        seeds = [b"wallet_registrations", payer.key().as_ref()],
+        bump = registration_index.bump
+    )]
+    pub registration_index: Option<Account<'info, WalletRegistrationIndex>>,
+
+    /// Payer's saved default address (required by register_for_airdrop_with_default)
+    #[account(
+        seeds = [b"wallet_profile", payer.key().as_ref()],
+        bump = wallet_profile.bump
+    )]
+    pub wallet_profile: Option<Account<'info, WalletProfile>>,
+
+    /// Proof-type registry (required when the credential has a registry proof type)
+    #[account(
+        seeds = [b"proof_type_registry"],
+        bump = proof_type_registry.bump
+    )]
+    pub proof_type_registry: Option<Account<'info, ProofTypeRegistry>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetDefaultShadowWire<'info> {
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = 8 + WalletProfile::INIT_SPACE,
+        seeds = [b"wallet_profile", wallet.key().as_ref()],
+        bump
+    )]
+    pub wallet_profile: Account<'info, WalletProfile>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(shadow_wire_address: String)]
+pub struct RegisterForAirdropOpen<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.status == CampaignStatus::Open @ VouchError::CampaignNotOpen
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AirdropRegistrationAccount::INIT_SPACE,
+        seeds = [b"airdrop_registration", campaign.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub registration: Account<'info, AirdropRegistrationAccount>,
+
+    /// Campaign activity feed (required when campaign.activity_log_enabled)
+    #[account(
+        mut,
+        constraint = activity_log.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub activity_log: Option<Account<'info, CampaignActivityLog>>,
+
+    /// ShadowWire reuse counter (required when campaign.max_registrations_per_shadow_wire > 0)
+    /// CHECK: Address is derived and verified in enforce_shadow_wire_reuse_limit;
+    /// created on first use
+    #[account(mut)]
+    pub shadow_wire_usage: Option<UncheckedAccount<'info>>,
+
+    /// Payer's registration index (opt-in, see init_wallet_registration_index)
+    #[account(
+        mut,
+        seeds = [b"wallet_registrations", payer.key().as_ref()],
+        bump = registration_index.bump
+    )]
+    pub registration_index: Option<Account<'info, WalletRegistrationIndex>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrate a nullifier account to the current layout
+/// Uses UncheckedAccount because nullifiers created under an older layout
+/// are too small to deserialize as NullifierAccount.
+#[derive(Accounts)]
+pub struct MigrateNullifier<'info> {
+    /// CHECK: Owner is checked here, discriminator is verified in the handler
+    #[account(mut, owner = crate::ID @ VouchError::InvalidNullifierAccount)]
+    pub nullifier_account: UncheckedAccount<'info>,
+
+    /// Pays for any additional rent from the realloc
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrate a campaign account to the current layout
+/// Uses UncheckedAccount because campaigns created under an older layout
+/// cannot be deserialized as AirdropCampaign.
+#[derive(Accounts)]
+pub struct MigrateCampaign<'info> {
+    /// CHECK: Owner is checked here, discriminator is verified in the handler
+    #[account(mut, owner = crate::ID @ VouchError::InvalidCampaign)]
+    pub campaign: UncheckedAccount<'info>,
+
+    /// Pays for any additional rent from the realloc
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BindRegistrationRecipient<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_registration", campaign.key().as_ref(), registration.nullifier.as_ref()],
+        bump = registration.bump,
+        constraint = registration.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub registration: Account<'info, AirdropRegistrationAccount>,
+
+    /// Credential the registration was made with
+    #[account(
+        seeds = [
+            b"nullifier",
+            nullifier_namespace_seed(&nullifier_account.namespace),
+            nullifier_account.nullifier.as_ref()
+        ],
+        bump = nullifier_account.bump
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    /// Campaign creator or admin
+    pub authority: Signer<'info>,
+}
+
+/// Migrate a registration account to the current layout
+/// Uses UncheckedAccount because registrations created under an older
+/// layout may be too small to deserialize as AirdropRegistrationAccount.
+#[derive(Accounts)]
+pub struct MigrateRegistration<'info> {
+    /// CHECK: Owner is checked here, discriminator is verified in the handler
+    #[account(mut, owner = crate::ID @ VouchError::InvalidRegistration)]
+    pub registration: UncheckedAccount<'info>,
+
+    /// Credential the registration was made with; when passed, an unbound
+    /// registration takes its recipient (PDA and nullifier checked in the handler)
+    pub nullifier_account: Option<Account<'info, NullifierAccount>>,
+
+    /// Pays for any additional rent from the realloc
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAirdropRegistration<'info> {
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() || campaign_manager.is_some() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Signer's manager registration; required when the signer is not the creator
+    #[account(
+        seeds = [b"campaign_manager", campaign.key().as_ref(), creator.key().as_ref()],
+        bump = campaign_manager.bump
+    )]
+    pub campaign_manager: Option<Account<'info, CampaignManager>>,
+}
+
+#[derive(Accounts)]
+#[instruction(manager: Pubkey)]
+pub struct AddCampaignManager<'info> {
+    #[account(
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CampaignManager::INIT_SPACE,
+        seeds = [b"campaign_manager", campaign.key().as_ref(), manager.as_ref()],
+        bump
+    )]
+    pub campaign_manager: Account<'info, CampaignManager>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveCampaignManager<'info> {
+    #[account(
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"campaign_manager", campaign.key().as_ref(), campaign_manager.manager.as_ref()],
+        bump = campaign_manager.bump
+    )]
+    pub campaign_manager: Account<'info, CampaignManager>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MarkAirdropDistributed<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() || campaign_manager.is_some() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_registration", campaign.key().as_ref(), registration.nullifier.as_ref()],
+        bump = registration.bump,
+        constraint = registration.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub registration: Account<'info, AirdropRegistrationAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Instructions sysvar for relay receipt verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// Signer's manager registration; required when the signer is not the creator
+    #[account(
+        seeds = [b"campaign_manager", campaign.key().as_ref(), creator.key().as_ref()],
+        bump = campaign_manager.bump
+    )]
+    pub campaign_manager: Option<Account<'info, CampaignManager>>,
+}
+
+/// Batch distribution marking; registrations are passed via remaining_accounts
+#[derive(Accounts)]
+pub struct MarkAirdropDistributedBatch<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() || campaign_manager.is_some() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Signer's manager registration; required when the signer is not the creator
+    #[account(
+        seeds = [b"campaign_manager", campaign.key().as_ref(), creator.key().as_ref()],
+        bump = campaign_manager.bump
+    )]
+    pub campaign_manager: Option<Account<'info, CampaignManager>>,
+}
+
+#[derive(Accounts)]
+pub struct SetRegistrationMemo<'info> {
+    #[account(
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() || campaign_manager.is_some() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_registration", campaign.key().as_ref(), registration.nullifier.as_ref()],
+        bump = registration.bump,
+        constraint = registration.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub registration: Account<'info, AirdropRegistrationAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Signer's manager registration; required when the signer is not the creator
+    #[account(
+        seeds = [b"campaign_manager", campaign.key().as_ref(), creator.key().as_ref()],
+        bump = campaign_manager.bump
+    )]
+    pub campaign_manager: Option<Account<'info, CampaignManager>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRegistrationAddress<'info> {
+    #[account(
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_registration", campaign.key().as_ref(), registration.nullifier.as_ref()],
+        bump = registration.bump,
+        constraint = registration.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub registration: Account<'info, AirdropRegistrationAccount>,
+
+    /// ShadowWire reuse counter for the new address
+    /// (required when campaign.max_registrations_per_shadow_wire > 0)
+    /// CHECK: Address is derived and verified in enforce_shadow_wire_reuse_limit;
+    /// created on first use
+    #[account(mut)]
+    pub shadow_wire_usage: Option<UncheckedAccount<'info>>,
+
+    /// ShadowWire reuse counter for the current address, released on change
+    /// (required when campaign.max_registrations_per_shadow_wire > 0)
+    /// CHECK: Address is derived and verified in release_shadow_wire_usage;
+    /// closed once no registration uses the address
+    #[account(mut)]
+    pub old_shadow_wire_usage: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creator-gated campaign settings update
+#[derive(Accounts)]
+pub struct UpdateAirdropCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteAirdropCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAirdropCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    /// Campaign token vault (ATA owned by campaign PDA)
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = campaign,
+    )]
+    pub campaign_vault: Account<'info, TokenAccount>,
+
+    /// Token mint for the campaign
+    #[account(
+        constraint = token_mint.key() == campaign.token_mint @ VouchError::InvalidMint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Creator's token account receiving the refund
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    /// Campaign's additional asset basket, whose vaults are refunded too
+    /// CHECK: May not exist; campaigns without additional assets have no basket
+    #[account(
+        mut,
+        seeds = [b"campaign_assets", campaign.key().as_ref()],
+        bump
+    )]
+    pub asset_basket: UncheckedAccount<'info>,
+
+    /// Protocol TVL aggregates (required when config.protocol_stats_enabled)
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless cleanup of an expired, unclaimed registration
+#[derive(Accounts)]
+pub struct ReapExpiredRegistration<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_registration", campaign.key().as_ref(), registration.nullifier.as_ref()],
+        bump = registration.bump,
+        constraint = registration.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub registration: Account<'info, AirdropRegistrationAccount>,
+
+    /// Registrant's index; the reaped entry is removed when supplied
+    #[account(mut)]
+    pub registration_index: Option<Account<'info, WalletRegistrationIndex>>,
+
+    /// Campaign creator receiving the non-bounty share of the rent
+    /// CHECK: Only receives lamports; address is pinned to campaign.creator
+    #[account(mut, address = campaign.creator @ VouchError::Unauthorized)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub reaper: Signer<'info>,
+}
+
+/// Fund an airdrop campaign's token vault
+#[derive(Accounts)]
+pub struct FundAirdropCampaign<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() || campaign_manager.is_some() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    /// Campaign token vault (ATA owned by campaign PDA)
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = campaign,
+    )]
+    pub campaign_vault: Account<'info, TokenAccount>,
+
+    /// Token mint for the campaign
+    #[account(
+        constraint = token_mint.key() == campaign.token_mint @ VouchError::InvalidMint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Token account to fund from, owned by the funding authority (the
+    /// signer's ATA, or the funder's account when `funder` is set)
+    #[account(
+        mut,
+        token::mint = token_mint,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    /// Campaign activity feed (required when campaign.activity_log_enabled)
+    #[account(
+        mut,
+        constraint = activity_log.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub activity_log: Option<Account<'info, CampaignActivityLog>>,
+
+    /// Protocol TVL aggregates (required when config.protocol_stats_enabled)
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// Signer's manager registration; required when the signer is not the creator
+    #[account(
+        seeds = [b"campaign_manager", campaign.key().as_ref(), creator.key().as_ref()],
+        bump = campaign_manager.bump
+    )]
+    pub campaign_manager: Option<Account<'info, CampaignManager>>,
+
+    /// Separate funding authority, e.g. a treasury program PDA signing via CPI
+    /// (owns creator_token_account when set)
+    pub funder: Option<Signer<'info>>,
+}
+
+/// Claim airdrop tokens from a campaign
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    /// Campaign token vault (ATA owned by campaign PDA)
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = campaign,
+    )]
+    pub campaign_vault: Account<'info, TokenAccount>,
+
+    /// Token mint for the campaign
+    #[account(
+        constraint = token_mint.key() == campaign.token_mint @ VouchError::InvalidMint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Registration proving eligibility
+    #[account(
+        mut,
+        seeds = [b"airdrop_registration", campaign.key().as_ref(), registration.nullifier.as_ref()],
+        bump = registration.bump,
+        constraint = registration.campaign == campaign.key() @ VouchError::InvalidCampaign,
+        constraint = !registration.is_claimed @ VouchError::AlreadyClaimed
+    )]
+    pub registration: Account<'info, AirdropRegistrationAccount>,
+
+    /// Credential the registration was made with (required unless it is an open
+    /// registration, see check_registration_credential)
+    #[account(
+        seeds = [
+            b"nullifier",
+            nullifier_namespace_seed(&nullifier_account.namespace),
+            registration.nullifier.as_ref()
+        ],
+        bump = nullifier_account.bump,
+        constraint = !nullifier_account.is_revoked @ VouchError::CredentialRevoked
+    )]
+    pub nullifier_account: Option<Account<'info, NullifierAccount>>,
+
+    /// Claim marker for the registration's nullifier, shared with claim_airdrop_merkle
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = 8 + ClaimStatus::INIT_SPACE,
+        seeds = [b"claim_status", campaign.key().as_ref(), registration.nullifier.as_ref()],
+        bump
+    )]
+    pub claim_status: Account<'info, ClaimStatus>,
+
+    /// claim_airdrop_private's marker for the same nullifier; must not exist
+    /// CHECK: Only emptiness is read (see require_unclaimed_marker)
+    #[account(
+        seeds = [b"private_claim", campaign.key().as_ref(), registration.nullifier.as_ref()],
+        bump
+    )]
+    pub private_claim_marker: UncheckedAccount<'info>,
+
+    /// Claimer's token account to receive tokens
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        associated_token::mint = token_mint,
+        associated_token::authority = claimer,
+    )]
+    pub claimer_token_account: Account<'info, TokenAccount>,
+
+    /// Creator's fee account (required when campaign_claim_fee_bps > 0)
+    #[account(
+        mut,
+        constraint = claim_fee_account.key() == campaign.claim_fee_account @ VouchError::InvalidClaimFee,
+        constraint = claim_fee_account.mint == campaign.token_mint @ VouchError::InvalidMint
+    )]
+    pub claim_fee_account: Option<Account<'info, TokenAccount>>,
+
+    /// Campaign activity feed (required when campaign.activity_log_enabled)
+    #[account(
+        mut,
+        constraint = activity_log.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub activity_log: Option<Account<'info, CampaignActivityLog>>,
+
+    /// Protocol TVL aggregates (required when config.protocol_stats_enabled)
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Claimer's cross-campaign claim tracker (required when a global claim cooldown is set)
+    #[account(
+        mut,
+        seeds = [b"claim_limit", claimer.key().as_ref()],
+        bump = claim_limit.bump
+    )]
+    pub claim_limit: Option<Account<'info, WalletClaimLimit>>,
+
+    /// Claimer's blocklist entry (required when config.enforce_recipient_blocklist is set)
+    /// CHECK: May not exist; the claimer is blocked only if it does
+    #[account(seeds = [b"blocked_recipient", claimer.key().as_ref()], bump)]
+    pub blocked_recipient: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    /// Instructions sysvar for claim authorization verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim airdrop tokens into the claimer's custody escrow
+#[derive(Accounts)]
+pub struct ClaimAirdropToCustody<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    /// Campaign token vault (ATA owned by campaign PDA)
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = campaign,
+    )]
+    pub campaign_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_mint.key() == campaign.token_mint @ VouchError::InvalidMint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Registration proving eligibility
+    #[account(
+        mut,
+        seeds = [b"airdrop_registration", campaign.key().as_ref(), registration.nullifier.as_ref()],
+        bump = registration.bump,
+        constraint = registration.campaign == campaign.key() @ VouchError::InvalidCampaign,
+        constraint = !registration.is_claimed @ VouchError::AlreadyClaimed
+    )]
+    pub registration: Account<'info, AirdropRegistrationAccount>,
+
+    /// Credential the registration was made with (required unless it is an open
+    /// registration, see check_registration_credential)
+    #[account(
+        seeds = [
+            b"nullifier",
+            nullifier_namespace_seed(&nullifier_account.namespace),
+            registration.nullifier.as_ref()
+        ],
+        bump = nullifier_account.bump,
+        constraint = !nullifier_account.is_revoked @ VouchError::CredentialRevoked
+    )]
+    pub nullifier_account: Option<Account<'info, NullifierAccount>>,
+
+    /// Claim marker for the registration's nullifier, shared with claim_airdrop_merkle
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = 8 + ClaimStatus::INIT_SPACE,
+        seeds = [b"claim_status", campaign.key().as_ref(), registration.nullifier.as_ref()],
+        bump
+    )]
+    pub claim_status: Account<'info, ClaimStatus>,
+
+    /// claim_airdrop_private's marker for the same nullifier; must not exist
+    /// CHECK: Only emptiness is read (see require_unclaimed_marker)
+    #[account(
+        seeds = [b"private_claim", campaign.key().as_ref(), registration.nullifier.as_ref()],
+        bump
+    )]
+    pub private_claim_marker: UncheckedAccount<'info>,
+
+    /// Escrow keyed to the claimer as beneficiary
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = 8 + CustodyEscrow::INIT_SPACE,
+        seeds = [b"custody_escrow", campaign.key().as_ref(), claimer.key().as_ref()],
+        bump
+    )]
+    pub custody_escrow: Account<'info, CustodyEscrow>,
+
+    /// Escrow token account (ATA owned by the escrow PDA)
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        associated_token::mint = token_mint,
+        associated_token::authority = custody_escrow,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    /// Creator's fee account (required when campaign_claim_fee_bps > 0)
+    #[account(
+        mut,
+        constraint = claim_fee_account.key() == campaign.claim_fee_account @ VouchError::InvalidClaimFee,
+        constraint = claim_fee_account.mint == campaign.token_mint @ VouchError::InvalidMint
+    )]
+    pub claim_fee_account: Option<Account<'info, TokenAccount>>,
+
+    /// Campaign activity feed (required when campaign.activity_log_enabled)
+    #[account(
+        mut,
+        constraint = activity_log.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub activity_log: Option<Account<'info, CampaignActivityLog>>,
+
+    /// Protocol TVL aggregates (required when config.protocol_stats_enabled)
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Claimer's cross-campaign claim tracker (required when a global claim cooldown is set)
+    #[account(
+        mut,
+        seeds = [b"claim_limit", claimer.key().as_ref()],
+        bump = claim_limit.bump
+    )]
+    pub claim_limit: Option<Account<'info, WalletClaimLimit>>,
+
+    /// Claimer's blocklist entry (required when config.enforce_recipient_blocklist is set)
+    /// CHECK: May not exist; the claimer is blocked only if it does
+    #[account(seeds = [b"blocked_recipient", claimer.key().as_ref()], bump)]
+    pub blocked_recipient: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    /// Instructions sysvar for claim authorization verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Pay a custody escrow out to its beneficiary
+#[derive(Accounts)]
+pub struct ReleaseCustodyEscrow<'info> {
+    #[account(
+        mut,
+        close = beneficiary,
+        seeds = [b"custody_escrow", custody_escrow.campaign.as_ref(), beneficiary.key().as_ref()],
+        bump = custody_escrow.bump,
+        constraint = custody_escrow.beneficiary == beneficiary.key() @ VouchError::Unauthorized
+    )]
+    pub custody_escrow: Account<'info, CustodyEscrow>,
+
+    /// Escrow token account (ATA owned by the escrow PDA)
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = custody_escrow,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_mint.key() == custody_escrow.token_mint @ VouchError::InvalidMint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = token_mint,
+        associated_token::authority = beneficiary,
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+
+    /// Custodian co-signature (releases before release_at)
+    #[account(
+        constraint = custodian.key() == custody_escrow.custodian @ VouchError::Unauthorized
+    )]
+    pub custodian: Option<Signer<'info>>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Return a completed campaign's unclaimed tokens to the creator
+#[derive(Accounts)]
+pub struct ReclaimUnclaimedAirdrop<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    /// Campaign token vault (ATA owned by campaign PDA)
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = campaign,
+    )]
+    pub campaign_vault: Account<'info, TokenAccount>,
+
+    /// Token mint for the campaign
+    #[account(
+        constraint = token_mint.key() == campaign.token_mint @ VouchError::InvalidMint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Creator's token account receiving the unclaimed balance
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    /// Protocol TVL aggregates (required when config.protocol_stats_enabled)
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Merkle claim: eligibility is proven against campaign.merkle_root
+#[derive(Accounts)]
+pub struct ClaimAirdropMerkle<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    /// Campaign token vault (ATA owned by campaign PDA)
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = campaign,
+    )]
+    pub campaign_vault: Account<'info, TokenAccount>,
+
+    /// Token mint for the campaign
+    #[account(
+        constraint = token_mint.key() == campaign.token_mint @ VouchError::InvalidMint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Verified credential the leaf commits to
+    #[account(
+        seeds = [
+            b"nullifier",
+            nullifier_namespace_seed(&nullifier_account.namespace),
+            nullifier_account.nullifier.as_ref()
+        ],
+        bump = nullifier_account.bump,
+        constraint = nullifier_account.is_used @ VouchError::NullifierNotVerified,
+        constraint = !nullifier_account.is_revoked @ VouchError::CredentialRevoked
+    )]
+    pub nullifier_account: Account<'info, NullifierAccount>,
+
+    /// Claim marker for the credential, shared with the registration claim paths
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = 8 + ClaimStatus::INIT_SPACE,
+        seeds = [b"claim_status", campaign.key().as_ref(), nullifier_account.nullifier.as_ref()],
+        bump
+    )]
+    pub claim_status: Account<'info, ClaimStatus>,
+
+    /// claim_airdrop_private's marker for the same nullifier; must not exist
+    /// CHECK: Only emptiness is read (see require_unclaimed_marker)
+    #[account(
+        seeds = [b"private_claim", campaign.key().as_ref(), nullifier_account.nullifier.as_ref()],
+        bump
+    )]
+    pub private_claim_marker: UncheckedAccount<'info>,
+
+    /// Claimer's token account to receive tokens
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        associated_token::mint = token_mint,
+        associated_token::authority = claimer,
+    )]
+    pub claimer_token_account: Account<'info, TokenAccount>,
+
+    /// Creator's fee account (required when campaign_claim_fee_bps > 0)
+    #[account(
+        mut,
+        constraint = claim_fee_account.key() == campaign.claim_fee_account @ VouchError::InvalidClaimFee,
+        constraint = claim_fee_account.mint == campaign.token_mint @ VouchError::InvalidMint
+    )]
+    pub claim_fee_account: Option<Account<'info, TokenAccount>>,
+
+    /// Campaign activity feed (required when campaign.activity_log_enabled)
+    #[account(
+        mut,
+        constraint = activity_log.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub activity_log: Option<Account<'info, CampaignActivityLog>>,
+
+    /// Protocol TVL aggregates (required when config.protocol_stats_enabled)
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Claimer's blocklist entry (required when config.enforce_recipient_blocklist is set)
+    /// CHECK: May not exist; the claimer is blocked only if it does
+    #[account(seeds = [b"blocked_recipient", claimer.key().as_ref()], bump)]
+    pub blocked_recipient: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32])]
+pub struct ClaimAirdropPrivate<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    /// Campaign token vault (ATA owned by campaign PDA)
     #[account(
         mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = campaign,
+    )]
+    pub campaign_vault: Account<'info, TokenAccount>,
+
+    /// Token mint for the campaign
+    #[account(
+        constraint = token_mint.key() == campaign.token_mint @ VouchError::InvalidMint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Verifier that signed the claim attestation
+    #[account(
         seeds = [b"verifier", verifier_account.verifier.as_ref()],
-        bump = verifier_account.bump,
-        constraint = verifier_account.is_active @ VouchError::VerifierNotAuthorized
+        bump = verifier_account.bump
     )]
     pub verifier_account: Account<'info, VerifierAccount>,
 
-    #[account(
-        mut,
-        seeds = [b"nullifier", nullifier.as_ref()],
-        bump = nullifier_account.bump,
-        constraint = !nullifier_account.is_used @ VouchError::NullifierAlreadyUsed
-    )]
-    pub nullifier_account: Account<'info, NullifierAccount>,
+    /// Consumed marker for the nullifier; init fails if it already claimed
+    #[account(
+        init,
+        payer = claimer,
+        space = 8 + PrivateClaimMarker::INIT_SPACE,
+        seeds = [b"private_claim", campaign.key().as_ref(), nullifier.as_ref()],
+        bump
+    )]
+    pub claim_marker: Account<'info, PrivateClaimMarker>,
+
+    /// ClaimStatus of the registration and merkle paths for the same nullifier;
+    /// must not exist
+    /// CHECK: Only emptiness is read (see require_unclaimed_marker)
+    #[account(
+        seeds = [b"claim_status", campaign.key().as_ref(), nullifier.as_ref()],
+        bump
+    )]
+    pub claim_status: UncheckedAccount<'info>,
+
+    /// Claimer's token account to receive tokens
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        associated_token::mint = token_mint,
+        associated_token::authority = claimer,
+    )]
+    pub claimer_token_account: Account<'info, TokenAccount>,
+
+    /// Creator's fee account (required when campaign_claim_fee_bps > 0)
+    #[account(
+        mut,
+        constraint = claim_fee_account.key() == campaign.claim_fee_account @ VouchError::InvalidClaimFee,
+        constraint = claim_fee_account.mint == campaign.token_mint @ VouchError::InvalidMint
+    )]
+    pub claim_fee_account: Option<Account<'info, TokenAccount>>,
+
+    /// Campaign activity feed (required when campaign.activity_log_enabled)
+    #[account(
+        mut,
+        constraint = activity_log.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub activity_log: Option<Account<'info, CampaignActivityLog>>,
+
+    /// Protocol TVL aggregates (required when config.protocol_stats_enabled)
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Claimer's blocklist entry (required when config.enforce_recipient_blocklist is set)
+    /// CHECK: May not exist; the claimer is blocked only if it does
+    #[account(seeds = [b"blocked_recipient", claimer.key().as_ref()], bump)]
+    pub blocked_recipient: Option<UncheckedAccount<'info>>,
+
+    /// Signature's UsedSignature PDA (required when config.track_used_signatures is set)
+    /// CHECK: Address is checked against the derived PDA and created in the handler
+    #[account(mut)]
+    pub used_signature: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// === Multi-Asset Campaign Accounts ===
+
+#[derive(Accounts)]
+pub struct ConfigureCampaignAssets<'info> {
+    #[account(
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CampaignAssetBasket::INIT_SPACE,
+        seeds = [b"campaign_assets", campaign.key().as_ref()],
+        bump
+    )]
+    pub asset_basket: Account<'info, CampaignAssetBasket>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundCampaignAsset<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump,
+        constraint = campaign.creator == creator.key() || campaign_manager.is_some() @ VouchError::Unauthorized
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"campaign_assets", campaign.key().as_ref()],
+        bump = asset_basket.bump
+    )]
+    pub asset_basket: Account<'info, CampaignAssetBasket>,
+
+    /// Asset vault (ATA owned by campaign PDA)
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = campaign,
+    )]
+    pub asset_vault: Account<'info, TokenAccount>,
+
+    /// Mint of the asset being funded (checked against the basket in the handler)
+    pub token_mint: Account<'info, Mint>,
+
+    /// Creator's token account to fund from
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    /// Protocol TVL aggregates (required when config.protocol_stats_enabled)
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// Signer's manager registration; required when the signer is not the creator
+    #[account(
+        seeds = [b"campaign_manager", campaign.key().as_ref(), creator.key().as_ref()],
+        bump = campaign_manager.bump
+    )]
+    pub campaign_manager: Option<Account<'info, CampaignManager>>,
+}
+
+/// Claim additional assets from a multi-asset campaign
+/// Asset vaults and claimer token accounts are passed via remaining_accounts
+#[derive(Accounts)]
+pub struct ClaimAirdropMulti<'info> {
+    #[account(
+        seeds = [b"config", config_epoch_seed(&config.config_epoch)],
+        bump = config.bump,
+        constraint = !config.retired @ VouchError::ConfigRetired
+    )]
+    pub config: Account<'info, ConfigAccount>,
+
+    #[account(
+        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"campaign_assets", campaign.key().as_ref()],
+        bump = asset_basket.bump
+    )]
+    pub asset_basket: Account<'info, CampaignAssetBasket>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_registration", campaign.key().as_ref(), registration.nullifier.as_ref()],
+        bump = registration.bump,
+        constraint = registration.campaign == campaign.key() @ VouchError::InvalidCampaign
+    )]
+    pub registration: Account<'info, AirdropRegistrationAccount>,
+
+    /// Credential the registration was made with (required unless it is an open
+    /// registration, see check_registration_credential)
+    #[account(
+        seeds = [
+            b"nullifier",
+            nullifier_namespace_seed(&nullifier_account.namespace),
+            registration.nullifier.as_ref()
+        ],
+        bump = nullifier_account.bump,
+        constraint = !nullifier_account.is_revoked @ VouchError::CredentialRevoked
+    )]
+    pub nullifier_account: Option<Account<'info, NullifierAccount>>,
+
+    /// Protocol TVL aggregates (required when config.protocol_stats_enabled)
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Claimer's blocklist entry (required when config.enforce_recipient_blocklist is set)
+    /// CHECK: May not exist; the claimer is blocked only if it does
+    #[account(seeds = [b"blocked_recipient", claimer.key().as_ref()], bump)]
+    pub blocked_recipient: Option<UncheckedAccount<'info>>,
+
+    pub claimer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// === State ===
+
+#[account]
+#[derive(InitSpace)]
+pub struct ConfigAccount {
+    /// Protocol admin with full control
+    pub admin: Pubkey,
+    /// Authority that can pause/unpause (initially same as admin)
+    pub pause_authority: Pubkey,
+    /// Number of active verifiers
+    pub verifier_count: u32,
+    /// Whether the protocol is paused
+    pub is_paused: bool,
+    /// Maximum proofs per wallet per day
+    pub max_proofs_per_day: u32,
+    /// Cooldown seconds between proofs
+    pub cooldown_seconds: i64,
+    /// Maximum age of epoch in days (proofs older than this are rejected)
+    pub max_epoch_age: u64,
+    /// Total proofs verified across all wallets
+    pub total_proofs_verified: u64,
+    /// PDA bump
+    pub bump: u8,
+    // Fields below are appended after `bump` so the v2 layout (106 bytes,
+    // see migrate_config and fix_config_layout) keeps its offsets and
+    // migrate_config zero-fills them for existing configs.
+    /// Whether record_attestation requires a commitment owned by the recipient
+    pub require_commitment: bool,
+    /// proof_type_bit mask of built-in proof types require_commitment applies to
+    /// (0 = every attestation; registered proof types are always gated)
+    pub commitment_proof_types: u8,
+    /// Seconds before the 24h boundary at which the daily counter may reset
+    pub reset_skew_tolerance_seconds: i64,
+    /// Protocol-wide minimum hold between registration and claim (0 = no floor)
+    pub global_min_hold_seconds: i64,
+    /// Whether record_attestation requires a proof commitment
+    pub require_proof_commitment: bool,
+    /// Maximum campaigns active (not completed) at once (0 = unlimited)
+    pub max_concurrent_open_campaigns: u32,
+    /// Campaigns created and not yet completed
+    pub active_campaign_count: u32,
+    /// Off-chain governance key allowed to sign verifier sets (default = disabled)
+    pub governance_key: Pubkey,
+    /// Nonce bound into the next signed verifier set (replay protection)
+    pub governance_nonce: u64,
+    /// Whether verifiers may upgrade a used nullifier's proof type in place
+    pub allow_credential_upgrades: bool,
+    /// Preceding instructions searched for the Ed25519 verify instruction (0 is treated as 1)
+    pub max_ed25519_scan_distance: u8,
+    /// Derive protocol time from slots instead of unix_timestamp (see effective_now)
+    pub use_slot_based_time: bool,
+    /// Assumed slot duration for slot-based time
+    pub ms_per_slot: u64,
+    /// Slot at which slot-based time was anchored
+    pub time_anchor_slot: u64,
+    /// Effective time at time_anchor_slot
+    pub time_anchor_timestamp: i64,
+    /// Whether pause_protocol also freezes registration, funding and claims
+    pub campaigns_respect_protocol_pause: bool,
+    /// Share of reaped registration rent paid to the reaper, in basis points
+    pub reaper_bounty_bps: u16,
+    /// Maximum seconds between proof generation and attestation (0 = no limit)
+    pub max_proof_age_seconds: i64,
+    /// Whether a matched Ed25519 instruction must not be shared with an earlier program instruction
+    pub exclusive_ed25519_instructions: bool,
+    /// Grace period before a scheduled verifier removal takes effect (0 = immediate)
+    pub verifier_removal_delay_seconds: i64,
+    /// Admin nominated by transfer_admin, awaiting accept_admin (default = none)
+    pub pending_admin: Pubkey,
+    /// Developer reputation proofs per wallet per day (global cap still applies)
+    pub max_dev_proofs_per_day: u32,
+    /// Whale trading proofs per wallet per day (global cap still applies)
+    pub max_whale_proofs_per_day: u32,
+    /// Attestations require at least this many active verifiers
+    pub min_active_verifiers: u32,
+    /// Whether campaign funding and claims update ProtocolStats
+    pub protocol_stats_enabled: bool,
+    /// Distinct verifier signatures an attestation needs (1 = single verifier)
+    pub required_signatures: u8,
+    /// Minimum seconds between one wallet's claims across all campaigns (0 = disabled)
+    pub global_claim_cooldown_seconds: i64,
+    /// Retention before a used nullifier can be closed (floored by the replay window)
+    pub min_nullifier_age_seconds: i64,
+    /// Whether attestations must sign the recipient's on-chain commitment
+    pub bind_commitment: bool,
+    /// ShadowWire program used for private distribution (default = not configured)
+    pub shadow_wire_program_id: Pubkey,
+    /// Attestations per window before the protocol pauses itself (0 = disabled)
+    pub max_attestations_per_window: u32,
+    /// Window over which max_attestations_per_window is measured
+    pub attestation_window_seconds: i64,
+    /// Decaying attestation counter, in ATTESTATION_RATE_SCALE units
+    pub attestation_rate_level: u64,
+    /// When attestation_rate_level was last updated
+    pub attestation_rate_updated_at: i64,
+    /// Whether attestations and claims reject BlockedRecipient entries
+    pub enforce_recipient_blocklist: bool,
+    /// Seed domain of this config (see config_epoch_seed)
+    pub config_epoch: u8,
+    /// Superseded by a later config epoch; every instruction rejects it
+    pub retired: bool,
+    /// Whether record_attestation records each signature in a UsedSignature PDA
+    pub track_used_signatures: bool,
+    /// Protocol-wide cap on attestations per UTC day (0 = unlimited)
+    pub max_global_proofs_per_day: u32,
+    /// Attestations recorded since global_day_start
+    pub global_proofs_today: u32,
+    /// Start of the UTC day global_proofs_today counts
+    pub global_day_start: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VerifierAccount {
+    pub verifier: Pubkey,
+    pub is_active: bool,
+    pub added_at: i64,
+    pub attestation_count: u64,
+    /// Latest verifier-signed merkle root of eligible credentials (zero = none)
+    pub merkle_root: [u8; 32],
+    /// When the current merkle root was published
+    pub merkle_root_published_at: i64,
+    /// Timestamp of the verifier's most recent attestation (0 = none)
+    pub last_attestation_at: i64,
+    /// When a scheduled removal takes effect (0 = none scheduled)
+    pub removal_scheduled_at: i64,
+    pub bump: u8,
+}
+
+/// Short-lived set of pre-authorized attestations (see begin_attestation_batch)
+#[account]
+#[derive(InitSpace)]
+pub struct AttestationBatch {
+    pub verifier: Pubkey,
+    pub batch_id: [u8; 32],
+    /// Merkle root over build_batch_attestation_leaf entries
+    pub entries_root: [u8; 32],
+    pub entry_count: u16,
+    pub applied_count: u16,
+    pub expires_at: i64,
+    /// Opener, refunded on close
+    pub payer: Pubkey,
+    pub bump: u8,
+}
+
+/// One verifier's signature in a threshold attestation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct VerifierSignature {
+    pub verifier: Pubkey,
+    pub signature: [u8; 64],
+}
+
+/// Attestation fields and signer set for record_attestation_threshold
+/// Every signature covers the same build_attestation_message_v3 message.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ThresholdAttestationArgs {
+    pub attestation_hash: [u8; 32],
+    pub proof_type_value: u8,
+    pub nullifier: [u8; 32],
+    pub epoch: u64,
+    pub data_hash: [u8; 32],
+    pub signatures: Vec<VerifierSignature>,
+    pub proof_commitment: Option<[u8; 32]>,
+    pub proof_generated_at: i64,
+    pub expires_at: i64,
+}
+
+/// A proof timestamp in a wallet's rolling rate-limit window
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
+pub struct RecentProof {
+    /// 0 = empty slot
+    pub timestamp: i64,
+    pub proof_type: ProofType,
+}
+
+/// Rate limit tracking per wallet
+///
+/// Limits apply over a rolling 24h window built from the last
+/// RATE_LIMIT_WINDOW_SIZE proof timestamps. Tradeoff: the account grows by
+/// RATE_LIMIT_WINDOW_SIZE entries and daily limits are capped at that size,
+/// in exchange for no fixed reset boundary to game.
+#[account]
+#[derive(InitSpace)]
+pub struct WalletRateLimit {
+    /// The wallet being rate limited
+    pub wallet: Pubkey,
+    /// Proofs in the rolling 24h window (as of the last proof)
+    pub proofs_today: u32,
+    /// Developer reputation proofs in the rolling window (as of the last proof)
+    pub dev_proofs_today: u32,
+    /// Whale trading proofs in the rolling window (as of the last proof)
+    pub whale_proofs_today: u32,
+    /// Timestamp of last proof submission
+    pub last_proof_at: i64,
+    /// Oldest proof in the rolling window (as of the last proof); 0 until the first proof
+    pub day_start: i64,
+    /// Total proofs ever submitted by this wallet
+    pub total_proofs: u64,
+    /// Ring buffer of recent proofs; recent_head is the next slot to write
+    pub recent_proofs: [RecentProof; RATE_LIMIT_WINDOW_SIZE],
+    pub recent_head: u8,
+    /// Skips cooldown and daily limits (see set_rate_limit_exempt)
+    pub exempt: bool,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Cross-campaign claim tracking per wallet (see set_global_claim_cooldown)
+#[account]
+#[derive(InitSpace)]
+pub struct WalletClaimLimit {
+    /// The claiming wallet
+    pub wallet: Pubkey,
+    /// Timestamp of the wallet's last claim_airdrop (0 = never)
+    pub last_claim_at: i64,
+    /// Claims counted while the cooldown was enforced
+    pub total_claims: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct CommitmentAccount {
+    pub commitment: [u8; 32],
+    pub owner: Pubkey,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct NullifierAccount {
+    pub nullifier: [u8; 32],
+    pub is_used: bool,
+    pub used_at: i64,
+    /// Epoch (day number) when proof was generated - prevents replay attacks
+    pub epoch: u64,
+    /// Hash of private data - ensures data integrity
+    pub data_hash: [u8; 32],
+    pub proof_type: ProofType,
+    pub bump: u8,
+    /// Account layout version (see migrate_nullifier)
+    /// New fields must be appended after this one.
+    pub version: u8,
+    /// Hash of the raw ZK proof that justified this credential (zero = not recorded)
+    pub proof_commitment: [u8; 32],
+    /// Bitmask of every proof type this credential has held (see proof_type_bit)
+    pub credential_types: u8,
+    /// Canonical credential reference (see build_credential_id; zero until recorded)
+    pub credential_id: [u8; 32],
+    /// Verifier that issued the credential (default until recorded)
+    pub verifier: Pubkey,
+    /// Retained for an investigation; must not be closed while set
+    pub pinned: bool,
+    /// Account that paid the rent (may close it via close_nullifier)
+    pub payer: Pubkey,
+    /// Registry proof type (0 = built-in, see proof_type and effective_proof_type_id)
+    pub proof_type_id: u16,
+    /// Reputation score reported by the issuing verifier (see update_credential_score)
+    pub score: u64,
+    /// Signed timestamp of the latest score update (0 = never updated)
+    pub score_updated_at: i64,
+    /// Invalidated by revoke_attestation; stays used but grants no benefits
+    pub is_revoked: bool,
+    /// PDA namespace (see nullifier_namespace_seed; 0 = legacy/registry)
+    pub namespace: u8,
+    /// Wallet the credential was issued to (default until recorded)
+    pub recipient: Pubkey,
+}
+
+/// Return data for get_effective_limits
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct EffectiveLimits {
+    pub max_per_day: u32,
+    pub cooldown_seconds: i64,
+}
+
+/// Return data for get_verifier_profile
+/// Fields are only ever appended; check `version` before reading newer fields.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct VerifierProfile {
+    pub version: u8,
+    pub verifier: Pubkey,
+    pub is_active: bool,
+    pub added_at: i64,
+    pub attestation_count: u64,
+    pub last_attestation_at: i64,
+    pub merkle_root_published_at: i64,
+    /// Bitmask of proof types this verifier may attest (see proof_type_bit);
+    /// None while per-verifier proof type restrictions are not supported
+    pub allowed_proof_types: Option<u8>,
+    /// None while verifier staking is not supported
+    pub staked_amount: Option<u64>,
+}
+
+/// Return data for nullifier_account_info
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct NullifierAccountStatus {
+    pub pda: Pubkey,
+    pub bump: u8,
+    pub is_initialized: bool,
+    pub is_used: bool,
+    pub is_revoked: bool,
+}
+
+/// Credential category added through the registry (see register_proof_type)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct RegisteredProofType {
+    pub id: u16,
+    #[max_len(MAX_PROOF_TYPE_NAME_LEN)]
+    pub name: String,
+    /// Added to a campaign's base_amount for registrations of this type
+    pub airdrop_bonus: u64,
+    pub is_active: bool,
+}
+
+/// Admin-managed proof types beyond the built-in ProofType values
+#[account]
+#[derive(InitSpace)]
+pub struct ProofTypeRegistry {
+    #[max_len(MAX_REGISTERED_PROOF_TYPES)]
+    pub entries: Vec<RegisteredProofType>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
+pub enum ProofType {
+    #[default]
+    Unset,
+    DeveloperReputation,
+    WhaleTrading,
+}
+
+// === Airdrop Registry State ===
+
+#[account]
+#[derive(InitSpace)]
+pub struct AirdropCampaign {
+    /// Unique campaign identifier (hash)
+    pub campaign_id: [u8; 32],
+    /// Campaign creator (project distributing tokens)
+    pub creator: Pubkey,
+    /// Human-readable campaign name
+    #[max_len(64)]
+    pub name: String,
+    /// Token mint for the airdrop
+    pub token_mint: Pubkey,
+    /// Base amount for anyone (tiered: open registration)
+    pub base_amount: u64,
+    /// Bonus amount for verified developers (gets base + dev_bonus)
+    pub dev_bonus: u64,
+    /// Bonus amount for verified whales (gets base + whale_bonus)
+    pub whale_bonus: u64,
+    /// Registration deadline (unix timestamp)
+    pub registration_deadline: i64,
+    /// Campaign status
+    pub status: CampaignStatus,
+    /// Total number of registrations
+    /// Registration and claim counters are u64 so very large (e.g. merkle-claim)
+    /// campaigns never hit the ceiling; campaigns created under the old u32
+    /// layout are converted by migrate_campaign.
+    pub total_registrations: u64,
+    /// Number of open (unverified) registrations
+    pub open_registrations: u64,
+    /// Number of developer registrations
+    pub dev_registrations: u64,
+    /// Number of whale registrations
+    pub whale_registrations: u64,
+    /// Campaign creation timestamp
+    pub created_at: i64,
+    /// Campaign completion timestamp (0 if not completed)
+    pub completed_at: i64,
+    /// Current vault balance (tokens available for claims)
+    pub vault_balance: u64,
+    /// Total number of claims made
+    pub total_claimed: u64,
+    /// PDA bump
+    pub bump: u8,
+    /// Account layout version (see migrate_campaign)
+    /// New fields must be appended after this one.
+    pub version: u8,
+    /// Creator fee taken from each claim, in basis points
+    pub campaign_claim_fee_bps: u16,
+    /// Token account receiving the creator fee
+    pub claim_fee_account: Pubkey,
+    /// ShadowWire relay key that signs distribution receipts (default = no proof required)
+    pub distribution_relay: Pubkey,
+    /// Minimum seconds between registration and claim (global floor still applies)
+    pub min_hold_seconds: i64,
+    /// Penalty for claiming inside the hold period (0 = early claims rejected)
+    pub early_claim_penalty_bps: u16,
+    /// Seconds added to registration_deadline per funding top-up (0 = disabled)
+    pub deadline_bump_per_fund: i64,
+    /// Cap on total deadline extension from funding
+    pub max_deadline_extension: i64,
+    /// Total deadline extension applied so far
+    pub total_deadline_extension: i64,
+    /// Sum of every registrant's maximum allocation (base + tier bonus + early-bird bonus)
+    pub total_allocated: u64,
+    /// Sum of amounts released by claims
+    pub total_claimed_amount: u64,
+    /// Whether registrations, claims and funding are recorded in the CampaignActivityLog
+    pub activity_log_enabled: bool,
+    /// Whether each claim needs claim_signer's Ed25519 authorization
+    pub require_claim_signature: bool,
+    /// Key that signs claim authorizations (creator or a dedicated key)
+    pub claim_signer: Pubkey,
+    /// Maximum registrations sharing one ShadowWire address (0 = unlimited)
+    pub max_registrations_per_shadow_wire: u16,
+    /// Only credentials issued by this verifier may register (None = any verifier)
+    pub required_verifier: Option<Pubkey>,
+    /// Claims close after this timestamp (0 = no claim deadline, never reapable)
+    pub claim_deadline: i64,
+    /// proof_type_bit mask of tiers allowed to register (bit 0 = open; 0 = everyone)
+    pub allowed_proof_types: u8,
+    /// Early-bird bonus for registering at campaign open (0 = no decay bonus)
+    pub decay_max_bonus: u64,
+    /// Early-bird bonus for registering at the registration deadline
+    pub decay_min_bonus: u64,
+    /// Timestamp the linear vesting schedule starts from
+    pub vesting_start: i64,
+    /// Seconds over which entitlements vest (0 = no vesting, claim in full)
+    pub vesting_duration: i64,
+    /// Seconds after vesting_start before the first claim is allowed
+    pub cliff_seconds: i64,
+    /// Root of the (nullifier, amount, proof_type) eligibility tree for
+    /// claim_airdrop_merkle (all zeros = merkle claims disabled)
+    pub merkle_root: [u8; 32],
+    /// Creator-controlled halt of registrations and claims (see pause_campaign)
+    pub is_paused: bool,
+    /// Number of registrations with a registry proof type
+    pub registry_registrations: u64,
+    /// Per-proof-type bonus table (see create_airdrop_campaign)
+    #[max_len(MAX_CAMPAIGN_PROOF_TYPE_BONUSES)]
+    pub proof_type_bonuses: Vec<ProofTypeBonus>,
+    /// Whether register_for_airdrop_open is refused (see set_open_registration_enabled)
+    pub open_registration_disabled: bool,
+    /// Whether claim_airdrop_private is accepted (see set_private_claims_enabled)
+    pub private_claims_enabled: bool,
+    /// Whether registrants must be the credential's recipient
+    /// (see set_require_credential_owner)
+    pub require_credential_owner: bool,
+}
+
+/// AirdropCampaign layout before versioning, with u32 counters
+/// Only read by migrate_campaign to convert campaigns created under it.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyAirdropCampaign {
+    pub campaign_id: [u8; 32],
+    pub creator: Pubkey,
+    #[max_len(64)]
+    pub name: String,
+    pub token_mint: Pubkey,
+    pub base_amount: u64,
+    pub dev_bonus: u64,
+    pub whale_bonus: u64,
+    pub registration_deadline: i64,
+    pub status: CampaignStatus,
+    pub total_registrations: u32,
+    pub open_registrations: u32,
+    pub dev_registrations: u32,
+    pub whale_registrations: u32,
+    pub created_at: i64,
+    pub completed_at: i64,
+    pub vault_balance: u64,
+    pub total_claimed: u32,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AirdropRegistrationAccount {
+    /// Campaign this registration belongs to
+    pub campaign: Pubkey,
+    /// Nullifier from the Vouch credential
+    pub nullifier: [u8; 32],
+    /// User's ShadowWire address for private receipt
+    #[max_len(44)]
+    pub shadow_wire_address: String,
+    /// Type of credential (dev or whale)
+    pub proof_type: ProofType,
+    /// Registration timestamp
+    pub registered_at: i64,
+    /// Whether tokens have been distributed (ShadowWire flow)
+    pub is_distributed: bool,
+    /// Distribution timestamp (0 if not distributed)
+    pub distributed_at: i64,
+    /// Distribution transaction signature
+    #[max_len(88)]
+    pub distribution_tx: String,
+    /// Whether the full entitlement has been claimed (direct claim flow)
+    pub is_claimed: bool,
+    /// Timestamp of the latest claim (0 if not claimed)
+    pub claimed_at: i64,
+    /// Cumulative amount of tokens claimed (partial claims on vesting campaigns)
+    pub claimed_amount: u64,
+    /// PDA bump
+    pub bump: u8,
+    /// Account layout version (see migrate_registration)
+    /// New fields must be appended after this one.
+    pub version: u8,
+    /// Amount delivered via ShadowWire (as attested by the relay receipt)
+    pub distributed_amount: u64,
+    /// Whether distribution was proven by a relay-signed receipt
+    pub distribution_receipt_verified: bool,
+    /// Bitmask of claimed additional assets (bit i = CampaignAssetBasket.assets[i])
+    pub assets_claimed: u8,
+    /// Internal creator note (e.g. "flagged for manual review")
+    #[max_len(100)]
+    pub memo: String,
+    /// Set while claim_airdrop performs token transfers (reentrancy guard)
+    pub claim_in_progress: bool,
+    /// Allocation fixed at registration (0 = registered before this was
+    /// stored; claim derives base + tier bonus from the campaign instead)
+    pub allocation: u64,
+    /// Registry ID of the registering credential (1 and 2 for the built-in types)
+    pub proof_type_id: u16,
+    /// Made via register_for_airdrop_open: `nullifier` holds the registrant's
+    /// pubkey, not a credential nullifier. Registrations made before this flag
+    /// existed read false; for those, proof_type Unset marks an open registration.
+    pub is_open_registration: bool,
+    /// Only wallet allowed to claim (default = unbound: open registrations and
+    /// registrations made before this was stored)
+    pub recipient: Pubkey,
+}
+
+/// Registrations per (campaign, ShadowWire address) for the reuse cap
+#[account]
+#[derive(InitSpace)]
+pub struct ShadowWireUsage {
+    pub campaign: Pubkey,
+    /// sha256 of the ShadowWire address (PDA seed)
+    pub address_hash: [u8; 32],
+    /// Full address, checked on every use to rule out hash collisions
+    #[max_len(44)]
+    pub shadow_wire_address: String,
+    pub registrations: u16,
+    pub bump: u8,
+}
+
+/// Campaign bonus for one proof type ID (1 and 2 are the built-in types)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct ProofTypeBonus {
+    pub proof_type_id: u16,
+    pub bonus_amount: u64,
+}
+
+/// Reusable campaign parameters (see create_campaign_template)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
+pub struct CampaignParams {
+    pub token_mint: Pubkey,
+    pub base_amount: u64,
+    pub dev_bonus: u64,
+    pub whale_bonus: u64,
+    pub campaign_claim_fee_bps: u16,
+    pub claim_fee_account: Pubkey,
+}
+
+/// Creator-owned parameter set for recurring campaigns
+#[account]
+#[derive(InitSpace)]
+pub struct CampaignTemplate {
+    pub creator: Pubkey,
+    pub template_id: [u8; 32],
+    pub params: CampaignParams,
+    /// Registration deadline offset from campaign creation
+    pub registration_window_seconds: i64,
+    /// Copied to AirdropCampaign.allowed_proof_types
+    pub allowed_proof_types: u8,
+    /// Copied to AirdropCampaign.proof_type_bonuses
+    #[max_len(MAX_CAMPAIGN_PROOF_TYPE_BONUSES)]
+    pub proof_type_bonuses: Vec<ProofTypeBonus>,
+    /// Claim deadline offset from the registration deadline (0 = no claim deadline)
+    pub claim_window_seconds: i64,
+    pub campaigns_created: u64,
+    pub bump: u8,
+}
+
+/// Wallet granted shared management rights over one campaign (see add_campaign_manager)
+#[account]
+#[derive(InitSpace)]
+pub struct CampaignManager {
+    pub campaign: Pubkey,
+    pub manager: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+/// Claim marker for one credential in one campaign, shared by the registration
+/// and merkle claim paths (claim_airdrop_private uses PrivateClaimMarker and
+/// requires this PDA to be empty, and vice versa).
+/// leaf is set only by claim_airdrop_merkle; registration claims leave it zero
+/// and accumulate vesting installments in amount.
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimStatus {
+    pub campaign: Pubkey,
+    pub nullifier: [u8; 32],
+    pub leaf: [u8; 32],
+    pub claimer: Pubkey,
+    pub amount: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
+
+/// Replay record for a verifier signature (see config.track_used_signatures)
+#[account]
+#[derive(InitSpace)]
+pub struct UsedSignature {
+    pub signature: [u8; 64],
+    /// Nullifier the signature covers; the batch id or merkle root for
+    /// begin_attestation_batch and publish_verifier_root signatures
+    pub nullifier: [u8; 32],
+    pub verifier: Pubkey,
+    pub used_at: i64,
+    pub bump: u8,
+}
+
+/// Consumed marker for one nullifier's private claim (see claim_airdrop_private)
+/// Deliberately stores neither the nullifier nor the claimer.
+#[account]
+#[derive(InitSpace)]
+pub struct PrivateClaimMarker {
+    pub campaign: Pubkey,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
+
+/// Compliance blocklist entry; exists while the recipient is blocked
+#[account]
+#[derive(InitSpace)]
+pub struct BlockedRecipient {
+    pub recipient: Pubkey,
+    pub blocked_by: Pubkey,
+    pub blocked_at: i64,
+    pub bump: u8,
+}
+
+/// Program-owned escrow holding a beneficiary's claimed tokens (see claim_airdrop_to_custody)
+#[account]
+#[derive(InitSpace)]
+pub struct CustodyEscrow {
+    pub campaign: Pubkey,
+    pub beneficiary: Pubkey,
+    /// Wallet whose co-signature releases the escrow early (default = none)
+    pub custodian: Pubkey,
+    /// Beneficiary may withdraw alone from this time (0 = custodian only)
+    pub release_at: i64,
+    pub token_mint: Pubkey,
+    /// Net tokens escrowed so far
+    pub amount: u64,
+    /// Most recent funding (0 = never funded)
+    pub funded_at: i64,
+    pub bump: u8,
+}
+
+/// Per-wallet registration preferences
+#[account]
+#[derive(InitSpace)]
+pub struct WalletProfile {
+    pub wallet: Pubkey,
+    /// ShadowWire address used by register_for_airdrop_with_default
+    #[max_len(44)]
+    pub default_shadow_wire_address: String,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// Reference to one of a wallet's registrations
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
+pub struct WalletRegistrationEntry {
+    pub campaign: Pubkey,
+    pub registration: Pubkey,
+}
+
+/// Opt-in index of a wallet's airdrop registrations across campaigns
+#[account]
+#[derive(InitSpace)]
+pub struct WalletRegistrationIndex {
+    pub wallet: Pubkey,
+    #[max_len(MAX_WALLET_INDEX_ENTRIES)]
+    pub entries: Vec<WalletRegistrationEntry>,
+    /// Registrations not indexed because the index was full
+    pub dropped_entries: u32,
+    pub bump: u8,
+}
+
+/// Kind of entry in a campaign activity feed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
+pub enum CampaignActivityKind {
+    #[default]
+    None,
+    Registration,
+    Claim,
+    Funding,
+}
+
+/// One entry in a campaign activity feed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
+pub struct CampaignActivityEntry {
+    pub kind: CampaignActivityKind,
+    /// Nullifier for registrations/claims, funder pubkey for funding
+    pub subject: [u8; 32],
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Rolling recent-activity feed for a campaign (ring buffer)
+/// Entries are in write order starting at `head` once the buffer has wrapped;
+/// before that, entries[0..len] are oldest to newest.
+#[account]
+#[derive(InitSpace)]
+pub struct CampaignActivityLog {
+    pub campaign: Pubkey,
+    /// Index the next entry will be written to
+    pub head: u8,
+    /// Number of valid entries (caps at CAMPAIGN_ACTIVITY_LOG_SIZE)
+    pub len: u8,
+    /// Entries ever written
+    pub total_entries: u64,
+    pub entries: [CampaignActivityEntry; CAMPAIGN_ACTIVITY_LOG_SIZE],
+    pub bump: u8,
+}
+
+/// Return data for campaign_claim_progress
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CampaignClaimProgress {
+    pub total_allocated: u64,
+    pub total_claimed_amount: u64,
+    pub total_claimed: u64,
+    pub total_registrations: u64,
+    /// total_claimed_amount / total_allocated in basis points
+    pub progress_bps: u16,
+}
+
+/// Return data for campaign_registration_breakdown
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CampaignRegistrationBreakdown {
+    pub open: u64,
+    pub dev: u64,
+    pub whale: u64,
+    /// Registrations with a registry proof type
+    pub registry: u64,
+    pub total: u64,
+    /// Whether open + dev + whale + registry == total
+    pub consistent: bool,
+}
+
+/// Funding and claim totals for one mint across all campaign vaults
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct TrackedMint {
+    pub mint: Pubkey,
+    pub total_funded: u64,
+    pub total_claimed: u64,
+}
+
+/// Protocol-wide campaign vault aggregates (see init_protocol_stats)
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolStats {
+    #[max_len(MAX_TVL_TRACKED_MINTS)]
+    pub tracked_mints: Vec<TrackedMint>,
+    /// Funding/claim flows for mints beyond MAX_TVL_TRACKED_MINTS (not in TVL)
+    pub untracked_flows: u64,
+    pub bump: u8,
+}
+
+/// Per-mint entry in protocol_tvl return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct MintTvl {
+    pub mint: Pubkey,
+    pub total_funded: u64,
+    pub total_claimed: u64,
+    /// total_funded - total_claimed
+    pub total_value_locked: u64,
+}
+
+/// Return data for protocol_tvl
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ProtocolTvl {
+    pub mints: Vec<MintTvl>,
+    pub untracked_flows: u64,
+}
+
+/// Additional asset in a multi-asset campaign
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
+pub struct CampaignAsset {
+    /// Token mint for this asset
+    pub mint: Pubkey,
+    /// Flat amount each registrant can claim
+    pub amount_per_claim: u64,
+    /// Current vault balance for this asset
+    pub vault_balance: u64,
+    /// Number of claims made for this asset
+    pub total_claimed: u64,
+}
+
+/// Additional assets distributed by a campaign alongside its primary mint
+#[account]
+#[derive(InitSpace)]
+pub struct CampaignAssetBasket {
+    /// Campaign these assets belong to
+    pub campaign: Pubkey,
+    /// Additional assets (max MAX_CAMPAIGN_ASSETS)
+    #[max_len(3)]
+    pub assets: Vec<CampaignAsset>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
+pub enum CampaignStatus {
+    #[default]
+    Open,
+    RegistrationClosed,
+    Completed,
+    /// Cancelled by the creator before any claims (see cancel_campaign)
+    Cancelled,
+}
+
+// === Events ===
+
+#[event]
+pub struct ConfigEpochRotated {
+    pub admin: Pubkey,
+    pub previous_config: Pubkey,
+    pub previous_epoch: u8,
+    pub config: Pubkey,
+    pub config_epoch: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigInitialized {
+    pub admin: Pubkey,
+    pub max_proofs_per_day: u32,
+    pub cooldown_seconds: i64,
+    pub max_epoch_age: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolPaused {
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolUnpaused {
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RateLimitsUpdated {
+    pub admin: Pubkey,
+    pub old_max_proofs_per_day: u32,
+    pub new_max_proofs_per_day: u32,
+    pub old_max_dev_proofs_per_day: u32,
+    pub new_max_dev_proofs_per_day: u32,
+    pub old_max_whale_proofs_per_day: u32,
+    pub new_max_whale_proofs_per_day: u32,
+    pub old_cooldown_seconds: i64,
+    pub new_cooldown_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GlobalProofLimitUpdated {
+    pub admin: Pubkey,
+    pub old_max_global_proofs_per_day: u32,
+    pub new_max_global_proofs_per_day: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseAuthorityUpdated {
+    pub admin: Pubkey,
+    pub old_pause_authority: Pubkey,
+    pub new_pause_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferInitiated {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferCancelled {
+    pub admin: Pubkey,
+    pub cancelled_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResetSkewToleranceUpdated {
+    pub admin: Pubkey,
+    pub old_tolerance_seconds: i64,
+    pub new_tolerance_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaxProofAgeUpdated {
+    pub admin: Pubkey,
+    pub old_max_proof_age_seconds: i64,
+    pub new_max_proof_age_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TimeSourceUpdated {
+    pub admin: Pubkey,
+    pub use_slot_based_time: bool,
+    pub ms_per_slot: u64,
+    pub anchor_slot: u64,
+    pub anchor_timestamp: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Ed25519ExclusivityUpdated {
+    pub admin: Pubkey,
+    pub exclusive_ed25519_instructions: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Ed25519ScanDistanceUpdated {
+    pub admin: Pubkey,
+    pub old_scan_distance: u8,
+    pub new_scan_distance: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GlobalMinHoldUpdated {
+    pub admin: Pubkey,
+    pub old_min_hold_seconds: i64,
+    pub new_min_hold_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NullifierInitialized {
+    pub nullifier: [u8; 32],
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NullifierPinned {
+    pub nullifier: [u8; 32],
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NullifierUnpinned {
+    pub nullifier: [u8; 32],
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CampaignPausePolicyUpdated {
+    pub admin: Pubkey,
+    pub campaigns_respect_protocol_pause: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CredentialUpgradePolicyUpdated {
+    pub admin: Pubkey,
+    pub allow_credential_upgrades: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CredentialScoreUpdated {
+    pub nullifier: [u8; 32],
+    pub verifier: Pubkey,
+    pub old_score: u64,
+    pub new_score: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CredentialUpgraded {
+    pub nullifier: [u8; 32],
+    pub verifier: Pubkey,
+    pub old_proof_type: ProofType,
+    pub new_proof_type: ProofType,
+    pub credential_types: u8,
+    pub epoch: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ShadowWireProgramUpdated {
+    pub admin: Pubkey,
+    pub old_program_id: Pubkey,
+    pub new_program_id: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierClosed {
+    pub verifier: Pubkey,
+    pub admin: Pubkey,
+    pub attestation_count: u64,
+    pub added_at: i64,
+    pub last_attestation_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierCountRecounted {
+    pub admin: Pubkey,
+    pub old_count: u32,
+    pub new_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProofTypeRegistered {
+    pub admin: Pubkey,
+    pub proof_type_id: u16,
+    pub name: String,
+    pub airdrop_bonus: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProofTypeUpdated {
+    pub admin: Pubkey,
+    pub proof_type_id: u16,
+    pub airdrop_bonus: u64,
+    pub is_active: bool,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        seeds = [b"rate_limit", recipient.key().as_ref()],
-        bump = rate_limit.bump
-    )]
-    pub rate_limit: Account<'info, WalletRateLimit>,
+#[event]
+pub struct RegisteredAttestationRecorded {
+    pub nullifier: [u8; 32],
+    pub attestation_hash: [u8; 32],
+    pub proof_type_id: u16,
+    pub verifier: Pubkey,
+    pub recipient: Pubkey,
+    pub credential_id: [u8; 32],
+    pub timestamp: i64,
+}
 
-    /// The wallet receiving the credential
-    /// CHECK: This is the recipient of the credential NFT
-    pub recipient: UncheckedAccount<'info>,
+#[event]
+pub struct RecipientBlocklistEnforcementUpdated {
+    pub admin: Pubkey,
+    pub enforce_recipient_blocklist: bool,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+#[event]
+pub struct UsedSignatureTrackingUpdated {
+    pub admin: Pubkey,
+    pub track_used_signatures: bool,
+    pub timestamp: i64,
+}
 
-    /// Instructions sysvar for Ed25519 signature verification
-    /// CHECK: This is the instructions sysvar
-    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
-    pub instructions_sysvar: UncheckedAccount<'info>,
+#[event]
+pub struct RecipientBlocked {
+    pub admin: Pubkey,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-#[instruction(commitment: [u8; 32])]
-pub struct CreateCommitment<'info> {
-    #[account(
-        init,
-        payer = owner,
-        space = 8 + CommitmentAccount::INIT_SPACE,
-        seeds = [b"commitment", commitment.as_ref()],
-        bump
-    )]
-    pub commitment_account: Account<'info, CommitmentAccount>,
+#[event]
+pub struct RecipientUnblocked {
+    pub admin: Pubkey,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub owner: Signer<'info>,
+#[event]
+pub struct AutoPauseThresholdUpdated {
+    pub admin: Pubkey,
+    pub old_max_attestations_per_window: u32,
+    pub new_max_attestations_per_window: u32,
+    pub old_window_seconds: i64,
+    pub new_window_seconds: i64,
+    pub timestamp: i64,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct AutoPauseTriggered {
+    pub max_attestations_per_window: u32,
+    pub window_seconds: i64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-#[instruction(nullifier: [u8; 32])]
-pub struct InitNullifier<'info> {
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + NullifierAccount::INIT_SPACE,
-        seeds = [b"nullifier", nullifier.as_ref()],
-        bump
-    )]
-    pub nullifier_account: Account<'info, NullifierAccount>,
+#[event]
+pub struct GovernanceKeyUpdated {
+    pub admin: Pubkey,
+    pub old_governance_key: Pubkey,
+    pub new_governance_key: Pubkey,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+#[event]
+pub struct VerifierSetImported {
+    pub governance_key: Pubkey,
+    pub nonce: u64,
+    pub verifier_count: u32,
+    pub activated_count: u32,
+    pub timestamp: i64,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct MaxConcurrentCampaignsUpdated {
+    pub admin: Pubkey,
+    pub old_max_concurrent_open_campaigns: u32,
+    pub new_max_concurrent_open_campaigns: u32,
+    pub active_campaign_count: u32,
+    pub timestamp: i64,
 }
 
-// === Airdrop Registry Accounts ===
+#[event]
+pub struct ProofCommitmentRequirementUpdated {
+    pub admin: Pubkey,
+    pub require_proof_commitment: bool,
+    pub timestamp: i64,
+}
 
-#[derive(Accounts)]
-#[instruction(campaign_id: [u8; 32])]
-pub struct CreateAirdropCampaign<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = 8 + AirdropCampaign::INIT_SPACE,
-        seeds = [b"airdrop_campaign", campaign_id.as_ref()],
-        bump
-    )]
-    pub campaign: Account<'info, AirdropCampaign>,
+#[event]
+pub struct CommitmentRequirementUpdated {
+    pub admin: Pubkey,
+    pub require_commitment: bool,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+#[event]
+pub struct CommitmentProofTypesUpdated {
+    pub admin: Pubkey,
+    pub old_commitment_proof_types: u8,
+    pub new_commitment_proof_types: u8,
+    pub timestamp: i64,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct CommitmentBindingUpdated {
+    pub admin: Pubkey,
+    pub bind_commitment: bool,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct RegisterForAirdrop<'info> {
-    #[account(
-        mut,
-        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
-        bump = campaign.bump,
-        constraint = campaign.status == CampaignStatus::Open @ VouchError::CampaignNotOpen
-    )]
-    pub campaign: Account<'info, AirdropCampaign>,
+#[event]
+pub struct RateLimitInitialized {
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
 
-    #[account(
-        seeds = [b"nullifier", nullifier_account.nullifier.as_ref()],
-        bump = nullifier_account.bump,
-        constraint = nullifier_account.is_used @ VouchError::NullifierNotVerified
-    )]
-    pub nullifier_account: Account<'info, NullifierAccount>,
+#[event]
+pub struct NullifierClosed {
+    pub nullifier: [u8; 32],
+    pub authority: Pubkey,
+    pub receiver: Pubkey,
+    pub timestamp: i64,
+}
 
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + AirdropRegistrationAccount::INIT_SPACE,
-        seeds = [b"airdrop_registration", campaign.key().as_ref(), nullifier_account.nullifier.as_ref()],
-        bump
-    )]
-    pub registration: Account<'info, AirdropRegistrationAccount>,
+#[event]
+pub struct MinNullifierAgeUpdated {
+    pub admin: Pubkey,
+    pub old_min_age_seconds: i64,
+    pub new_min_age_seconds: i64,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+#[event]
+pub struct ClaimLimitInitialized {
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct GlobalClaimCooldownUpdated {
+    pub admin: Pubkey,
+    pub old_cooldown_seconds: i64,
+    pub new_cooldown_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestationRevoked {
+    pub nullifier: [u8; 32],
+    pub credential_id: [u8; 32],
+    /// Admin or issuing verifier
+    pub revoked_by: Pubkey,
+    /// Caller-defined reason code
+    pub reason: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RateLimitExemptionUpdated {
+    pub wallet: Pubkey,
+    pub admin: Pubkey,
+    pub exempt: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RateLimitReset {
+    pub wallet: Pubkey,
+    pub admin: Pubkey,
+    pub previous_proofs_today: u32,
+    /// Cooldown anchor cleared by the reset
+    pub previous_last_proof_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierAdded {
+    pub verifier: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierRemoved {
+    pub verifier: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierReactivated {
+    pub verifier: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierRotated {
+    pub old_verifier: Pubkey,
+    pub new_verifier: Pubkey,
+    pub admin: Pubkey,
+    pub attestation_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierRemovalScheduled {
+    pub verifier: Pubkey,
+    pub admin: Pubkey,
+    /// When the removal takes effect
+    pub removal_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierRemovalFinalized {
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RequiredSignaturesUpdated {
+    pub admin: Pubkey,
+    pub old_required_signatures: u8,
+    pub new_required_signatures: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MinActiveVerifiersUpdated {
+    pub admin: Pubkey,
+    pub old_min_active_verifiers: u32,
+    pub new_min_active_verifiers: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierRemovalDelayUpdated {
+    pub admin: Pubkey,
+    pub old_delay_seconds: i64,
+    pub new_delay_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestationRecorded {
+    pub nullifier: [u8; 32],
+    pub attestation_hash: [u8; 32],
+    /// Epoch (day number) when proof was generated
+    pub epoch: u64,
+    /// Hash of private data for integrity verification
+    pub data_hash: [u8; 32],
+    pub verifier: Pubkey,
+    pub proof_type: ProofType,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+    pub signature: [u8; 64],
+    /// Hash of the raw ZK proof (zero when not supplied)
+    pub proof_commitment: [u8; 32],
+    /// Canonical credential reference for off-chain indexing
+    pub credential_id: [u8; 32],
+    /// Every verifier that signed (just `verifier` outside threshold attestations)
+    pub signers: Vec<Pubkey>,
 }
 
-#[derive(Accounts)]
-#[instruction(shadow_wire_address: String)]
-pub struct RegisterForAirdropOpen<'info> {
-    #[account(
-        mut,
-        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
-        bump = campaign.bump,
-        constraint = campaign.status == CampaignStatus::Open @ VouchError::CampaignNotOpen
-    )]
-    pub campaign: Account<'info, AirdropCampaign>,
+#[event]
+pub struct VerifierRootPublished {
+    pub verifier: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+}
 
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + AirdropRegistrationAccount::INIT_SPACE,
-        seeds = [b"airdrop_registration", campaign.key().as_ref(), payer.key().as_ref()],
-        bump
-    )]
-    pub registration: Account<'info, AirdropRegistrationAccount>,
+#[event]
+pub struct MerkleAttestationRecorded {
+    pub nullifier: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub verifier: Pubkey,
+    pub proof_type: ProofType,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+#[event]
+pub struct AttestationBatchOpened {
+    pub batch: Pubkey,
+    pub verifier: Pubkey,
+    pub batch_id: [u8; 32],
+    pub entries_root: [u8; 32],
+    pub entry_count: u16,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct BatchedAttestationRecorded {
+    pub batch: Pubkey,
+    pub nullifier: [u8; 32],
+    pub verifier: Pubkey,
+    pub proof_type: ProofType,
+    pub recipient: Pubkey,
+    pub credential_id: [u8; 32],
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct CloseAirdropRegistration<'info> {
-    #[account(
-        mut,
-        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
-        bump = campaign.bump,
-        constraint = campaign.creator == creator.key() @ VouchError::Unauthorized
-    )]
-    pub campaign: Account<'info, AirdropCampaign>,
+#[event]
+pub struct AttestationBatchClosed {
+    pub batch: Pubkey,
+    pub verifier: Pubkey,
+    pub applied_count: u16,
+    pub entry_count: u16,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+#[event]
+pub struct CommitmentCreated {
+    pub owner: Pubkey,
+    pub commitment: [u8; 32],
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct MarkAirdropDistributed<'info> {
-    #[account(
-        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
-        bump = campaign.bump,
-        constraint = campaign.creator == creator.key() @ VouchError::Unauthorized
-    )]
-    pub campaign: Account<'info, AirdropCampaign>,
+// === Airdrop Events ===
 
-    #[account(
-        mut,
-        seeds = [b"airdrop_registration", campaign.key().as_ref(), registration.nullifier.as_ref()],
-        bump = registration.bump,
-        constraint = registration.campaign == campaign.key() @ VouchError::InvalidCampaign
-    )]
-    pub registration: Account<'info, AirdropRegistrationAccount>,
+#[event]
+pub struct AirdropCampaignCreated {
+    pub campaign_id: [u8; 32],
+    pub creator: Pubkey,
+    pub name: String,
+    pub token_mint: Pubkey,
+    pub base_amount: u64,
+    pub dev_bonus: u64,
+    pub whale_bonus: u64,
+    pub proof_type_bonuses: Vec<ProofTypeBonus>,
+    pub registration_deadline: i64,
+    /// Claim cutoff (0 = none)
+    pub claim_deadline: i64,
+    pub campaign_claim_fee_bps: u16,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+#[event]
+pub struct CampaignTemplateSaved {
+    pub template: Pubkey,
+    pub creator: Pubkey,
+    pub template_id: [u8; 32],
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct CompleteAirdropCampaign<'info> {
-    #[account(
-        mut,
-        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
-        bump = campaign.bump,
-        constraint = campaign.creator == creator.key() @ VouchError::Unauthorized
-    )]
-    pub campaign: Account<'info, AirdropCampaign>,
+#[event]
+pub struct CampaignCreatedFromTemplate {
+    pub campaign_id: [u8; 32],
+    pub template: Pubkey,
+    pub template_id: [u8; 32],
+    pub creator: Pubkey,
+    pub registration_deadline: i64,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+#[event]
+pub struct AirdropRegistration {
+    pub campaign_id: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub shadow_wire_address: String,
+    pub proof_type: ProofType,
+    /// Registry ID of the credential (1 and 2 for the built-in types)
+    pub proof_type_id: u16,
+    /// Campaign total_allocated after this registration
+    pub total_allocated: u64,
+    pub timestamp: i64,
 }
 
-/// Fund an airdrop campaign's token vault
-#[derive(Accounts)]
-pub struct FundAirdropCampaign<'info> {
-    #[account(
-        mut,
-        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
-        bump = campaign.bump,
-        constraint = campaign.creator == creator.key() @ VouchError::Unauthorized
-    )]
-    pub campaign: Account<'info, AirdropCampaign>,
+#[event]
+pub struct ShadowWireReuseLimitUpdated {
+    pub campaign_id: [u8; 32],
+    pub max_registrations_per_shadow_wire: u16,
+    pub timestamp: i64,
+}
 
-    /// Campaign token vault (ATA owned by campaign PDA)
-    #[account(
-        init_if_needed,
-        payer = creator,
-        associated_token::mint = token_mint,
-        associated_token::authority = campaign,
-    )]
-    pub campaign_vault: Account<'info, TokenAccount>,
+#[event]
+pub struct DefaultShadowWireUpdated {
+    pub wallet: Pubkey,
+    pub shadow_wire_address: String,
+    pub timestamp: i64,
+}
 
-    /// Token mint for the campaign
-    #[account(
-        constraint = token_mint.key() == campaign.token_mint @ VouchError::InvalidMint
-    )]
-    pub token_mint: Account<'info, Mint>,
+#[event]
+pub struct ReaperBountyUpdated {
+    pub admin: Pubkey,
+    pub old_reaper_bounty_bps: u16,
+    pub new_reaper_bounty_bps: u16,
+    pub timestamp: i64,
+}
 
-    /// Creator's token account to fund from
-    #[account(
-        mut,
-        associated_token::mint = token_mint,
-        associated_token::authority = creator,
-    )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct ClaimDeadlineUpdated {
+    pub campaign_id: [u8; 32],
+    pub claim_deadline: i64,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+#[event]
+pub struct RegistrationReaped {
+    pub campaign_id: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub reaper: Pubkey,
+    pub bounty_lamports: u64,
+    pub creator_lamports: u64,
+    pub timestamp: i64,
+}
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct RequiredVerifierUpdated {
+    pub campaign_id: [u8; 32],
+    pub required_verifier: Option<Pubkey>,
+    pub timestamp: i64,
 }
 
-/// Claim airdrop tokens from a campaign
-#[derive(Accounts)]
-pub struct ClaimAirdrop<'info> {
-    #[account(
-        mut,
-        seeds = [b"airdrop_campaign", campaign.campaign_id.as_ref()],
-        bump = campaign.bump
-    )]
-    pub campaign: Account<'info, AirdropCampaign>,
+#[event]
+pub struct ClaimSignatureRequirementUpdated {
+    pub campaign_id: [u8; 32],
+    pub require_claim_signature: bool,
+    pub claim_signer: Pubkey,
+    pub timestamp: i64,
+}
 
-    /// Campaign token vault (ATA owned by campaign PDA)
-    #[account(
-        mut,
-        associated_token::mint = token_mint,
-        associated_token::authority = campaign,
-    )]
-    pub campaign_vault: Account<'info, TokenAccount>,
+#[event]
+pub struct ProtocolStatsInitialized {
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
 
-    /// Token mint for the campaign
-    #[account(
-        constraint = token_mint.key() == campaign.token_mint @ VouchError::InvalidMint
-    )]
-    pub token_mint: Account<'info, Mint>,
+#[event]
+pub struct CampaignActivityLogEnabled {
+    pub campaign_id: [u8; 32],
+    pub creator: Pubkey,
+    pub timestamp: i64,
+}
 
-    /// Registration proving eligibility
-    #[account(
-        mut,
-        seeds = [b"airdrop_registration", campaign.key().as_ref(), registration.nullifier.as_ref()],
-        bump = registration.bump,
-        constraint = registration.campaign == campaign.key() @ VouchError::InvalidCampaign,
-        constraint = !registration.is_claimed @ VouchError::AlreadyClaimed
-    )]
-    pub registration: Account<'info, AirdropRegistrationAccount>,
+#[event]
+pub struct NullifierMigrated {
+    pub nullifier_account: Pubkey,
+    pub nullifier: [u8; 32],
+    pub old_version: u8,
+    pub new_version: u8,
+    pub old_size: u32,
+    pub new_size: u32,
+    pub timestamp: i64,
+}
 
-    /// Claimer's token account to receive tokens
-    #[account(
-        init_if_needed,
-        payer = claimer,
-        associated_token::mint = token_mint,
-        associated_token::authority = claimer,
-    )]
-    pub claimer_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct CampaignMigrated {
+    pub campaign: Pubkey,
+    pub campaign_id: [u8; 32],
+    pub old_version: u8,
+    pub new_version: u8,
+    pub old_size: u32,
+    pub new_size: u32,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub claimer: Signer<'info>,
+#[event]
+pub struct RegistrationRecipientBound {
+    pub registration: Pubkey,
+    pub campaign: Pubkey,
+    pub nullifier: [u8; 32],
+    pub recipient: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct RegistrationMigrated {
+    pub registration: Pubkey,
+    pub campaign: Pubkey,
+    /// Bound recipient after migration (default = unbound)
+    pub recipient: Pubkey,
+    pub old_version: u8,
+    pub new_version: u8,
+    pub old_size: u32,
+    pub new_size: u32,
+    pub timestamp: i64,
 }
 
-// === State ===
-
-#[account]
-#[derive(InitSpace)]
-pub struct ConfigAccount {
-    /// Protocol admin with full control
-    pub admin: Pubkey,
-    /// Authority that can pause/unpause (initially same as admin)
-    pub pause_authority: Pubkey,
-    /// Number of active verifiers
-    pub verifier_count: u32,
-    /// Whether the protocol is paused
-    pub is_paused: bool,
-    /// Maximum proofs per wallet per day
-    pub max_proofs_per_day: u32,
-    /// Cooldown seconds between proofs
-    pub cooldown_seconds: i64,
-    /// Maximum age of epoch in days (proofs older than this are rejected)
-    pub max_epoch_age: u64,
-    /// Total proofs verified across all wallets
-    pub total_proofs_verified: u64,
-    /// PDA bump
-    pub bump: u8,
+#[event]
+pub struct RegistrationMemoSet {
+    pub campaign_id: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub memo: String,
+    pub timestamp: i64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct VerifierAccount {
-    pub verifier: Pubkey,
-    pub is_active: bool,
-    pub added_at: i64,
-    pub attestation_count: u64,
-    pub bump: u8,
+#[event]
+pub struct RegistrationAddressUpdated {
+    pub campaign_id: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub owner: Pubkey,
+    pub old_address: String,
+    pub new_address: String,
+    pub timestamp: i64,
 }
 
-/// Rate limit tracking per wallet
-#[account]
-#[derive(InitSpace)]
-pub struct WalletRateLimit {
-    /// The wallet being rate limited
-    pub wallet: Pubkey,
-    /// Number of proofs submitted today
-    pub proofs_today: u32,
-    /// Timestamp of last proof submission
-    pub last_proof_at: i64,
-    /// Start of current day (for daily reset)
-    pub day_start: i64,
-    /// Total proofs ever submitted by this wallet
-    pub total_proofs: u64,
-    /// PDA bump
-    pub bump: u8,
+#[event]
+pub struct CampaignManagerAdded {
+    pub campaign_id: [u8; 32],
+    pub manager: Pubkey,
+    pub creator: Pubkey,
+    pub timestamp: i64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct CommitmentAccount {
-    pub commitment: [u8; 32],
-    pub owner: Pubkey,
-    pub created_at: i64,
-    pub bump: u8,
+#[event]
+pub struct CampaignManagerRemoved {
+    pub campaign_id: [u8; 32],
+    pub manager: Pubkey,
+    pub creator: Pubkey,
+    pub timestamp: i64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct NullifierAccount {
-    pub nullifier: [u8; 32],
-    pub is_used: bool,
-    pub used_at: i64,
-    /// Epoch (day number) when proof was generated - prevents replay attacks
-    pub epoch: u64,
-    /// Hash of private data - ensures data integrity
-    pub data_hash: [u8; 32],
-    pub proof_type: ProofType,
-    pub bump: u8,
+#[event]
+pub struct BonusDecayUpdated {
+    pub campaign_id: [u8; 32],
+    pub max_bonus: u64,
+    pub min_bonus: u64,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
-pub enum ProofType {
-    #[default]
-    Unset,
-    DeveloperReputation,
-    WhaleTrading,
+#[event]
+pub struct CampaignAmountsUpdated {
+    pub campaign_id: [u8; 32],
+    pub old_base_amount: u64,
+    pub old_dev_bonus: u64,
+    pub old_whale_bonus: u64,
+    pub new_base_amount: u64,
+    pub new_dev_bonus: u64,
+    pub new_whale_bonus: u64,
+    pub timestamp: i64,
 }
 
-// === Airdrop Registry State ===
+#[event]
+pub struct CampaignPaused {
+    pub campaign_id: [u8; 32],
+    pub creator: Pubkey,
+    pub timestamp: i64,
+}
 
-#[account]
-#[derive(InitSpace)]
-pub struct AirdropCampaign {
-    /// Unique campaign identifier (hash)
+#[event]
+pub struct CampaignResumed {
     pub campaign_id: [u8; 32],
-    /// Campaign creator (project distributing tokens)
     pub creator: Pubkey,
-    /// Human-readable campaign name
-    #[max_len(64)]
-    pub name: String,
-    /// Token mint for the airdrop
-    pub token_mint: Pubkey,
-    /// Base amount for anyone (tiered: open registration)
-    pub base_amount: u64,
-    /// Bonus amount for verified developers (gets base + dev_bonus)
-    pub dev_bonus: u64,
-    /// Bonus amount for verified whales (gets base + whale_bonus)
-    pub whale_bonus: u64,
-    /// Registration deadline (unix timestamp)
-    pub registration_deadline: i64,
-    /// Campaign status
-    pub status: CampaignStatus,
-    /// Total number of registrations
-    pub total_registrations: u32,
-    /// Number of open (unverified) registrations
-    pub open_registrations: u32,
-    /// Number of developer registrations
-    pub dev_registrations: u32,
-    /// Number of whale registrations
-    pub whale_registrations: u32,
-    /// Campaign creation timestamp
-    pub created_at: i64,
-    /// Campaign completion timestamp (0 if not completed)
-    pub completed_at: i64,
-    /// Current vault balance (tokens available for claims)
-    pub vault_balance: u64,
-    /// Total number of claims made
-    pub total_claimed: u32,
-    /// PDA bump
-    pub bump: u8,
+    pub timestamp: i64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct AirdropRegistrationAccount {
-    /// Campaign this registration belongs to
-    pub campaign: Pubkey,
-    /// Nullifier from the Vouch credential
-    pub nullifier: [u8; 32],
-    /// User's ShadowWire address for private receipt
-    #[max_len(44)]
-    pub shadow_wire_address: String,
-    /// Type of credential (dev or whale)
-    pub proof_type: ProofType,
-    /// Registration timestamp
-    pub registered_at: i64,
-    /// Whether tokens have been distributed (ShadowWire flow)
-    pub is_distributed: bool,
-    /// Distribution timestamp (0 if not distributed)
-    pub distributed_at: i64,
-    /// Distribution transaction signature
-    #[max_len(88)]
-    pub distribution_tx: String,
-    /// Whether tokens have been claimed (direct claim flow)
-    pub is_claimed: bool,
-    /// Claim timestamp (0 if not claimed)
-    pub claimed_at: i64,
-    /// Amount of tokens claimed
-    pub claimed_amount: u64,
-    /// PDA bump
-    pub bump: u8,
+#[event]
+pub struct CampaignOpenRegistrationUpdated {
+    pub campaign_id: [u8; 32],
+    pub enabled: bool,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
-pub enum CampaignStatus {
-    #[default]
-    Open,
-    RegistrationClosed,
-    Completed,
+#[event]
+pub struct AirdropRegistrationClosed {
+    pub campaign_id: [u8; 32],
+    pub total_registrations: u64,
+    pub dev_registrations: u64,
+    pub whale_registrations: u64,
+    pub timestamp: i64,
 }
 
-// === Events ===
+#[event]
+pub struct AirdropDistributed {
+    pub campaign_id: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub shadow_wire_address: String,
+    pub tx_signature: String,
+    pub amount: u64,
+    /// Whether a relay-signed receipt was verified on-chain
+    pub receipt_verified: bool,
+    pub timestamp: i64,
+}
 
 #[event]
-pub struct ConfigInitialized {
-    pub admin: Pubkey,
-    pub max_proofs_per_day: u32,
-    pub cooldown_seconds: i64,
-    pub max_epoch_age: u64,
+pub struct CampaignMinHoldUpdated {
+    pub campaign_id: [u8; 32],
+    pub min_hold_seconds: i64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct ProtocolPaused {
-    pub admin: Pubkey,
+pub struct DeadlineExtensionUpdated {
+    pub campaign_id: [u8; 32],
+    pub deadline_bump_per_fund: i64,
+    pub max_deadline_extension: i64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct ProtocolUnpaused {
-    pub admin: Pubkey,
+pub struct VestingScheduleUpdated {
+    pub campaign_id: [u8; 32],
+    pub vesting_start: i64,
+    pub vesting_duration: i64,
+    pub cliff_seconds: i64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct RateLimitsUpdated {
-    pub admin: Pubkey,
-    pub old_max_proofs_per_day: u32,
-    pub new_max_proofs_per_day: u32,
-    pub old_cooldown_seconds: i64,
-    pub new_cooldown_seconds: i64,
+pub struct EarlyClaimPenaltyUpdated {
+    pub campaign_id: [u8; 32],
+    pub early_claim_penalty_bps: u16,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct AdminTransferred {
-    pub old_admin: Pubkey,
-    pub new_admin: Pubkey,
+pub struct DistributionRelayUpdated {
+    pub campaign_id: [u8; 32],
+    pub distribution_relay: Pubkey,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct RateLimitInitialized {
-    pub wallet: Pubkey,
+pub struct AirdropCampaignCompleted {
+    pub campaign_id: [u8; 32],
+    pub total_distributed: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct VerifierAdded {
-    pub verifier: Pubkey,
-    pub admin: Pubkey,
+pub struct AirdropCampaignCancelled {
+    pub campaign_id: [u8; 32],
+    pub creator: Pubkey,
+    /// Vault balance returned to the creator
+    pub refunded_amount: u64,
+    pub total_registrations: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct VerifierRemoved {
-    pub verifier: Pubkey,
-    pub admin: Pubkey,
+pub struct AirdropCampaignFunded {
+    pub campaign_id: [u8; 32],
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub total_funded: u64,
+    /// Registration deadline after any funding extension
+    pub registration_deadline: i64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct AttestationRecorded {
+pub struct AirdropClaimed {
+    pub campaign_id: [u8; 32],
+    pub claimer: Pubkey,
     pub nullifier: [u8; 32],
-    pub attestation_hash: [u8; 32],
-    /// Epoch (day number) when proof was generated
-    pub epoch: u64,
-    /// Hash of private data for integrity verification
-    pub data_hash: [u8; 32],
-    pub verifier: Pubkey,
+    /// Gross amount released from the vault (after any early claim penalty)
+    pub amount: u64,
+    /// Portion routed to the campaign fee account
+    pub campaign_fee: u64,
+    /// Portion received by the claimer
+    pub net_amount: u64,
+    /// Portion withheld for claiming early (returned to the pool)
+    pub early_claim_penalty: u64,
+    /// Hold period that was enforced for this claim
+    pub effective_hold_seconds: i64,
+    /// Portion of the entitlement vested at claim time (full entitlement without vesting)
+    pub vested_amount: u64,
+    /// Cumulative amount claimed by this registration, including this claim
+    pub claimed_amount: u64,
     pub proof_type: ProofType,
-    pub recipient: Pubkey,
     pub timestamp: i64,
-    pub signature: [u8; 64],
 }
 
 #[event]
-pub struct CommitmentCreated {
-    pub owner: Pubkey,
-    pub commitment: [u8; 32],
+pub struct AirdropMerkleClaimed {
+    pub campaign_id: [u8; 32],
+    pub claimer: Pubkey,
+    pub nullifier: [u8; 32],
+    pub leaf: [u8; 32],
+    /// Leaf amount released from the vault
+    pub amount: u64,
+    /// Portion routed to the campaign fee account
+    pub campaign_fee: u64,
+    /// Portion received by the claimer
+    pub net_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CampaignPrivateClaimsUpdated {
+    pub campaign_id: [u8; 32],
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CampaignCredentialOwnerRequirementUpdated {
+    pub campaign_id: [u8; 32],
+    pub required: bool,
     pub timestamp: i64,
 }
 
-// === Airdrop Events ===
-
 #[event]
-pub struct AirdropCampaignCreated {
+pub struct AirdropPrivateClaimed {
     pub campaign_id: [u8; 32],
-    pub creator: Pubkey,
-    pub name: String,
-    pub token_mint: Pubkey,
-    pub base_amount: u64,
-    pub dev_bonus: u64,
-    pub whale_bonus: u64,
-    pub registration_deadline: i64,
+    pub nullifier: [u8; 32],
+    pub verifier: Pubkey,
+    /// Attested amount released from the vault
+    pub amount: u64,
+    /// Portion routed to the campaign fee account
+    pub campaign_fee: u64,
+    /// Portion received by the claimer
+    pub net_amount: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct AirdropRegistration {
+pub struct CampaignMerkleRootUpdated {
     pub campaign_id: [u8; 32],
-    pub nullifier: [u8; 32],
-    pub shadow_wire_address: String,
-    pub proof_type: ProofType,
+    pub old_merkle_root: [u8; 32],
+    pub new_merkle_root: [u8; 32],
     pub timestamp: i64,
 }
 
 #[event]
-pub struct AirdropRegistrationClosed {
+pub struct CustodyEscrowFunded {
     pub campaign_id: [u8; 32],
-    pub total_registrations: u32,
-    pub dev_registrations: u32,
-    pub whale_registrations: u32,
+    pub escrow: Pubkey,
+    pub beneficiary: Pubkey,
+    pub custodian: Pubkey,
+    pub release_at: i64,
+    /// Net amount added by this claim
+    pub amount: u64,
+    /// Escrow total after this claim
+    pub escrow_amount: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct AirdropDistributed {
+pub struct CustodyEscrowReleased {
+    pub campaign: Pubkey,
+    pub escrow: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    /// Whether the custodian co-signed (otherwise the time lock had elapsed)
+    pub released_by_custodian: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AirdropReclaimed {
     pub campaign_id: [u8; 32],
-    pub nullifier: [u8; 32],
-    pub shadow_wire_address: String,
-    pub tx_signature: String,
+    pub creator: Pubkey,
+    /// Unclaimed vault balance returned to the creator
+    pub amount: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct AirdropCampaignCompleted {
+pub struct CampaignAssetsConfigured {
     pub campaign_id: [u8; 32],
-    pub total_distributed: u32,
+    pub mints: Vec<Pubkey>,
+    pub amounts_per_claim: Vec<u64>,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct AirdropCampaignFunded {
+pub struct CampaignAssetFunded {
     pub campaign_id: [u8; 32],
+    pub mint: Pubkey,
     pub funder: Pubkey,
     pub amount: u64,
     pub total_funded: u64,
@@ -1777,12 +13024,22 @@ pub struct AirdropCampaignFunded {
 }
 
 #[event]
-pub struct AirdropClaimed {
+pub struct CampaignAssetRefunded {
+    pub campaign_id: [u8; 32],
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    /// Asset vault balance returned to the creator
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AirdropAssetClaimed {
     pub campaign_id: [u8; 32],
     pub claimer: Pubkey,
     pub nullifier: [u8; 32],
+    pub mint: Pubkey,
     pub amount: u64,
-    pub proof_type: ProofType,
     pub timestamp: i64,
 }
 
@@ -1840,6 +13097,15 @@ pub enum VouchError {
     #[msg("Proof epoch is in the future (clock manipulation)")]
     EpochInFuture,
 
+    #[msg("A commitment owned by the recipient is required")]
+    CommitmentRequired,
+
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+
+    #[msg("Nullifier account does not match the derived PDA")]
+    InvalidNullifierAccount,
+
     // === Airdrop Errors ===
 
     #[msg("Campaign name too long (max 64 chars)")]
@@ -1880,4 +13146,849 @@ pub enum VouchError {
 
     #[msg("Token mint does not match campaign")]
     InvalidMint,
+
+    #[msg("Invalid campaign claim fee configuration")]
+    InvalidClaimFee,
+
+    #[msg("Account is not a valid airdrop registration")]
+    InvalidRegistration,
+
+    #[msg("Distribution receipt is missing or invalid")]
+    DistributionProofInvalid,
+
+    #[msg("Hold period must not be negative")]
+    InvalidHoldPeriod,
+
+    #[msg("Claim hold period has not elapsed since registration")]
+    ClaimHoldActive,
+
+    #[msg("Invalid multi-asset configuration")]
+    InvalidAssetConfig,
+
+    #[msg("Asset index out of range")]
+    InvalidAssetIndex,
+
+    #[msg("Asset vault or token account does not match")]
+    InvalidAssetAccounts,
+
+    #[msg("Campaign already has registrations")]
+    CampaignHasRegistrations,
+
+    #[msg("Registration memo too long (max 100 chars)")]
+    MemoTooLong,
+
+    #[msg("Early claim penalty exceeds maximum")]
+    InvalidEarlyClaimPenalty,
+
+    #[msg("Invalid batch size")]
+    InvalidBatchSize,
+
+    #[msg("Proof commitment is required")]
+    ProofCommitmentRequired,
+
+    #[msg("Rate limit account does not belong to the recipient")]
+    RateLimitWalletMismatch,
+
+    #[msg("Too many active campaigns")]
+    TooManyActiveCampaigns,
+
+    #[msg("Governance key is not set")]
+    GovernanceKeyNotSet,
+
+    #[msg("Verifier set does not match the provided accounts")]
+    InvalidVerifierSet,
+
+    #[msg("Credential upgrades are disabled")]
+    CredentialUpgradesDisabled,
+
+    #[msg("Invalid credential upgrade")]
+    InvalidCredentialUpgrade,
+
+    #[msg("Fee split exceeds maximum")]
+    InvalidFeeSplit,
+
+    #[msg("Invalid Ed25519 scan distance")]
+    InvalidScanDistance,
+
+    #[msg("No matching Ed25519 instruction found within the scan window")]
+    Ed25519InstructionNotFound,
+
+    #[msg("Ed25519 instruction was already consumed by another instruction")]
+    Ed25519InstructionReused,
+
+    #[msg("Nullifier is already pinned")]
+    NullifierAlreadyPinned,
+
+    #[msg("Nullifier is not pinned")]
+    NullifierNotPinned,
+
+    #[msg("Invalid time source configuration")]
+    InvalidTimeSource,
+
+    #[msg("Campaign activity log account is required")]
+    ActivityLogRequired,
+
+    #[msg("Claim is not authorized by the campaign's claim signer")]
+    ClaimNotAuthorized,
+
+    #[msg("ShadowWire usage counter account is required")]
+    ShadowWireUsageRequired,
+
+    #[msg("Too many registrations share this ShadowWire address")]
+    ShadowWireReuseLimitExceeded,
+
+    #[msg("Credential was not issued by the campaign's required verifier")]
+    WrongVerifier,
+
+    #[msg("Claim period has ended")]
+    ClaimPeriodEnded,
+
+    #[msg("Claim window is still open")]
+    ClaimWindowOpen,
+
+    #[msg("Campaign is not completed")]
+    CampaignNotCompleted,
+
+    #[msg("Proof was generated too long before attestation")]
+    ProofTooOld,
+
+    #[msg("Invalid proof age limit")]
+    InvalidProofAge,
+
+    #[msg("Wallet profile with a default ShadowWire address is required")]
+    WalletProfileRequired,
+
+    #[msg("Verifier removal is already scheduled")]
+    VerifierRemovalAlreadyScheduled,
+
+    #[msg("Verifier removal is not scheduled or its grace period has not passed")]
+    VerifierRemovalNotDue,
+
+    #[msg("Invalid verifier removal delay")]
+    InvalidRemovalDelay,
+
+    #[msg("Credential tier is not eligible for this campaign")]
+    ProofTypeNotEligible,
+
+    #[msg("No admin transfer is pending")]
+    NoPendingAdmin,
+
+    #[msg("A claim for this registration is already in progress")]
+    ClaimInProgress,
+
+    #[msg("Pause authority cannot be the default pubkey")]
+    InvalidPauseAuthority,
+
+    #[msg("Not enough active verifiers to record attestations")]
+    InsufficientVerifiers,
+
+    #[msg("Protocol stats account is required while TVL tracking is enabled")]
+    ProtocolStatsRequired,
+
+    #[msg("Not enough verifier signatures for this attestation")]
+    InsufficientSignatures,
+
+    #[msg("A verifier signed more than once")]
+    DuplicateSigner,
+
+    #[msg("Required signatures must be between 1 and the verifier set size")]
+    InvalidSignatureThreshold,
+
+    #[msg("Batch expiry must be in the future and within the maximum batch lifetime")]
+    InvalidBatchExpiry,
+
+    #[msg("Attestation batch has expired")]
+    AttestationBatchExpired,
+
+    #[msg("All entries of this attestation batch have been applied")]
+    AttestationBatchExhausted,
+
+    #[msg("Attestation batch is still open")]
+    AttestationBatchOpen,
+
+    #[msg("Wallet claimed too recently; global claim cooldown is active")]
+    ClaimCooldownActive,
+
+    #[msg("Claim limit account is required while a global claim cooldown is set")]
+    ClaimLimitRequired,
+
+    #[msg("Nullifier is pinned and cannot be closed")]
+    PinnedNullifier,
+
+    #[msg("Nullifier retention period has not elapsed")]
+    NullifierRetentionActive,
+
+    #[msg("Commitment account is not owned by the attestation recipient")]
+    CommitmentMismatch,
+
+    #[msg("Campaign manager must be a non-default wallet other than the creator")]
+    InvalidCampaignManager,
+
+    #[msg("Unclaimed tokens cannot be reclaimed until the grace period ends")]
+    ReclaimGracePeriodActive,
+
+    #[msg("Bonus decay min_bonus must not exceed max_bonus")]
+    InvalidBonusDecay,
+
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+
+    #[msg("Vesting cliff has not been reached")]
+    VestingCliffActive,
+
+    #[msg("No newly vested tokens to claim")]
+    NothingVested,
+
+    #[msg("Campaign has no merkle root for merkle claims")]
+    MerkleClaimsDisabled,
+
+    #[msg("Requested amount exceeds the unclaimed vested balance")]
+    ExceedsVested,
+
+    #[msg("Campaign is paused by its creator")]
+    CampaignPaused,
+
+    #[msg("Custody escrow needs a custodian or a future release time matching the escrow")]
+    InvalidCustodyRelease,
+
+    #[msg("Custody escrow is locked until release_at or a custodian signature")]
+    CustodyLocked,
+
+    #[msg("Attestation signature has expired")]
+    AttestationExpired,
+
+    #[msg("Recipient is blocked")]
+    RecipientBlocked,
+
+    #[msg("Recipient blocklist account is required while the blocklist is enforced")]
+    BlocklistAccountRequired,
+
+    #[msg("Config epoch must follow an unretired previous config")]
+    InvalidConfigEpoch,
+
+    #[msg("Config has been retired by a later config epoch")]
+    ConfigRetired,
+
+    #[msg("Proof type name must be 1-32 bytes")]
+    InvalidProofTypeName,
+
+    #[msg("Proof type ID is already registered")]
+    ProofTypeAlreadyRegistered,
+
+    #[msg("Proof type registry is full")]
+    ProofTypeRegistryFull,
+
+    #[msg("Proof type is not registered or inactive")]
+    ProofTypeNotRegistered,
+
+    #[msg("Proof type registry account is required for registry proof types")]
+    ProofTypeRegistryRequired,
+
+    #[msg("Campaign lists more than 8 proof type bonuses")]
+    TooManyProofTypeBonuses,
+
+    #[msg("Campaign lists a proof type bonus more than once")]
+    DuplicateProofTypeBonus,
+
+    #[msg("Score update is not newer than the stored score, or is in the future")]
+    StaleScoreUpdate,
+
+    #[msg("Verifier is already active")]
+    VerifierAlreadyActive,
+
+    #[msg("Campaign does not accept open registrations")]
+    OpenRegistrationDisabled,
+
+    #[msg("Verifier must be removed before its account can be closed")]
+    VerifierStillActive,
+
+    #[msg("Used signature account is required while signature tracking is enabled")]
+    UsedSignatureAccountRequired,
+
+    #[msg("Used signature account does not match the derived PDA")]
+    InvalidUsedSignatureAccount,
+
+    #[msg("Attestation signature has already been used")]
+    SignatureAlreadyUsed,
+
+    #[msg("Funding token account is not owned by the funding authority")]
+    InvalidFundingAccount,
+
+    #[msg("Campaign does not accept attestation-authorized claims")]
+    PrivateClaimsDisabled,
+
+    #[msg("Protocol-wide daily proof limit exceeded")]
+    GlobalRateLimitExceeded,
+
+    #[msg("Credential has been revoked")]
+    CredentialRevoked,
+
+    #[msg("Campaign requires the credential's recipient to register")]
+    NotCredentialOwner,
+
+    #[msg("Only the registration's recipient can claim")]
+    ClaimerNotOwner,
+
+    #[msg("Campaign has already been funded")]
+    CampaignAlreadyFunded,
+
+    #[msg("Campaign has been cancelled")]
+    CampaignCancelled,
+
+    #[msg("Campaign has already paid out claims")]
+    CampaignHasClaims,
+
+    #[msg("Registration already has an owner")]
+    RegistrationAlreadyBound,
+
+    #[msg("Credential has a recipient; use migrate_registration")]
+    RecipientBackfillAvailable,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zeroed<T: AccountDeserialize>(space: usize) -> T {
+        T::try_deserialize_unchecked(&mut &vec![0u8; 8 + space][..]).unwrap()
+    }
+
+    fn config_with_limit(max_proofs_per_day: u32) -> ConfigAccount {
+        let mut config: ConfigAccount = zeroed(ConfigAccount::INIT_SPACE);
+        config.max_proofs_per_day = max_proofs_per_day;
+        config
+    }
+
+    fn rate_limit_with_proofs(timestamps: &[i64]) -> WalletRateLimit {
+        let mut rate_limit: WalletRateLimit = zeroed(WalletRateLimit::INIT_SPACE);
+        for (slot, &timestamp) in timestamps.iter().enumerate() {
+            rate_limit.recent_proofs[slot] = RecentProof {
+                timestamp,
+                proof_type: ProofType::DeveloperReputation,
+            };
+        }
+        rate_limit
+    }
+
+    #[test]
+    fn remaining_quota_counts_proofs_in_window() {
+        let config = config_with_limit(3);
+        let rate_limit = rate_limit_with_proofs(&[1_000, 2_000]);
+        assert_eq!(remaining_quota(&rate_limit, &config, 2_000), 1);
+        assert_eq!(remaining_quota(&rate_limit_with_proofs(&[]), &config, 2_000), 3);
+    }
+
+    #[test]
+    fn remaining_quota_rolls_over_at_window_boundary() {
+        let config = config_with_limit(3);
+        let rate_limit = rate_limit_with_proofs(&[1_000]);
+        // Still inside the window one second before it closes
+        assert_eq!(remaining_quota(&rate_limit, &config, 1_000 + SECONDS_PER_DAY - 1), 2);
+        // A proof exactly 24h old has left the window
+        assert_eq!(remaining_quota(&rate_limit, &config, 1_000 + SECONDS_PER_DAY), 3);
+    }
+
+    #[test]
+    fn remaining_quota_applies_skew_tolerance() {
+        let mut config = config_with_limit(3);
+        config.reset_skew_tolerance_seconds = 60;
+        let rate_limit = rate_limit_with_proofs(&[1_000]);
+        assert_eq!(remaining_quota(&rate_limit, &config, 1_000 + SECONDS_PER_DAY - 61), 2);
+        assert_eq!(remaining_quota(&rate_limit, &config, 1_000 + SECONDS_PER_DAY - 60), 3);
+    }
+
+    #[test]
+    fn remaining_quota_is_unlimited_for_exempt_wallets() {
+        let config = config_with_limit(1);
+        let mut rate_limit = rate_limit_with_proofs(&[1_000]);
+        rate_limit.exempt = true;
+        assert_eq!(remaining_quota(&rate_limit, &config, 1_000), u32::MAX);
+    }
+
+    #[test]
+    fn claim_amount_uses_tier_bonus_and_table_override() {
+        let mut campaign: AirdropCampaign = zeroed(AirdropCampaign::INIT_SPACE);
+        campaign.base_amount = 1_000;
+        campaign.dev_bonus = 500;
+        campaign.whale_bonus = 2_000;
+        assert_eq!(
+            compute_claim_amount(&campaign, ProofType::Unset).unwrap(),
+            1_000
+        );
+        assert_eq!(
+            compute_claim_amount(&campaign, ProofType::DeveloperReputation).unwrap(),
+            1_500
+        );
+        assert_eq!(
+            compute_claim_amount(&campaign, ProofType::WhaleTrading).unwrap(),
+            3_000
+        );
+
+        campaign.proof_type_bonuses.push(ProofTypeBonus {
+            proof_type_id: ProofType::WhaleTrading as u16,
+            bonus_amount: 100,
+        });
+        assert_eq!(
+            compute_claim_amount(&campaign, ProofType::WhaleTrading).unwrap(),
+            1_100
+        );
+
+        let mut registration: AirdropRegistrationAccount =
+            zeroed(AirdropRegistrationAccount::INIT_SPACE);
+        registration.proof_type = ProofType::DeveloperReputation;
+        // Older registrations derive the amount from the tier
+        assert_eq!(
+            registration_claim_amount(&campaign, &registration).unwrap(),
+            1_500
+        );
+        registration.allocation = 1_750;
+        assert_eq!(
+            registration_claim_amount(&campaign, &registration).unwrap(),
+            1_750
+        );
+    }
+
+    #[test]
+    fn claim_amount_rejects_overflowing_bonus() {
+        let mut campaign: AirdropCampaign = zeroed(AirdropCampaign::INIT_SPACE);
+        campaign.base_amount = u64::MAX;
+        assert_eq!(
+            compute_claim_amount(&campaign, ProofType::Unset).unwrap(),
+            u64::MAX
+        );
+        campaign.dev_bonus = 1;
+        assert!(compute_claim_amount(&campaign, ProofType::DeveloperReputation).is_err());
+        assert!(registration_allocation(&campaign, ProofType::DeveloperReputation, 0).is_err());
+    }
+
+    #[test]
+    fn registration_claims_are_bound_to_the_recipient() {
+        let owner = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let mut registration: AirdropRegistrationAccount =
+            zeroed(AirdropRegistrationAccount::INIT_SPACE);
+        // Verified registrations made before recipients were stored have no
+        // owner until migrate_registration backfills one
+        registration.proof_type = ProofType::DeveloperReputation;
+        assert!(check_registration_claimer(&registration, &owner).is_err());
+        assert!(check_registration_claimer(&registration, &stranger).is_err());
+
+        // Open registrations are keyed by the registrant's pubkey
+        registration.is_open_registration = true;
+        registration.nullifier = owner.to_bytes();
+        assert!(check_registration_claimer(&registration, &owner).is_ok());
+        assert!(check_registration_claimer(&registration, &stranger).is_err());
+
+        registration.is_open_registration = false;
+        registration.nullifier = [0u8; 32];
+        registration.recipient = owner;
+        assert!(check_registration_claimer(&registration, &owner).is_ok());
+        assert!(check_registration_claimer(&registration, &stranger).is_err());
+    }
+
+    #[test]
+    fn reentrant_claims_are_rejected_mid_transfer() {
+        let mut registration: AirdropRegistrationAccount =
+            zeroed(AirdropRegistrationAccount::INIT_SPACE);
+        assert!(check_claim_not_in_progress(&registration).is_ok());
+
+        // What a transfer hook calling back into claim_airdrop would observe
+        registration.claim_in_progress = true;
+        assert!(check_claim_not_in_progress(&registration).is_err());
+
+        // Cleared once the outer claim's transfers complete
+        registration.claim_in_progress = false;
+        assert!(check_claim_not_in_progress(&registration).is_ok());
+    }
+
+    #[test]
+    fn reclaim_waits_for_grace_period_and_claim_deadline() {
+        let mut campaign: AirdropCampaign = zeroed(AirdropCampaign::INIT_SPACE);
+        campaign.completed_at = 1_000;
+        assert_eq!(
+            airdrop_reclaim_at(&campaign),
+            1_000 + AIRDROP_RECLAIM_GRACE_SECONDS
+        );
+
+        // A claim deadline past the grace period holds the vault until it ends
+        campaign.claim_deadline = 1_000 + AIRDROP_RECLAIM_GRACE_SECONDS + 50;
+        assert_eq!(airdrop_reclaim_at(&campaign), campaign.claim_deadline);
+        campaign.claim_deadline = 500;
+        assert_eq!(
+            airdrop_reclaim_at(&campaign),
+            1_000 + AIRDROP_RECLAIM_GRACE_SECONDS
+        );
+    }
+
+    #[test]
+    fn registration_and_merkle_claims_share_one_marker() {
+        let claimer = Pubkey::new_unique();
+        let mut registration: AirdropRegistrationAccount =
+            zeroed(AirdropRegistrationAccount::INIT_SPACE);
+        registration.nullifier = [3u8; 32];
+        let mut claim_status: ClaimStatus = zeroed(ClaimStatus::INIT_SPACE);
+
+        // Vesting installments accumulate on the same marker
+        assert!(
+            record_registration_claim(&mut claim_status, 1, &registration, claimer, 40, 10).is_ok()
+        );
+        assert!(
+            record_registration_claim(&mut claim_status, 1, &registration, claimer, 60, 20).is_ok()
+        );
+        assert_eq!(claim_status.amount, 100);
+        assert_eq!(claim_status.nullifier, registration.nullifier);
+
+        // A merkle claim already paid this nullifier out
+        let mut merkle_claimed: ClaimStatus = zeroed(ClaimStatus::INIT_SPACE);
+        merkle_claimed.leaf = [9u8; 32];
+        assert!(
+            record_registration_claim(&mut merkle_claimed, 1, &registration, claimer, 40, 10)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn merkle_attestation_leaves_bind_recipient_and_expiry() {
+        let nullifier = [5u8; 32];
+        let recipient = Pubkey::new_unique();
+        let commitment = [6u8; 32];
+        let leaf = build_merkle_attestation_leaf(1, &nullifier, &recipient, 1_000, None);
+
+        assert_ne!(
+            leaf,
+            build_merkle_attestation_leaf(1, &nullifier, &Pubkey::new_unique(), 1_000, None)
+        );
+        assert_ne!(
+            leaf,
+            build_merkle_attestation_leaf(1, &nullifier, &recipient, 2_000, None)
+        );
+        assert_ne!(
+            leaf,
+            build_merkle_attestation_leaf(1, &nullifier, &recipient, 1_000, Some(&commitment))
+        );
+
+        let message = build_verifier_root_message(&recipient, &[8u8; 32]);
+        assert_eq!(&message[19..51], crate::ID.as_ref());
+    }
+
+    #[test]
+    fn shadow_wire_addresses_must_decode_to_32_bytes() {
+        let address = Pubkey::new_unique();
+        assert_eq!(
+            decode_base58_32(&address.to_string()),
+            Some(address.to_bytes())
+        );
+        // All-zero key: every leading '1' is a zero byte
+        assert!(is_valid_shadow_wire_address(&"1".repeat(32)));
+        assert!(validate_shadow_wire_address(&address.to_string()).is_ok());
+
+        // Right length, wrong alphabet
+        assert!(!is_valid_shadow_wire_address(&"!".repeat(32)));
+        assert!(!is_valid_shadow_wire_address(&"0OIl".repeat(10)));
+        // Valid characters that decode to fewer or more than 32 bytes
+        assert!(!is_valid_shadow_wire_address(&"2".repeat(32)));
+        assert!(!is_valid_shadow_wire_address(&"z".repeat(44)));
+        assert!(!is_valid_shadow_wire_address(&"1".repeat(33)));
+        assert!(validate_shadow_wire_address(&"!".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn nullifier_namespaces_derive_distinct_pdas() {
+        let nullifier = [7u8; 32];
+        let pda = |namespace: u8| {
+            Pubkey::find_program_address(
+                &[
+                    b"nullifier",
+                    nullifier_namespace_seed(&namespace),
+                    nullifier.as_ref(),
+                ],
+                &crate::ID,
+            )
+            .0
+        };
+        // Namespace 0 keeps the pre-namespacing address
+        let (legacy, _) =
+            Pubkey::find_program_address(&[b"nullifier", nullifier.as_ref()], &crate::ID);
+        assert_eq!(pda(0), legacy);
+        assert_ne!(pda(1), legacy);
+        assert_ne!(pda(1), pda(2));
+    }
+
+    #[test]
+    fn commitment_requirement_is_scoped_to_proof_types() {
+        let recipient = Pubkey::new_unique();
+        let dev = ProofType::DeveloperReputation as u8;
+        let whale = ProofType::WhaleTrading as u8;
+        let mut config = config_with_limit(3);
+        assert!(check_commitment_requirement(&config, None, &recipient, Some(dev)).is_ok());
+
+        config.require_commitment = true;
+        assert!(check_commitment_requirement(&config, None, &recipient, Some(dev)).is_err());
+        assert!(check_commitment_requirement(&config, None, &recipient, None).is_err());
+
+        config.commitment_proof_types = proof_type_bit(dev);
+        assert!(check_commitment_requirement(&config, None, &recipient, Some(dev)).is_err());
+        assert!(check_commitment_requirement(&config, None, &recipient, Some(whale)).is_ok());
+        // Registered proof types stay gated under a scoped requirement
+        assert!(check_commitment_requirement(&config, None, &recipient, None).is_err());
+    }
+
+    #[test]
+    fn config_keeps_v2_offsets_and_migrates_appended_defaults() {
+        let mut config = config_with_limit(5);
+        config.max_epoch_age = 9;
+        config.total_proofs_verified = 42;
+        config.bump = 254;
+        config.required_signatures = 3;
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        assert_eq!(&data[89..97], &9u64.to_le_bytes());
+        assert_eq!(&data[97..105], &42u64.to_le_bytes());
+        assert_eq!(data[CONFIG_V2_SPACE - 1], 254);
+
+        // A v2 account grown by migrate_config: zero-filled tail
+        data[CONFIG_V2_SPACE..].fill(0);
+        let mut migrated = ConfigAccount::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(migrated.required_signatures, 0);
+        apply_appended_config_defaults(&mut migrated);
+        assert_eq!(migrated.bump, 254);
+        assert_eq!(migrated.max_dev_proofs_per_day, 5);
+        assert_eq!(migrated.max_whale_proofs_per_day, 5);
+        assert_eq!(migrated.required_signatures, 1);
+        assert_eq!(migrated.min_active_verifiers, DEFAULT_MIN_ACTIVE_VERIFIERS);
+        assert!(migrated.campaigns_respect_protocol_pause);
+        assert!(!migrated.require_commitment);
+    }
+
+    #[test]
+    fn legacy_campaigns_convert_to_the_current_layout() {
+        let legacy = LegacyAirdropCampaign {
+            campaign_id: [7u8; 32],
+            creator: Pubkey::new_unique(),
+            name: "genesis".to_string(),
+            token_mint: Pubkey::new_unique(),
+            base_amount: 100,
+            dev_bonus: 50,
+            whale_bonus: 200,
+            registration_deadline: 1_000,
+            status: CampaignStatus::RegistrationClosed,
+            total_registrations: u32::MAX,
+            open_registrations: 1,
+            dev_registrations: 2,
+            whale_registrations: 3,
+            created_at: 10,
+            completed_at: 0,
+            vault_balance: 5_000,
+            total_claimed: 4,
+            bump: 253,
+        };
+        // Pre-versioning campaigns were allocated at the u32-counter size
+        assert_eq!(8 + LegacyAirdropCampaign::INIT_SPACE, 250);
+        let mut data = legacy.try_to_vec().unwrap();
+        data.resize(LegacyAirdropCampaign::INIT_SPACE, 0);
+        let decoded = LegacyAirdropCampaign::deserialize(&mut &data[..]).unwrap();
+
+        let mut campaign: AirdropCampaign = zeroed(AirdropCampaign::INIT_SPACE);
+        apply_legacy_campaign(&mut campaign, decoded);
+        assert_eq!(campaign.name, "genesis");
+        assert_eq!(campaign.total_registrations, u32::MAX as u64);
+        assert_eq!(campaign.whale_registrations, 3);
+        assert_eq!(campaign.total_claimed, 4);
+        assert_eq!(campaign.vault_balance, 5_000);
+        assert_eq!(campaign.bump, 253);
+        assert!(campaign.status == CampaignStatus::RegistrationClosed);
+        assert_eq!(campaign.version, 0);
+        assert_eq!(campaign.required_verifier, None);
+        assert!(campaign.proof_type_bonuses.is_empty());
+    }
+
+    #[test]
+    fn claim_deadline_changes_may_only_extend_the_window() {
+        assert!(extends_claim_deadline(1_000, 2_000));
+        assert!(extends_claim_deadline(1_000, 1_000));
+        assert!(extends_claim_deadline(1_000, 0));
+        assert!(extends_claim_deadline(0, 0));
+        assert!(!extends_claim_deadline(2_000, 1_000));
+        // Adding a deadline where there was none shortens the window
+        assert!(!extends_claim_deadline(0, 1_000));
+    }
+
+    #[test]
+    fn migration_backfills_recipient_from_the_credential() {
+        let owner = Pubkey::new_unique();
+        let mut registration: AirdropRegistrationAccount =
+            zeroed(AirdropRegistrationAccount::INIT_SPACE);
+        registration.nullifier = [3u8; 32];
+        registration.proof_type = ProofType::DeveloperReputation;
+        let mut nullifier_account: NullifierAccount = zeroed(NullifierAccount::INIT_SPACE);
+        nullifier_account.nullifier = [3u8; 32];
+        nullifier_account.is_used = true;
+
+        // A credential without a recipient has nothing to copy
+        assert!(!backfill_registration_recipient(&mut registration, &nullifier_account).unwrap());
+        assert!(check_registration_claimer(&registration, &owner).is_err());
+
+        nullifier_account.recipient = owner;
+        assert!(backfill_registration_recipient(&mut registration, &nullifier_account).unwrap());
+        assert!(check_registration_claimer(&registration, &owner).is_ok());
+
+        // An owned registration is never rebound
+        nullifier_account.recipient = Pubkey::new_unique();
+        assert!(!backfill_registration_recipient(&mut registration, &nullifier_account).unwrap());
+        assert_eq!(registration.recipient, owner);
+
+        // The credential must be the one the registration was made with
+        let mut other: AirdropRegistrationAccount = zeroed(AirdropRegistrationAccount::INIT_SPACE);
+        other.nullifier = [4u8; 32];
+        other.proof_type = ProofType::DeveloperReputation;
+        assert!(backfill_registration_recipient(&mut other, &nullifier_account).is_err());
+    }
+
+    fn ed25519_ix_data(pubkey: &Pubkey, signature: &[u8; 64], message: &[u8], index: u16) -> Vec<u8> {
+        let pubkey_offset = 16u16;
+        let sig_offset = pubkey_offset + ED25519_PUBKEY_SIZE as u16;
+        let msg_offset = sig_offset + ED25519_SIGNATURE_SIZE as u16;
+        let mut data = vec![1u8, 0];
+        for field in [
+            sig_offset,
+            index,
+            pubkey_offset,
+            index,
+            msg_offset,
+            message.len() as u16,
+            index,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        data.extend_from_slice(pubkey.as_ref());
+        data.extend_from_slice(signature);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn ed25519_match_rejects_offsets_into_other_instructions() {
+        let pubkey = Pubkey::new_unique();
+        let signature = [9u8; 64];
+        let message = b"attest";
+
+        let data = ed25519_ix_data(&pubkey, &signature, message, u16::MAX);
+        assert!(ed25519_instruction_matches(&data, 2, &pubkey, &signature, message));
+        let data = ed25519_ix_data(&pubkey, &signature, message, 2);
+        assert!(ed25519_instruction_matches(&data, 2, &pubkey, &signature, message));
+
+        // Data redirected to another instruction: the precompile verified different bytes
+        let data = ed25519_ix_data(&pubkey, &signature, message, 0);
+        assert!(!ed25519_instruction_matches(&data, 2, &pubkey, &signature, message));
+        for index_at in [4, 8, 14] {
+            let mut data = ed25519_ix_data(&pubkey, &signature, message, u16::MAX);
+            data[index_at..index_at + 2].copy_from_slice(&3u16.to_le_bytes());
+            assert!(!ed25519_instruction_matches(&data, 2, &pubkey, &signature, message));
+        }
+    }
+
+    #[test]
+    fn circuit_breaker_drains_and_trips_on_sustained_excess() {
+        let mut config = config_with_limit(10);
+        config.max_attestations_per_window = 3;
+        config.attestation_window_seconds = 300;
+
+        // A burst up to the threshold passes
+        for _ in 0..3 {
+            assert!(!trip_attestation_circuit_breaker(&mut config, 1_000).unwrap());
+        }
+        assert_eq!(config.attestation_rate_level, 3 * ATTESTATION_RATE_SCALE);
+
+        // A third of the window drains room for one more
+        assert!(!trip_attestation_circuit_breaker(&mut config, 1_100).unwrap());
+        assert_eq!(config.attestation_rate_level, 3 * ATTESTATION_RATE_SCALE);
+        assert!(!config.is_paused);
+
+        // The next one over the threshold pauses and resets the bucket
+        assert!(trip_attestation_circuit_breaker(&mut config, 1_100).unwrap());
+        assert!(config.is_paused);
+        assert_eq!(config.attestation_rate_level, 0);
+
+        // Disabled breaker never trips
+        let mut config = config_with_limit(10);
+        for _ in 0..100 {
+            assert!(!trip_attestation_circuit_breaker(&mut config, 1_000).unwrap());
+        }
+        assert!(!config.is_paused);
+    }
+
+    #[test]
+    fn ownerless_registrations_bind_once() {
+        let owner = Pubkey::new_unique();
+        let mut registration: AirdropRegistrationAccount =
+            zeroed(AirdropRegistrationAccount::INIT_SPACE);
+        registration.nullifier = [3u8; 32];
+        registration.proof_type = ProofType::WhaleTrading;
+        let mut nullifier_account: NullifierAccount = zeroed(NullifierAccount::INIT_SPACE);
+        nullifier_account.nullifier = [3u8; 32];
+        nullifier_account.is_used = true;
+
+        // Revoked credentials can't be bound to anyone
+        nullifier_account.is_revoked = true;
+        assert!(bind_ownerless_registration(&mut registration, &nullifier_account, owner).is_err());
+        nullifier_account.is_revoked = false;
+
+        // A credential recipient must be backfilled through migrate_registration
+        nullifier_account.recipient = Pubkey::new_unique();
+        assert!(bind_ownerless_registration(&mut registration, &nullifier_account, owner).is_err());
+        nullifier_account.recipient = Pubkey::default();
+
+        bind_ownerless_registration(&mut registration, &nullifier_account, owner).unwrap();
+        assert!(check_registration_claimer(&registration, &owner).is_ok());
+
+        // Bound registrations are never rebound
+        let other = Pubkey::new_unique();
+        assert!(bind_ownerless_registration(&mut registration, &nullifier_account, other).is_err());
+        assert_eq!(registration.recipient, owner);
+    }
+
+    #[test]
+    fn attestation_message_distinguishes_commitment_slots() {
+        let build = |proof: Option<&[u8; 32]>, wallet: Option<&[u8; 32]>| {
+            build_attestation_message_v3(
+                1, &[1u8; 32], 7, &[2u8; 32], &[3u8; 32], 10, 20, proof, wallet,
+            )
+            .unwrap()
+        };
+        let commitment = [5u8; 32];
+        let proof_only = build(Some(&commitment), None);
+        let wallet_only = build(None, Some(&commitment));
+        assert_eq!(proof_only.len(), wallet_only.len());
+        assert_ne!(proof_only, wallet_only);
+        assert_eq!(build(None, None).len() + 32, proof_only.len());
+    }
+
+    #[test]
+    fn revoked_credentials_block_registration_claims() {
+        let mut registration: AirdropRegistrationAccount =
+            zeroed(AirdropRegistrationAccount::INIT_SPACE);
+        registration.nullifier = [3u8; 32];
+        registration.proof_type = ProofType::DeveloperReputation;
+        let mut nullifier_account: NullifierAccount = zeroed(NullifierAccount::INIT_SPACE);
+        nullifier_account.nullifier = [3u8; 32];
+        nullifier_account.namespace = ProofType::DeveloperReputation as u8;
+        nullifier_account.is_used = true;
+
+        assert!(check_registration_credential(&registration, Some(&nullifier_account)).is_ok());
+        // Verified registrations must present their credential
+        assert!(check_registration_credential(&registration, None).is_err());
+        // ...from their own proof type's namespace
+        nullifier_account.namespace = ProofType::WhaleTrading as u8;
+        assert!(check_registration_credential(&registration, Some(&nullifier_account)).is_err());
+        nullifier_account.namespace = 0;
+        assert!(check_registration_credential(&registration, Some(&nullifier_account)).is_ok());
+
+        nullifier_account.is_revoked = true;
+        assert!(check_registration_credential(&registration, Some(&nullifier_account)).is_err());
+
+        // Open registrations have no credential to check
+        let mut open: AirdropRegistrationAccount = zeroed(AirdropRegistrationAccount::INIT_SPACE);
+        open.is_open_registration = true;
+        assert!(check_registration_credential(&open, None).is_ok());
+    }
 }
@@ -131,9 +131,12 @@ async function main(): Promise<void> {
         { pubkey: configPda, isSigner: false, isWritable: true },
         { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        // previous_config: None (the program ID stands in for an omitted optional account)
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
       ],
       programId: PROGRAM_ID,
-      data: discriminator,
+      // Instruction data: discriminator + config_epoch (0 = original config PDA)
+      data: Buffer.concat([discriminator, Buffer.from([0])]),
     });
 
     try {
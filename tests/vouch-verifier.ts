@@ -22,7 +22,17 @@
 import * as anchor from '@coral-xyz/anchor';
 import { Program } from '@coral-xyz/anchor';
 import { PublicKey, Keypair, SystemProgram } from '@solana/web3.js';
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from '@solana/spl-token';
 import { expect } from 'chai';
+import { createHash } from 'crypto';
 import { VouchVerifier } from '../target/types/vouch_verifier';
 
 describe('vouch-verifier', () => {
@@ -54,10 +64,11 @@ describe('vouch-verifier', () => {
     return pda;
   }
 
-  // Helper to get nullifier PDA
-  function getNullifierPda(nullifier: Uint8Array): PublicKey {
+  // Helper to get nullifier PDA (namespace 0 = legacy/registry, else the proof type value)
+  function getNullifierPda(nullifier: Uint8Array, namespace: number): PublicKey {
+    const namespaceSeed = namespace === 0 ? Buffer.alloc(0) : Buffer.from([namespace]);
     const [pda] = PublicKey.findProgramAddressSync(
-      [Buffer.from('nullifier'), Buffer.from(nullifier)],
+      [Buffer.from('nullifier'), namespaceSeed, Buffer.from(nullifier)],
       program.programId
     );
     return pda;
@@ -90,7 +101,487 @@ describe('vouch-verifier', () => {
     return pda;
   }
 
+  // Helper to get used signature PDA (seeded by the SHA-256 of the signature)
+  function getUsedSignaturePda(signature: number[]): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('used_signature'), createHash('sha256').update(Buffer.from(signature)).digest()],
+      program.programId
+    );
+    return pda;
+  }
+
   // Helper to initialize config if not exists
+  // Helper to create an open campaign (via a fresh template, so no token mint is needed)
+  async function createTestCampaign(name: string): Promise<PublicKey> {
+    const templateId = randomBytes(32);
+    const [templatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('campaign_template'), admin.publicKey.toBuffer(), Buffer.from(templateId)],
+      program.programId
+    );
+    await program.methods
+      .createCampaignTemplate(
+        Array.from(templateId),
+        {
+          tokenMint: Keypair.generate().publicKey,
+          baseAmount: new anchor.BN(1_000),
+          devBonus: new anchor.BN(0),
+          whaleBonus: new anchor.BN(0),
+          campaignClaimFeeBps: 0,
+          claimFeeAccount: PublicKey.default,
+        },
+        new anchor.BN(86_400),
+        0,
+        [],
+        new anchor.BN(0)
+      )
+      .accounts({
+        template: templatePda,
+        creator: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const campaignId = randomBytes(32);
+    const [campaignPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('airdrop_campaign'), Buffer.from(campaignId)],
+      program.programId
+    );
+    await program.methods
+      .createCampaignFromTemplate(Array.from(campaignId), name)
+      .accounts({
+        config: configPda,
+        template: templatePda,
+        campaign: campaignPda,
+        creator: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return campaignPda;
+  }
+
+  // Mirrors build_attestation_message_v3 for a developer credential
+  function buildDevAttestationMessage(
+    nullifier: Uint8Array,
+    epoch: bigint,
+    dataHash: Uint8Array,
+    attestationHash: Uint8Array,
+    proofGeneratedAt: bigint,
+    expiresAt: bigint
+  ): Buffer {
+    const epochBytes = Buffer.alloc(8);
+    epochBytes.writeBigUInt64BE(epoch);
+    const generatedAtBytes = Buffer.alloc(8);
+    generatedAtBytes.writeBigInt64BE(proofGeneratedAt);
+    const expiresAtBytes = Buffer.alloc(8);
+    expiresAtBytes.writeBigInt64BE(expiresAt);
+    return Buffer.concat([
+      Buffer.from('vouch_dev_attestation_v3'),
+      program.programId.toBuffer(),
+      Buffer.from([1]),
+      Buffer.from(nullifier),
+      epochBytes,
+      Buffer.from(dataHash),
+      Buffer.from(attestationHash),
+      generatedAtBytes,
+      expiresAtBytes,
+      // No proof commitment, no wallet commitment
+      Buffer.from([0, 0]),
+    ]);
+  }
+
+  // Mirrors verify_merkle_proof: each level hashes the sorted pair
+  function hashPair(a: Buffer, b: Buffer): Buffer {
+    const [first, second] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+    return createHash('sha256').update(Buffer.concat([first, second])).digest();
+  }
+
+  function getBlockedRecipientPda(recipient: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('blocked_recipient'), recipient.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  // Helper to create a wallet's rate limit account unless it already exists
+  async function ensureRateLimit(wallet: PublicKey): Promise<PublicKey> {
+    const rateLimitPda = getRateLimitPda(wallet);
+    if (!(await provider.connection.getAccountInfo(rateLimitPda))) {
+      await program.methods
+        .initRateLimit()
+        .accounts({
+          rateLimit: rateLimitPda,
+          wallet,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+    return rateLimitPda;
+  }
+
+  // Helper to fund a fresh wallet so it can pay for its own accounts
+  async function fundWallet(wallet: PublicKey): Promise<void> {
+    const signature = await provider.connection.requestAirdrop(
+      wallet,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    const latest = await provider.connection.getLatestBlockhash();
+    await provider.connection.confirmTransaction({ signature, ...latest });
+  }
+
+  // Helper to record a verified developer credential for a recipient
+  // (fresh verifier, namespace-1 nullifier, Ed25519-signed record_attestation)
+  async function recordDevCredential(
+    recipient: PublicKey,
+    commitmentAccount: PublicKey | null = null
+  ): Promise<{ nullifier: Uint8Array; nullifierPda: PublicKey; verifier: Keypair }> {
+    const verifier = Keypair.generate();
+    const verifierPda = getVerifierPda(verifier.publicKey);
+    await program.methods
+      .addVerifier(verifier.publicKey)
+      .accounts({
+        config: configPda,
+        verifierAccount: verifierPda,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const nullifier = randomBytes(32);
+    const nullifierPda = getNullifierPda(nullifier, 1);
+    await program.methods
+      .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+      .accounts({
+        nullifierAccount: nullifierPda,
+        payer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const rateLimitPda = await ensureRateLimit(recipient);
+
+    const epoch = BigInt(Math.floor(Date.now() / 1000 / 86400));
+    const proofGeneratedAt = BigInt(Math.floor(Date.now() / 1000));
+    const expiresAt = proofGeneratedAt + BigInt(600);
+    const dataHash = randomBytes(32);
+    const attestationHash = randomBytes(32);
+    const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: verifier.secretKey,
+      message: buildDevAttestationMessage(
+        nullifier,
+        epoch,
+        dataHash,
+        attestationHash,
+        proofGeneratedAt,
+        expiresAt
+      ),
+    });
+    // Signature sits after the 16-byte header and 32-byte public key
+    const signature = Array.from(ed25519Ix.data.subarray(48, 112)) as number[] & { length: 64 };
+
+    const recordIx = await program.methods
+      .recordAttestation(
+        Array.from(attestationHash) as number[] & { length: 32 },
+        1,
+        Array.from(nullifier) as number[] & { length: 32 },
+        new anchor.BN(epoch.toString()),
+        Array.from(dataHash) as number[] & { length: 32 },
+        signature,
+        null,
+        new anchor.BN(proofGeneratedAt.toString()),
+        new anchor.BN(expiresAt.toString())
+      )
+      .accounts({
+        config: configPda,
+        verifierAccount: verifierPda,
+        nullifierAccount: nullifierPda,
+        rateLimit: rateLimitPda,
+        recipient,
+        commitmentAccount,
+        blockedRecipient: null,
+        usedSignature: null,
+        payer: admin.publicKey,
+        instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .instruction();
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(ed25519Ix, recordIx));
+
+    return { nullifier, nullifierPda, verifier };
+  }
+
+  // Helper to create a campaign for a fresh mint and fund its vault
+  async function createFundedCampaign(
+    name: string,
+    baseAmount: number,
+    funding: number,
+    feeBps = 0,
+    feeAccount?: PublicKey,
+    devBonus = 0
+  ): Promise<{
+    campaignPda: PublicKey;
+    mint: PublicKey;
+    campaignVault: PublicKey;
+    creatorTokenAccount: PublicKey;
+  }> {
+    const mint = await createMint(provider.connection, admin.payer, admin.publicKey, null, 0);
+    const creatorTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      mint,
+      admin.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      mint,
+      creatorTokenAccount.address,
+      admin.payer,
+      funding
+    );
+
+    const campaignId = randomBytes(32);
+    const [campaignPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('airdrop_campaign'), Buffer.from(campaignId)],
+      program.programId
+    );
+    await program.methods
+      .createAirdropCampaign(
+        Array.from(campaignId) as number[] & { length: 32 },
+        name,
+        mint,
+        new anchor.BN(baseAmount),
+        new anchor.BN(devBonus),
+        new anchor.BN(0),
+        new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+        new anchor.BN(0),
+        feeBps,
+        feeAccount ?? (feeBps > 0 ? creatorTokenAccount.address : PublicKey.default),
+        []
+      )
+      .accounts({
+        config: configPda,
+        campaign: campaignPda,
+        creator: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const campaignVault = getAssociatedTokenAddressSync(mint, campaignPda, true);
+    await program.methods
+      .fundAirdropCampaign(new anchor.BN(funding))
+      .accounts({
+        config: configPda,
+        campaign: campaignPda,
+        campaignVault,
+        tokenMint: mint,
+        creatorTokenAccount: creatorTokenAccount.address,
+        activityLog: null,
+        protocolStats: null,
+        creator: admin.publicKey,
+        campaignManager: null,
+        funder: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return { campaignPda, mint, campaignVault, creatorTokenAccount: creatorTokenAccount.address };
+  }
+
+  // Helper to get a campaign registration PDA
+  function getRegistrationPda(campaign: PublicKey, nullifier: Uint8Array): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('airdrop_registration'), campaign.toBuffer(), Buffer.from(nullifier)],
+      program.programId
+    );
+    return pda;
+  }
+
+  // Helper to get the per-(campaign, nullifier) claim marker PDA
+  function getClaimStatusPda(campaign: PublicKey, nullifier: Uint8Array): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('claim_status'), campaign.toBuffer(), Buffer.from(nullifier)],
+      program.programId
+    );
+    return pda;
+  }
+
+  // Helper to get claim_airdrop_private's per-(campaign, nullifier) marker PDA
+  function getPrivateClaimMarkerPda(campaign: PublicKey, nullifier: Uint8Array): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('private_claim'), campaign.toBuffer(), Buffer.from(nullifier)],
+      program.programId
+    );
+    return pda;
+  }
+
+  // Helper to register a recorded credential for a campaign, paid by its recipient
+  async function registerCredential(
+    campaignPda: PublicKey,
+    nullifier: Uint8Array,
+    nullifierPda: PublicKey,
+    recipient: Keypair
+  ): Promise<PublicKey> {
+    const registrationPda = getRegistrationPda(campaignPda, nullifier);
+    await program.methods
+      .registerForAirdrop(Keypair.generate().publicKey.toBase58())
+      .accounts({
+        config: configPda,
+        campaign: campaignPda,
+        nullifierAccount: nullifierPda,
+        registration: registrationPda,
+        activityLog: null,
+        shadowWireUsage: null,
+        registrationIndex: null,
+        walletProfile: null,
+        proofTypeRegistry: null,
+        payer: recipient.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([recipient])
+      .rpc();
+    return registrationPda;
+  }
+
+  // Helper to register a wallet without a credential, paid by the registrant
+  async function registerOpen(
+    campaignPda: PublicKey,
+    registrant: Keypair,
+    shadowWireAddress: string = Keypair.generate().publicKey.toBase58(),
+    activityLog: PublicKey | null = null
+  ): Promise<PublicKey> {
+    const registrationPda = getRegistrationPda(campaignPda, registrant.publicKey.toBytes());
+    await program.methods
+      .registerForAirdropOpen(shadowWireAddress)
+      .accounts({
+        config: configPda,
+        campaign: campaignPda,
+        registration: registrationPda,
+        activityLog,
+        shadowWireUsage: null,
+        registrationIndex: null,
+        payer: registrant.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([registrant])
+      .rpc();
+    return registrationPda;
+  }
+
+  // Helper to claim a registration's tokens into the claimer's associated account
+  async function claimRegistration(
+    campaignPda: PublicKey,
+    mint: PublicKey,
+    campaignVault: PublicKey,
+    nullifier: Uint8Array,
+    claimer: Keypair,
+    claimFeeAccount: PublicKey | null = null
+  ): Promise<string> {
+    return program.methods
+      .claimAirdrop(null, null)
+      .accounts({
+        config: configPda,
+        campaign: campaignPda,
+        campaignVault,
+        tokenMint: mint,
+        registration: getRegistrationPda(campaignPda, nullifier),
+        // Registrations here are made with recordDevCredential credentials
+        nullifierAccount: getNullifierPda(nullifier, 1),
+        claimStatus: getClaimStatusPda(campaignPda, nullifier),
+        privateClaimMarker: getPrivateClaimMarkerPda(campaignPda, nullifier),
+        claimerTokenAccount: getAssociatedTokenAddressSync(mint, claimer.publicKey),
+        claimFeeAccount,
+        activityLog: null,
+        protocolStats: null,
+        claimLimit: null,
+        blockedRecipient: null,
+        claimer: claimer.publicKey,
+        instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([claimer])
+      .rpc();
+  }
+
+  // Helper to give a campaign one additional asset and mint its funding to the creator
+  async function configureTestAsset(
+    campaignPda: PublicKey,
+    funding: number
+  ): Promise<{
+    assetMint: PublicKey;
+    assetBasketPda: PublicKey;
+    assetVault: PublicKey;
+    creatorAssetAccount: PublicKey;
+  }> {
+    const assetMint = await createMint(provider.connection, admin.payer, admin.publicKey, null, 0);
+    const creatorAssetAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      assetMint,
+      admin.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      assetMint,
+      creatorAssetAccount.address,
+      admin.payer,
+      funding
+    );
+    const [assetBasketPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('campaign_assets'), campaignPda.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .configureCampaignAssets([assetMint], [new anchor.BN(10)])
+      .accounts({
+        campaign: campaignPda,
+        assetBasket: assetBasketPda,
+        creator: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return {
+      assetMint,
+      assetBasketPda,
+      assetVault: getAssociatedTokenAddressSync(assetMint, campaignPda, true),
+      creatorAssetAccount: creatorAssetAccount.address,
+    };
+  }
+
+  // Helper to fund a campaign's first additional asset from the creator
+  async function fundTestAsset(
+    campaignPda: PublicKey,
+    assetMint: PublicKey,
+    amount: number
+  ): Promise<string> {
+    const [assetBasketPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('campaign_assets'), campaignPda.toBuffer()],
+      program.programId
+    );
+    return program.methods
+      .fundCampaignAsset(0, new anchor.BN(amount))
+      .accounts({
+        config: configPda,
+        campaign: campaignPda,
+        assetBasket: assetBasketPda,
+        assetVault: getAssociatedTokenAddressSync(assetMint, campaignPda, true),
+        tokenMint: assetMint,
+        creatorTokenAccount: getAssociatedTokenAddressSync(assetMint, admin.publicKey),
+        protocolStats: null,
+        creator: admin.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        campaignManager: null,
+      })
+      .rpc();
+  }
+
   async function ensureConfigInitialized(): Promise<void> {
     configPda = getConfigPda();
     try {
@@ -98,11 +589,12 @@ describe('vouch-verifier', () => {
     } catch {
       // Config doesn't exist, initialize it
       await program.methods
-        .initializeConfig()
+        .initializeConfig(0)
         .accounts({
           config: configPda,
           admin: admin.publicKey,
           systemProgram: SystemProgram.programId,
+          previousConfig: null,
         })
         .rpc();
     }
@@ -133,11 +625,12 @@ describe('vouch-verifier', () => {
     it('should fail to re-initialize config', async () => {
       try {
         await program.methods
-          .initializeConfig()
+          .initializeConfig(0)
           .accounts({
             config: configPda,
             admin: admin.publicKey,
             systemProgram: SystemProgram.programId,
+            previousConfig: null,
           })
           .rpc();
         expect.fail('Should have thrown an error');
@@ -145,6 +638,31 @@ describe('vouch-verifier', () => {
         expect(error).to.exist;
       }
     });
+
+    it('should require the previous config to start a new config epoch', async () => {
+      const [nextConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('config'), Buffer.from([1])],
+        program.programId
+      );
+      try {
+        await program.methods
+          .initializeConfig(1)
+          .accounts({
+            config: nextConfigPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+            previousConfig: null,
+          })
+          .rpc();
+        expect.fail('Should have thrown InvalidConfigEpoch error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidConfigEpoch');
+      }
+
+      const config = await program.account.configAccount.fetch(configPda);
+      expect(config.configEpoch).to.equal(0);
+      expect(config.retired).to.be.false;
+    });
   });
 
   // ==========================================
@@ -161,7 +679,7 @@ describe('vouch-verifier', () => {
             .unpauseProtocol()
             .accounts({
               config: configPda,
-              admin: admin.publicKey,
+              pauseAuthority: admin.publicKey,
             })
             .rpc();
         }
@@ -170,7 +688,7 @@ describe('vouch-verifier', () => {
           .pauseProtocol()
           .accounts({
             config: configPda,
-            admin: admin.publicKey,
+            pauseAuthority: admin.publicKey,
           })
           .rpc();
 
@@ -185,7 +703,7 @@ describe('vouch-verifier', () => {
             .pauseProtocol()
             .accounts({
               config: configPda,
-              admin: admin.publicKey,
+              pauseAuthority: admin.publicKey,
             })
             .rpc();
         }
@@ -195,7 +713,7 @@ describe('vouch-verifier', () => {
             .pauseProtocol()
             .accounts({
               config: configPda,
-              admin: admin.publicKey,
+              pauseAuthority: admin.publicKey,
             })
             .rpc();
           expect.fail('Should have thrown AlreadyPaused error');
@@ -212,7 +730,7 @@ describe('vouch-verifier', () => {
             .pauseProtocol()
             .accounts({
               config: configPda,
-              admin: nonAdmin.publicKey,
+              pauseAuthority: nonAdmin.publicKey,
             })
             .signers([nonAdmin])
             .rpc();
@@ -232,7 +750,7 @@ describe('vouch-verifier', () => {
             .pauseProtocol()
             .accounts({
               config: configPda,
-              admin: admin.publicKey,
+              pauseAuthority: admin.publicKey,
             })
             .rpc();
         }
@@ -241,7 +759,7 @@ describe('vouch-verifier', () => {
           .unpauseProtocol()
           .accounts({
             config: configPda,
-            admin: admin.publicKey,
+            pauseAuthority: admin.publicKey,
           })
           .rpc();
 
@@ -256,7 +774,7 @@ describe('vouch-verifier', () => {
             .unpauseProtocol()
             .accounts({
               config: configPda,
-              admin: admin.publicKey,
+              pauseAuthority: admin.publicKey,
             })
             .rpc();
         }
@@ -266,7 +784,7 @@ describe('vouch-verifier', () => {
             .unpauseProtocol()
             .accounts({
               config: configPda,
-              admin: admin.publicKey,
+              pauseAuthority: admin.publicKey,
             })
             .rpc();
           expect.fail('Should have thrown NotPaused error');
@@ -276,10 +794,67 @@ describe('vouch-verifier', () => {
       });
     });
 
+    describe('set_pause_authority', () => {
+      it('should let a delegated pause authority pause without admin', async () => {
+        const hotKey = Keypair.generate();
+
+        await program.methods
+          .setPauseAuthority(hotKey.publicKey)
+          .accounts({
+            config: configPda,
+            admin: admin.publicKey,
+          })
+          .rpc();
+
+        try {
+          await program.methods
+            .pauseProtocol()
+            .accounts({
+              config: configPda,
+              pauseAuthority: admin.publicKey,
+            })
+            .rpc();
+          expect.fail('Admin should no longer be able to pause');
+        } catch (error) {
+          expect(error.toString()).to.include('Unauthorized');
+        }
+
+        await program.methods
+          .pauseProtocol()
+          .accounts({
+            config: configPda,
+            pauseAuthority: hotKey.publicKey,
+          })
+          .signers([hotKey])
+          .rpc();
+        await program.methods
+          .unpauseProtocol()
+          .accounts({
+            config: configPda,
+            pauseAuthority: hotKey.publicKey,
+          })
+          .signers([hotKey])
+          .rpc();
+
+        // Restore admin as pause authority
+        await program.methods
+          .setPauseAuthority(admin.publicKey)
+          .accounts({
+            config: configPda,
+            admin: admin.publicKey,
+          })
+          .rpc();
+
+        const config = await program.account.configAccount.fetch(configPda);
+        expect(config.pauseAuthority.toBase58()).to.equal(admin.publicKey.toBase58());
+        expect(config.isPaused).to.be.false;
+      });
+    });
+
     describe('update_rate_limits', () => {
       it('should update rate limits', async () => {
         await program.methods
-          .updateRateLimits(20, new anchor.BN(120))
+          .updateRateLimits(20, 15, 5, new anchor.BN(120))
           .accounts({
             config: configPda,
             admin: admin.publicKey,
@@ -288,11 +863,13 @@ describe('vouch-verifier', () => {
 
         const config = await program.account.configAccount.fetch(configPda);
         expect(config.maxProofsPerDay).to.equal(20);
+        expect(config.maxDevProofsPerDay).to.equal(15);
+        expect(config.maxWhaleProofsPerDay).to.equal(5);
         expect(config.cooldownSeconds.toNumber()).to.equal(120);
 
         // Reset to defaults
         await program.methods
-          .updateRateLimits(10, new anchor.BN(60))
+          .updateRateLimits(10, 10, 10, new anchor.BN(60))
           .accounts({
             config: configPda,
             admin: admin.publicKey,
@@ -303,7 +880,37 @@ describe('vouch-verifier', () => {
       it('should fail with zero max_proofs_per_day', async () => {
         try {
           await program.methods
-            .updateRateLimits(0, new anchor.BN(60))
+            .updateRateLimits(0, 10, 10, new anchor.BN(60))
+            .accounts({
+              config: configPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+          expect.fail('Should have thrown InvalidRateLimit error');
+        } catch (error) {
+          expect(error.toString()).to.include('InvalidRateLimit');
+        }
+      });
+
+      it('should fail with max_proofs_per_day above the rolling window size', async () => {
+        try {
+          await program.methods
+            .updateRateLimits(33, 10, 10, new anchor.BN(60))
+            .accounts({
+              config: configPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+          expect.fail('Should have thrown InvalidRateLimit error');
+        } catch (error) {
+          expect(error.toString()).to.include('InvalidRateLimit');
+        }
+      });
+
+      it('should fail with a zero per-type limit', async () => {
+        try {
+          await program.methods
+            .updateRateLimits(10, 10, 0, new anchor.BN(60))
             .accounts({
               config: configPda,
               admin: admin.publicKey,
@@ -318,7 +925,7 @@ describe('vouch-verifier', () => {
       it('should fail with negative cooldown', async () => {
         try {
           await program.methods
-            .updateRateLimits(10, new anchor.BN(-1))
+            .updateRateLimits(10, 10, 10, new anchor.BN(-1))
             .accounts({
               config: configPda,
               admin: admin.publicKey,
@@ -331,320 +938,5528 @@ describe('vouch-verifier', () => {
       });
     });
 
-    describe('transfer_admin', () => {
-      it('should transfer admin authority', async () => {
-        const newAdmin = Keypair.generate();
+    describe('set_max_global_proofs_per_day', () => {
+      it('should reject attestations once the global daily cap is reached', async () => {
+        const config = await program.account.configAccount.fetch(configPda);
+        const todayStart = Math.floor(Date.now() / 1000 / 86400) * 86400;
+        const proofsToday =
+          config.globalDayStart.toNumber() === todayStart ? config.globalProofsToday : 0;
+        await program.methods
+          .setMaxGlobalProofsPerDay(proofsToday + 1)
+          .accounts({ config: configPda, admin: admin.publicKey })
+          .rpc();
+
+        try {
+          const updated = await program.account.configAccount.fetch(configPda);
+          expect(updated.maxGlobalProofsPerDay).to.equal(proofsToday + 1);
+
+          await recordDevCredential(Keypair.generate().publicKey);
+          try {
+            await recordDevCredential(Keypair.generate().publicKey);
+            expect.fail('Should have thrown GlobalRateLimitExceeded error');
+          } catch (error) {
+            expect(error.toString()).to.include('GlobalRateLimitExceeded');
+          }
+        } finally {
+          await program.methods
+            .setMaxGlobalProofsPerDay(0)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .rpc();
+        }
+      });
+    });
+
+    describe('set_min_active_verifiers', () => {
+      it('should update the attestation verifier floor', async () => {
+        let config = await program.account.configAccount.fetch(configPda);
+        expect(config.minActiveVerifiers).to.equal(1);
 
         await program.methods
-          .transferAdmin(newAdmin.publicKey)
+          .setMinActiveVerifiers(3)
           .accounts({
             config: configPda,
             admin: admin.publicKey,
           })
           .rpc();
 
-        let config = await program.account.configAccount.fetch(configPda);
-        expect(config.admin.toBase58()).to.equal(newAdmin.publicKey.toBase58());
+        config = await program.account.configAccount.fetch(configPda);
+        expect(config.minActiveVerifiers).to.equal(3);
 
-        // Transfer back to original admin
+        // Reset to default
         await program.methods
-          .transferAdmin(admin.publicKey)
+          .setMinActiveVerifiers(1)
           .accounts({
             config: configPda,
-            admin: newAdmin.publicKey,
+            admin: admin.publicKey,
           })
-          .signers([newAdmin])
           .rpc();
-
-        config = await program.account.configAccount.fetch(configPda);
-        expect(config.admin.toBase58()).to.equal(admin.publicKey.toBase58());
       });
     });
-  });
-
-  // ==========================================
-  // Rate Limiting Tests
-  // ==========================================
-
-  describe('rate_limiting', () => {
-    describe('init_rate_limit', () => {
-      it('should initialize rate limit for a wallet', async () => {
-        const testWallet = Keypair.generate();
-        const rateLimitPda = getRateLimitPda(testWallet.publicKey);
 
+    describe('set_reset_skew_tolerance', () => {
+      it('should update the reset skew tolerance', async () => {
         await program.methods
-          .initRateLimit()
+          .setResetSkewTolerance(new anchor.BN(30))
           .accounts({
-            rateLimit: rateLimitPda,
-            wallet: testWallet.publicKey,
-            payer: admin.publicKey,
-            systemProgram: SystemProgram.programId,
+            config: configPda,
+            admin: admin.publicKey,
           })
           .rpc();
 
-        const rateLimit = await program.account.walletRateLimit.fetch(rateLimitPda);
-        expect(rateLimit.wallet.toBase58()).to.equal(testWallet.publicKey.toBase58());
-        expect(rateLimit.proofsToday).to.equal(0);
-        expect(rateLimit.totalProofs.toNumber()).to.equal(0);
-      });
-
-      it('should fail to re-initialize rate limit', async () => {
-        const testWallet = Keypair.generate();
-        const rateLimitPda = getRateLimitPda(testWallet.publicKey);
+        const config = await program.account.configAccount.fetch(configPda);
+        expect(config.resetSkewToleranceSeconds.toNumber()).to.equal(30);
 
+        // Reset to default (exact boundary)
         await program.methods
-          .initRateLimit()
+          .setResetSkewTolerance(new anchor.BN(0))
           .accounts({
-            rateLimit: rateLimitPda,
-            wallet: testWallet.publicKey,
-            payer: admin.publicKey,
-            systemProgram: SystemProgram.programId,
+            config: configPda,
+            admin: admin.publicKey,
           })
           .rpc();
+      });
 
+      it('should fail with tolerance above the maximum', async () => {
         try {
           await program.methods
-            .initRateLimit()
+            .setResetSkewTolerance(new anchor.BN(301))
             .accounts({
-              rateLimit: rateLimitPda,
-              wallet: testWallet.publicKey,
-              payer: admin.publicKey,
-              systemProgram: SystemProgram.programId,
+              config: configPda,
+              admin: admin.publicKey,
             })
             .rpc();
-          expect.fail('Should have thrown an error');
+          expect.fail('Should have thrown InvalidRateLimit error');
         } catch (error) {
-          expect(error).to.exist;
+          expect(error.toString()).to.include('InvalidRateLimit');
         }
       });
     });
-  });
 
-  // ==========================================
-  // Verifier Management Tests
-  // ==========================================
-
-  describe('verifier_management', () => {
-    describe('add_verifier', () => {
-      it('should add a verifier', async () => {
-        // Ensure protocol is not paused
+    describe('get_effective_limits', () => {
+      it('should return the limits for each proof type', async () => {
         const config = await program.account.configAccount.fetch(configPda);
-        if (config.isPaused) {
-          await program.methods
-            .unpauseProtocol()
-            .accounts({
-              config: configPda,
-              admin: admin.publicKey,
-            })
-            .rpc();
+        const typeLimits = {
+          1: config.maxDevProofsPerDay,
+          2: config.maxWhaleProofsPerDay,
+        };
+
+        for (const proofTypeValue of [1, 2]) {
+          const limits = await program.methods
+            .getEffectiveLimits(proofTypeValue)
+            .accounts({ config: configPda })
+            .view();
+
+          expect(limits.maxPerDay).to.equal(
+            Math.min(typeLimits[proofTypeValue], config.maxProofsPerDay)
+          );
+          expect(limits.cooldownSeconds.toNumber()).to.equal(config.cooldownSeconds.toNumber());
         }
+      });
 
-        const verifierKeypair = Keypair.generate();
-        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+      it('should reject an unknown proof type', async () => {
+        try {
+          await program.methods
+            .getEffectiveLimits(9)
+            .accounts({ config: configPda })
+            .view();
+          expect.fail('Should have thrown InvalidProofType error');
+        } catch (error) {
+          expect(error.toString()).to.include('InvalidProofType');
+        }
+      });
+    });
 
+    describe('set_shadow_wire_program', () => {
+      it('should record and expose the ShadowWire program id', async () => {
+        const shadowWireProgram = Keypair.generate().publicKey;
         await program.methods
-          .addVerifier(verifierKeypair.publicKey)
+          .setShadowWireProgram(shadowWireProgram)
           .accounts({
             config: configPda,
-            verifierAccount: verifierPda,
             admin: admin.publicKey,
-            systemProgram: SystemProgram.programId,
           })
           .rpc();
 
-        const verifierAccount = await program.account.verifierAccount.fetch(verifierPda);
-        expect(verifierAccount.verifier.toBase58()).to.equal(verifierKeypair.publicKey.toBase58());
-        expect(verifierAccount.isActive).to.be.true;
-        expect(verifierAccount.attestationCount.toNumber()).to.equal(0);
-
-        const updatedConfig = await program.account.configAccount.fetch(configPda);
-        expect(updatedConfig.verifierCount).to.be.greaterThan(0);
+        const programId = await program.methods
+          .getShadowWireProgram()
+          .accounts({ config: configPda })
+          .view();
+        expect(programId.toBase58()).to.equal(shadowWireProgram.toBase58());
       });
 
-      it('should fail to add verifier when paused', async () => {
-        // Pause the protocol
-        let config = await program.account.configAccount.fetch(configPda);
-        if (!config.isPaused) {
+      it('should fail with non-admin', async () => {
+        const other = Keypair.generate();
+        try {
           await program.methods
-            .pauseProtocol()
+            .setShadowWireProgram(other.publicKey)
             .accounts({
               config: configPda,
-              admin: admin.publicKey,
+              admin: other.publicKey,
             })
+            .signers([other])
             .rpc();
+          expect.fail('Should have thrown Unauthorized error');
+        } catch (error) {
+          expect(error.toString()).to.include('Unauthorized');
         }
+      });
+    });
 
-        const verifierKeypair = Keypair.generate();
-        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+    describe('set_auto_pause_threshold', () => {
+      it('should configure and disable the attestation circuit breaker', async () => {
+        await program.methods
+          .setAutoPauseThreshold(100, new anchor.BN(60))
+          .accounts({
+            config: configPda,
+            admin: admin.publicKey,
+          })
+          .rpc();
+
+        let config = await program.account.configAccount.fetch(configPda);
+        expect(config.maxAttestationsPerWindow).to.equal(100);
+        expect(config.attestationWindowSeconds.toNumber()).to.equal(60);
+        expect(config.attestationRateLevel.toNumber()).to.equal(0);
+
+        await program.methods
+          .setAutoPauseThreshold(0, new anchor.BN(0))
+          .accounts({
+            config: configPda,
+            admin: admin.publicKey,
+          })
+          .rpc();
+
+        config = await program.account.configAccount.fetch(configPda);
+        expect(config.maxAttestationsPerWindow).to.equal(0);
+      });
 
+      it('should reject an enabled threshold without a window', async () => {
         try {
           await program.methods
-            .addVerifier(verifierKeypair.publicKey)
+            .setAutoPauseThreshold(100, new anchor.BN(0))
             .accounts({
               config: configPda,
-              verifierAccount: verifierPda,
               admin: admin.publicKey,
-              systemProgram: SystemProgram.programId,
             })
             .rpc();
-          expect.fail('Should have thrown ProtocolPaused error');
+          expect.fail('Should have thrown InvalidRateLimit error');
         } catch (error) {
-          expect(error.toString()).to.include('ProtocolPaused');
+          expect(error.toString()).to.include('InvalidRateLimit');
         }
+      });
+    });
+
+    describe('transfer_admin', () => {
+      it('should transfer admin authority once the nominee accepts', async () => {
+        const newAdmin = Keypair.generate();
 
-        // Unpause for other tests
         await program.methods
-          .unpauseProtocol()
+          .transferAdmin(newAdmin.publicKey)
           .accounts({
             config: configPda,
             admin: admin.publicKey,
           })
           .rpc();
+
+        let config = await program.account.configAccount.fetch(configPda);
+        expect(config.admin.toBase58()).to.equal(admin.publicKey.toBase58());
+        expect(config.pendingAdmin.toBase58()).to.equal(newAdmin.publicKey.toBase58());
+
+        await program.methods
+          .acceptAdmin()
+          .accounts({
+            config: configPda,
+            newAdmin: newAdmin.publicKey,
+          })
+          .signers([newAdmin])
+          .rpc();
+
+        config = await program.account.configAccount.fetch(configPda);
+        expect(config.admin.toBase58()).to.equal(newAdmin.publicKey.toBase58());
+        expect(config.pendingAdmin.toBase58()).to.equal(PublicKey.default.toBase58());
+
+        // Transfer back to original admin
+        await program.methods
+          .transferAdmin(admin.publicKey)
+          .accounts({
+            config: configPda,
+            admin: newAdmin.publicKey,
+          })
+          .signers([newAdmin])
+          .rpc();
+
+        await program.methods
+          .acceptAdmin()
+          .accounts({
+            config: configPda,
+            newAdmin: admin.publicKey,
+          })
+          .rpc();
+
+        config = await program.account.configAccount.fetch(configPda);
+        expect(config.admin.toBase58()).to.equal(admin.publicKey.toBase58());
       });
-    });
 
-    describe('remove_verifier', () => {
-      it('should remove a verifier', async () => {
-        const verifierKeypair = Keypair.generate();
-        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+      it('should let the admin cancel a pending transfer', async () => {
+        const newAdmin = Keypair.generate();
 
-        // First add the verifier
         await program.methods
-          .addVerifier(verifierKeypair.publicKey)
+          .transferAdmin(newAdmin.publicKey)
           .accounts({
             config: configPda,
-            verifierAccount: verifierPda,
             admin: admin.publicKey,
-            systemProgram: SystemProgram.programId,
           })
           .rpc();
 
-        // Then remove it
         await program.methods
-          .removeVerifier()
+          .cancelAdminTransfer()
           .accounts({
             config: configPda,
-            verifierAccount: verifierPda,
             admin: admin.publicKey,
           })
           .rpc();
 
-        const verifierAccount = await program.account.verifierAccount.fetch(verifierPda);
-        expect(verifierAccount.isActive).to.be.false;
+        try {
+          await program.methods
+            .acceptAdmin()
+            .accounts({
+              config: configPda,
+              newAdmin: newAdmin.publicKey,
+            })
+            .signers([newAdmin])
+            .rpc();
+          expect.fail('Should have thrown NoPendingAdmin error');
+        } catch (error) {
+          expect(error.toString()).to.include('NoPendingAdmin');
+        }
+      });
+    });
+
+    describe('validate_config', () => {
+      it('should report no setter-guarded violations', async () => {
+        const issues = await program.methods
+          .validateConfig()
+          .accounts({ config: configPda })
+          .view();
+
+        // Rate limit, reset skew, durations, reaper bounty, scan distance, time source
+        // and pause authority are all at defaults or valid setter values
+        const guarded = 0b1011_1111;
+        expect(issues & guarded).to.equal(0);
       });
     });
   });
 
   // ==========================================
-  // Commitment Tests
+  // Rate Limiting Tests
   // ==========================================
 
-  describe('create_commitment', () => {
-    it('should create a new commitment', async () => {
-      const uniqueCommitment = randomBytes(32);
-      const commitmentPda = getCommitmentPda(uniqueCommitment);
-
-      await program.methods
-        .createCommitment(Array.from(uniqueCommitment) as number[] & { length: 32 })
-        .accounts({
-          commitmentAccount: commitmentPda,
-          owner: admin.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .rpc();
-
-      const account = await program.account.commitmentAccount.fetch(commitmentPda);
-      expect(account.owner.toBase58()).to.equal(admin.publicKey.toBase58());
-      expect(Buffer.from(account.commitment).equals(Buffer.from(uniqueCommitment))).to.be.true;
-      expect(account.createdAt.toNumber()).to.be.greaterThan(0);
-    });
-
-    it('should fail to create duplicate commitment', async () => {
-      const [commitmentPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from('commitment'), Buffer.from(testCommitment)],
-        program.programId
-      );
+  describe('rate_limiting', () => {
+    describe('init_rate_limit', () => {
+      it('should initialize rate limit for a wallet', async () => {
+        const testWallet = Keypair.generate();
+        const rateLimitPda = getRateLimitPda(testWallet.publicKey);
 
-      // Create commitment if it doesn't exist
-      try {
         await program.methods
-          .createCommitment(Array.from(testCommitment) as number[] & { length: 32 })
+          .initRateLimit()
           .accounts({
-            commitmentAccount: commitmentPda,
-            owner: admin.publicKey,
+            rateLimit: rateLimitPda,
+            wallet: testWallet.publicKey,
+            payer: admin.publicKey,
             systemProgram: SystemProgram.programId,
           })
           .rpc();
-      } catch {
-        // Commitment already exists, which is fine
-      }
 
-      // Try to create duplicate
-      try {
+        const rateLimit = await program.account.walletRateLimit.fetch(rateLimitPda);
+        expect(rateLimit.wallet.toBase58()).to.equal(testWallet.publicKey.toBase58());
+        expect(rateLimit.proofsToday).to.equal(0);
+        expect(rateLimit.totalProofs.toNumber()).to.equal(0);
+      });
+
+      it('should leave the daily window unstarted until the first proof', async () => {
+        const testWallet = Keypair.generate();
+        const rateLimitPda = getRateLimitPda(testWallet.publicKey);
+
         await program.methods
-          .createCommitment(Array.from(testCommitment) as number[] & { length: 32 })
+          .initRateLimit()
           .accounts({
-            commitmentAccount: commitmentPda,
-            owner: admin.publicKey,
+            rateLimit: rateLimitPda,
+            wallet: testWallet.publicKey,
+            payer: admin.publicKey,
             systemProgram: SystemProgram.programId,
           })
           .rpc();
-        expect.fail('Should have thrown an error');
-      } catch (error) {
-        expect(error).to.exist;
-      }
-    });
-  });
-
-  // ==========================================
-  // Nullifier Tests
-  // ==========================================
-
-  describe('init_nullifier', () => {
-    it('should initialize a new nullifier account', async () => {
-      const uniqueNullifier = randomBytes(32);
-      const nullifierPda = getNullifierPda(uniqueNullifier);
-
-      await program.methods
-        .initNullifier(Array.from(uniqueNullifier) as number[] & { length: 32 })
-        .accounts({
-          nullifierAccount: nullifierPda,
-          payer: admin.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .rpc();
-
-      const account = await program.account.nullifierAccount.fetch(nullifierPda);
-      expect(account.isUsed).to.be.false;
-      expect(account.proofType).to.deep.equal({ unset: {} });
-      expect(Buffer.from(account.nullifier).equals(Buffer.from(uniqueNullifier))).to.be.true;
-    });
 
-    it('should fail to initialize duplicate nullifier', async () => {
-      const uniqueNullifier = randomBytes(32);
-      const nullifierPda = getNullifierPda(uniqueNullifier);
+        // Provisioning early must not burn the first day: the window opens on first proof
+        const rateLimit = await program.account.walletRateLimit.fetch(rateLimitPda);
+        expect(rateLimit.dayStart.toNumber()).to.equal(0);
+        expect(rateLimit.lastProofAt.toNumber()).to.equal(0);
+        expect(rateLimit.recentHead).to.equal(0);
+        expect(rateLimit.recentProofs.every((p) => p.timestamp.toNumber() === 0)).to.be.true;
+      });
 
-      await program.methods
-        .initNullifier(Array.from(uniqueNullifier) as number[] & { length: 32 })
-        .accounts({
-          nullifierAccount: nullifierPda,
-          payer: admin.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .rpc();
+      it('should fail to re-initialize rate limit', async () => {
+        const testWallet = Keypair.generate();
+        const rateLimitPda = getRateLimitPda(testWallet.publicKey);
 
-      try {
         await program.methods
-          .initNullifier(Array.from(uniqueNullifier) as number[] & { length: 32 })
+          .initRateLimit()
           .accounts({
-            nullifierAccount: nullifierPda,
+            rateLimit: rateLimitPda,
+            wallet: testWallet.publicKey,
             payer: admin.publicKey,
             systemProgram: SystemProgram.programId,
           })
           .rpc();
-        expect.fail('Should have thrown an error');
-      } catch (error) {
-        expect(error).to.exist;
+
+        try {
+          await program.methods
+            .initRateLimit()
+            .accounts({
+              rateLimit: rateLimitPda,
+              wallet: testWallet.publicKey,
+              payer: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+          expect.fail('Should have thrown an error');
+        } catch (error) {
+          expect(error).to.exist;
+        }
+      });
+    });
+
+    describe('global claim cooldown', () => {
+      it('should initialize a wallet claim limit', async () => {
+        const testWallet = Keypair.generate();
+        const [claimLimitPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from('claim_limit'), testWallet.publicKey.toBuffer()],
+          program.programId
+        );
+
+        await program.methods
+          .initClaimLimit()
+          .accounts({
+            claimLimit: claimLimitPda,
+            wallet: testWallet.publicKey,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const claimLimit = await program.account.walletClaimLimit.fetch(claimLimitPda);
+        expect(claimLimit.wallet.toBase58()).to.equal(testWallet.publicKey.toBase58());
+        expect(claimLimit.lastClaimAt.toNumber()).to.equal(0);
+      });
+
+      it('should reject a negative claim cooldown', async () => {
+        try {
+          await program.methods
+            .setGlobalClaimCooldown(new anchor.BN(-1))
+            .accounts({
+              config: configPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+          expect.fail('Should have thrown InvalidRateLimit error');
+        } catch (error) {
+          expect(error.toString()).to.include('InvalidRateLimit');
+        }
+      });
+    });
+
+    describe('reset_wallet_rate_limit', () => {
+      it('should reset a wallet rate limit as admin', async () => {
+        const testWallet = Keypair.generate();
+        const rateLimitPda = getRateLimitPda(testWallet.publicKey);
+
+        await program.methods
+          .initRateLimit()
+          .accounts({
+            rateLimit: rateLimitPda,
+            wallet: testWallet.publicKey,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        await program.methods
+          .resetWalletRateLimit()
+          .accounts({
+            config: configPda,
+            rateLimit: rateLimitPda,
+            wallet: testWallet.publicKey,
+            admin: admin.publicKey,
+          })
+          .rpc();
+
+        const rateLimit = await program.account.walletRateLimit.fetch(rateLimitPda);
+        expect(rateLimit.proofsToday).to.equal(0);
+        expect(rateLimit.lastProofAt.toNumber()).to.equal(0);
+        expect(rateLimit.dayStart.toNumber()).to.be.greaterThan(0);
+        expect(rateLimit.totalProofs.toNumber()).to.equal(0);
+      });
+
+      it('should fail to reset with non-admin', async () => {
+        const testWallet = Keypair.generate();
+        const rateLimitPda = getRateLimitPda(testWallet.publicKey);
+        const nonAdmin = Keypair.generate();
+
+        await program.methods
+          .initRateLimit()
+          .accounts({
+            rateLimit: rateLimitPda,
+            wallet: testWallet.publicKey,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        try {
+          await program.methods
+            .resetWalletRateLimit()
+            .accounts({
+              config: configPda,
+              rateLimit: rateLimitPda,
+              wallet: testWallet.publicKey,
+              admin: nonAdmin.publicKey,
+            })
+            .signers([nonAdmin])
+            .rpc();
+          expect.fail('Should have thrown an error');
+        } catch (error) {
+          expect(error).to.exist;
+        }
+      });
+    });
+
+    describe('set_rate_limit_exempt', () => {
+      it('should toggle a wallet exemption as admin', async () => {
+        const testWallet = Keypair.generate();
+        const rateLimitPda = getRateLimitPda(testWallet.publicKey);
+
+        await program.methods
+          .initRateLimit()
+          .accounts({
+            rateLimit: rateLimitPda,
+            wallet: testWallet.publicKey,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        let rateLimit = await program.account.walletRateLimit.fetch(rateLimitPda);
+        expect(rateLimit.exempt).to.be.false;
+
+        await program.methods
+          .setRateLimitExempt(true)
+          .accounts({
+            config: configPda,
+            rateLimit: rateLimitPda,
+            wallet: testWallet.publicKey,
+            admin: admin.publicKey,
+          })
+          .rpc();
+
+        rateLimit = await program.account.walletRateLimit.fetch(rateLimitPda);
+        expect(rateLimit.exempt).to.be.true;
+      });
+
+      it('should reject exemptions from a non-admin', async () => {
+        const testWallet = Keypair.generate();
+        const rateLimitPda = getRateLimitPda(testWallet.publicKey);
+        const nonAdmin = Keypair.generate();
+
+        await program.methods
+          .initRateLimit()
+          .accounts({
+            rateLimit: rateLimitPda,
+            wallet: testWallet.publicKey,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        try {
+          await program.methods
+            .setRateLimitExempt(true)
+            .accounts({
+              config: configPda,
+              rateLimit: rateLimitPda,
+              wallet: testWallet.publicKey,
+              admin: nonAdmin.publicKey,
+            })
+            .signers([nonAdmin])
+            .rpc();
+          expect.fail('Should have thrown Unauthorized error');
+        } catch (error) {
+          expect(error.toString()).to.include('Unauthorized');
+        }
+      });
+    });
+
+    describe('record_attestation rate limit binding', () => {
+      it('should reject a rate limit account belonging to a different wallet', async () => {
+        const recipient = Keypair.generate();
+        const otherWallet = Keypair.generate();
+        const otherRateLimitPda = getRateLimitPda(otherWallet.publicKey);
+
+        await program.methods
+          .initRateLimit()
+          .accounts({
+            rateLimit: otherRateLimitPda,
+            wallet: otherWallet.publicKey,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const nullifier = randomBytes(32);
+        const nullifierPda = getNullifierPda(nullifier, 1);
+        await program.methods
+          .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+          .accounts({
+            nullifierAccount: nullifierPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        try {
+          await program.methods
+            .recordAttestation(
+              Array.from(randomBytes(32)) as number[] & { length: 32 },
+              1,
+              Array.from(nullifier) as number[] & { length: 32 },
+              new anchor.BN(Math.floor(Date.now() / 1000 / 86400)),
+              Array.from(randomBytes(32)) as number[] & { length: 32 },
+              Array.from(new Uint8Array(64)) as number[] & { length: 64 },
+              null,
+              new anchor.BN(Math.floor(Date.now() / 1000)),
+              new anchor.BN(Math.floor(Date.now() / 1000) + 600)
+            )
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              nullifierAccount: nullifierPda,
+              rateLimit: otherRateLimitPda,
+              recipient: recipient.publicKey,
+              commitmentAccount: null,
+              blockedRecipient: null,
+              usedSignature: null,
+              payer: admin.publicKey,
+              instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            })
+            .rpc();
+          expect.fail('Should have thrown an error');
+        } catch (error: any) {
+          // Rejected by account validation before the signature is checked
+          expect(error.toString()).to.match(/ConstraintSeeds|RateLimitWalletMismatch/);
+        }
+      });
+
+      it('should log VOUCH_ERR context when the epoch is too old', async () => {
+        const recipient = Keypair.generate();
+        const rateLimitPda = getRateLimitPda(recipient.publicKey);
+        await program.methods
+          .initRateLimit()
+          .accounts({
+            rateLimit: rateLimitPda,
+            wallet: recipient.publicKey,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const nullifier = randomBytes(32);
+        const nullifierPda = getNullifierPda(nullifier, 1);
+        await program.methods
+          .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+          .accounts({
+            nullifierAccount: nullifierPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        try {
+          await program.methods
+            .recordAttestation(
+              Array.from(randomBytes(32)) as number[] & { length: 32 },
+              1,
+              Array.from(nullifier) as number[] & { length: 32 },
+              new anchor.BN(0),
+              Array.from(randomBytes(32)) as number[] & { length: 32 },
+              Array.from(new Uint8Array(64)) as number[] & { length: 64 },
+              null,
+              new anchor.BN(Math.floor(Date.now() / 1000)),
+              new anchor.BN(Math.floor(Date.now() / 1000) + 600)
+            )
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              nullifierAccount: nullifierPda,
+              rateLimit: rateLimitPda,
+              recipient: recipient.publicKey,
+              commitmentAccount: null,
+              blockedRecipient: null,
+              usedSignature: null,
+              payer: admin.publicKey,
+              instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            })
+            .rpc();
+          expect.fail('Should have thrown EpochTooOld error');
+        } catch (error: any) {
+          expect(error.toString()).to.include('EpochTooOld');
+          const logs = (error.logs ?? []).join('\n');
+          expect(logs).to.match(/VOUCH_ERR:EpochTooOld epoch=0 current_epoch=\d+ max_epoch_age=\d+/);
+        }
+      });
+
+      it('should reject an attestation past its expiry', async () => {
+        const recipient = Keypair.generate();
+        const rateLimitPda = getRateLimitPda(recipient.publicKey);
+        await program.methods
+          .initRateLimit()
+          .accounts({
+            rateLimit: rateLimitPda,
+            wallet: recipient.publicKey,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const nullifier = randomBytes(32);
+        const nullifierPda = getNullifierPda(nullifier, 1);
+        await program.methods
+          .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+          .accounts({
+            nullifierAccount: nullifierPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        try {
+          await program.methods
+            .recordAttestation(
+              Array.from(randomBytes(32)) as number[] & { length: 32 },
+              1,
+              Array.from(nullifier) as number[] & { length: 32 },
+              new anchor.BN(Math.floor(Date.now() / 1000 / 86400)),
+              Array.from(randomBytes(32)) as number[] & { length: 32 },
+              Array.from(new Uint8Array(64)) as number[] & { length: 64 },
+              null,
+              new anchor.BN(Math.floor(Date.now() / 1000)),
+              new anchor.BN(Math.floor(Date.now() / 1000) - 600)
+            )
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              nullifierAccount: nullifierPda,
+              rateLimit: rateLimitPda,
+              recipient: recipient.publicKey,
+              commitmentAccount: null,
+              blockedRecipient: null,
+              usedSignature: null,
+              payer: admin.publicKey,
+              instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            })
+            .rpc();
+          expect.fail('Should have thrown AttestationExpired error');
+        } catch (error: any) {
+          expect(error.toString()).to.include('AttestationExpired');
+        }
+      });
+    });
+
+    describe('recipient blocklist', () => {
+      it('should reject attestations for a blocked recipient while enforced', async () => {
+        const recipient = Keypair.generate();
+        const rateLimitPda = getRateLimitPda(recipient.publicKey);
+        await program.methods
+          .initRateLimit()
+          .accounts({
+            rateLimit: rateLimitPda,
+            wallet: recipient.publicKey,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const nullifier = randomBytes(32);
+        const nullifierPda = getNullifierPda(nullifier, 1);
+        await program.methods
+          .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+          .accounts({
+            nullifierAccount: nullifierPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const [blockedPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from('blocked_recipient'), recipient.publicKey.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .blockRecipient(recipient.publicKey)
+          .accounts({
+            config: configPda,
+            blockedRecipient: blockedPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        const blocked = await program.account.blockedRecipient.fetch(blockedPda);
+        expect(blocked.recipient.toBase58()).to.equal(recipient.publicKey.toBase58());
+
+        await program.methods
+          .setEnforceRecipientBlocklist(true)
+          .accounts({ config: configPda, admin: admin.publicKey })
+          .rpc();
+
+        try {
+          try {
+            await program.methods
+              .recordAttestation(
+                Array.from(randomBytes(32)) as number[] & { length: 32 },
+                1,
+                Array.from(nullifier) as number[] & { length: 32 },
+                new anchor.BN(Math.floor(Date.now() / 1000 / 86400)),
+                Array.from(randomBytes(32)) as number[] & { length: 32 },
+                Array.from(new Uint8Array(64)) as number[] & { length: 64 },
+                null,
+                new anchor.BN(Math.floor(Date.now() / 1000)),
+                new anchor.BN(Math.floor(Date.now() / 1000) + 600)
+              )
+              .accounts({
+                config: configPda,
+                verifierAccount: verifierPda,
+                nullifierAccount: nullifierPda,
+                rateLimit: rateLimitPda,
+                recipient: recipient.publicKey,
+                commitmentAccount: null,
+                blockedRecipient: blockedPda,
+                usedSignature: null,
+                payer: admin.publicKey,
+                instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+              })
+              .rpc();
+            expect.fail('Should have thrown RecipientBlocked error');
+          } catch (error: any) {
+            expect(error.toString()).to.include('RecipientBlocked');
+          }
+        } finally {
+          await program.methods
+            .setEnforceRecipientBlocklist(false)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .rpc();
+          await program.methods
+            .unblockRecipient()
+            .accounts({
+              config: configPda,
+              blockedRecipient: blockedPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+        }
+        expect(await provider.connection.getAccountInfo(blockedPda)).to.be.null;
+      });
+    });
+
+    describe('commitment binding', () => {
+      it('should require the recipient commitment when binding is enabled', async () => {
+        await program.methods
+          .setBindCommitment(true)
+          .accounts({
+            config: configPda,
+            admin: admin.publicKey,
+          })
+          .rpc();
+
+        try {
+          const recipient = Keypair.generate();
+          const rateLimitPda = getRateLimitPda(recipient.publicKey);
+          await program.methods
+            .initRateLimit()
+            .accounts({
+              rateLimit: rateLimitPda,
+              wallet: recipient.publicKey,
+              payer: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+          const verifierKeypair = Keypair.generate();
+          const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+          await program.methods
+            .addVerifier(verifierKeypair.publicKey)
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              admin: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+          const nullifier = randomBytes(32);
+          const nullifierPda = getNullifierPda(nullifier, 1);
+          await program.methods
+            .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+            .accounts({
+              nullifierAccount: nullifierPda,
+              payer: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+          try {
+            await program.methods
+              .recordAttestation(
+                Array.from(randomBytes(32)) as number[] & { length: 32 },
+                1,
+                Array.from(nullifier) as number[] & { length: 32 },
+                new anchor.BN(Math.floor(Date.now() / 1000 / 86400)),
+                Array.from(randomBytes(32)) as number[] & { length: 32 },
+                Array.from(new Uint8Array(64)) as number[] & { length: 64 },
+                null,
+                new anchor.BN(Math.floor(Date.now() / 1000)),
+                new anchor.BN(Math.floor(Date.now() / 1000) + 600)
+              )
+              .accounts({
+                config: configPda,
+                verifierAccount: verifierPda,
+                nullifierAccount: nullifierPda,
+                rateLimit: rateLimitPda,
+                recipient: recipient.publicKey,
+                commitmentAccount: null,
+                blockedRecipient: null,
+                usedSignature: null,
+                payer: admin.publicKey,
+                instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+              })
+              .rpc();
+            expect.fail('Should have thrown CommitmentRequired error');
+          } catch (error) {
+            expect(error.toString()).to.include('CommitmentRequired');
+          }
+        } finally {
+          await program.methods
+            .setBindCommitment(false)
+            .accounts({
+              config: configPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+        }
+      });
+    });
+
+    describe('commitment requirement', () => {
+      async function createOwnedCommitment(owner: Keypair): Promise<PublicKey> {
+        const commitment = randomBytes(32);
+        const commitmentPda = getCommitmentPda(commitment);
+        await program.methods
+          .createCommitment(Array.from(commitment) as number[] & { length: 32 })
+          .accounts({
+            commitmentAccount: commitmentPda,
+            owner: owner.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([owner])
+          .rpc();
+        return commitmentPda;
+      }
+
+      async function setRequireCommitment(requireCommitment: boolean, proofTypes: number) {
+        await program.methods
+          .setRequireCommitment(requireCommitment)
+          .accounts({ config: configPda, admin: admin.publicKey })
+          .rpc();
+        await program.methods
+          .setCommitmentProofTypes(proofTypes)
+          .accounts({ config: configPda, admin: admin.publicKey })
+          .rpc();
+      }
+
+      it('should require a commitment owned by the recipient', async () => {
+        await setRequireCommitment(true, 0);
+        try {
+          const recipient = Keypair.generate();
+          await fundWallet(recipient.publicKey);
+          try {
+            await recordDevCredential(recipient.publicKey);
+            expect.fail('Should have thrown CommitmentRequired error');
+          } catch (error) {
+            expect(error.toString()).to.include('CommitmentRequired');
+          }
+
+          const stranger = Keypair.generate();
+          await fundWallet(stranger.publicKey);
+          const strangerCommitment = await createOwnedCommitment(stranger);
+          try {
+            await recordDevCredential(recipient.publicKey, strangerCommitment);
+            expect.fail('Should have thrown CommitmentRequired error');
+          } catch (error) {
+            expect(error.toString()).to.include('CommitmentRequired');
+          }
+
+          const commitmentPda = await createOwnedCommitment(recipient);
+          const { nullifierPda } = await recordDevCredential(recipient.publicKey, commitmentPda);
+          const nullifierAccount = await program.account.nullifierAccount.fetch(nullifierPda);
+          expect(nullifierAccount.isUsed).to.be.true;
+        } finally {
+          await setRequireCommitment(false, 0);
+        }
+      });
+
+      it('should only gate the proof types in commitment_proof_types', async () => {
+        // proof_type_bit: DeveloperReputation = 1 << 1, WhaleTrading = 1 << 2
+        await setRequireCommitment(true, 1 << 2);
+        try {
+          const { nullifierPda } = await recordDevCredential(Keypair.generate().publicKey);
+          const nullifierAccount = await program.account.nullifierAccount.fetch(nullifierPda);
+          expect(nullifierAccount.isUsed).to.be.true;
+
+          await setRequireCommitment(true, 1 << 1);
+          try {
+            await recordDevCredential(Keypair.generate().publicKey);
+            expect.fail('Should have thrown CommitmentRequired error');
+          } catch (error) {
+            expect(error.toString()).to.include('CommitmentRequired');
+          }
+        } finally {
+          await setRequireCommitment(false, 0);
+        }
+      });
+    });
+
+    describe('record_attestation_threshold', () => {
+      it('should reject the same verifier signing twice', async () => {
+        const recipient = Keypair.generate();
+        const rateLimitPda = getRateLimitPda(recipient.publicKey);
+        await program.methods
+          .initRateLimit()
+          .accounts({
+            rateLimit: rateLimitPda,
+            wallet: recipient.publicKey,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const nullifier = randomBytes(32);
+        const nullifierPda = getNullifierPda(nullifier, 1);
+        await program.methods
+          .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+          .accounts({
+            nullifierAccount: nullifierPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const signature = {
+          verifier: verifierKeypair.publicKey,
+          signature: Array.from(new Uint8Array(64)) as number[] & { length: 64 },
+        };
+
+        try {
+          await program.methods
+            .recordAttestationThreshold({
+              attestationHash: Array.from(randomBytes(32)) as number[] & { length: 32 },
+              proofTypeValue: 1,
+              nullifier: Array.from(nullifier) as number[] & { length: 32 },
+              epoch: new anchor.BN(Math.floor(Date.now() / 1000 / 86400)),
+              dataHash: Array.from(randomBytes(32)) as number[] & { length: 32 },
+              signatures: [signature, signature],
+              proofCommitment: null,
+              proofGeneratedAt: new anchor.BN(Math.floor(Date.now() / 1000)),
+              expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 600),
+            })
+            .accounts({
+              config: configPda,
+              nullifierAccount: nullifierPda,
+              rateLimit: rateLimitPda,
+              recipient: recipient.publicKey,
+              commitmentAccount: null,
+              blockedRecipient: null,
+              payer: admin.publicKey,
+              instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+              systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts([
+              { pubkey: verifierPda, isWritable: true, isSigner: false },
+              { pubkey: verifierPda, isWritable: true, isSigner: false },
+            ])
+            .rpc();
+          expect.fail('Should have thrown DuplicateSigner error');
+        } catch (error) {
+          expect(error.toString()).to.include('DuplicateSigner');
+        }
+      });
+
+      it('should reject a zero signature threshold', async () => {
+        try {
+          await program.methods
+            .setRequiredSignatures(0)
+            .accounts({
+              config: configPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+          expect.fail('Should have thrown InvalidSignatureThreshold error');
+        } catch (error) {
+          expect(error.toString()).to.include('InvalidSignatureThreshold');
+        }
+      });
+    });
+
+    describe('attestation batches', () => {
+      // Mirrors build_attestation_batch_message
+      function buildBatchMessage(
+        verifier: PublicKey,
+        batchId: Uint8Array,
+        entriesRoot: Buffer,
+        entryCount: number,
+        expiresAt: bigint
+      ): Buffer {
+        const countBytes = Buffer.alloc(2);
+        countBytes.writeUInt16BE(entryCount);
+        const expiresBytes = Buffer.alloc(8);
+        expiresBytes.writeBigInt64BE(expiresAt);
+        return Buffer.concat([
+          Buffer.from('vouch_attestation_batch'),
+          verifier.toBuffer(),
+          Buffer.from(batchId),
+          entriesRoot,
+          countBytes,
+          expiresBytes,
+        ]);
+      }
+
+      it('should apply only entries inside the signed batch descriptor', async () => {
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const recipient = Keypair.generate();
+        const outsider = Keypair.generate();
+        for (const wallet of [recipient, outsider]) {
+          await program.methods
+            .initRateLimit()
+            .accounts({
+              rateLimit: getRateLimitPda(wallet.publicKey),
+              wallet: wallet.publicKey,
+              payer: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+        }
+
+        const nullifier = randomBytes(32);
+        const nullifierPda = getNullifierPda(nullifier, 1);
+        await program.methods
+          .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+          .accounts({
+            nullifierAccount: nullifierPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        // Single-entry batch: the root is the leaf itself and proofs are empty
+        const entriesRoot = createHash('sha256')
+          .update(Buffer.from('vouch_batch_leaf'))
+          .update(Buffer.from([1]))
+          .update(Buffer.from(nullifier))
+          .update(recipient.publicKey.toBuffer())
+          .digest();
+        const batchId = randomBytes(32);
+        const expiresAt = BigInt(Math.floor(Date.now() / 1000) + 600);
+        const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: verifierKeypair.secretKey,
+          message: buildBatchMessage(verifierKeypair.publicKey, batchId, entriesRoot, 1, expiresAt),
+        });
+        const signature = Array.from(ed25519Ix.data.subarray(48, 112)) as number[] & { length: 64 };
+
+        const [batchPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from('attestation_batch'), verifierKeypair.publicKey.toBuffer(), Buffer.from(batchId)],
+          program.programId
+        );
+        const beginIx = await program.methods
+          .beginAttestationBatch(
+            Array.from(batchId) as number[] & { length: 32 },
+            Array.from(entriesRoot) as number[] & { length: 32 },
+            1,
+            new anchor.BN(expiresAt.toString()),
+            signature
+          )
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            batch: batchPda,
+            usedSignature: null,
+            payer: admin.publicKey,
+            instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+          })
+          .instruction();
+        await provider.sendAndConfirm(new anchor.web3.Transaction().add(ed25519Ix, beginIx));
+
+        const applyTo = (wallet: Keypair) =>
+          program.methods
+            .applyBatchedAttestation(1, Array.from(nullifier) as number[] & { length: 32 }, [])
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              batch: batchPda,
+              nullifierAccount: nullifierPda,
+              rateLimit: getRateLimitPda(wallet.publicKey),
+              recipient: wallet.publicKey,
+              commitmentAccount: null,
+              blockedRecipient: null,
+              payer: admin.publicKey,
+            })
+            .rpc();
+
+        try {
+          await applyTo(outsider);
+          expect.fail('Should have thrown InvalidMerkleProof error');
+        } catch (error) {
+          expect(error.toString()).to.include('InvalidMerkleProof');
+        }
+
+        await applyTo(recipient);
+
+        const nullifierAccount = await program.account.nullifierAccount.fetch(nullifierPda);
+        expect(nullifierAccount.isUsed).to.be.true;
+        const batch = await program.account.attestationBatch.fetch(batchPda);
+        expect(batch.appliedCount).to.equal(1);
+      });
+    });
+
+    describe('record_attestation_merkle', () => {
+      // Mirrors build_merkle_attestation_leaf for a developer credential
+      function buildMerkleAttestationLeaf(
+        nullifier: Uint8Array,
+        recipient: PublicKey,
+        expiresAt: number
+      ): Buffer {
+        const expiresAtBytes = Buffer.alloc(8);
+        expiresAtBytes.writeBigInt64BE(BigInt(expiresAt));
+        return createHash('sha256')
+          .update(
+            Buffer.concat([
+              Buffer.from('vouch_merkle_leaf'),
+              Buffer.from([1]),
+              Buffer.from(nullifier),
+              recipient.toBuffer(),
+              expiresAtBytes,
+            ])
+          )
+          .digest();
+      }
+
+      // Publish a fresh verifier's root over one recipient-bound entry and a sibling
+      async function publishMerkleEntry(recipient: PublicKey, expiresAt: number) {
+        const verifier = Keypair.generate();
+        const verifierPda = getVerifierPda(verifier.publicKey);
+        await program.methods
+          .addVerifier(verifier.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const nullifier = randomBytes(32);
+        const nullifierPda = getNullifierPda(nullifier, 1);
+        await program.methods
+          .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+          .accounts({
+            nullifierAccount: nullifierPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const sibling = Buffer.from(randomBytes(32));
+        const root = hashPair(buildMerkleAttestationLeaf(nullifier, recipient, expiresAt), sibling);
+        const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: verifier.secretKey,
+          message: Buffer.concat([
+            Buffer.from('vouch_verifier_root'),
+            program.programId.toBuffer(),
+            verifier.publicKey.toBuffer(),
+            root,
+          ]),
+        });
+        const publishIx = await program.methods
+          .publishVerifierRoot(
+            Array.from(root) as number[] & { length: 32 },
+            Array.from(ed25519Ix.data.subarray(48, 112)) as number[] & { length: 64 }
+          )
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            usedSignature: null,
+            payer: admin.publicKey,
+            instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+          })
+          .instruction();
+        await provider.sendAndConfirm(new anchor.web3.Transaction().add(ed25519Ix, publishIx));
+
+        const record = async (wallet: PublicKey, blockedRecipient: PublicKey | null = null) =>
+          program.methods
+            .recordAttestationMerkle(
+              1,
+              Array.from(nullifier) as number[] & { length: 32 },
+              new anchor.BN(expiresAt),
+              [Array.from(sibling) as number[] & { length: 32 }]
+            )
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              nullifierAccount: nullifierPda,
+              rateLimit: await ensureRateLimit(wallet),
+              recipient: wallet,
+              commitmentAccount: null,
+              blockedRecipient,
+              payer: admin.publicKey,
+            })
+            .rpc();
+
+        return { nullifierPda, record };
+      }
+
+      it('should record an entry for the recipient it names', async () => {
+        const recipient = Keypair.generate().publicKey;
+        const { nullifierPda, record } = await publishMerkleEntry(
+          recipient,
+          Math.floor(Date.now() / 1000) + 600
+        );
+
+        await record(recipient);
+
+        const nullifierAccount = await program.account.nullifierAccount.fetch(nullifierPda);
+        expect(nullifierAccount.isUsed).to.be.true;
+        expect(nullifierAccount.recipient.toBase58()).to.equal(recipient.toBase58());
+      });
+
+      it('should reject an entry submitted for another recipient', async () => {
+        const { record } = await publishMerkleEntry(
+          Keypair.generate().publicKey,
+          Math.floor(Date.now() / 1000) + 600
+        );
+
+        try {
+          await record(Keypair.generate().publicKey);
+          expect.fail('Should have thrown InvalidMerkleProof error');
+        } catch (error: any) {
+          expect(error.toString()).to.include('InvalidMerkleProof');
+        }
+      });
+
+      it('should reject an expired entry', async () => {
+        const recipient = Keypair.generate().publicKey;
+        const { record } = await publishMerkleEntry(recipient, Math.floor(Date.now() / 1000) - 600);
+
+        try {
+          await record(recipient);
+          expect.fail('Should have thrown AttestationExpired error');
+        } catch (error: any) {
+          expect(error.toString()).to.include('AttestationExpired');
+        }
+      });
+
+      it('should reject a blocked recipient while enforced', async () => {
+        const recipient = Keypair.generate().publicKey;
+        const { record } = await publishMerkleEntry(recipient, Math.floor(Date.now() / 1000) + 600);
+        const blockedPda = getBlockedRecipientPda(recipient);
+        await program.methods
+          .blockRecipient(recipient)
+          .accounts({
+            config: configPda,
+            blockedRecipient: blockedPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        await program.methods
+          .setEnforceRecipientBlocklist(true)
+          .accounts({ config: configPda, admin: admin.publicKey })
+          .rpc();
+
+        try {
+          await record(recipient, blockedPda);
+          expect.fail('Should have thrown RecipientBlocked error');
+        } catch (error: any) {
+          expect(error.toString()).to.include('RecipientBlocked');
+        } finally {
+          await program.methods
+            .setEnforceRecipientBlocklist(false)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .rpc();
+          await program.methods
+            .unblockRecipient()
+            .accounts({
+              config: configPda,
+              blockedRecipient: blockedPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+        }
+      });
+    });
+
+    describe('record_attestation signature reuse', () => {
+      it('should reject one Ed25519 instruction backing two attestations', async () => {
+        // Widen the scan so the second attestation can reach the shared Ed25519 instruction
+        await program.methods
+          .setEd25519ScanDistance(2)
+          .accounts({ config: configPda, admin: admin.publicKey })
+          .rpc();
+
+        try {
+          const verifierKeypair = Keypair.generate();
+          const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+          await program.methods
+            .addVerifier(verifierKeypair.publicKey)
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              admin: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+          const nullifier = randomBytes(32);
+          const nullifierPda = getNullifierPda(nullifier, 1);
+          await program.methods
+            .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+            .accounts({
+              nullifierAccount: nullifierPda,
+              payer: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+          // Distinct recipients so the second attestation isn't stopped by the cooldown
+          const recipients = [Keypair.generate(), Keypair.generate()];
+          for (const recipient of recipients) {
+            await program.methods
+              .initRateLimit()
+              .accounts({
+                rateLimit: getRateLimitPda(recipient.publicKey),
+                wallet: recipient.publicKey,
+                payer: admin.publicKey,
+                systemProgram: SystemProgram.programId,
+              })
+              .rpc();
+          }
+
+          const epoch = BigInt(Math.floor(Date.now() / 1000 / 86400));
+          const proofGeneratedAt = BigInt(Math.floor(Date.now() / 1000));
+          const expiresAt = proofGeneratedAt + BigInt(600);
+          const dataHash = randomBytes(32);
+          const attestationHash = randomBytes(32);
+          const message = buildDevAttestationMessage(
+            nullifier,
+            epoch,
+            dataHash,
+            attestationHash,
+            proofGeneratedAt,
+            expiresAt
+          );
+          const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: verifierKeypair.secretKey,
+            message,
+          });
+          // Signature sits after the 16-byte header and 32-byte public key
+          const signature = Array.from(ed25519Ix.data.subarray(48, 112)) as number[] & { length: 64 };
+
+          const recordIxs = await Promise.all(
+            recipients.map((recipient) =>
+              program.methods
+                .recordAttestation(
+                  Array.from(attestationHash) as number[] & { length: 32 },
+                  1,
+                  Array.from(nullifier) as number[] & { length: 32 },
+                  new anchor.BN(epoch.toString()),
+                  Array.from(dataHash) as number[] & { length: 32 },
+                  signature,
+                  null,
+                  new anchor.BN(proofGeneratedAt.toString()),
+                  new anchor.BN(expiresAt.toString())
+                )
+                .accounts({
+                  config: configPda,
+                  verifierAccount: verifierPda,
+                  nullifierAccount: nullifierPda,
+                  rateLimit: getRateLimitPda(recipient.publicKey),
+                  recipient: recipient.publicKey,
+                  commitmentAccount: null,
+                  blockedRecipient: null,
+                  usedSignature: null,
+                  payer: admin.publicKey,
+                  instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+                })
+                .instruction()
+            )
+          );
+
+          try {
+            await provider.sendAndConfirm(
+              new anchor.web3.Transaction().add(ed25519Ix, ...recordIxs)
+            );
+            expect.fail('Should have thrown Ed25519InstructionReused error');
+          } catch (error: any) {
+            const logs = (error.logs ?? []).join('\n');
+            expect(`${error.toString()}\n${logs}`).to.include('Ed25519InstructionReused');
+          }
+        } finally {
+          await program.methods
+            .setEd25519ScanDistance(1)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .rpc();
+        }
+      });
+
+      it('should record accepted signatures while signature tracking is enabled', async () => {
+        await program.methods
+          .setTrackUsedSignatures(true)
+          .accounts({ config: configPda, admin: admin.publicKey })
+          .rpc();
+
+        try {
+          const verifierKeypair = Keypair.generate();
+          const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+          await program.methods
+            .addVerifier(verifierKeypair.publicKey)
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              admin: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+          const nullifier = randomBytes(32);
+          const nullifierPda = getNullifierPda(nullifier, 1);
+          await program.methods
+            .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+            .accounts({
+              nullifierAccount: nullifierPda,
+              payer: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+          const recipient = Keypair.generate();
+          await program.methods
+            .initRateLimit()
+            .accounts({
+              rateLimit: getRateLimitPda(recipient.publicKey),
+              wallet: recipient.publicKey,
+              payer: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+          const epoch = BigInt(Math.floor(Date.now() / 1000 / 86400));
+          const proofGeneratedAt = BigInt(Math.floor(Date.now() / 1000));
+          const expiresAt = proofGeneratedAt + BigInt(600);
+          const dataHash = randomBytes(32);
+          const attestationHash = randomBytes(32);
+          const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: verifierKeypair.secretKey,
+            message: buildDevAttestationMessage(
+              nullifier,
+              epoch,
+              dataHash,
+              attestationHash,
+              proofGeneratedAt,
+              expiresAt
+            ),
+          });
+          const signature = Array.from(ed25519Ix.data.subarray(48, 112)) as number[] & { length: 64 };
+          const usedSignaturePda = getUsedSignaturePda(signature);
+
+          const recordIx = await program.methods
+            .recordAttestation(
+              Array.from(attestationHash) as number[] & { length: 32 },
+              1,
+              Array.from(nullifier) as number[] & { length: 32 },
+              new anchor.BN(epoch.toString()),
+              Array.from(dataHash) as number[] & { length: 32 },
+              signature,
+              null,
+              new anchor.BN(proofGeneratedAt.toString()),
+              new anchor.BN(expiresAt.toString())
+            )
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              nullifierAccount: nullifierPda,
+              rateLimit: getRateLimitPda(recipient.publicKey),
+              recipient: recipient.publicKey,
+              commitmentAccount: null,
+              blockedRecipient: null,
+              usedSignature: usedSignaturePda,
+              payer: admin.publicKey,
+              instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            })
+            .instruction();
+          await provider.sendAndConfirm(new anchor.web3.Transaction().add(ed25519Ix, recordIx));
+
+          const record = await program.account.usedSignature.fetch(usedSignaturePda);
+          expect(record.verifier.toBase58()).to.equal(verifierKeypair.publicKey.toBase58());
+
+          const used = await program.methods
+            .isSignatureUsed(signature)
+            .accounts({ usedSignature: usedSignaturePda })
+            .view();
+          expect(used).to.be.true;
+
+          const unusedSignature = Array.from(randomBytes(64)) as number[] & { length: 64 };
+          const unused = await program.methods
+            .isSignatureUsed(unusedSignature)
+            .accounts({ usedSignature: getUsedSignaturePda(unusedSignature) })
+            .view();
+          expect(unused).to.be.false;
+        } finally {
+          await program.methods
+            .setTrackUsedSignatures(false)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .rpc();
+        }
+      });
+
+      it('should reject a replayed verifier root signature while tracking is enabled', async () => {
+        await program.methods
+          .setTrackUsedSignatures(true)
+          .accounts({ config: configPda, admin: admin.publicKey })
+          .rpc();
+
+        try {
+          const verifierKeypair = Keypair.generate();
+          const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+          await program.methods
+            .addVerifier(verifierKeypair.publicKey)
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              admin: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+          const merkleRoot = Buffer.from(randomBytes(32));
+          const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: verifierKeypair.secretKey,
+            message: Buffer.concat([
+              Buffer.from('vouch_verifier_root'),
+              program.programId.toBuffer(),
+              verifierKeypair.publicKey.toBuffer(),
+              merkleRoot,
+            ]),
+          });
+          const signature = Array.from(ed25519Ix.data.subarray(48, 112)) as number[] & { length: 64 };
+          const usedSignaturePda = getUsedSignaturePda(signature);
+          const publishIx = await program.methods
+            .publishVerifierRoot(Array.from(merkleRoot) as number[] & { length: 32 }, signature)
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              usedSignature: usedSignaturePda,
+              payer: admin.publicKey,
+              instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+              systemProgram: SystemProgram.programId,
+            })
+            .instruction();
+          await provider.sendAndConfirm(new anchor.web3.Transaction().add(ed25519Ix, publishIx));
+
+          const record = await program.account.usedSignature.fetch(usedSignaturePda);
+          expect(Buffer.from(record.nullifier)).to.deep.equal(merkleRoot);
+
+          // A distinct transaction carrying the same signed root
+          const computeIx = anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+            units: 400_000,
+          });
+          try {
+            await provider.sendAndConfirm(
+              new anchor.web3.Transaction().add(computeIx, ed25519Ix, publishIx)
+            );
+            expect.fail('Should have thrown SignatureAlreadyUsed error');
+          } catch (error) {
+            expect(error.toString()).to.include('SignatureAlreadyUsed');
+          }
+        } finally {
+          await program.methods
+            .setTrackUsedSignatures(false)
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .rpc();
+        }
+      });
+    });
+  });
+
+  // ==========================================
+  // Verifier Management Tests
+  // ==========================================
+
+  describe('verifier_management', () => {
+    describe('add_verifier', () => {
+      it('should add a verifier', async () => {
+        // Ensure protocol is not paused
+        const config = await program.account.configAccount.fetch(configPda);
+        if (config.isPaused) {
+          await program.methods
+            .unpauseProtocol()
+            .accounts({
+              config: configPda,
+              pauseAuthority: admin.publicKey,
+            })
+            .rpc();
+        }
+
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const verifierAccount = await program.account.verifierAccount.fetch(verifierPda);
+        expect(verifierAccount.verifier.toBase58()).to.equal(verifierKeypair.publicKey.toBase58());
+        expect(verifierAccount.isActive).to.be.true;
+        expect(verifierAccount.attestationCount.toNumber()).to.equal(0);
+
+        const updatedConfig = await program.account.configAccount.fetch(configPda);
+        expect(updatedConfig.verifierCount).to.be.greaterThan(0);
+      });
+
+      it('should fail to add verifier when paused', async () => {
+        // Pause the protocol
+        let config = await program.account.configAccount.fetch(configPda);
+        if (!config.isPaused) {
+          await program.methods
+            .pauseProtocol()
+            .accounts({
+              config: configPda,
+              pauseAuthority: admin.publicKey,
+            })
+            .rpc();
+        }
+
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+
+        try {
+          await program.methods
+            .addVerifier(verifierKeypair.publicKey)
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              admin: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+          expect.fail('Should have thrown ProtocolPaused error');
+        } catch (error) {
+          expect(error.toString()).to.include('ProtocolPaused');
+        }
+
+        // Unpause for other tests
+        await program.methods
+          .unpauseProtocol()
+          .accounts({
+            config: configPda,
+            pauseAuthority: admin.publicKey,
+          })
+          .rpc();
+      });
+    });
+
+    describe('remove_verifier', () => {
+      it('should remove a verifier', async () => {
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+
+        // First add the verifier
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        // Then remove it
+        await program.methods
+          .removeVerifier(false)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+          })
+          .rpc();
+
+        const verifierAccount = await program.account.verifierAccount.fetch(verifierPda);
+        expect(verifierAccount.isActive).to.be.false;
+      });
+
+      it('should remove a verifier while paused', async () => {
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        await program.methods
+          .pauseProtocol()
+          .accounts({
+            config: configPda,
+            pauseAuthority: admin.publicKey,
+          })
+          .rpc();
+
+        try {
+          await program.methods
+            .removeVerifier(true)
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+
+          const verifierAccount = await program.account.verifierAccount.fetch(verifierPda);
+          expect(verifierAccount.isActive).to.be.false;
+        } finally {
+          // Unpause for other tests
+          await program.methods
+            .unpauseProtocol()
+            .accounts({
+              config: configPda,
+              pauseAuthority: admin.publicKey,
+            })
+            .rpc();
+        }
+      });
+    });
+
+    describe('verifier_count accounting', () => {
+      it('should track add, remove and re-add sequences exactly', async () => {
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+        const countOf = async () =>
+          (await program.account.configAccount.fetch(configPda)).verifierCount;
+        const add = () =>
+          program.methods
+            .addVerifier(verifierKeypair.publicKey)
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              admin: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+        const remove = () =>
+          program.methods
+            .removeVerifier(true)
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+
+        const initial = await countOf();
+
+        await add();
+        expect(await countOf()).to.equal(initial + 1);
+
+        try {
+          await add();
+          expect.fail('Should have thrown VerifierAlreadyActive error');
+        } catch (error) {
+          expect(error.toString()).to.include('VerifierAlreadyActive');
+        }
+
+        await remove();
+        expect(await countOf()).to.equal(initial);
+
+        try {
+          await remove();
+          expect.fail('Should have thrown VerifierNotAuthorized error');
+        } catch (error) {
+          expect(error.toString()).to.include('VerifierNotAuthorized');
+        }
+        expect(await countOf()).to.equal(initial);
+
+        await add();
+        expect(await countOf()).to.equal(initial + 1);
+        const verifierAccount = await program.account.verifierAccount.fetch(verifierPda);
+        expect(verifierAccount.isActive).to.be.true;
+
+        await remove();
+        expect(await countOf()).to.equal(initial);
+      });
+    });
+
+    describe('reactivate_verifier', () => {
+      it('should restore a removed verifier and count it again', async () => {
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+        const countOf = async () =>
+          (await program.account.configAccount.fetch(configPda)).verifierCount;
+        const reactivate = () =>
+          program.methods
+            .reactivateVerifier()
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        const initial = await countOf();
+
+        try {
+          await reactivate();
+          expect.fail('Should have thrown VerifierAlreadyActive error');
+        } catch (error) {
+          expect(error.toString()).to.include('VerifierAlreadyActive');
+        }
+
+        await program.methods
+          .removeVerifier(true)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+          })
+          .rpc();
+        expect(await countOf()).to.equal(initial - 1);
+
+        await reactivate();
+        expect(await countOf()).to.equal(initial);
+        const verifierAccount = await program.account.verifierAccount.fetch(verifierPda);
+        expect(verifierAccount.isActive).to.be.true;
+      });
+    });
+
+    describe('rotate_verifier', () => {
+      it('should move attestation history to the new key and deactivate the old one', async () => {
+        const oldKeypair = Keypair.generate();
+        const newKeypair = Keypair.generate();
+        const oldPda = getVerifierPda(oldKeypair.publicKey);
+        const newPda = getVerifierPda(newKeypair.publicKey);
+
+        await program.methods
+          .addVerifier(oldKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: oldPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        const before = await program.account.verifierAccount.fetch(oldPda);
+        const countBefore = (await program.account.configAccount.fetch(configPda)).verifierCount;
+
+        await program.methods
+          .rotateVerifier(oldKeypair.publicKey, newKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            oldVerifierAccount: oldPda,
+            newVerifierAccount: newPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const oldAccount = await program.account.verifierAccount.fetch(oldPda);
+        const newAccount = await program.account.verifierAccount.fetch(newPda);
+        expect(oldAccount.isActive).to.be.false;
+        expect(newAccount.isActive).to.be.true;
+        expect(newAccount.verifier.toBase58()).to.equal(newKeypair.publicKey.toBase58());
+        expect(newAccount.attestationCount.toString()).to.equal(
+          before.attestationCount.toString()
+        );
+        expect(newAccount.addedAt.toString()).to.equal(before.addedAt.toString());
+
+        const countAfter = (await program.account.configAccount.fetch(configPda)).verifierCount;
+        expect(countAfter).to.equal(countBefore);
+      });
+    });
+
+    describe('close_verifier', () => {
+      it('should close only removed verifiers', async () => {
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        try {
+          await program.methods
+            .closeVerifier()
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+          expect.fail('Should have thrown VerifierStillActive error');
+        } catch (error) {
+          expect(error.toString()).to.include('VerifierStillActive');
+        }
+
+        await program.methods
+          .removeVerifier(true)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+          })
+          .rpc();
+
+        await program.methods
+          .closeVerifier()
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+          })
+          .rpc();
+
+        const closed = await provider.connection.getAccountInfo(verifierPda);
+        expect(closed).to.be.null;
+      });
+    });
+
+    describe('import_signed_verifier_set', () => {
+      const governance = Keypair.generate();
+
+      async function setGovernanceKey(governanceKey: PublicKey) {
+        await program.methods
+          .setGovernanceKey(governanceKey)
+          .accounts({ config: configPda, admin: admin.publicKey })
+          .rpc();
+      }
+
+      // "vouch_verifier_set" | nonce (u64 BE) | sha256(verifier pubkeys)
+      function buildVerifierSetMessage(nonce: bigint, verifiers: PublicKey[]): Buffer {
+        const nonceBytes = Buffer.alloc(8);
+        nonceBytes.writeBigUInt64BE(nonce);
+        const listHash = createHash('sha256')
+          .update(Buffer.concat(verifiers.map((verifier) => verifier.toBuffer())))
+          .digest();
+        return Buffer.concat([Buffer.from('vouch_verifier_set'), nonceBytes, listHash]);
+      }
+
+      async function importVerifierSet(
+        verifiers: PublicKey[],
+        message: Buffer,
+        verifierAccounts: PublicKey[] = verifiers.map(getVerifierPda),
+        computeUnits = 200_000
+      ) {
+        const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: governance.secretKey,
+          message,
+        });
+        const signature = Array.from(ed25519Ix.data.subarray(48, 112)) as number[] & { length: 64 };
+        const importIx = await program.methods
+          .importSignedVerifierSet(verifiers, signature)
+          .accounts({
+            config: configPda,
+            payer: admin.publicKey,
+            instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(
+            verifierAccounts.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+          )
+          .instruction();
+        // The compute limit instruction keeps otherwise identical retries distinct
+        const computeIx = anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+          units: computeUnits,
+        });
+        await provider.sendAndConfirm(
+          new anchor.web3.Transaction().add(computeIx, ed25519Ix, importIx)
+        );
+      }
+
+      it('should create governance-approved verifiers and advance the nonce', async () => {
+        await setGovernanceKey(governance.publicKey);
+        try {
+          const before = await program.account.configAccount.fetch(configPda);
+          const verifiers = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+          const message = buildVerifierSetMessage(
+            BigInt(before.governanceNonce.toString()),
+            verifiers
+          );
+          await importVerifierSet(verifiers, message);
+
+          for (const verifier of verifiers) {
+            const account = await program.account.verifierAccount.fetch(getVerifierPda(verifier));
+            expect(account.isActive).to.be.true;
+          }
+          const after = await program.account.configAccount.fetch(configPda);
+          expect(after.governanceNonce.toNumber()).to.equal(before.governanceNonce.toNumber() + 1);
+          expect(after.verifierCount).to.equal(before.verifierCount + 2);
+
+          // The nonce moved on, so the same signed set can't be replayed
+          try {
+            await importVerifierSet(verifiers, message, undefined, 210_000);
+            expect.fail('Should have thrown Ed25519InstructionNotFound error');
+          } catch (error) {
+            expect(error.toString()).to.include('Ed25519InstructionNotFound');
+          }
+        } finally {
+          await setGovernanceKey(PublicKey.default);
+        }
+      });
+
+      it('should reject verifier accounts that do not match the set', async () => {
+        await setGovernanceKey(governance.publicKey);
+        try {
+          const config = await program.account.configAccount.fetch(configPda);
+          const verifiers = [Keypair.generate().publicKey];
+          const message = buildVerifierSetMessage(
+            BigInt(config.governanceNonce.toString()),
+            verifiers
+          );
+          const strangerPda = getVerifierPda(Keypair.generate().publicKey);
+          try {
+            await importVerifierSet(verifiers, message, [strangerPda]);
+            expect.fail('Should have thrown InvalidVerifierSet error');
+          } catch (error) {
+            expect(error.toString()).to.include('InvalidVerifierSet');
+          }
+        } finally {
+          await setGovernanceKey(PublicKey.default);
+        }
+      });
+
+      it('should reject imports while no governance key is set', async () => {
+        const verifiers = [Keypair.generate().publicKey];
+        try {
+          await importVerifierSet(verifiers, buildVerifierSetMessage(BigInt(0), verifiers));
+          expect.fail('Should have thrown GovernanceKeyNotSet error');
+        } catch (error) {
+          expect(error.toString()).to.include('GovernanceKeyNotSet');
+        }
+      });
+    });
+
+    describe('scheduled verifier removal', () => {
+      it('should keep a verifier active during the removal grace period', async () => {
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        await program.methods
+          .setVerifierRemovalDelay(new anchor.BN(3600))
+          .accounts({ config: configPda, admin: admin.publicKey })
+          .rpc();
+
+        try {
+          await program.methods
+            .removeVerifier(false)
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+
+          const verifierAccount = await program.account.verifierAccount.fetch(verifierPda);
+          expect(verifierAccount.isActive).to.be.true;
+          expect(verifierAccount.removalScheduledAt.toNumber()).to.be.greaterThan(0);
+
+          try {
+            await program.methods
+              .finalizeVerifierRemoval()
+              .accounts({ config: configPda, verifierAccount: verifierPda })
+              .rpc();
+            expect.fail('Should have thrown VerifierRemovalNotDue error');
+          } catch (error) {
+            expect(error.toString()).to.include('VerifierRemovalNotDue');
+          }
+
+          // Emergency removal bypasses the grace period
+          await program.methods
+            .removeVerifier(true)
+            .accounts({
+              config: configPda,
+              verifierAccount: verifierPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+
+          const removed = await program.account.verifierAccount.fetch(verifierPda);
+          expect(removed.isActive).to.be.false;
+          expect(removed.removalScheduledAt.toNumber()).to.equal(0);
+        } finally {
+          await program.methods
+            .setVerifierRemovalDelay(new anchor.BN(0))
+            .accounts({ config: configPda, admin: admin.publicKey })
+            .rpc();
+        }
+      });
+    });
+
+    describe('get_verifier_profile', () => {
+      it('should return the profile of an active verifier', async () => {
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        const profile = await program.methods
+          .getVerifierProfile()
+          .accounts({ verifierAccount: verifierPda })
+          .view();
+
+        const verifierAccount = await program.account.verifierAccount.fetch(verifierPda);
+        expect(profile.version).to.equal(1);
+        expect(profile.verifier.toBase58()).to.equal(verifierKeypair.publicKey.toBase58());
+        expect(profile.isActive).to.be.true;
+        expect(profile.addedAt.toNumber()).to.equal(verifierAccount.addedAt.toNumber());
+        expect(profile.attestationCount.toNumber()).to.equal(0);
+        // Not tracked yet, so reported as unsupported rather than a made-up value
+        expect(profile.allowedProofTypes).to.be.null;
+        expect(profile.stakedAmount).to.be.null;
+      });
+
+      it('should return the profile of a removed verifier', async () => {
+        const verifierKeypair = Keypair.generate();
+        const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+
+        await program.methods
+          .addVerifier(verifierKeypair.publicKey)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        await program.methods
+          .removeVerifier(false)
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            admin: admin.publicKey,
+          })
+          .rpc();
+
+        const profile = await program.methods
+          .getVerifierProfile()
+          .accounts({ verifierAccount: verifierPda })
+          .view();
+
+        expect(profile.verifier.toBase58()).to.equal(verifierKeypair.publicKey.toBase58());
+        expect(profile.isActive).to.be.false;
+      });
+    });
+  });
+
+  // ==========================================
+  // Commitment Tests
+  // ==========================================
+
+  describe('create_commitment', () => {
+    it('should create a new commitment', async () => {
+      const uniqueCommitment = randomBytes(32);
+      const commitmentPda = getCommitmentPda(uniqueCommitment);
+
+      await program.methods
+        .createCommitment(Array.from(uniqueCommitment) as number[] & { length: 32 })
+        .accounts({
+          commitmentAccount: commitmentPda,
+          owner: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const account = await program.account.commitmentAccount.fetch(commitmentPda);
+      expect(account.owner.toBase58()).to.equal(admin.publicKey.toBase58());
+      expect(Buffer.from(account.commitment).equals(Buffer.from(uniqueCommitment))).to.be.true;
+      expect(account.createdAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it('should fail to create duplicate commitment', async () => {
+      const [commitmentPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('commitment'), Buffer.from(testCommitment)],
+        program.programId
+      );
+
+      // Create commitment if it doesn't exist
+      try {
+        await program.methods
+          .createCommitment(Array.from(testCommitment) as number[] & { length: 32 })
+          .accounts({
+            commitmentAccount: commitmentPda,
+            owner: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      } catch {
+        // Commitment already exists, which is fine
+      }
+
+      // Try to create duplicate
+      try {
+        await program.methods
+          .createCommitment(Array.from(testCommitment) as number[] & { length: 32 })
+          .accounts({
+            commitmentAccount: commitmentPda,
+            owner: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error).to.exist;
+      }
+    });
+  });
+
+  // ==========================================
+  // Nullifier Tests
+  // ==========================================
+
+  describe('init_nullifier', () => {
+    it('should initialize a new nullifier account', async () => {
+      const uniqueNullifier = randomBytes(32);
+      const nullifierPda = getNullifierPda(uniqueNullifier, 1);
+
+      await program.methods
+        .initNullifier(Array.from(uniqueNullifier) as number[] & { length: 32 }, 1)
+        .accounts({
+          nullifierAccount: nullifierPda,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const account = await program.account.nullifierAccount.fetch(nullifierPda);
+      expect(account.isUsed).to.be.false;
+      expect(account.proofType).to.deep.equal({ unset: {} });
+      expect(account.recipient.toBase58()).to.equal(PublicKey.default.toBase58());
+      expect(Buffer.from(account.nullifier).equals(Buffer.from(uniqueNullifier))).to.be.true;
+    });
+
+    it('should fail to initialize duplicate nullifier', async () => {
+      const uniqueNullifier = randomBytes(32);
+      const nullifierPda = getNullifierPda(uniqueNullifier, 1);
+
+      await program.methods
+        .initNullifier(Array.from(uniqueNullifier) as number[] & { length: 32 }, 1)
+        .accounts({
+          nullifierAccount: nullifierPda,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .initNullifier(Array.from(uniqueNullifier) as number[] & { length: 32 }, 1)
+          .accounts({
+            nullifierAccount: nullifierPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error).to.exist;
+      }
+    });
+
+    it('should keep the same nullifier separate across proof type namespaces', async () => {
+      const uniqueNullifier = randomBytes(32);
+      for (const namespace of [1, 2]) {
+        await program.methods
+          .initNullifier(Array.from(uniqueNullifier) as number[] & { length: 32 }, namespace)
+          .accounts({
+            nullifierAccount: getNullifierPda(uniqueNullifier, namespace),
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+
+      const developer = await program.account.nullifierAccount.fetch(
+        getNullifierPda(uniqueNullifier, 1)
+      );
+      const whale = await program.account.nullifierAccount.fetch(
+        getNullifierPda(uniqueNullifier, 2)
+      );
+      expect(developer.namespace).to.equal(1);
+      expect(whale.namespace).to.equal(2);
+    });
+
+    it('should reject a namespace that is not a built-in proof type', async () => {
+      const uniqueNullifier = randomBytes(32);
+      try {
+        await program.methods
+          .initNullifier(Array.from(uniqueNullifier) as number[] & { length: 32 }, 3)
+          .accounts({
+            nullifierAccount: getNullifierPda(uniqueNullifier, 3),
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail('Should have thrown InvalidProofType error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidProofType');
+      }
+    });
+  });
+
+  describe('upgrade_credential', () => {
+    async function setAllowCredentialUpgrades(allow: boolean) {
+      await program.methods
+        .setAllowCredentialUpgrades(allow)
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+    }
+
+    // "vouch_credential_upgrade" | nullifier | old type | new type | epoch (u64 BE)
+    async function upgradeCredential(
+      nullifier: Uint8Array,
+      nullifierPda: PublicKey,
+      verifier: Keypair,
+      oldProofType: number,
+      newProofType: number,
+      signer: Keypair = verifier
+    ) {
+      const epoch = BigInt(Math.floor(Date.now() / 1000 / 86400));
+      const epochBytes = Buffer.alloc(8);
+      epochBytes.writeBigUInt64BE(epoch);
+      const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: signer.secretKey,
+        message: Buffer.concat([
+          Buffer.from('vouch_credential_upgrade'),
+          Buffer.from(nullifier),
+          Buffer.from([oldProofType, newProofType]),
+          epochBytes,
+        ]),
+      });
+      const upgradeIx = await program.methods
+        .upgradeCredential(
+          Array.from(nullifier) as number[] & { length: 32 },
+          newProofType,
+          new anchor.BN(epoch.toString()),
+          Array.from(ed25519Ix.data.subarray(48, 112)) as number[] & { length: 64 }
+        )
+        .accounts({
+          config: configPda,
+          verifierAccount: getVerifierPda(verifier.publicKey),
+          nullifierAccount: nullifierPda,
+          usedSignature: null,
+          payer: admin.publicKey,
+          instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+      await provider.sendAndConfirm(new anchor.web3.Transaction().add(ed25519Ix, upgradeIx));
+    }
+
+    it('should refuse upgrades while they are disabled', async () => {
+      const { nullifier, nullifierPda, verifier } = await recordDevCredential(
+        Keypair.generate().publicKey
+      );
+      try {
+        await upgradeCredential(nullifier, nullifierPda, verifier, 1, 2);
+        expect.fail('Should have thrown CredentialUpgradesDisabled error');
+      } catch (error) {
+        expect(error.toString()).to.include('CredentialUpgradesDisabled');
+      }
+    });
+
+    it('should upgrade a credential in place and keep its type history', async () => {
+      await setAllowCredentialUpgrades(true);
+      try {
+        const { nullifier, nullifierPda, verifier } = await recordDevCredential(
+          Keypair.generate().publicKey
+        );
+        await upgradeCredential(nullifier, nullifierPda, verifier, 1, 2);
+
+        const nullifierAccount = await program.account.nullifierAccount.fetch(nullifierPda);
+        expect(nullifierAccount.proofType).to.deep.equal({ whaleTrading: {} });
+        // proof_type_bit: DeveloperReputation = 1 << 1, WhaleTrading = 1 << 2
+        expect(nullifierAccount.credentialTypes).to.equal((1 << 1) | (1 << 2));
+
+        try {
+          await upgradeCredential(nullifier, nullifierPda, verifier, 2, 2);
+          expect.fail('Should have thrown InvalidCredentialUpgrade error');
+        } catch (error) {
+          expect(error.toString()).to.include('InvalidCredentialUpgrade');
+        }
+      } finally {
+        await setAllowCredentialUpgrades(false);
+      }
+    });
+
+    it('should reject an upgrade not signed by the verifier', async () => {
+      await setAllowCredentialUpgrades(true);
+      try {
+        const { nullifier, nullifierPda, verifier } = await recordDevCredential(
+          Keypair.generate().publicKey
+        );
+        try {
+          await upgradeCredential(nullifier, nullifierPda, verifier, 1, 2, Keypair.generate());
+          expect.fail('Should have thrown Ed25519InstructionNotFound error');
+        } catch (error) {
+          expect(error.toString()).to.include('Ed25519InstructionNotFound');
+        }
+      } finally {
+        await setAllowCredentialUpgrades(false);
+      }
+    });
+  });
+
+  describe('update_credential_score', () => {
+    it('should refuse to score an unused nullifier', async () => {
+      const verifierKeypair = Keypair.generate();
+      const verifierPda = getVerifierPda(verifierKeypair.publicKey);
+      await program.methods
+        .addVerifier(verifierKeypair.publicKey)
+        .accounts({
+          config: configPda,
+          verifierAccount: verifierPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const nullifier = randomBytes(32);
+      const nullifierPda = getNullifierPda(nullifier, 1);
+      await program.methods
+        .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+        .accounts({
+          nullifierAccount: nullifierPda,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const nullifierAccount = await program.account.nullifierAccount.fetch(nullifierPda);
+      expect(nullifierAccount.score.toNumber()).to.equal(0);
+
+      try {
+        await program.methods
+          .updateCredentialScore(
+            Array.from(nullifier) as number[] & { length: 32 },
+            new anchor.BN(42),
+            new anchor.BN(Math.floor(Date.now() / 1000)),
+            Array.from(new Uint8Array(64)) as number[] & { length: 64 }
+          )
+          .accounts({
+            config: configPda,
+            verifierAccount: verifierPda,
+            nullifierAccount: nullifierPda,
+            usedSignature: null,
+            payer: admin.publicKey,
+            instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail('Should have thrown NullifierNotVerified error');
+      } catch (error) {
+        expect(error.toString()).to.include('NullifierNotVerified');
+      }
+    });
+  });
+
+  describe('revoke_attestation', () => {
+    it('should refuse to revoke an unused nullifier', async () => {
+      const nullifier = randomBytes(32);
+      const nullifierPda = getNullifierPda(nullifier, 1);
+      await program.methods
+        .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+        .accounts({
+          nullifierAccount: nullifierPda,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const nullifierAccount = await program.account.nullifierAccount.fetch(nullifierPda);
+      expect(nullifierAccount.isRevoked).to.be.false;
+
+      try {
+        await program.methods
+          .revokeAttestation(1)
+          .accounts({
+            config: configPda,
+            nullifierAccount: nullifierPda,
+            verifierAccount: null,
+            authority: admin.publicKey,
+          })
+          .rpc();
+        expect.fail('Should have thrown NullifierNotVerified error');
+      } catch (error) {
+        expect(error.toString()).to.include('NullifierNotVerified');
+      }
+    });
+  });
+
+  describe('close_nullifier', () => {
+    it('should refuse to close an unused nullifier', async () => {
+      const nullifier = randomBytes(32);
+      const nullifierPda = getNullifierPda(nullifier, 1);
+      await program.methods
+        .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+        .accounts({
+          nullifierAccount: nullifierPda,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const nullifierAccount = await program.account.nullifierAccount.fetch(nullifierPda);
+      expect(nullifierAccount.payer.toBase58()).to.equal(admin.publicKey.toBase58());
+
+      try {
+        await program.methods
+          .closeNullifier()
+          .accounts({
+            config: configPda,
+            nullifierAccount: nullifierPda,
+            authority: admin.publicKey,
+            receiver: admin.publicKey,
+          })
+          .rpc();
+        expect.fail('Should have thrown NullifierNotVerified error');
+      } catch (error) {
+        expect(error.toString()).to.include('NullifierNotVerified');
+      }
+    });
+
+    it('should refuse a close from someone other than the payer or admin', async () => {
+      const nullifier = randomBytes(32);
+      const nullifierPda = getNullifierPda(nullifier, 1);
+      await program.methods
+        .initNullifier(Array.from(nullifier) as number[] & { length: 32 }, 1)
+        .accounts({
+          nullifierAccount: nullifierPda,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const stranger = Keypair.generate();
+      try {
+        await program.methods
+          .closeNullifier()
+          .accounts({
+            config: configPda,
+            nullifierAccount: nullifierPda,
+            authority: stranger.publicKey,
+            receiver: stranger.publicKey,
+          })
+          .signers([stranger])
+          .rpc();
+        expect.fail('Should have thrown Unauthorized error');
+      } catch (error) {
+        expect(error.toString()).to.include('Unauthorized');
+      }
+    });
+  });
+
+  describe('nullifier_account_info', () => {
+    it('should report an initialized, unused nullifier', async () => {
+      const uniqueNullifier = randomBytes(32);
+      const nullifierPda = getNullifierPda(uniqueNullifier, 1);
+
+      await program.methods
+        .initNullifier(Array.from(uniqueNullifier) as number[] & { length: 32 }, 1)
+        .accounts({
+          nullifierAccount: nullifierPda,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const status = await program.methods
+        .nullifierAccountInfo(Array.from(uniqueNullifier) as number[] & { length: 32 }, 1)
+        .accounts({ nullifierAccount: nullifierPda })
+        .view();
+
+      expect(status.pda.toBase58()).to.equal(nullifierPda.toBase58());
+      expect(status.isInitialized).to.be.true;
+      expect(status.isUsed).to.be.false;
+    });
+
+    it('should report a never-created nullifier as uninitialized', async () => {
+      const uniqueNullifier = randomBytes(32);
+      const nullifierPda = getNullifierPda(uniqueNullifier, 1);
+
+      const status = await program.methods
+        .nullifierAccountInfo(Array.from(uniqueNullifier) as number[] & { length: 32 }, 1)
+        .accounts({ nullifierAccount: nullifierPda })
+        .view();
+
+      expect(status.pda.toBase58()).to.equal(nullifierPda.toBase58());
+      expect(status.isInitialized).to.be.false;
+      expect(status.isUsed).to.be.false;
+    });
+  });
+
+  // ==========================================
+  // Wallet Profile Tests
+  // ==========================================
+
+  describe('set_default_shadow_wire', () => {
+    const [walletProfilePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('wallet_profile'), admin.publicKey.toBuffer()],
+      program.programId
+    );
+
+    it('should save a default ShadowWire address', async () => {
+      const shadowWireAddress = Keypair.generate().publicKey.toBase58();
+
+      await program.methods
+        .setDefaultShadowWire(shadowWireAddress)
+        .accounts({
+          walletProfile: walletProfilePda,
+          wallet: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const profile = await program.account.walletProfile.fetch(walletProfilePda);
+      expect(profile.wallet.toBase58()).to.equal(admin.publicKey.toBase58());
+      expect(profile.defaultShadowWireAddress).to.equal(shadowWireAddress);
+    });
+
+    it('should reject a non-base58 address', async () => {
+      try {
+        await program.methods
+          .setDefaultShadowWire('0OIl'.repeat(10))
+          .accounts({
+            walletProfile: walletProfilePda,
+            wallet: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail('Should have thrown InvalidShadowWireAddress error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidShadowWireAddress');
+      }
+    });
+
+    it('should reject base58 that does not decode to 32 bytes', async () => {
+      for (const malformed of ['2'.repeat(32), 'z'.repeat(44)]) {
+        try {
+          await program.methods
+            .setDefaultShadowWire(malformed)
+            .accounts({
+              walletProfile: walletProfilePda,
+              wallet: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+          expect.fail('Should have thrown InvalidShadowWireAddress error');
+        } catch (error) {
+          expect(error.toString()).to.include('InvalidShadowWireAddress');
+        }
+      }
+    });
+  });
+
+  describe('protocol_tvl', () => {
+    const [protocolStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('protocol_stats')],
+      program.programId
+    );
+
+    it('should start TVL tracking with no locked value', async () => {
+      await program.methods
+        .initProtocolStats()
+        .accounts({
+          config: configPda,
+          protocolStats: protocolStatsPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const config = await program.account.configAccount.fetch(configPda);
+      expect(config.protocolStatsEnabled).to.be.true;
+
+      const tvl = await program.methods
+        .protocolTvl()
+        .accounts({ protocolStats: protocolStatsPda })
+        .view();
+      expect(tvl.mints).to.have.length(0);
+      expect(tvl.untrackedFlows.toNumber()).to.equal(0);
+    });
+  });
+
+  describe('campaign templates', () => {
+    const templateId = randomBytes(32);
+    const [templatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('campaign_template'), admin.publicKey.toBuffer(), Buffer.from(templateId)],
+      program.programId
+    );
+    const params = {
+      tokenMint: Keypair.generate().publicKey,
+      baseAmount: new anchor.BN(1_000),
+      devBonus: new anchor.BN(500),
+      whaleBonus: new anchor.BN(500),
+      campaignClaimFeeBps: 0,
+      claimFeeAccount: PublicKey.default,
+    };
+
+    it('should create a campaign from a saved template', async () => {
+      await program.methods
+        .createCampaignTemplate(
+          Array.from(templateId),
+          params,
+          new anchor.BN(86_400),
+          0,
+          [{ proofTypeId: 3, bonusAmount: new anchor.BN(250) }],
+          new anchor.BN(7 * 86_400)
+        )
+        .accounts({
+          template: templatePda,
+          creator: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const campaignId = randomBytes(32);
+      const [campaignPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('airdrop_campaign'), Buffer.from(campaignId)],
+        program.programId
+      );
+
+      await program.methods
+        .createCampaignFromTemplate(Array.from(campaignId), 'Weekly drop')
+        .accounts({
+          config: configPda,
+          template: templatePda,
+          campaign: campaignPda,
+          creator: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.baseAmount.toNumber()).to.equal(1_000);
+      expect(campaign.tokenMint.toBase58()).to.equal(params.tokenMint.toBase58());
+      expect(campaign.registrationDeadline.sub(campaign.createdAt).toNumber()).to.equal(86_400);
+      expect(campaign.claimDeadline.sub(campaign.registrationDeadline).toNumber()).to.equal(
+        7 * 86_400
+      );
+      expect(campaign.proofTypeBonuses).to.have.length(1);
+      expect(campaign.proofTypeBonuses[0].proofTypeId).to.equal(3);
+      expect(campaign.proofTypeBonuses[0].bonusAmount.toNumber()).to.equal(250);
+
+      const template = await program.account.campaignTemplate.fetch(templatePda);
+      expect(template.campaignsCreated.toNumber()).to.equal(1);
+    });
+
+    it('should reject template updates from another wallet', async () => {
+      const other = Keypair.generate();
+      try {
+        await program.methods
+          .updateCampaignTemplate(params, new anchor.BN(3_600), 0, [], new anchor.BN(0))
+          .accounts({
+            template: templatePda,
+            creator: other.publicKey,
+          })
+          .signers([other])
+          .rpc();
+        expect.fail('Should have rejected a non-creator update');
+      } catch (error) {
+        expect(error.toString()).to.match(/Unauthorized|ConstraintSeeds/);
+      }
+    });
+  });
+
+  describe('proof type registry', () => {
+    const [registryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('proof_type_registry')],
+      program.programId
+    );
+
+    it('should register and update a custom proof type', async () => {
+      await program.methods
+        .initProofTypeRegistry()
+        .accounts({
+          config: configPda,
+          proofTypeRegistry: registryPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .registerProofType(3, 'DAO Governance', new anchor.BN(250))
+        .accounts({
+          config: configPda,
+          proofTypeRegistry: registryPda,
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+      let registry = await program.account.proofTypeRegistry.fetch(registryPda);
+      expect(registry.entries).to.have.length(1);
+      expect(registry.entries[0].id).to.equal(3);
+      expect(registry.entries[0].name).to.equal('DAO Governance');
+      expect(registry.entries[0].airdropBonus.toNumber()).to.equal(250);
+      expect(registry.entries[0].isActive).to.be.true;
+
+      await program.methods
+        .updateProofType(3, new anchor.BN(100), false)
+        .accounts({
+          config: configPda,
+          proofTypeRegistry: registryPda,
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+      registry = await program.account.proofTypeRegistry.fetch(registryPda);
+      expect(registry.entries[0].airdropBonus.toNumber()).to.equal(100);
+      expect(registry.entries[0].isActive).to.be.false;
+    });
+
+    it('should reject reserved and duplicate proof type IDs', async () => {
+      for (const [id, expected] of [
+        [2, 'InvalidProofType'],
+        [3, 'ProofTypeAlreadyRegistered'],
+      ] as const) {
+        try {
+          await program.methods
+            .registerProofType(id, 'Duplicate', new anchor.BN(0))
+            .accounts({
+              config: configPda,
+              proofTypeRegistry: registryPda,
+              admin: admin.publicKey,
+            })
+            .rpc();
+          expect.fail(`Should have thrown ${expected} error`);
+        } catch (error) {
+          expect(error.toString()).to.include(expected);
+        }
+      }
+    });
+  });
+
+  describe('campaign proof type bonuses', () => {
+    async function createCampaignWithBonuses(
+      proofTypeBonuses: { proofTypeId: number; bonusAmount: anchor.BN }[]
+    ): Promise<PublicKey> {
+      const campaignId = randomBytes(32);
+      const [campaignPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('airdrop_campaign'), Buffer.from(campaignId)],
+        program.programId
+      );
+      await program.methods
+        .createAirdropCampaign(
+          Array.from(campaignId),
+          'Tiered drop',
+          Keypair.generate().publicKey,
+          new anchor.BN(1_000),
+          new anchor.BN(0),
+          new anchor.BN(0),
+          new anchor.BN(Math.floor(Date.now() / 1000) + 86_400),
+          new anchor.BN(0),
+          0,
+          PublicKey.default,
+          proofTypeBonuses
+        )
+        .accounts({
+          config: configPda,
+          campaign: campaignPda,
+          creator: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      return campaignPda;
+    }
+
+    it('should store the bonus table on the campaign', async () => {
+      const campaignPda = await createCampaignWithBonuses([
+        { proofTypeId: 1, bonusAmount: new anchor.BN(500) },
+        { proofTypeId: 3, bonusAmount: new anchor.BN(250) },
+      ]);
+
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.proofTypeBonuses).to.have.length(2);
+      expect(campaign.proofTypeBonuses[1].proofTypeId).to.equal(3);
+      expect(campaign.proofTypeBonuses[1].bonusAmount.toNumber()).to.equal(250);
+    });
+
+    it('should reject duplicate proof type IDs', async () => {
+      try {
+        await createCampaignWithBonuses([
+          { proofTypeId: 2, bonusAmount: new anchor.BN(500) },
+          { proofTypeId: 2, bonusAmount: new anchor.BN(100) },
+        ]);
+        expect.fail('Should have thrown DuplicateProofTypeBonus error');
+      } catch (error) {
+        expect(error.toString()).to.include('DuplicateProofTypeBonus');
+      }
+    });
+  });
+
+  describe('campaign activity log', () => {
+    async function enableActivityLog(campaignPda: PublicKey, creator: Keypair = admin.payer) {
+      const [activityLogPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('campaign_activity'), campaignPda.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .enableCampaignActivityLog()
+        .accounts({
+          campaign: campaignPda,
+          activityLog: activityLogPda,
+          creator: creator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers(creator === admin.payer ? [] : [creator])
+        .rpc();
+      return activityLogPda;
+    }
+
+    it('should keep the latest 16 registrations in a ring buffer', async () => {
+      const campaignPda = await createTestCampaign('Activity feed');
+      const activityLogPda = await enableActivityLog(campaignPda);
+
+      const registrants: Keypair[] = [];
+      for (let i = 0; i < 17; i++) {
+        const registrant = Keypair.generate();
+        await fundWallet(registrant.publicKey);
+        await registerOpen(campaignPda, registrant, undefined, activityLogPda);
+        registrants.push(registrant);
+      }
+
+      const log = await program.account.campaignActivityLog.fetch(activityLogPda);
+      expect(log.len).to.equal(16);
+      expect(log.head).to.equal(1);
+      expect(log.totalEntries.toNumber()).to.equal(17);
+      // The 17th entry overwrote the oldest slot; slot 1 is now the oldest
+      expect(log.entries[0].kind).to.deep.equal({ registration: {} });
+      const subjectOf = (slot: number) => new PublicKey(log.entries[slot].subject).toBase58();
+      expect(subjectOf(0)).to.equal(registrants[16].publicKey.toBase58());
+      expect(subjectOf(1)).to.equal(registrants[1].publicKey.toBase58());
+    });
+
+    it('should require the feed account once it is enabled', async () => {
+      const campaignPda = await createTestCampaign('Activity feed required');
+      await enableActivityLog(campaignPda);
+
+      const registrant = Keypair.generate();
+      await fundWallet(registrant.publicKey);
+      try {
+        await registerOpen(campaignPda, registrant);
+        expect.fail('Should have thrown ActivityLogRequired error');
+      } catch (error) {
+        expect(error.toString()).to.include('ActivityLogRequired');
+      }
+    });
+
+    it('should only let the creator enable the feed', async () => {
+      const campaignPda = await createTestCampaign('Activity feed owner');
+      const stranger = Keypair.generate();
+      await fundWallet(stranger.publicKey);
+      try {
+        await enableActivityLog(campaignPda, stranger);
+        expect.fail('Should have thrown Unauthorized error');
+      } catch (error) {
+        expect(error.toString()).to.include('Unauthorized');
+      }
+    });
+  });
+
+  describe('migrate_nullifier', () => {
+    it('should leave a current nullifier unchanged', async () => {
+      const { nullifierPda } = await recordDevCredential(admin.publicKey);
+      const before = await provider.connection.getAccountInfo(nullifierPda);
+
+      for (let i = 0; i < 2; i++) {
+        await program.methods
+          .migrateNullifier()
+          .accounts({
+            nullifierAccount: nullifierPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+
+      const after = await provider.connection.getAccountInfo(nullifierPda);
+      expect(after.data.equals(before.data)).to.be.true;
+      const nullifierAccount = await program.account.nullifierAccount.fetch(nullifierPda);
+      expect(nullifierAccount.version).to.equal(1);
+    });
+
+    it('should reject an account that is not a nullifier', async () => {
+      const campaignPda = await createTestCampaign('Nullifier migration target');
+      try {
+        await program.methods
+          .migrateNullifier()
+          .accounts({
+            nullifierAccount: campaignPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail('Should have thrown InvalidNullifierAccount error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidNullifierAccount');
+      }
+    });
+  });
+
+  describe('migrate_campaign', () => {
+    it('should leave a current campaign unchanged', async () => {
+      const campaignPda = await createTestCampaign('Campaign migration no-op');
+      const before = await provider.connection.getAccountInfo(campaignPda);
+
+      for (let i = 0; i < 2; i++) {
+        await program.methods
+          .migrateCampaign()
+          .accounts({
+            campaign: campaignPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+
+      const after = await provider.connection.getAccountInfo(campaignPda);
+      expect(after.data.equals(before.data)).to.be.true;
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.version).to.equal(1);
+    });
+
+    it('should reject an account that is not a campaign', async () => {
+      const campaignPda = await createTestCampaign('Campaign migration target');
+      const registrant = Keypair.generate();
+      await fundWallet(registrant.publicKey);
+      const registrationPda = await registerOpen(campaignPda, registrant);
+      try {
+        await program.methods
+          .migrateCampaign()
+          .accounts({
+            campaign: registrationPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail('Should have thrown InvalidCampaign error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidCampaign');
+      }
+    });
+  });
+
+  describe('migrate_registration', () => {
+    it('should leave a current registration unchanged', async () => {
+      const campaignPda = await createTestCampaign('Migration no-op');
+      const registrant = Keypair.generate();
+      await fundWallet(registrant.publicKey);
+      const registrationPda = await registerOpen(campaignPda, registrant);
+      const before = await provider.connection.getAccountInfo(registrationPda);
+
+      // Migrating twice is a no-op both times
+      for (let i = 0; i < 2; i++) {
+        await program.methods
+          .migrateRegistration()
+          .accounts({
+            registration: registrationPda,
+            nullifierAccount: null,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+
+      const after = await provider.connection.getAccountInfo(registrationPda);
+      expect(after.data.equals(before.data)).to.be.true;
+      const registration = await program.account.airdropRegistrationAccount.fetch(registrationPda);
+      expect(registration.version).to.equal(1);
+    });
+
+    it('should reject an account that is not a registration', async () => {
+      const campaignPda = await createTestCampaign('Migration target');
+      try {
+        await program.methods
+          .migrateRegistration()
+          .accounts({
+            registration: campaignPda,
+            nullifierAccount: null,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail('Should have thrown InvalidRegistration error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidRegistration');
+      }
+    });
+
+    it('should keep a bound registration when given its credential', async () => {
+      const campaignPda = await createTestCampaign('Migration backfill no-op');
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(recipient.publicKey);
+      const registrationPda = await registerCredential(
+        campaignPda,
+        nullifier,
+        nullifierPda,
+        recipient
+      );
+
+      await program.methods
+        .migrateRegistration()
+        .accounts({
+          registration: registrationPda,
+          nullifierAccount: nullifierPda,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const registration = await program.account.airdropRegistrationAccount.fetch(registrationPda);
+      expect(registration.recipient.toBase58()).to.equal(recipient.publicKey.toBase58());
+    });
+
+    it('should reject a credential the registration was not made with', async () => {
+      const campaignPda = await createTestCampaign('Migration backfill mismatch');
+      const registrant = Keypair.generate();
+      await fundWallet(registrant.publicKey);
+      const { nullifierPda } = await recordDevCredential(registrant.publicKey);
+      const { nullifier: otherNullifier, nullifierPda: otherNullifierPda } =
+        await recordDevCredential(registrant.publicKey);
+      const registrationPda = await registerCredential(
+        campaignPda,
+        otherNullifier,
+        otherNullifierPda,
+        registrant
+      );
+
+      try {
+        await program.methods
+          .migrateRegistration()
+          .accounts({
+            registration: registrationPda,
+            nullifierAccount: nullifierPda,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail('Should have thrown InvalidNullifierAccount error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidNullifierAccount');
+      }
+    });
+  });
+
+  describe('bind_registration_recipient', () => {
+    it('should reject binding a registration that already has an owner', async () => {
+      const campaignPda = await createTestCampaign('Bind owned');
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(recipient.publicKey);
+      const registrationPda = await registerCredential(
+        campaignPda,
+        nullifier,
+        nullifierPda,
+        recipient
+      );
+
+      try {
+        await program.methods
+          .bindRegistrationRecipient(Keypair.generate().publicKey)
+          .accounts({
+            config: configPda,
+            campaign: campaignPda,
+            registration: registrationPda,
+            nullifierAccount: nullifierPda,
+            authority: admin.publicKey,
+          })
+          .rpc();
+        expect.fail('Should have thrown RegistrationAlreadyBound error');
+      } catch (error) {
+        expect(error.toString()).to.include('RegistrationAlreadyBound');
+      }
+
+      const registration = await program.account.airdropRegistrationAccount.fetch(registrationPda);
+      expect(registration.recipient.toBase58()).to.equal(recipient.publicKey.toBase58());
+    });
+
+    it('should reject a signer that is neither the creator nor admin', async () => {
+      const campaignPda = await createTestCampaign('Bind unauthorized');
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(recipient.publicKey);
+      const registrationPda = await registerCredential(
+        campaignPda,
+        nullifier,
+        nullifierPda,
+        recipient
+      );
+
+      try {
+        await program.methods
+          .bindRegistrationRecipient(recipient.publicKey)
+          .accounts({
+            config: configPda,
+            campaign: campaignPda,
+            registration: registrationPda,
+            nullifierAccount: nullifierPda,
+            authority: recipient.publicKey,
+          })
+          .signers([recipient])
+          .rpc();
+        expect.fail('Should have thrown Unauthorized error');
+      } catch (error) {
+        expect(error.toString()).to.include('Unauthorized');
+      }
+    });
+  });
+
+  describe('update_registration_address', () => {
+    it('should let an open registrant correct their ShadowWire address', async () => {
+      const campaignPda = await createTestCampaign('Address fix');
+      const [registrationPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('airdrop_registration'), campaignPda.toBuffer(), admin.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .registerForAirdropOpen(Keypair.generate().publicKey.toBase58())
+        .accounts({
+          config: configPda,
+          campaign: campaignPda,
+          registration: registrationPda,
+          activityLog: null,
+          shadowWireUsage: null,
+          registrationIndex: null,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const stranger = Keypair.generate();
+      try {
+        await program.methods
+          .updateRegistrationAddress(stranger.publicKey.toBase58())
+          .accounts({
+            campaign: campaignPda,
+            registration: registrationPda,
+            shadowWireUsage: null,
+            oldShadowWireUsage: null,
+            owner: stranger.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([stranger])
+          .rpc();
+        expect.fail('Should have thrown Unauthorized error');
+      } catch (error) {
+        expect(error.toString()).to.include('Unauthorized');
+      }
+
+      const newAddress = Keypair.generate().publicKey.toBase58();
+      await program.methods
+        .updateRegistrationAddress(newAddress)
+        .accounts({
+          campaign: campaignPda,
+          registration: registrationPda,
+          shadowWireUsage: null,
+          oldShadowWireUsage: null,
+          owner: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const registration = await program.account.airdropRegistrationAccount.fetch(registrationPda);
+      expect(registration.shadowWireAddress).to.equal(newAddress);
+    });
+
+    it('should release the old address from the ShadowWire reuse cap', async () => {
+      const campaignPda = await createTestCampaign('Address fix with cap');
+      await program.methods
+        .setShadowWireReuseLimit(1)
+        .accounts({ campaign: campaignPda, creator: admin.publicKey })
+        .rpc();
+      const usagePda = (address: string) =>
+        PublicKey.findProgramAddressSync(
+          [
+            Buffer.from('shadow_wire_usage'),
+            campaignPda.toBuffer(),
+            createHash('sha256').update(Buffer.from(address)).digest(),
+          ],
+          program.programId
+        )[0];
+      const registerOpen = async (registrant: Keypair, address: string) => {
+        const [registrationPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from('airdrop_registration'),
+            campaignPda.toBuffer(),
+            registrant.publicKey.toBuffer(),
+          ],
+          program.programId
+        );
+        await program.methods
+          .registerForAirdropOpen(address)
+          .accounts({
+            config: configPda,
+            campaign: campaignPda,
+            registration: registrationPda,
+            activityLog: null,
+            shadowWireUsage: usagePda(address),
+            registrationIndex: null,
+            payer: registrant.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([registrant])
+          .rpc();
+        return registrationPda;
+      };
+
+      const registrant = Keypair.generate();
+      await fundWallet(registrant.publicKey);
+      const oldAddress = Keypair.generate().publicKey.toBase58();
+      const newAddress = Keypair.generate().publicKey.toBase58();
+      const registrationPda = await registerOpen(registrant, oldAddress);
+
+      try {
+        await program.methods
+          .updateRegistrationAddress(newAddress)
+          .accounts({
+            campaign: campaignPda,
+            registration: registrationPda,
+            shadowWireUsage: usagePda(newAddress),
+            oldShadowWireUsage: null,
+            owner: registrant.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([registrant])
+          .rpc();
+        expect.fail('Should have thrown ShadowWireUsageRequired error');
+      } catch (error) {
+        expect(error.toString()).to.include('ShadowWireUsageRequired');
+      }
+
+      await program.methods
+        .updateRegistrationAddress(newAddress)
+        .accounts({
+          campaign: campaignPda,
+          registration: registrationPda,
+          shadowWireUsage: usagePda(newAddress),
+          oldShadowWireUsage: usagePda(oldAddress),
+          owner: registrant.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([registrant])
+        .rpc();
+
+      // The old counter hit zero and was closed; the new one holds the registration
+      expect(await provider.connection.getAccountInfo(usagePda(oldAddress))).to.be.null;
+      const usage = await program.account.shadowWireUsage.fetch(usagePda(newAddress));
+      expect(usage.registrations).to.equal(1);
+
+      // The freed address can be registered again under the cap of one
+      const nextRegistrant = Keypair.generate();
+      await fundWallet(nextRegistrant.publicKey);
+      await registerOpen(nextRegistrant, oldAddress);
+      const reused = await program.account.shadowWireUsage.fetch(usagePda(oldAddress));
+      expect(reused.registrations).to.equal(1);
+    });
+  });
+
+  describe('distribution receipts', () => {
+    // "vouch_distribution_receipt" | campaign_id | nullifier | amount (u64 BE) | len | address
+    function buildReceiptMessage(
+      campaignId: number[],
+      nullifier: Uint8Array,
+      amount: bigint,
+      address: string
+    ): Buffer {
+      const amountBytes = Buffer.alloc(8);
+      amountBytes.writeBigUInt64BE(amount);
+      return Buffer.concat([
+        Buffer.from('vouch_distribution_receipt'),
+        Buffer.from(campaignId),
+        Buffer.from(nullifier),
+        amountBytes,
+        Buffer.from([address.length]),
+        Buffer.from(address),
+      ]);
+    }
+
+    async function markDistributed(
+      campaignPda: PublicKey,
+      registrationPda: PublicKey,
+      amount: number,
+      receipt: { relay: Keypair; message: Buffer } | null
+    ) {
+      const ed25519Ix = receipt
+        ? anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: receipt.relay.secretKey,
+            message: receipt.message,
+          })
+        : null;
+      const markIx = await program.methods
+        .markAirdropDistributed(
+          'shadowwire-tx',
+          new anchor.BN(amount),
+          ed25519Ix
+            ? (Array.from(ed25519Ix.data.subarray(48, 112)) as number[] & { length: 64 })
+            : null
+        )
+        .accounts({
+          config: configPda,
+          campaign: campaignPda,
+          registration: registrationPda,
+          creator: admin.publicKey,
+          instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          campaignManager: null,
+        })
+        .instruction();
+      const tx = new anchor.web3.Transaction();
+      if (ed25519Ix) tx.add(ed25519Ix);
+      await provider.sendAndConfirm(tx.add(markIx));
+    }
+
+    async function createRelayCampaign(name: string, relay: Keypair) {
+      const campaignPda = await createTestCampaign(name);
+      await program.methods
+        .setDistributionRelay(relay.publicKey)
+        .accounts({ campaign: campaignPda, creator: admin.publicKey })
+        .rpc();
+      const registrant = Keypair.generate();
+      await fundWallet(registrant.publicKey);
+      const address = Keypair.generate().publicKey.toBase58();
+      const registrationPda = await registerOpen(campaignPda, registrant, address);
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      return { campaignPda, registrationPda, registrant, address, campaignId: campaign.campaignId };
+    }
+
+    it('should mark a distribution proven by the relay receipt', async () => {
+      const relay = Keypair.generate();
+      const { campaignPda, registrationPda, registrant, address, campaignId } =
+        await createRelayCampaign('Receipt drop', relay);
+
+      const message = buildReceiptMessage(
+        campaignId,
+        registrant.publicKey.toBytes(),
+        BigInt(1_000),
+        address
+      );
+      await markDistributed(campaignPda, registrationPda, 1_000, { relay, message });
+
+      const registration = await program.account.airdropRegistrationAccount.fetch(registrationPda);
+      expect(registration.isDistributed).to.be.true;
+      expect(registration.distributionReceiptVerified).to.be.true;
+      expect(registration.distributedAmount.toNumber()).to.equal(1_000);
+
+      try {
+        await markDistributed(campaignPda, registrationPda, 1_000, null);
+        expect.fail('Should have thrown AlreadyDistributed error');
+      } catch (error) {
+        expect(error.toString()).to.include('AlreadyDistributed');
+      }
+    });
+
+    it('should reject a missing or mismatched relay receipt', async () => {
+      const relay = Keypair.generate();
+      const { campaignPda, registrationPda, registrant, address, campaignId } =
+        await createRelayCampaign('Receipt required', relay);
+
+      try {
+        await markDistributed(campaignPda, registrationPda, 1_000, null);
+        expect.fail('Should have thrown DistributionProofInvalid error');
+      } catch (error) {
+        expect(error.toString()).to.include('DistributionProofInvalid');
+      }
+
+      // The relay signed a smaller amount than the creator reports
+      const message = buildReceiptMessage(
+        campaignId,
+        registrant.publicKey.toBytes(),
+        BigInt(10),
+        address
+      );
+      try {
+        await markDistributed(campaignPda, registrationPda, 1_000, { relay, message });
+        expect.fail('Should have thrown DistributionProofInvalid error');
+      } catch (error) {
+        expect(error.toString()).to.include('DistributionProofInvalid');
+      }
+
+      // A receipt signed by another key is not the campaign relay's
+      const impostor = Keypair.generate();
+      const validMessage = buildReceiptMessage(
+        campaignId,
+        registrant.publicKey.toBytes(),
+        BigInt(1_000),
+        address
+      );
+      try {
+        await markDistributed(campaignPda, registrationPda, 1_000, {
+          relay: impostor,
+          message: validMessage,
+        });
+        expect.fail('Should have thrown DistributionProofInvalid error');
+      } catch (error) {
+        expect(error.toString()).to.include('DistributionProofInvalid');
+      }
+    });
+
+    it('should mark an unverified distribution when no relay is set', async () => {
+      const campaignPda = await createTestCampaign('No relay');
+      const registrant = Keypair.generate();
+      await fundWallet(registrant.publicKey);
+      const registrationPda = await registerOpen(campaignPda, registrant);
+
+      await markDistributed(campaignPda, registrationPda, 500, null);
+
+      const registration = await program.account.airdropRegistrationAccount.fetch(registrationPda);
+      expect(registration.isDistributed).to.be.true;
+      expect(registration.distributionReceiptVerified).to.be.false;
+    });
+  });
+
+  describe('mark_airdrop_distributed_batch', () => {
+    async function registerOpenWallets(campaignPda: PublicKey, count: number) {
+      const registrations: PublicKey[] = [];
+      for (let i = 0; i < count; i++) {
+        const registrant = Keypair.generate();
+        await fundWallet(registrant.publicKey);
+        registrations.push(await registerOpen(campaignPda, registrant));
+      }
+      return registrations;
+    }
+
+    function markBatch(
+      campaignPda: PublicKey,
+      registrations: PublicKey[],
+      txSignatures: string[],
+      amounts: number[]
+    ) {
+      return program.methods
+        .markAirdropDistributedBatch(txSignatures, amounts.map((amount) => new anchor.BN(amount)))
+        .accounts({
+          config: configPda,
+          campaign: campaignPda,
+          creator: admin.publicKey,
+          campaignManager: null,
+        })
+        .remainingAccounts(
+          registrations.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+        )
+        .rpc();
+    }
+
+    it('should mark each registration and skip already-distributed ones', async () => {
+      const campaignPda = await createTestCampaign('Batch distribution');
+      const registrations = await registerOpenWallets(campaignPda, 3);
+
+      await markBatch(campaignPda, [registrations[0]], ['first-tx'], [100]);
+      await markBatch(campaignPda, registrations, ['batch-tx'], [200, 300, 400]);
+
+      const first = await program.account.airdropRegistrationAccount.fetch(registrations[0]);
+      expect(first.distributionTx).to.equal('first-tx');
+      expect(first.distributedAmount.toNumber()).to.equal(100);
+      for (const [i, registrationPda] of registrations.slice(1).entries()) {
+        const registration =
+          await program.account.airdropRegistrationAccount.fetch(registrationPda);
+        expect(registration.isDistributed).to.be.true;
+        expect(registration.distributionTx).to.equal('batch-tx');
+        expect(registration.distributedAmount.toNumber()).to.equal(300 + i * 100);
+      }
+    });
+
+    it('should reject mismatched signature and amount lists', async () => {
+      const campaignPda = await createTestCampaign('Batch mismatch');
+      const registrations = await registerOpenWallets(campaignPda, 2);
+
+      try {
+        await markBatch(campaignPda, registrations, ['tx'], [100]);
+        expect.fail('Should have thrown InvalidBatchSize error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidBatchSize');
+      }
+      try {
+        await markBatch(campaignPda, registrations, ['a', 'b', 'c'], [100, 100]);
+        expect.fail('Should have thrown InvalidBatchSize error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidBatchSize');
+      }
+    });
+
+    it('should reject a registration from another campaign', async () => {
+      const campaignPda = await createTestCampaign('Batch owner');
+      const otherCampaignPda = await createTestCampaign('Batch intruder');
+      const [foreignRegistration] = await registerOpenWallets(otherCampaignPda, 1);
+
+      try {
+        await markBatch(campaignPda, [foreignRegistration], ['tx'], [100]);
+        expect.fail('Should have thrown InvalidCampaign error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidCampaign');
+      }
+    });
+
+    it('should refuse campaigns that require relay receipts', async () => {
+      const campaignPda = await createTestCampaign('Batch with relay');
+      await program.methods
+        .setDistributionRelay(Keypair.generate().publicKey)
+        .accounts({ campaign: campaignPda, creator: admin.publicKey })
+        .rpc();
+      const registrations = await registerOpenWallets(campaignPda, 1);
+
+      try {
+        await markBatch(campaignPda, registrations, ['tx'], [100]);
+        expect.fail('Should have thrown DistributionProofInvalid error');
+      } catch (error) {
+        expect(error.toString()).to.include('DistributionProofInvalid');
+      }
+    });
+  });
+
+  describe('update_campaign_amounts', () => {
+    it('should let the creator correct amounts before funding', async () => {
+      const campaignPda = await createTestCampaign('Typo drop');
+
+      await program.methods
+        .updateCampaignAmounts(new anchor.BN(2_000), new anchor.BN(300), new anchor.BN(700))
+        .accounts({
+          campaign: campaignPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.baseAmount.toNumber()).to.equal(2_000);
+      expect(campaign.devBonus.toNumber()).to.equal(300);
+      expect(campaign.whaleBonus.toNumber()).to.equal(700);
+    });
+
+    it('should reject a zero base amount', async () => {
+      const campaignPda = await createTestCampaign('Zero base');
+
+      try {
+        await program.methods
+          .updateCampaignAmounts(new anchor.BN(0), new anchor.BN(0), new anchor.BN(0))
+          .accounts({
+            campaign: campaignPda,
+            creator: admin.publicKey,
+          })
+          .rpc();
+        expect.fail('Should have thrown InvalidAmount error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidAmount');
+      }
+    });
+  });
+
+  describe('set_campaign_min_hold', () => {
+    it('should set the hold before anyone registers', async () => {
+      const campaignPda = await createTestCampaign('Hold before registrations');
+
+      await program.methods
+        .setCampaignMinHold(new anchor.BN(3_600))
+        .accounts({
+          campaign: campaignPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.minHoldSeconds.toNumber()).to.equal(3_600);
+    });
+
+    it('should reject changing the hold once users have registered', async () => {
+      const campaignPda = await createTestCampaign('Hold after registrations');
+      const registrant = Keypair.generate();
+      await fundWallet(registrant.publicKey);
+      await registerOpen(campaignPda, registrant);
+
+      try {
+        await program.methods
+          .setCampaignMinHold(new anchor.BN(30 * 86_400))
+          .accounts({
+            campaign: campaignPda,
+            creator: admin.publicKey,
+          })
+          .rpc();
+        expect.fail('Should have thrown CampaignHasRegistrations error');
+      } catch (error) {
+        expect(error.toString()).to.include('CampaignHasRegistrations');
+      }
+    });
+  });
+
+  describe('multi-asset claims', () => {
+    async function setupAssetClaim(name: string) {
+      const { campaignPda, mint } = await createFundedCampaign(name, 500, 5_000);
+      const { assetMint, assetBasketPda, assetVault } = await configureTestAsset(campaignPda, 100);
+      await fundTestAsset(campaignPda, assetMint, 100);
+
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(recipient.publicKey);
+      const registrationPda = await registerCredential(
+        campaignPda,
+        nullifier,
+        nullifierPda,
+        recipient
+      );
+      const recipientAssetAccount = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        assetMint,
+        recipient.publicKey
+      );
+      return {
+        campaignPda,
+        mint,
+        assetBasketPda,
+        assetVault,
+        registrationPda,
+        recipient,
+        recipientAssetAccount: recipientAssetAccount.address,
+      };
+    }
+
+    async function claimAssets(
+      campaignPda: PublicKey,
+      assetBasketPda: PublicKey,
+      registrationPda: PublicKey,
+      claimer: Keypair,
+      assetIndices: number[],
+      accounts: PublicKey[]
+    ) {
+      const registration = await program.account.airdropRegistrationAccount.fetch(registrationPda);
+      return program.methods
+        .claimAirdropMulti(Buffer.from(assetIndices))
+        .accounts({
+          config: configPda,
+          campaign: campaignPda,
+          assetBasket: assetBasketPda,
+          registration: registrationPda,
+          nullifierAccount: getNullifierPda(Uint8Array.from(registration.nullifier), 1),
+          protocolStats: null,
+          blockedRecipient: null,
+          claimer: claimer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(
+          accounts.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+        )
+        .signers([claimer])
+        .rpc();
+    }
+
+    it('should pay each asset once per registration', async () => {
+      const {
+        campaignPda,
+        assetBasketPda,
+        assetVault,
+        registrationPda,
+        recipient,
+        recipientAssetAccount,
+      } = await setupAssetClaim('Basket drop');
+
+      await claimAssets(campaignPda, assetBasketPda, registrationPda, recipient, [0], [
+        assetVault,
+        recipientAssetAccount,
+      ]);
+
+      const received = await getAccount(provider.connection, recipientAssetAccount);
+      expect(Number(received.amount)).to.equal(10);
+      const registration = await program.account.airdropRegistrationAccount.fetch(registrationPda);
+      expect(registration.assetsClaimed).to.equal(1);
+      const basket = await program.account.campaignAssetBasket.fetch(assetBasketPda);
+      expect(basket.assets[0].vaultBalance.toNumber()).to.equal(90);
+
+      try {
+        await claimAssets(campaignPda, assetBasketPda, registrationPda, recipient, [0], [
+          assetVault,
+          recipientAssetAccount,
+        ]);
+        expect.fail('Should have thrown AlreadyClaimed error');
+      } catch (error) {
+        expect(error.toString()).to.include('AlreadyClaimed');
+      }
+    });
+
+    it('should reject unknown asset indices and foreign vaults', async () => {
+      const {
+        campaignPda,
+        mint,
+        assetBasketPda,
+        assetVault,
+        registrationPda,
+        recipient,
+        recipientAssetAccount,
+      } = await setupAssetClaim('Basket guards');
+
+      try {
+        await claimAssets(campaignPda, assetBasketPda, registrationPda, recipient, [1], [
+          assetVault,
+          recipientAssetAccount,
+        ]);
+        expect.fail('Should have thrown InvalidAssetIndex error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidAssetIndex');
+      }
+
+      // The primary campaign vault is not the asset's vault
+      try {
+        await claimAssets(campaignPda, assetBasketPda, registrationPda, recipient, [0], [
+          getAssociatedTokenAddressSync(mint, campaignPda, true),
+          recipientAssetAccount,
+        ]);
+        expect.fail('Should have thrown InvalidAssetAccounts error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidAssetAccounts');
+      }
+
+      try {
+        await claimAssets(campaignPda, assetBasketPda, registrationPda, recipient, [0], [
+          assetVault,
+        ]);
+        expect.fail('Should have thrown InvalidAssetAccounts error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidAssetAccounts');
+      }
+    });
+
+    it('should reject the campaign token as an additional asset', async () => {
+      const { campaignPda, mint } = await createFundedCampaign('Basket duplicate mint', 500, 500);
+      const [assetBasketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('campaign_assets'), campaignPda.toBuffer()],
+        program.programId
+      );
+      try {
+        await program.methods
+          .configureCampaignAssets([mint], [new anchor.BN(10)])
+          .accounts({
+            campaign: campaignPda,
+            assetBasket: assetBasketPda,
+            creator: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail('Should have thrown InvalidAssetConfig error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidAssetConfig');
+      }
+    });
+  });
+
+  describe('cancel_campaign', () => {
+    it('should refund the primary and asset vaults to the creator only', async () => {
+      const { campaignPda, mint, campaignVault } = await createFundedCampaign(
+        'Cancelled drop',
+        100,
+        1_000
+      );
+
+      // One additional asset, fully funded from the creator's account
+      const { assetMint, assetBasketPda, assetVault, creatorAssetAccount } =
+        await configureTestAsset(campaignPda, 300);
+      await fundTestAsset(campaignPda, assetMint, 300);
+
+      const cancel = (creator: Keypair) =>
+        program.methods
+          .cancelCampaign()
+          .accounts({
+            config: configPda,
+            campaign: campaignPda,
+            campaignVault,
+            tokenMint: mint,
+            creatorTokenAccount: getAssociatedTokenAddressSync(mint, creator.publicKey),
+            assetBasket: assetBasketPda,
+            protocolStats: null,
+            creator: creator.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            { pubkey: assetVault, isSigner: false, isWritable: true },
+            { pubkey: creatorAssetAccount, isSigner: false, isWritable: true },
+          ])
+          .signers([creator])
+          .rpc();
+
+      const stranger = Keypair.generate();
+      await fundWallet(stranger.publicKey);
+      try {
+        await cancel(stranger);
+        expect.fail('Should have thrown Unauthorized error');
+      } catch (error) {
+        expect(error.toString()).to.include('Unauthorized');
+      }
+
+      await cancel(admin.payer);
+
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.status).to.deep.equal({ cancelled: {} });
+      expect(Number((await getAccount(provider.connection, campaignVault)).amount)).to.equal(0);
+      expect(Number((await getAccount(provider.connection, assetVault)).amount)).to.equal(0);
+      const refundedAsset = await getAccount(provider.connection, creatorAssetAccount);
+      expect(Number(refundedAsset.amount)).to.equal(300);
+      const basket = await program.account.campaignAssetBasket.fetch(assetBasketPda);
+      expect(basket.assets[0].vaultBalance.toNumber()).to.equal(0);
+    });
+  });
+
+  describe('bonus decay', () => {
+    it('should decay the early-bird bonus linearly over the registration window', async () => {
+      const templateId = randomBytes(32);
+      const [templatePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('campaign_template'), admin.publicKey.toBuffer(), Buffer.from(templateId)],
+        program.programId
+      );
+      await program.methods
+        .createCampaignTemplate(
+          Array.from(templateId),
+          {
+            tokenMint: Keypair.generate().publicKey,
+            baseAmount: new anchor.BN(1_000),
+            devBonus: new anchor.BN(500),
+            whaleBonus: new anchor.BN(0),
+            campaignClaimFeeBps: 0,
+            claimFeeAccount: PublicKey.default,
+          },
+          new anchor.BN(86_400),
+          0,
+          [],
+          new anchor.BN(0)
+        )
+        .accounts({
+          template: templatePda,
+          creator: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const campaignId = randomBytes(32);
+      const [campaignPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('airdrop_campaign'), Buffer.from(campaignId)],
+        program.programId
+      );
+      await program.methods
+        .createCampaignFromTemplate(Array.from(campaignId), 'Early bird')
+        .accounts({
+          config: configPda,
+          template: templatePda,
+          campaign: campaignPda,
+          creator: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .setBonusDecay(new anchor.BN(1_000), new anchor.BN(200))
+        .accounts({
+          campaign: campaignPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      const open = campaign.createdAt;
+      const deadline = campaign.registrationDeadline;
+      const midpoint = open.add(deadline.sub(open).divn(2));
+
+      const allocationAt = async (proofType: number, registeredAt: anchor.BN) =>
+        (
+          await program.methods
+            .previewRegistrationAllocation(proofType, registeredAt)
+            .accounts({ campaign: campaignPda })
+            .view()
+        ).toNumber();
+
+      // Open registration: base 1_000 + bonus decaying 1_000 -> 200
+      expect(await allocationAt(0, open)).to.equal(2_000);
+      expect(await allocationAt(0, midpoint)).to.equal(1_600);
+      expect(await allocationAt(0, deadline)).to.equal(1_200);
+      // Developer tier keeps its bonus on top
+      expect(await allocationAt(1, midpoint)).to.equal(2_100);
+    });
+  });
+
+  describe('reclaim_unclaimed_airdrop', () => {
+    function reclaim(campaignPda: PublicKey, mint: PublicKey, creator: Keypair = admin.payer) {
+      return program.methods
+        .reclaimUnclaimedAirdrop()
+        .accounts({
+          config: configPda,
+          campaign: campaignPda,
+          campaignVault: getAssociatedTokenAddressSync(mint, campaignPda, true),
+          tokenMint: mint,
+          creatorTokenAccount: getAssociatedTokenAddressSync(mint, creator.publicKey),
+          protocolStats: null,
+          creator: creator.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+    }
+
+    it('should refuse to reclaim before completion and during the grace period', async () => {
+      const { campaignPda, mint, campaignVault } = await createFundedCampaign(
+        'Reclaim grace',
+        500,
+        5_000
+      );
+      try {
+        await reclaim(campaignPda, mint);
+        expect.fail('Should have thrown CampaignNotCompleted error');
+      } catch (error) {
+        expect(error.toString()).to.include('CampaignNotCompleted');
+      }
+
+      await program.methods
+        .closeAirdropRegistration()
+        .accounts({ campaign: campaignPda, creator: admin.publicKey, campaignManager: null })
+        .rpc();
+      await program.methods
+        .completeAirdropCampaign()
+        .accounts({ config: configPda, campaign: campaignPda, creator: admin.publicKey })
+        .rpc();
+
+      const stranger = Keypair.generate();
+      await fundWallet(stranger.publicKey);
+      try {
+        await reclaim(campaignPda, mint, stranger);
+        expect.fail('Should have thrown Unauthorized error');
+      } catch (error) {
+        expect(error.toString()).to.include('Unauthorized');
+      }
+
+      // Just completed: registrants keep the grace period to claim
+      try {
+        await reclaim(campaignPda, mint);
+        expect.fail('Should have thrown ReclaimGracePeriodActive error');
+      } catch (error) {
+        expect(error.toString()).to.include('ReclaimGracePeriodActive');
+      }
+
+      const vault = await getAccount(provider.connection, campaignVault);
+      expect(Number(vault.amount)).to.equal(5_000);
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.vaultBalance.toNumber()).to.equal(5_000);
+    });
+  });
+
+  describe('reap_expired_registration', () => {
+    async function closeAndComplete(campaignPda: PublicKey) {
+      await program.methods
+        .closeAirdropRegistration()
+        .accounts({ campaign: campaignPda, creator: admin.publicKey, campaignManager: null })
+        .rpc();
+      await program.methods
+        .completeAirdropCampaign()
+        .accounts({ config: configPda, campaign: campaignPda, creator: admin.publicKey })
+        .rpc();
+    }
+
+    function reap(campaignPda: PublicKey, registrationPda: PublicKey, reaper: Keypair) {
+      return program.methods
+        .reapExpiredRegistration()
+        .accounts({
+          config: configPda,
+          campaign: campaignPda,
+          registration: registrationPda,
+          registrationIndex: null,
+          creator: admin.publicKey,
+          reaper: reaper.publicKey,
+        })
+        .signers([reaper])
+        .rpc();
+    }
+
+    it('should refuse to reap registrations that can still claim', async () => {
+      const campaignPda = await createTestCampaign('Reap too early');
+      const registrant = Keypair.generate();
+      await fundWallet(registrant.publicKey);
+      const registrationPda = await registerOpen(campaignPda, registrant);
+      const reaper = Keypair.generate();
+      await fundWallet(reaper.publicKey);
+
+      try {
+        await reap(campaignPda, registrationPda, reaper);
+        expect.fail('Should have thrown CampaignNotCompleted error');
+      } catch (error) {
+        expect(error.toString()).to.include('CampaignNotCompleted');
+      }
+
+      // Completed, but with no claim deadline the registration stays claimable
+      await closeAndComplete(campaignPda);
+      try {
+        await reap(campaignPda, registrationPda, reaper);
+        expect.fail('Should have thrown ClaimWindowOpen error');
+      } catch (error) {
+        expect(error.toString()).to.include('ClaimWindowOpen');
+      }
+    });
+
+    it('should split the rent of an expired registration', async () => {
+      const before = await program.account.configAccount.fetch(configPda);
+      await program.methods
+        .setReaperBounty(1_000)
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+
+      try {
+        const campaignId = randomBytes(32);
+        const [campaignPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from('airdrop_campaign'), Buffer.from(campaignId)],
+          program.programId
+        );
+        const now = Math.floor(Date.now() / 1000);
+        const claimDeadline = now + 8;
+        await program.methods
+          .createAirdropCampaign(
+            Array.from(campaignId) as number[] & { length: 32 },
+            'Reapable drop',
+            Keypair.generate().publicKey,
+            new anchor.BN(1_000),
+            new anchor.BN(0),
+            new anchor.BN(0),
+            new anchor.BN(now + 6),
+            new anchor.BN(claimDeadline),
+            0,
+            PublicKey.default,
+            []
+          )
+          .accounts({
+            config: configPda,
+            campaign: campaignPda,
+            creator: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        const registrant = Keypair.generate();
+        await fundWallet(registrant.publicKey);
+        const registrationPda = await registerOpen(campaignPda, registrant);
+        await closeAndComplete(campaignPda);
+
+        // Wait for the cluster clock to pass the claim deadline
+        const connection = provider.connection;
+        while ((await connection.getBlockTime(await connection.getSlot())) <= claimDeadline) {
+          await new Promise((resolve) => setTimeout(resolve, 1_000));
+        }
+
+        const reaper = Keypair.generate();
+        await fundWallet(reaper.publicKey);
+        const reaperBefore = await provider.connection.getBalance(reaper.publicKey);
+        const rent = await provider.connection.getBalance(registrationPda);
+        await reap(campaignPda, registrationPda, reaper);
+
+        expect(await provider.connection.getAccountInfo(registrationPda)).to.be.null;
+        const reaperAfter = await provider.connection.getBalance(reaper.publicKey);
+        expect(reaperAfter - reaperBefore).to.equal(Math.floor((rent * 1_000) / 10_000));
+      } finally {
+        await program.methods
+          .setReaperBounty(before.reaperBountyBps)
+          .accounts({ config: configPda, admin: admin.publicKey })
+          .rpc();
+      }
+    });
+  });
+
+  describe('vesting schedule', () => {
+    const campaignId = randomBytes(32);
+    const [campaignPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('airdrop_campaign'), Buffer.from(campaignId)],
+      program.programId
+    );
+
+    before(async () => {
+      const templateId = randomBytes(32);
+      const [templatePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('campaign_template'), admin.publicKey.toBuffer(), Buffer.from(templateId)],
+        program.programId
+      );
+      await program.methods
+        .createCampaignTemplate(
+          Array.from(templateId),
+          {
+            tokenMint: Keypair.generate().publicKey,
+            baseAmount: new anchor.BN(1_000),
+            devBonus: new anchor.BN(0),
+            whaleBonus: new anchor.BN(0),
+            campaignClaimFeeBps: 0,
+            claimFeeAccount: PublicKey.default,
+          },
+          new anchor.BN(86_400),
+          0,
+          [],
+          new anchor.BN(0)
+        )
+        .accounts({
+          template: templatePda,
+          creator: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      await program.methods
+        .createCampaignFromTemplate(Array.from(campaignId), 'Vested grant')
+        .accounts({
+          config: configPda,
+          template: templatePda,
+          campaign: campaignPda,
+          creator: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it('should store a linear vesting schedule', async () => {
+      const start = Math.floor(Date.now() / 1000) + 86_400;
+      await program.methods
+        .setVestingSchedule(new anchor.BN(start), new anchor.BN(30 * 86_400), new anchor.BN(7 * 86_400))
+        .accounts({
+          campaign: campaignPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.vestingStart.toNumber()).to.equal(start);
+      expect(campaign.vestingDuration.toNumber()).to.equal(30 * 86_400);
+      expect(campaign.cliffSeconds.toNumber()).to.equal(7 * 86_400);
+    });
+
+    it('should reject a cliff longer than the vesting duration', async () => {
+      try {
+        await program.methods
+          .setVestingSchedule(
+            new anchor.BN(Math.floor(Date.now() / 1000)),
+            new anchor.BN(86_400),
+            new anchor.BN(2 * 86_400)
+          )
+          .accounts({
+            campaign: campaignPda,
+            creator: admin.publicKey,
+          })
+          .rpc();
+        expect.fail('Should have thrown InvalidVestingSchedule error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidVestingSchedule');
+      }
+    });
+  });
+
+  describe('merkle claims', () => {
+    // Mirrors build_airdrop_claim_leaf for a developer credential
+    function buildClaimLeaf(nullifier: Uint8Array, amount: number): Buffer {
+      const amountBytes = Buffer.alloc(8);
+      amountBytes.writeBigUInt64BE(BigInt(amount));
+      return createHash('sha256')
+        .update(
+          Buffer.concat([
+            Buffer.from('vouch_airdrop_leaf'),
+            Buffer.from(nullifier),
+            amountBytes,
+            Buffer.from([1]),
+          ])
+        )
+        .digest();
+    }
+
+    // Funded campaign whose two-leaf root includes a credential for a fresh recipient
+    async function setupMerkleClaim(amount: number) {
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(recipient.publicKey);
+      const { campaignPda, mint, campaignVault } = await createFundedCampaign(
+        'Merkle claims',
+        amount,
+        10 * amount
+      );
+
+      const leaf = buildClaimLeaf(nullifier, amount);
+      const sibling = Buffer.from(randomBytes(32));
+      await program.methods
+        .setMerkleRoot(Array.from(hashPair(leaf, sibling)) as number[] & { length: 32 })
+        .accounts({
+          campaign: campaignPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+
+      const claim = (claimer: Keypair, blockedRecipient: PublicKey | null = null) =>
+        program.methods
+          .claimAirdropMerkle(
+            Array.from(leaf) as number[] & { length: 32 },
+            new anchor.BN(amount),
+            [Array.from(sibling) as number[] & { length: 32 }]
+          )
+          .accounts({
+            config: configPda,
+            campaign: campaignPda,
+            campaignVault,
+            tokenMint: mint,
+            nullifierAccount: nullifierPda,
+            claimStatus: getClaimStatusPda(campaignPda, nullifier),
+            privateClaimMarker: getPrivateClaimMarkerPda(campaignPda, nullifier),
+            claimerTokenAccount: getAssociatedTokenAddressSync(mint, claimer.publicKey),
+            claimFeeAccount: null,
+            activityLog: null,
+            protocolStats: null,
+            blockedRecipient,
+            claimer: claimer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([claimer])
+          .rpc();
+
+      return { recipient, nullifier, nullifierPda, campaignPda, mint, campaignVault, claim };
+    }
+
+    it('should pay the credential recipient once', async () => {
+      const { recipient, mint, claim } = await setupMerkleClaim(500);
+
+      await claim(recipient);
+      const tokenAccount = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(mint, recipient.publicKey)
+      );
+      expect(Number(tokenAccount.amount)).to.equal(500);
+
+      try {
+        await claim(recipient);
+        expect.fail('Should have thrown AlreadyClaimed error');
+      } catch (error) {
+        expect(error.toString()).to.include('AlreadyClaimed');
+      }
+    });
+
+    it('should reject a claim from a wallet other than the credential recipient', async () => {
+      const { claim } = await setupMerkleClaim(500);
+      const thief = Keypair.generate();
+      await fundWallet(thief.publicKey);
+
+      try {
+        await claim(thief);
+        expect.fail('Should have thrown ClaimerNotOwner error');
+      } catch (error) {
+        expect(error.toString()).to.include('ClaimerNotOwner');
+      }
+    });
+
+    it('should reject a blocked claimer while enforced', async () => {
+      const { recipient, claim } = await setupMerkleClaim(500);
+      const blockedPda = getBlockedRecipientPda(recipient.publicKey);
+      await program.methods
+        .blockRecipient(recipient.publicKey)
+        .accounts({
+          config: configPda,
+          blockedRecipient: blockedPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      await program.methods
+        .setEnforceRecipientBlocklist(true)
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+
+      try {
+        await claim(recipient, blockedPda);
+        expect.fail('Should have thrown RecipientBlocked error');
+      } catch (error) {
+        expect(error.toString()).to.include('RecipientBlocked');
+      } finally {
+        await program.methods
+          .setEnforceRecipientBlocklist(false)
+          .accounts({ config: configPda, admin: admin.publicKey })
+          .rpc();
+        await program.methods
+          .unblockRecipient()
+          .accounts({
+            config: configPda,
+            blockedRecipient: blockedPda,
+            admin: admin.publicKey,
+          })
+          .rpc();
+      }
+    });
+
+    it('should refuse a merkle claim after the registration path paid the credential', async () => {
+      const { recipient, nullifier, nullifierPda, campaignPda, mint, campaignVault, claim } =
+        await setupMerkleClaim(500);
+      await registerCredential(campaignPda, nullifier, nullifierPda, recipient);
+      await claimRegistration(campaignPda, mint, campaignVault, nullifier, recipient);
+
+      try {
+        await claim(recipient);
+        expect.fail('Should have thrown AlreadyClaimed error');
+      } catch (error) {
+        expect(error.toString()).to.include('AlreadyClaimed');
+      }
+    });
+
+    it('should store the campaign eligibility root', async () => {
+      const campaignPda = await createTestCampaign('Merkle drop');
+      const merkleRoot = randomBytes(32);
+
+      await program.methods
+        .setMerkleRoot(Array.from(merkleRoot) as number[] & { length: 32 })
+        .accounts({
+          campaign: campaignPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(Buffer.from(campaign.merkleRoot)).to.deep.equal(Buffer.from(merkleRoot));
+    });
+
+    it('should reject root updates from another wallet', async () => {
+      const campaignPda = await createTestCampaign('Merkle drop');
+      const other = Keypair.generate();
+      try {
+        await program.methods
+          .setMerkleRoot(Array.from(randomBytes(32)) as number[] & { length: 32 })
+          .accounts({
+            campaign: campaignPda,
+            creator: other.publicKey,
+          })
+          .signers([other])
+          .rpc();
+        expect.fail('Should have thrown Unauthorized error');
+      } catch (error) {
+        expect(error.toString()).to.include('Unauthorized');
+      }
+    });
+  });
+
+  describe('open registration toggle', () => {
+    it('should refuse open registrations once the creator disables them', async () => {
+      const campaignPda = await createTestCampaign('Verified only');
+      const [registrationPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('airdrop_registration'), campaignPda.toBuffer(), admin.publicKey.toBuffer()],
+        program.programId
+      );
+      const registerOpen = () =>
+        program.methods
+          .registerForAirdropOpen(Keypair.generate().publicKey.toBase58())
+          .accounts({
+            config: configPda,
+            campaign: campaignPda,
+            registration: registrationPda,
+            activityLog: null,
+            shadowWireUsage: null,
+            registrationIndex: null,
+            payer: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+      await program.methods
+        .setOpenRegistrationEnabled(false)
+        .accounts({
+          campaign: campaignPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+
+      try {
+        await registerOpen();
+        expect.fail('Should have thrown OpenRegistrationDisabled error');
+      } catch (error) {
+        expect(error.toString()).to.include('OpenRegistrationDisabled');
+      }
+
+      await program.methods
+        .setOpenRegistrationEnabled(true)
+        .accounts({
+          campaign: campaignPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+      await registerOpen();
+
+      const registration = await program.account.airdropRegistrationAccount.fetch(registrationPda);
+      expect(registration.isOpenRegistration).to.be.true;
+    });
+  });
+
+  describe('private claims', () => {
+    it('should keep attestation claims disabled until the creator opts in', async () => {
+      const campaignPda = await createTestCampaign('Private drop');
+
+      let campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.privateClaimsEnabled).to.be.false;
+
+      await program.methods
+        .setPrivateClaimsEnabled(true)
+        .accounts({
+          campaign: campaignPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+
+      campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.privateClaimsEnabled).to.be.true;
+    });
+  });
+
+  describe('claim recipient binding', () => {
+    it('should reject a registration claim from a wallet other than the recipient', async () => {
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(recipient.publicKey);
+      const { campaignPda, mint, campaignVault } = await createFundedCampaign(
+        'Bound claims',
+        500,
+        5_000
+      );
+      await registerCredential(campaignPda, nullifier, nullifierPda, recipient);
+
+      const thief = Keypair.generate();
+      await fundWallet(thief.publicKey);
+      try {
+        await claimRegistration(campaignPda, mint, campaignVault, nullifier, thief);
+        expect.fail('Should have thrown ClaimerNotOwner error');
+      } catch (error) {
+        expect(error.toString()).to.include('ClaimerNotOwner');
+      }
+
+      await claimRegistration(campaignPda, mint, campaignVault, nullifier, recipient);
+      const tokenAccount = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(mint, recipient.publicKey)
+      );
+      expect(Number(tokenAccount.amount)).to.equal(500);
+    });
+
+    it('should reject claiming a registration whose credential was revoked', async () => {
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(recipient.publicKey);
+      const { campaignPda, mint, campaignVault } = await createFundedCampaign(
+        'Revoked after registering',
+        500,
+        5_000
+      );
+      await registerCredential(campaignPda, nullifier, nullifierPda, recipient);
+
+      await program.methods
+        .revokeAttestation(1)
+        .accounts({
+          config: configPda,
+          nullifierAccount: nullifierPda,
+          verifierAccount: null,
+          authority: admin.publicKey,
+        })
+        .rpc();
+
+      try {
+        await claimRegistration(campaignPda, mint, campaignVault, nullifier, recipient);
+        expect.fail('Should have thrown CredentialRevoked error');
+      } catch (error) {
+        expect(error.toString()).to.include('CredentialRevoked');
+      }
+    });
+
+    it('should reject a private claim signature submitted by another wallet', async () => {
+      const verifier = Keypair.generate();
+      const verifierPda = getVerifierPda(verifier.publicKey);
+      await program.methods
+        .addVerifier(verifier.publicKey)
+        .accounts({
+          config: configPda,
+          verifierAccount: verifierPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      const { campaignPda, mint, campaignVault } = await createFundedCampaign(
+        'Private bound claims',
+        500,
+        5_000
+      );
+      await program.methods
+        .setPrivateClaimsEnabled(true)
+        .accounts({ campaign: campaignPda, creator: admin.publicKey })
+        .rpc();
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+
+      // The verifier authorizes the recipient; a thief replays the signed instruction
+      const recipient = Keypair.generate();
+      const thief = Keypair.generate();
+      await fundWallet(thief.publicKey);
+      const nullifier = randomBytes(32);
+      const amountBytes = Buffer.alloc(8);
+      amountBytes.writeBigUInt64BE(BigInt(500));
+      const expiresAt = Math.floor(Date.now() / 1000) + 600;
+      const expiresAtBytes = Buffer.alloc(8);
+      expiresAtBytes.writeBigInt64BE(BigInt(expiresAt));
+      const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: verifier.secretKey,
+        message: Buffer.concat([
+          Buffer.from('vouch_private_claim_v1'),
+          program.programId.toBuffer(),
+          Buffer.from(campaign.campaignId),
+          Buffer.from(nullifier),
+          amountBytes,
+          recipient.publicKey.toBuffer(),
+          expiresAtBytes,
+        ]),
+      });
+      const claimIx = await program.methods
+        .claimAirdropPrivate(
+          Array.from(nullifier) as number[] & { length: 32 },
+          new anchor.BN(500),
+          new anchor.BN(expiresAt),
+          Array.from(ed25519Ix.data.subarray(48, 112)) as number[] & { length: 64 }
+        )
+        .accounts({
+          config: configPda,
+          campaign: campaignPda,
+          campaignVault,
+          tokenMint: mint,
+          verifierAccount: verifierPda,
+          claimMarker: getPrivateClaimMarkerPda(campaignPda, nullifier),
+          claimStatus: getClaimStatusPda(campaignPda, nullifier),
+          claimerTokenAccount: getAssociatedTokenAddressSync(mint, thief.publicKey),
+          claimFeeAccount: null,
+          activityLog: null,
+          protocolStats: null,
+          blockedRecipient: null,
+          usedSignature: null,
+          claimer: thief.publicKey,
+          instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+      try {
+        await provider.sendAndConfirm(new anchor.web3.Transaction().add(ed25519Ix, claimIx), [
+          thief,
+        ]);
+        expect.fail('Should have thrown Ed25519InstructionNotFound error');
+      } catch (error) {
+        expect(error.toString()).to.include('Ed25519InstructionNotFound');
+      }
+    });
+
+    it('should reject a private claim for a nullifier already claimed via registration', async () => {
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda, verifier } = await recordDevCredential(recipient.publicKey);
+      const { campaignPda, mint, campaignVault } = await createFundedCampaign(
+        'Private after registration',
+        500,
+        5_000
+      );
+      await registerCredential(campaignPda, nullifier, nullifierPda, recipient);
+      await claimRegistration(campaignPda, mint, campaignVault, nullifier, recipient);
+      await program.methods
+        .setPrivateClaimsEnabled(true)
+        .accounts({ campaign: campaignPda, creator: admin.publicKey })
+        .rpc();
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+
+      const amountBytes = Buffer.alloc(8);
+      amountBytes.writeBigUInt64BE(BigInt(500));
+      const expiresAt = Math.floor(Date.now() / 1000) + 600;
+      const expiresAtBytes = Buffer.alloc(8);
+      expiresAtBytes.writeBigInt64BE(BigInt(expiresAt));
+      const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: verifier.secretKey,
+        message: Buffer.concat([
+          Buffer.from('vouch_private_claim_v1'),
+          program.programId.toBuffer(),
+          Buffer.from(campaign.campaignId),
+          Buffer.from(nullifier),
+          amountBytes,
+          recipient.publicKey.toBuffer(),
+          expiresAtBytes,
+        ]),
+      });
+      const claimIx = await program.methods
+        .claimAirdropPrivate(
+          Array.from(nullifier) as number[] & { length: 32 },
+          new anchor.BN(500),
+          new anchor.BN(expiresAt),
+          Array.from(ed25519Ix.data.subarray(48, 112)) as number[] & { length: 64 }
+        )
+        .accounts({
+          config: configPda,
+          campaign: campaignPda,
+          campaignVault,
+          tokenMint: mint,
+          verifierAccount: getVerifierPda(verifier.publicKey),
+          claimMarker: getPrivateClaimMarkerPda(campaignPda, nullifier),
+          claimStatus: getClaimStatusPda(campaignPda, nullifier),
+          claimerTokenAccount: getAssociatedTokenAddressSync(mint, recipient.publicKey),
+          claimFeeAccount: null,
+          activityLog: null,
+          protocolStats: null,
+          blockedRecipient: null,
+          usedSignature: null,
+          claimer: recipient.publicKey,
+          instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+      try {
+        await provider.sendAndConfirm(new anchor.web3.Transaction().add(ed25519Ix, claimIx), [
+          recipient,
+        ]);
+        expect.fail('Should have thrown AlreadyClaimed error');
+      } catch (error) {
+        expect(error.toString()).to.include('AlreadyClaimed');
+      }
+    });
+  });
+
+  describe('claim amount', () => {
+    it('should pay a developer registration the base amount plus the tier bonus', async () => {
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(recipient.publicKey);
+      const { campaignPda, mint, campaignVault } = await createFundedCampaign(
+        'Tier claim amount',
+        500,
+        5_000,
+        0,
+        undefined,
+        250
+      );
+      const registrationPda = await registerCredential(
+        campaignPda,
+        nullifier,
+        nullifierPda,
+        recipient
+      );
+      const registration = await program.account.airdropRegistrationAccount.fetch(registrationPda);
+      expect(registration.allocation.toNumber()).to.equal(750);
+
+      await claimRegistration(campaignPda, mint, campaignVault, nullifier, recipient);
+      const tokenAccount = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(mint, recipient.publicKey)
+      );
+      expect(Number(tokenAccount.amount)).to.equal(750);
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.totalClaimedAmount.toNumber()).to.equal(750);
+    });
+  });
+
+  describe('claim reentrancy guard', () => {
+    it('should clear claim_in_progress once the claim transfers complete', async () => {
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(recipient.publicKey);
+      const { campaignPda, mint, campaignVault } = await createFundedCampaign(
+        'Reentrancy guard',
+        500,
+        5_000
+      );
+      const registrationPda = await registerCredential(
+        campaignPda,
+        nullifier,
+        nullifierPda,
+        recipient
+      );
+      let registration = await program.account.airdropRegistrationAccount.fetch(registrationPda);
+      expect(registration.claimInProgress).to.be.false;
+
+      await claimRegistration(campaignPda, mint, campaignVault, nullifier, recipient);
+      registration = await program.account.airdropRegistrationAccount.fetch(registrationPda);
+      expect(registration.isClaimed).to.be.true;
+      expect(registration.claimInProgress).to.be.false;
+
+      // A later call on the settled registration hits the claimed check, not the guard
+      try {
+        await claimRegistration(campaignPda, mint, campaignVault, nullifier, recipient);
+        expect.fail('Should have thrown AlreadyClaimed error');
+      } catch (error) {
+        expect(error.toString()).to.include('AlreadyClaimed');
+        expect(error.toString()).to.not.include('ClaimInProgress');
+      }
+    });
+  });
+
+  describe('custody escrow claims', () => {
+    function getCustodyEscrowPda(campaign: PublicKey, beneficiary: PublicKey): PublicKey {
+      const [pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('custody_escrow'), campaign.toBuffer(), beneficiary.toBuffer()],
+        program.programId
+      );
+      return pda;
+    }
+
+    async function setupRegistration(name: string) {
+      const beneficiary = Keypair.generate();
+      await fundWallet(beneficiary.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(beneficiary.publicKey);
+      const { campaignPda, mint, campaignVault } = await createFundedCampaign(name, 500, 5_000);
+      await registerCredential(campaignPda, nullifier, nullifierPda, beneficiary);
+      return { beneficiary, nullifier, campaignPda, mint, campaignVault };
+    }
+
+    function claimToCustody(
+      campaignPda: PublicKey,
+      mint: PublicKey,
+      nullifier: Uint8Array,
+      beneficiary: Keypair,
+      custodian: PublicKey,
+      releaseAt: number
+    ) {
+      const escrowPda = getCustodyEscrowPda(campaignPda, beneficiary.publicKey);
+      return program.methods
+        .claimAirdropToCustody(null, custodian, new anchor.BN(releaseAt))
+        .accounts({
+          config: configPda,
+          campaign: campaignPda,
+          campaignVault: getAssociatedTokenAddressSync(mint, campaignPda, true),
+          tokenMint: mint,
+          registration: getRegistrationPda(campaignPda, nullifier),
+          nullifierAccount: getNullifierPda(nullifier, 1),
+          claimStatus: getClaimStatusPda(campaignPda, nullifier),
+          privateClaimMarker: getPrivateClaimMarkerPda(campaignPda, nullifier),
+          custodyEscrow: escrowPda,
+          escrowVault: getAssociatedTokenAddressSync(mint, escrowPda, true),
+          claimFeeAccount: null,
+          activityLog: null,
+          protocolStats: null,
+          claimLimit: null,
+          blockedRecipient: null,
+          claimer: beneficiary.publicKey,
+          instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([beneficiary])
+        .rpc();
+    }
+
+    function release(
+      campaignPda: PublicKey,
+      mint: PublicKey,
+      beneficiary: Keypair,
+      custodian: Keypair | null = null
+    ) {
+      const escrowPda = getCustodyEscrowPda(campaignPda, beneficiary.publicKey);
+      return program.methods
+        .releaseCustodyEscrow()
+        .accounts({
+          custodyEscrow: escrowPda,
+          escrowVault: getAssociatedTokenAddressSync(mint, escrowPda, true),
+          tokenMint: mint,
+          beneficiaryTokenAccount: getAssociatedTokenAddressSync(mint, beneficiary.publicKey),
+          custodian: custodian ? custodian.publicKey : null,
+          beneficiary: beneficiary.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers(custodian ? [beneficiary, custodian] : [beneficiary])
+        .rpc();
+    }
+
+    it('should escrow a claim until the custodian co-signs the release', async () => {
+      const { beneficiary, nullifier, campaignPda, mint } = await setupRegistration(
+        'Custodian release'
+      );
+      const custodian = Keypair.generate();
+
+      // Neither a custodian nor a future release time: nothing would ever lock it
+      try {
+        await claimToCustody(campaignPda, mint, nullifier, beneficiary, PublicKey.default, 0);
+        expect.fail('Should have thrown InvalidCustodyRelease error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidCustodyRelease');
+      }
+
+      await claimToCustody(campaignPda, mint, nullifier, beneficiary, custodian.publicKey, 0);
+      const escrowPda = getCustodyEscrowPda(campaignPda, beneficiary.publicKey);
+      const escrow = await program.account.custodyEscrow.fetch(escrowPda);
+      expect(escrow.beneficiary.toString()).to.equal(beneficiary.publicKey.toString());
+      expect(escrow.custodian.toString()).to.equal(custodian.publicKey.toString());
+      expect(escrow.amount.toNumber()).to.equal(500);
+      const escrowVault = getAssociatedTokenAddressSync(mint, escrowPda, true);
+      expect(Number((await getAccount(provider.connection, escrowVault)).amount)).to.equal(500);
+      const registration = await program.account.airdropRegistrationAccount.fetch(
+        getRegistrationPda(campaignPda, nullifier)
+      );
+      expect(registration.isClaimed).to.be.true;
+
+      try {
+        await release(campaignPda, mint, beneficiary);
+        expect.fail('Should have thrown CustodyLocked error');
+      } catch (error) {
+        expect(error.toString()).to.include('CustodyLocked');
+      }
+
+      try {
+        await release(campaignPda, mint, beneficiary, Keypair.generate());
+        expect.fail('Should have thrown Unauthorized error');
+      } catch (error) {
+        expect(error.toString()).to.include('Unauthorized');
+      }
+
+      await release(campaignPda, mint, beneficiary, custodian);
+      const tokenAccount = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(mint, beneficiary.publicKey)
+      );
+      expect(Number(tokenAccount.amount)).to.equal(500);
+      expect(await provider.connection.getAccountInfo(escrowPda)).to.be.null;
+      expect(await provider.connection.getAccountInfo(escrowVault)).to.be.null;
+    });
+
+    it('should let the beneficiary withdraw alone once release_at passes', async () => {
+      const { beneficiary, nullifier, campaignPda, mint } = await setupRegistration(
+        'Time-locked release'
+      );
+      const connection = provider.connection;
+      const releaseAt = (await connection.getBlockTime(await connection.getSlot())) + 3;
+      await claimToCustody(campaignPda, mint, nullifier, beneficiary, PublicKey.default, releaseAt);
+
+      try {
+        await release(campaignPda, mint, beneficiary);
+        expect.fail('Should have thrown CustodyLocked error');
+      } catch (error) {
+        expect(error.toString()).to.include('CustodyLocked');
+      }
+
+      // Wait for the cluster clock to reach the release time
+      while ((await connection.getBlockTime(await connection.getSlot())) < releaseAt) {
+        await new Promise((resolve) => setTimeout(resolve, 1_000));
+      }
+
+      await release(campaignPda, mint, beneficiary);
+      const tokenAccount = await getAccount(
+        connection,
+        getAssociatedTokenAddressSync(mint, beneficiary.publicKey)
+      );
+      expect(Number(tokenAccount.amount)).to.equal(500);
+      expect(
+        await connection.getAccountInfo(getCustodyEscrowPda(campaignPda, beneficiary.publicKey))
+      ).to.be.null;
+    });
+  });
+
+  describe('campaign claim fees', () => {
+    it('should route the creator fee to the campaign fee account', async () => {
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(recipient.publicKey);
+      const { campaignPda, mint, campaignVault, creatorTokenAccount } =
+        await createFundedCampaign('Fee routing', 1_000, 5_000, 500);
+      await registerCredential(campaignPda, nullifier, nullifierPda, recipient);
+
+      await claimRegistration(
+        campaignPda,
+        mint,
+        campaignVault,
+        nullifier,
+        recipient,
+        creatorTokenAccount
+      );
+
+      const recipientAccount = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(mint, recipient.publicKey)
+      );
+      expect(Number(recipientAccount.amount)).to.equal(950);
+      const feeAccount = await getAccount(provider.connection, creatorTokenAccount);
+      expect(Number(feeAccount.amount)).to.equal(50);
+      const vault = await getAccount(provider.connection, campaignVault);
+      expect(Number(vault.amount)).to.equal(4_000);
+    });
+
+    it('should reject a fee campaign claim without the fee account', async () => {
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(recipient.publicKey);
+      const { campaignPda, mint, campaignVault } = await createFundedCampaign(
+        'Fee account missing',
+        1_000,
+        5_000,
+        500
+      );
+      await registerCredential(campaignPda, nullifier, nullifierPda, recipient);
+
+      try {
+        await claimRegistration(campaignPda, mint, campaignVault, nullifier, recipient);
+        expect.fail('Should have thrown InvalidClaimFee error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidClaimFee');
+      }
+    });
+
+    it('should reject a fee campaign claim routed to another token account', async () => {
+      const recipient = Keypair.generate();
+      await fundWallet(recipient.publicKey);
+      const { nullifier, nullifierPda } = await recordDevCredential(recipient.publicKey);
+      const { campaignPda, mint, campaignVault } = await createFundedCampaign(
+        'Fee account swapped',
+        1_000,
+        5_000,
+        500
+      );
+      await registerCredential(campaignPda, nullifier, nullifierPda, recipient);
+      const otherAccount = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        mint,
+        Keypair.generate().publicKey
+      );
+
+      try {
+        await claimRegistration(
+          campaignPda,
+          mint,
+          campaignVault,
+          nullifier,
+          recipient,
+          otherAccount.address
+        );
+        expect.fail('Should have thrown InvalidClaimFee error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidClaimFee');
+      }
+    });
+
+    it('should reject a campaign fee above the maximum', async () => {
+      try {
+        await createFundedCampaign('Fee too high', 1_000, 5_000, 1_001);
+        expect.fail('Should have thrown InvalidClaimFee error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidClaimFee');
+      }
+    });
+
+    it('should reject a campaign fee without a fee account', async () => {
+      try {
+        await createFundedCampaign('Fee without account', 1_000, 5_000, 500, PublicKey.default);
+        expect.fail('Should have thrown InvalidClaimFee error');
+      } catch (error) {
+        expect(error.toString()).to.include('InvalidClaimFee');
+      }
+    });
+  });
+
+  describe('credential owner requirement', () => {
+    it('should let only the creator require credential owners', async () => {
+      const campaignPda = await createTestCampaign('Owners only');
+
+      let campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.requireCredentialOwner).to.be.false;
+
+      const stranger = Keypair.generate();
+      try {
+        await program.methods
+          .setRequireCredentialOwner(true)
+          .accounts({
+            campaign: campaignPda,
+            creator: stranger.publicKey,
+          })
+          .signers([stranger])
+          .rpc();
+        expect.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error).to.exist;
+      }
+
+      await program.methods
+        .setRequireCredentialOwner(true)
+        .accounts({
+          campaign: campaignPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+
+      campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.requireCredentialOwner).to.be.true;
+    });
+  });
+
+  describe('campaign_registration_breakdown', () => {
+    it('should match the raw campaign counters', async () => {
+      const campaignPda = await createTestCampaign('Breakdown');
+
+      const breakdown = await program.methods
+        .campaignRegistrationBreakdown()
+        .accounts({ campaign: campaignPda })
+        .view();
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+
+      expect(breakdown.open.toNumber()).to.equal(campaign.openRegistrations.toNumber());
+      expect(breakdown.dev.toNumber()).to.equal(campaign.devRegistrations.toNumber());
+      expect(breakdown.whale.toNumber()).to.equal(campaign.whaleRegistrations.toNumber());
+      expect(breakdown.total.toNumber()).to.equal(campaign.totalRegistrations.toNumber());
+      expect(breakdown.consistent).to.equal(true);
+    });
+  });
+
+  describe('protocol pause and campaigns', () => {
+    it('should block campaign creation during a protocol pause', async () => {
+      await program.methods
+        .pauseProtocol()
+        .accounts({
+          config: configPda,
+          pauseAuthority: admin.publicKey,
+        })
+        .rpc();
+
+      try {
+        await createTestCampaign('Paused drop');
+        expect.fail('Should have thrown ProtocolPaused error');
+      } catch (error) {
+        expect(error.toString()).to.include('ProtocolPaused');
+      } finally {
+        await program.methods
+          .unpauseProtocol()
+          .accounts({
+            config: configPda,
+            pauseAuthority: admin.publicKey,
+          })
+          .rpc();
+      }
+    });
+
+    it('should block asset funding during a protocol pause', async () => {
+      const campaignPda = await createTestCampaign('Paused asset drop');
+      const { assetMint, assetBasketPda } = await configureTestAsset(campaignPda, 100);
+
+      await program.methods
+        .pauseProtocol()
+        .accounts({
+          config: configPda,
+          pauseAuthority: admin.publicKey,
+        })
+        .rpc();
+
+      try {
+        await fundTestAsset(campaignPda, assetMint, 100);
+        expect.fail('Should have thrown ProtocolPaused error');
+      } catch (error) {
+        expect(error.toString()).to.include('ProtocolPaused');
+      } finally {
+        await program.methods
+          .unpauseProtocol()
+          .accounts({
+            config: configPda,
+            pauseAuthority: admin.publicKey,
+          })
+          .rpc();
+      }
+
+      await fundTestAsset(campaignPda, assetMint, 100);
+      const basket = await program.account.campaignAssetBasket.fetch(assetBasketPda);
+      expect(basket.assets[0].vaultBalance.toNumber()).to.equal(100);
+    });
+  });
+
+  describe('campaign pause', () => {
+    it('should pause and resume a campaign', async () => {
+      const campaignPda = await createTestCampaign('Pausable');
+
+      await program.methods
+        .pauseCampaign()
+        .accounts({
+          campaign: campaignPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+      let campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.isPaused).to.equal(true);
+
+      try {
+        await program.methods
+          .pauseCampaign()
+          .accounts({
+            campaign: campaignPda,
+            creator: admin.publicKey,
+          })
+          .rpc();
+        expect.fail('Should have thrown AlreadyPaused error');
+      } catch (error) {
+        expect(error.toString()).to.include('AlreadyPaused');
+      }
+
+      await program.methods
+        .resumeCampaign()
+        .accounts({
+          campaign: campaignPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+      campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.isPaused).to.equal(false);
+    });
+
+    it('should reject pausing from another wallet', async () => {
+      const campaignPda = await createTestCampaign('Pausable');
+      const other = Keypair.generate();
+      try {
+        await program.methods
+          .pauseCampaign()
+          .accounts({
+            campaign: campaignPda,
+            creator: other.publicKey,
+          })
+          .signers([other])
+          .rpc();
+        expect.fail('Should have thrown Unauthorized error');
+      } catch (error) {
+        expect(error.toString()).to.include('Unauthorized');
+      }
+    });
+  });
+
+  describe('campaign managers', () => {
+    const manager = Keypair.generate();
+    const campaignId = randomBytes(32);
+    const [campaignPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('airdrop_campaign'), Buffer.from(campaignId)],
+      program.programId
+    );
+    const [managerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('campaign_manager'), campaignPda.toBuffer(), manager.publicKey.toBuffer()],
+      program.programId
+    );
+
+    before(async () => {
+      const templateId = randomBytes(32);
+      const [templatePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('campaign_template'), admin.publicKey.toBuffer(), Buffer.from(templateId)],
+        program.programId
+      );
+      await program.methods
+        .createCampaignTemplate(
+          Array.from(templateId),
+          {
+            tokenMint: Keypair.generate().publicKey,
+            baseAmount: new anchor.BN(1_000),
+            devBonus: new anchor.BN(0),
+            whaleBonus: new anchor.BN(0),
+            campaignClaimFeeBps: 0,
+            claimFeeAccount: PublicKey.default,
+          },
+          new anchor.BN(86_400),
+          0,
+          [],
+          new anchor.BN(0)
+        )
+        .accounts({
+          template: templatePda,
+          creator: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      await program.methods
+        .createCampaignFromTemplate(Array.from(campaignId), 'Team drop')
+        .accounts({
+          config: configPda,
+          template: templatePda,
+          campaign: campaignPda,
+          creator: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it('should let a registered manager close registration', async () => {
+      await program.methods
+        .addCampaignManager(manager.publicKey)
+        .accounts({
+          campaign: campaignPda,
+          campaignManager: managerPda,
+          creator: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .closeAirdropRegistration()
+        .accounts({
+          campaign: campaignPda,
+          creator: manager.publicKey,
+          campaignManager: managerPda,
+        })
+        .signers([manager])
+        .rpc();
+
+      const campaign = await program.account.airdropCampaign.fetch(campaignPda);
+      expect(campaign.status).to.deep.equal({ registrationClosed: {} });
+    });
+
+    it('should reject a removed manager', async () => {
+      await program.methods
+        .removeCampaignManager()
+        .accounts({
+          campaign: campaignPda,
+          campaignManager: managerPda,
+          creator: admin.publicKey,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .setRegistrationMemo('flagged')
+          .accounts({
+            campaign: campaignPda,
+            registration: Keypair.generate().publicKey,
+            creator: manager.publicKey,
+            campaignManager: null,
+          })
+          .signers([manager])
+          .rpc();
+        expect.fail('Should have rejected a removed manager');
+      } catch (error) {
+        expect(error.toString()).to.match(/Unauthorized|AccountNotInitialized/);
       }
     });
   });